@@ -0,0 +1,37 @@
+//! Regenerates the Tracy-Widom tables in `src/tracy_widom_tables.rs`.
+//!
+//! ```text
+//! cargo run --release --example tracy_widom_tables > tables.txt
+//! ```
+//!
+//! Prints logit F_beta(s) = ln F - ln(1 - F) from
+//! [`rmt::tracy_widom_cdf_fredholm`] on each table's grid. The grid constants
+//! below must match `STEP` and `TW*_LO` in that file.
+
+use rmt::tracy_widom_cdf_fredholm;
+
+/// Grid spacing shared by all tables.
+const STEP: f64 = 1.0 / 32.0;
+
+fn main() {
+    for &(beta, name, lo, hi) in &[
+        (1.0, "TW1", -10.0, 8.0),
+        (2.0, "TW2", -9.0, 4.5),
+        (4.0, "TW4", -8.0, 2.5),
+    ] {
+        let n = ((hi - lo) / STEP) as usize + 1;
+        let values: Vec<String> = (0..n)
+            .map(|k| {
+                let f = tracy_widom_cdf_fredholm(lo + k as f64 * STEP, beta);
+                format!("{:?}", f.ln() - (1.0 - f).ln())
+            })
+            .collect();
+        println!("/// logit F_{} at s = {name}_LO + k STEP.", beta as u32);
+        println!("#[rustfmt::skip]");
+        println!("pub(crate) const {name}_LOGIT: [f64; {n}] = [");
+        for row in values.chunks(4) {
+            println!("    {},", row.join(", "));
+        }
+        println!("];");
+    }
+}
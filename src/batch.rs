@@ -0,0 +1,146 @@
+//! Limiting densities evaluated over whole grids.
+//!
+//! Fitting and plotting code evaluates the same law at thousands of points.
+//! The batch functions hoist the parameter-dependent constants (edges,
+//! normalizations) out of the loop and keep the inner loop branch-free so it
+//! auto-vectorizes. The Tracy-Widom functions interpolate tables and do more
+//! work per point, so they also get `par_*` variants behind the `parallel`
+//! feature; the closed-form densities are memory-bound and gain nothing from
+//! threads.
+
+use std::f64::consts::PI;
+
+use crate::distributions::TracyWidom;
+use crate::tracy_widom::tracy_widom_cdf;
+
+/// [`crate::marchenko_pastur_density`] at every point of `lambdas`.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{marchenko_pastur_density, marchenko_pastur_density_batch};
+///
+/// let grid: Vec<f64> = (0..=400).map(|i| i as f64 / 100.0).collect();
+/// let rho = marchenko_pastur_density_batch(&grid, 0.5, 1.0);
+/// assert_eq!(rho[150], marchenko_pastur_density(grid[150], 0.5, 1.0));
+/// ```
+pub fn marchenko_pastur_density_batch(lambdas: &[f64], ratio: f64, sigma_sq: f64) -> Vec<f64> {
+    if ratio <= 0.0 {
+        return vec![0.0; lambdas.len()];
+    }
+    let gamma = ratio.min(1.0 / ratio);
+    let lambda_plus = sigma_sq * (1.0 + gamma.sqrt()).powi(2);
+    let lambda_minus = sigma_sq * (1.0 - gamma.sqrt()).powi(2);
+    let scale = 2.0 * PI * sigma_sq * gamma;
+    lambdas
+        .iter()
+        .map(|&lambda| {
+            let inside = lambda > 0.0 && lambda >= lambda_minus && lambda <= lambda_plus;
+            let value =
+                ((lambda_plus - lambda) * (lambda - lambda_minus)).sqrt() / (scale * lambda);
+            if inside {
+                value
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// [`crate::wigner_semicircle_density`] at every point of `lambdas`.
+pub fn wigner_semicircle_density_batch(lambdas: &[f64], sigma: f64) -> Vec<f64> {
+    let r = 2.0 * sigma;
+    let r_sq = r * r;
+    let scale = 2.0 / (PI * r_sq);
+    lambdas
+        .iter()
+        .map(|&lambda| {
+            let value = scale * (r_sq - lambda * lambda).sqrt();
+            if lambda.abs() > r {
+                0.0
+            } else {
+                value
+            }
+        })
+        .collect()
+}
+
+/// [`crate::tracy_widom_cdf`] at every point of `s` (all NaN unless beta is 1, 2, or 4).
+pub fn tracy_widom_cdf_batch(s: &[f64], beta: f64) -> Vec<f64> {
+    s.iter().map(|&s| tracy_widom_cdf(s, beta)).collect()
+}
+
+/// [`crate::TracyWidom::pdf`] at every point of `s` (all NaN unless beta is 1, 2, or 4).
+pub fn tracy_widom_pdf_batch(s: &[f64], beta: f64) -> Vec<f64> {
+    match TracyWidom::new(beta) {
+        Ok(tw) => s.iter().map(|&s| tw.pdf(s)).collect(),
+        Err(_) => vec![f64::NAN; s.len()],
+    }
+}
+
+/// Parallel [`tracy_widom_cdf_batch`].
+#[cfg(feature = "parallel")]
+pub fn par_tracy_widom_cdf_batch(s: &[f64], beta: f64) -> Vec<f64> {
+    use rayon::prelude::*;
+    s.par_iter().map(|&s| tracy_widom_cdf(s, beta)).collect()
+}
+
+/// Parallel [`tracy_widom_pdf_batch`].
+#[cfg(feature = "parallel")]
+pub fn par_tracy_widom_pdf_batch(s: &[f64], beta: f64) -> Vec<f64> {
+    use rayon::prelude::*;
+    match TracyWidom::new(beta) {
+        Ok(tw) => s.par_iter().map(|&s| tw.pdf(s)).collect(),
+        Err(_) => vec![f64::NAN; s.len()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{marchenko_pastur_density, wigner_semicircle_density};
+
+    #[test]
+    fn test_batch_matches_pointwise() {
+        let grid: Vec<f64> = (-50..=500).map(|i| i as f64 / 100.0).collect();
+        for &(ratio, sigma_sq) in &[(0.25, 1.0), (2.0, 0.5), (1.0, 1.0), (0.0, 1.0)] {
+            let batch = marchenko_pastur_density_batch(&grid, ratio, sigma_sq);
+            for (&x, &y) in grid.iter().zip(&batch) {
+                let expected = marchenko_pastur_density(x, ratio, sigma_sq);
+                assert!(
+                    (y - expected).abs() <= 1e-12 * expected.max(1.0),
+                    "MP at {x}"
+                );
+            }
+        }
+        let batch = wigner_semicircle_density_batch(&grid, 1.5);
+        for (&x, &y) in grid.iter().zip(&batch) {
+            assert!((y - wigner_semicircle_density(x, 1.5)).abs() < 1e-15);
+        }
+
+        let s = [-3.0, -1.2, 0.0, 1.5];
+        let tw = TracyWidom::new(2.0).unwrap();
+        let cdf = tracy_widom_cdf_batch(&s, 2.0);
+        let pdf = tracy_widom_pdf_batch(&s, 2.0);
+        for (i, &s) in s.iter().enumerate() {
+            assert_eq!(cdf[i], tw.cdf(s));
+            assert_eq!(pdf[i], tw.pdf(s));
+        }
+        assert!(tracy_widom_cdf_batch(&s, 3.0).iter().all(|v| v.is_nan()));
+        assert!(tracy_widom_pdf_batch(&s, 3.0).iter().all(|v| v.is_nan()));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_tracy_widom_matches_serial() {
+        let s: Vec<f64> = (0..16).map(|i| -4.0 + 0.5 * i as f64).collect();
+        assert_eq!(
+            par_tracy_widom_cdf_batch(&s, 1.0),
+            tracy_widom_cdf_batch(&s, 1.0)
+        );
+        assert_eq!(
+            par_tracy_widom_pdf_batch(&s, 4.0),
+            tracy_widom_pdf_batch(&s, 4.0)
+        );
+    }
+}
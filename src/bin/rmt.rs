@@ -0,0 +1,395 @@
+//! Command-line spectrum diagnostics (`cli` feature).
+//!
+//! ```text
+//! rmt analyze data.csv --law mp
+//! rmt sample goe --n 2000 --out eigs.csv
+//! rmt clean data.csv --method rie --out cleaned.csv
+//! ```
+//!
+//! Matrices are read with [`rmt::read_matrix_csv`] (one row per line, optional
+//! header); outputs go to `--out` or standard output.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use ndarray::Array2;
+use rand::SeedableRng;
+use rmt::{
+    clip_eigenvalues, count_spikes_with, estimate_sigma_sq, ks_test_mp, ks_test_semicircle,
+    marchenko_pastur_support, mean_spacing_ratio, nonlinear_shrinkage_eigenvalues, read_matrix_csv,
+    rie_eigenvalues, sample_covariance, symmetric_eigen, symmetric_eigenvalues,
+    write_eigenvalues_csv, write_matrix_csv, CleaningStrategy, CovarianceCleaner, Ensemble, Goe,
+    SpikeThreshold, Wishart,
+};
+
+/// Random matrix theory diagnostics for spectra stored in CSV files.
+#[derive(Debug, Parser)]
+#[command(name = "rmt", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compare the spectrum of a matrix with a limiting law.
+    Analyze {
+        /// CSV matrix: n x p data (rows are observations) for `mp`, symmetric for `semicircle`
+        input: PathBuf,
+        /// Reference law
+        #[arg(long, value_enum, default_value_t = Law::Mp)]
+        law: Law,
+        /// Significance level of the Tracy-Widom spike test
+        #[arg(long, default_value_t = 0.05)]
+        alpha: f64,
+    },
+    /// Sample eigenvalues from a random matrix ensemble.
+    Sample {
+        #[arg(value_enum)]
+        ensemble: EnsembleKind,
+        /// Matrix dimension (number of samples for `wishart`)
+        #[arg(long)]
+        n: usize,
+        /// Number of variables for `wishart`
+        #[arg(long, required_if_eq("ensemble", "wishart"))]
+        p: Option<usize>,
+        /// Number of matrices; eigenvalues are concatenated
+        #[arg(long, default_value_t = 1)]
+        trials: usize,
+        /// Seed for reproducible output (random if omitted)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Output CSV (standard output if omitted)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Clean a covariance matrix.
+    Clean {
+        /// CSV data matrix (rows are observations), or a covariance with `--samples`
+        input: PathBuf,
+        #[arg(long, value_enum, default_value_t = Method::Nonlinear)]
+        method: Method,
+        /// Treat the input as a p x p covariance estimated from this many observations
+        #[arg(long)]
+        samples: Option<usize>,
+        /// Clean the correlation matrix (data input only)
+        #[arg(long, conflicts_with = "samples")]
+        standardize: bool,
+        /// Output CSV (standard output if omitted)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Law {
+    /// Marchenko-Pastur law of the sample covariance
+    Mp,
+    /// Wigner semicircle of a symmetric matrix
+    Semicircle,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum EnsembleKind {
+    Goe,
+    Wishart,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Method {
+    /// Plain sample covariance
+    Sample,
+    /// Marchenko-Pastur eigenvalue clipping
+    Clip,
+    /// Rotationally invariant estimator
+    Rie,
+    /// Ledoit-Wolf linear shrinkage (data input only)
+    Linear,
+    /// Ledoit-Wolf analytical nonlinear shrinkage
+    Nonlinear,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("rmt: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Analyze { input, law, alpha } => analyze(&input, law, alpha),
+        Command::Sample {
+            ensemble,
+            n,
+            p,
+            trials,
+            seed,
+            out,
+        } => {
+            let mut rng = match seed {
+                Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+                None => rand::rngs::StdRng::from_os_rng(),
+            };
+            let mut eigenvalues = Vec::with_capacity(trials * n);
+            for _ in 0..trials {
+                eigenvalues.extend(match ensemble {
+                    EnsembleKind::Goe => Goe::new(n).sample_eigenvalues(&mut rng),
+                    EnsembleKind::Wishart => {
+                        let p = p.ok_or("--p is required for wishart")?;
+                        Wishart::new(n, p).sample_eigenvalues(&mut rng)
+                    }
+                });
+            }
+            let mut writer = output(out.as_deref())?;
+            write_eigenvalues_csv(&mut writer, &eigenvalues)?;
+            writer.flush()?;
+            Ok(())
+        }
+        Command::Clean {
+            input,
+            method,
+            samples,
+            standardize,
+            out,
+        } => {
+            let matrix = read_matrix(&input)?;
+            let covariance = match samples {
+                Some(n) => clean_covariance(&matrix, n, method)?,
+                None => {
+                    let strategy = match method {
+                        Method::Sample => CleaningStrategy::Sample,
+                        Method::Clip => CleaningStrategy::Clipping,
+                        Method::Rie => CleaningStrategy::Rie,
+                        Method::Linear => CleaningStrategy::LinearShrinkage,
+                        Method::Nonlinear => CleaningStrategy::NonlinearShrinkage,
+                    };
+                    CovarianceCleaner::new()
+                        .standardize(standardize)
+                        .strategy(strategy)
+                        .clean(&matrix)
+                        .ok_or("cannot clean this data set (see CovarianceCleaner::clean)")?
+                        .covariance
+                }
+            };
+            let mut writer = output(out.as_deref())?;
+            write_matrix_csv(&mut writer, &covariance)?;
+            writer.flush()?;
+            Ok(())
+        }
+    }
+}
+
+fn analyze(input: &Path, law: Law, alpha: f64) -> Result<(), Box<dyn Error>> {
+    let matrix = read_matrix(input)?;
+    let (rows, cols) = matrix.dim();
+    let mut stdout = io::stdout().lock();
+    match law {
+        Law::Mp => {
+            if rows < 2 || cols == 0 {
+                return Err("need at least two observations".into());
+            }
+            let eigenvalues = symmetric_eigenvalues(&sample_covariance(&matrix));
+            let ratio = cols as f64 / rows as f64;
+            let sigma_sq = estimate_sigma_sq(&eigenvalues, ratio);
+            let spikes = count_spikes_with(
+                &eigenvalues,
+                ratio,
+                sigma_sq,
+                SpikeThreshold::TracyWidom { alpha },
+            );
+            let (lo, hi, bulk) = mp_bulk(&eigenvalues, rows, ratio, sigma_sq, spikes);
+            writeln!(stdout, "observations: {rows}")?;
+            writeln!(stdout, "variables: {cols}")?;
+            writeln!(stdout, "ratio: {ratio}")?;
+            writeln!(stdout, "sigma_sq: {sigma_sq}")?;
+            writeln!(stdout, "mp_support: [{lo}, {hi}]")?;
+            writeln!(stdout, "lambda_max: {}", eigenvalues[eigenvalues.len() - 1])?;
+            writeln!(stdout, "spikes: {spikes}")?;
+            if let Some(ks) = ks_test_mp(&bulk, ratio, sigma_sq) {
+                writeln!(stdout, "bulk_ks_statistic: {}", ks.statistic)?;
+                writeln!(stdout, "bulk_ks_p_value: {}", ks.p_value)?;
+            }
+        }
+        Law::Semicircle => {
+            if rows != cols || rows < 3 {
+                return Err(
+                    format!("need a square matrix of size >= 3, got {rows} x {cols}").into(),
+                );
+            }
+            let eigenvalues = symmetric_eigenvalues(&matrix);
+            // ||H||_F^2 = sum lambda_i^2 = n^2 v for entries of variance v, and
+            // the semicircle radius is 2 sqrt(n v).
+            let sigma = (matrix.iter().map(|v| v * v).sum::<f64>() / rows as f64).sqrt();
+            writeln!(stdout, "dimension: {rows}")?;
+            writeln!(stdout, "sigma: {sigma}")?;
+            writeln!(
+                stdout,
+                "semicircle_support: [{}, {}]",
+                -2.0 * sigma,
+                2.0 * sigma
+            )?;
+            writeln!(stdout, "lambda_min: {}", eigenvalues[0])?;
+            writeln!(stdout, "lambda_max: {}", eigenvalues[rows - 1])?;
+            writeln!(
+                stdout,
+                "mean_spacing_ratio: {}",
+                mean_spacing_ratio(&eigenvalues)
+            )?;
+            if let Some(ks) = ks_test_semicircle(&eigenvalues, sigma) {
+                writeln!(stdout, "ks_statistic: {}", ks.statistic)?;
+                writeln!(stdout, "ks_p_value: {}", ks.p_value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// MP support of the nonzero eigenvalues of a sample covariance from `rows`
+/// centered observations, and its bulk below the top `spikes`, scaled for
+/// [`ks_test_mp`].
+///
+/// For gamma = p/n > 1 the centered covariance has rank n - 1; the null
+/// eigenvalues are dropped, and the rest, divided by gamma, are those of
+/// X X^T / p, which follow the folded law MP(1/gamma) that [`ks_test_mp`]
+/// uses. The support is reported on the covariance scale,
+/// sigma^2 [(sqrt(gamma) - 1)^2, (sqrt(gamma) + 1)^2].
+fn mp_bulk(
+    eigenvalues: &[f64],
+    rows: usize,
+    ratio: f64,
+    sigma_sq: f64,
+    spikes: usize,
+) -> (f64, f64, Vec<f64>) {
+    let scale = ratio.max(1.0);
+    let (lo, hi) = marchenko_pastur_support(ratio, sigma_sq);
+    let p = eigenvalues.len();
+    let top = p - spikes.min(p);
+    let start = (p - p.min(rows - 1)).min(top);
+    let bulk = eigenvalues[start..top].iter().map(|v| v / scale).collect();
+    (scale * lo, scale * hi, bulk)
+}
+
+/// Cleans a covariance matrix estimated from `n` observations.
+fn clean_covariance(
+    covariance: &Array2<f64>,
+    n: usize,
+    method: Method,
+) -> Result<Array2<f64>, Box<dyn Error>> {
+    let p = covariance.nrows();
+    if covariance.ncols() != p || p == 0 {
+        return Err("--samples expects a square covariance matrix".into());
+    }
+    if n == 0 {
+        return Err("--samples must be positive".into());
+    }
+    let (eigenvalues, vectors) = symmetric_eigen(covariance);
+    let ratio = p as f64 / n as f64;
+    let cleaned = match method {
+        Method::Sample => eigenvalues,
+        Method::Clip => {
+            clip_eigenvalues(&eigenvalues, ratio, estimate_sigma_sq(&eigenvalues, ratio))
+        }
+        Method::Rie => rie_eigenvalues(&eigenvalues, n),
+        Method::Nonlinear => nonlinear_shrinkage_eigenvalues(&eigenvalues, n),
+        Method::Linear => {
+            return Err("linear shrinkage needs the data matrix, not a covariance".into())
+        }
+    };
+    if cleaned.len() != p {
+        return Err("cannot clean this spectrum (check --samples; rie needs p < n)".into());
+    }
+    let scaled = &vectors * &ndarray::Array1::from(cleaned);
+    Ok(scaled.dot(&vectors.t()))
+}
+
+fn read_matrix(path: &Path) -> Result<Array2<f64>, Box<dyn Error>> {
+    let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(read_matrix_csv(BufReader::new(file)).map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+/// Buffered writer for `--out` or standard output; callers flush it so write
+/// errors are reported rather than lost on drop.
+fn output(path: Option<&Path>) -> io::Result<Box<dyn Write>> {
+    Ok(match path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+        let cli = Cli::parse_from(["rmt", "sample", "goe", "--n", "10", "--seed", "3"]);
+        assert!(matches!(
+            cli.command,
+            Command::Sample {
+                n: 10,
+                seed: Some(3),
+                ..
+            }
+        ));
+        assert!(Cli::try_parse_from(["rmt", "sample", "wishart", "--n", "10"]).is_err());
+        assert!(Cli::try_parse_from([
+            "rmt",
+            "clean",
+            "cov.csv",
+            "--samples",
+            "100",
+            "--standardize"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_clean_covariance_matches_eigenvalue_cleaner() {
+        let covariance = Array2::from_diag(&ndarray::Array1::from(vec![1.0, 2.0, 8.0]));
+        let cleaned = clean_covariance(&covariance, 100, Method::Sample).unwrap();
+        assert!((&cleaned - &covariance).iter().all(|v| v.abs() < 1e-12));
+        assert!(clean_covariance(&covariance, 100, Method::Linear).is_err());
+        assert!(clean_covariance(&covariance, 0, Method::Clip).is_err());
+    }
+
+    #[test]
+    fn test_more_variables_than_samples() {
+        use rand_distr::{Distribution, StandardNormal};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let (n, p) = (60, 150);
+        let x = Array2::from_shape_fn((n, p), |_| {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            z
+        });
+        let covariance = x.t().dot(&x) / n as f64;
+        let eigenvalues = symmetric_eigenvalues(&covariance);
+        let ratio = p as f64 / n as f64;
+
+        // Support on the covariance scale, bulk without the null eigenvalues
+        // (x is not centered, so its rank n is that of n + 1 centered rows).
+        let (lo, hi, bulk) = mp_bulk(&eigenvalues, n + 1, ratio, 1.0, 0);
+        assert!((hi - (1.0 + ratio.sqrt()).powi(2)).abs() < 1e-12);
+        assert!((lo - (ratio.sqrt() - 1.0).powi(2)).abs() < 1e-12);
+        assert_eq!(bulk.len(), n);
+        assert!(ks_test_mp(&bulk, ratio, 1.0).unwrap().p_value > 0.05);
+
+        // Clipping flattens the nonzero bulk (the top eigenvalue of this draw
+        // sits just past the edge) and keeps the null eigenvalues.
+        let cleaned = clean_covariance(&covariance, n, Method::Clip).unwrap();
+        let values = symmetric_eigenvalues(&cleaned);
+        let bulk = &values[p - n..p - 1];
+        assert!(bulk.iter().all(|&v| (v - bulk[0]).abs() < 1e-8));
+        assert!(values[..p - n].iter().all(|v| v.abs() < 1e-8));
+    }
+}
@@ -0,0 +1,404 @@
+//! Out-of-core processing of eigenvalue files.
+//!
+//! Pooling the spectra of thousands of Monte Carlo realizations easily gives
+//! hundreds of millions of eigenvalues, more than is comfortable to hold as a
+//! `Vec<f64>`. The readers here hand a file to the caller in fixed-size
+//! chunks, and [`accumulate_chunks`] folds them into a
+//! [`StreamingSpectrum`] whose partial results merge across files, threads,
+//! or machines.
+//!
+//! The binary format is raw little-endian `f64` with no header, the layout of
+//! NumPy's `a.astype('<f8').tofile(path)`, so realizations can be appended to
+//! the same file one after another. With the `mmap` feature,
+//! [`MappedEigenvalues`] memory-maps such a file and lets the OS page it in.
+
+use std::io::{self, BufRead, Read, Write};
+
+use crate::io::{invalid_data, parse_line};
+use crate::streaming::StreamingSpectrum;
+
+/// Bytes per stored eigenvalue.
+const WIDTH: usize = std::mem::size_of::<f64>();
+
+/// Appends eigenvalues to `writer` as raw little-endian `f64`.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{read_eigenvalues_binary_chunks, write_eigenvalues_binary};
+///
+/// let mut buffer = Vec::new();
+/// write_eigenvalues_binary(&mut buffer, &[0.5, 1.25]).unwrap();
+/// write_eigenvalues_binary(&mut buffer, &[2.0]).unwrap();
+/// assert_eq!(buffer.len(), 24);
+/// let chunks: Vec<Vec<f64>> = read_eigenvalues_binary_chunks(buffer.as_slice(), 2)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(chunks, vec![vec![0.5, 1.25], vec![2.0]]);
+/// ```
+pub fn write_eigenvalues_binary<W: Write>(mut writer: W, eigenvalues: &[f64]) -> io::Result<()> {
+    for value in eigenvalues {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads raw little-endian `f64` values in chunks of `chunk_len` (the last may be shorter).
+///
+/// Memory use is one chunk regardless of the file size. A trailing partial
+/// value yields [`io::ErrorKind::InvalidData`]; a `chunk_len` of 0 is
+/// treated as 1.
+pub fn read_eigenvalues_binary_chunks<R: Read>(reader: R, chunk_len: usize) -> BinaryChunks<R> {
+    BinaryChunks {
+        reader,
+        buffer: vec![0; chunk_len.max(1) * WIDTH],
+        done: false,
+    }
+}
+
+/// Iterator returned by [`read_eigenvalues_binary_chunks`].
+#[derive(Debug)]
+pub struct BinaryChunks<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl<R: Read> Iterator for BinaryChunks<R> {
+    type Item = io::Result<Vec<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut filled = 0;
+        while filled < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        if filled < self.buffer.len() {
+            self.done = true;
+        }
+        if filled % WIDTH != 0 {
+            return Some(Err(invalid_data(format!(
+                "{} trailing bytes after the last f64",
+                filled % WIDTH
+            ))));
+        }
+        if filled == 0 {
+            return None;
+        }
+        Some(Ok(decode(&self.buffer[..filled]).collect()))
+    }
+}
+
+/// Reads the first column of a CSV file in chunks of `chunk_len` values.
+///
+/// The streaming counterpart of [`crate::read_eigenvalues_csv`], with the
+/// same header and error handling; a `chunk_len` of 0 is treated as 1.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::read_eigenvalues_csv_chunks;
+///
+/// let csv = "eigenvalue\n0.5\n1.0\n1.5\n";
+/// let lens: Vec<usize> = read_eigenvalues_csv_chunks(csv.as_bytes(), 2)
+///     .map(|chunk| chunk.unwrap().len())
+///     .collect();
+/// assert_eq!(lens, vec![2, 1]);
+/// ```
+pub fn read_eigenvalues_csv_chunks<R: BufRead>(reader: R, chunk_len: usize) -> CsvChunks<R> {
+    CsvChunks {
+        lines: reader.lines().enumerate(),
+        chunk_len: chunk_len.max(1),
+    }
+}
+
+/// Iterator returned by [`read_eigenvalues_csv_chunks`].
+#[derive(Debug)]
+pub struct CsvChunks<R> {
+    lines: std::iter::Enumerate<io::Lines<R>>,
+    chunk_len: usize,
+}
+
+impl<R: BufRead> Iterator for CsvChunks<R> {
+    type Item = io::Result<Vec<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_len);
+        while chunk.len() < self.chunk_len {
+            let Some((index, line)) = self.lines.next() else {
+                break;
+            };
+            match line.and_then(|line| parse_line(index, &line)) {
+                Ok(Some(row)) => chunk.push(row[0]),
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+}
+
+/// Folds chunks of eigenvalues into a copy of `acc`.
+///
+/// `acc` fixes the histogram range, bin count, and moment order (and may
+/// already hold data, e.g. from an earlier file). The first I/O error stops
+/// the fold and is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{
+///     accumulate_chunks, read_eigenvalues_binary_chunks, write_eigenvalues_binary,
+///     StreamingSpectrum,
+/// };
+///
+/// let mut file = Vec::new();
+/// for trial in 0..100 {
+///     let eigenvalues: Vec<f64> = (0..50).map(|i| ((trial * 50 + i) as f64).sin()).collect();
+///     write_eigenvalues_binary(&mut file, &eigenvalues).unwrap();
+/// }
+/// let acc = StreamingSpectrum::new(-1.0, 1.0, 20).moments(2);
+/// let chunks = read_eigenvalues_binary_chunks(file.as_slice(), 1024);
+/// let acc = accumulate_chunks(chunks, &acc).unwrap();
+/// assert_eq!(acc.count(), 5000);
+/// assert!(acc.spectral_moments()[0].abs() < 0.01);
+/// ```
+pub fn accumulate_chunks<I, C>(chunks: I, acc: &StreamingSpectrum) -> io::Result<StreamingSpectrum>
+where
+    I: IntoIterator<Item = io::Result<C>>,
+    C: AsRef<[f64]>,
+{
+    let mut acc = acc.clone();
+    for chunk in chunks {
+        acc.extend(chunk?.as_ref().iter().copied());
+    }
+    Ok(acc)
+}
+
+/// Little-endian `f64` values of a byte slice whose length is a multiple of 8.
+fn decode(bytes: &[u8]) -> impl Iterator<Item = f64> + '_ {
+    bytes.chunks_exact(WIDTH).map(|b| {
+        let mut raw = [0; WIDTH];
+        raw.copy_from_slice(b);
+        f64::from_le_bytes(raw)
+    })
+}
+
+/// A memory-mapped file of raw little-endian `f64` eigenvalues.
+///
+/// Only the pages being read are resident, so files far larger than RAM can
+/// be scanned; with the `parallel` feature [`MappedEigenvalues::accumulate`]
+/// splits the scan across threads and merges the partial accumulators.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rmt::{MappedEigenvalues, StreamingSpectrum};
+///
+/// let file = MappedEigenvalues::open("pooled_eigenvalues.f64").unwrap();
+/// let acc = file.accumulate(&StreamingSpectrum::new(0.0, 4.0, 200).moments(4), 1 << 20);
+/// println!("{} eigenvalues, mean {}", acc.count(), acc.spectral_moments()[0]);
+/// ```
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MappedEigenvalues {
+    map: memmap2::Mmap,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedEigenvalues {
+    /// Maps the file at `path` read-only.
+    ///
+    /// # Errors
+    ///
+    /// I/O errors from opening or mapping the file, and
+    /// [`io::ErrorKind::InvalidData`] if its length is not a multiple of 8.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read-only; as with any mmap, the caller must not
+        // truncate or rewrite the file while it is mapped.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        if map.len() % WIDTH != 0 {
+            return Err(invalid_data(format!(
+                "file length {} is not a multiple of {WIDTH}",
+                map.len()
+            )));
+        }
+        Ok(Self { map })
+    }
+
+    /// Number of eigenvalues in the file.
+    pub fn len(&self) -> usize {
+        self.map.len() / WIDTH
+    }
+
+    /// Whether the file holds no eigenvalues.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Eigenvalue `i`, or `None` past the end.
+    pub fn get(&self, i: usize) -> Option<f64> {
+        let bytes = self.map.get(i * WIDTH..(i + 1) * WIDTH)?;
+        decode(bytes).next()
+    }
+
+    /// Copies out eigenvalues `start..end` (e.g. one realization), clamped to the file.
+    pub fn range(&self, start: usize, end: usize) -> Vec<f64> {
+        let end = end.min(self.len());
+        let start = start.min(end);
+        decode(&self.map[start * WIDTH..end * WIDTH]).collect()
+    }
+
+    /// Iterates over all eigenvalues without copying the file.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        decode(&self.map)
+    }
+
+    /// Folds the whole file into a copy of `acc`, `chunk_len` values at a time.
+    ///
+    /// With the `parallel` feature the chunks are processed on the rayon
+    /// pool; the result is the same up to floating-point summation order.
+    pub fn accumulate(&self, acc: &StreamingSpectrum, chunk_len: usize) -> StreamingSpectrum {
+        let chunk_bytes = chunk_len.max(1) * WIDTH;
+        let fold = |bytes: &[u8]| {
+            let mut partial = acc.cleared();
+            partial.extend(decode(bytes));
+            partial
+        };
+        #[cfg(feature = "parallel")]
+        let partial = {
+            use rayon::prelude::*;
+            self.map.par_chunks(chunk_bytes).map(fold).reduce(
+                || acc.cleared(),
+                |mut a, b| {
+                    a.merge(&b);
+                    a
+                },
+            )
+        };
+        #[cfg(not(feature = "parallel"))]
+        let partial = self
+            .map
+            .chunks(chunk_bytes)
+            .map(fold)
+            .fold(acc.cleared(), |mut a, b| {
+                a.merge(&b);
+                a
+            });
+        let mut total = acc.clone();
+        total.merge(&partial);
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_eigenvalues_csv, spectral_moments, write_eigenvalues_csv};
+
+    fn pooled_spectrum() -> Vec<f64> {
+        (0..10_000)
+            .map(|i| 2.0 * (i as f64 * 0.618).fract())
+            .collect()
+    }
+
+    #[test]
+    fn test_chunked_readers_match_in_memory() {
+        let values = pooled_spectrum();
+        let acc = StreamingSpectrum::new(0.0, 2.0, 25).moments(3);
+        let mut expected = acc.clone();
+        expected.extend(values.iter().copied());
+
+        let mut binary = Vec::new();
+        for realization in values.chunks(300) {
+            write_eigenvalues_binary(&mut binary, realization).unwrap();
+        }
+        let chunks: Vec<Vec<f64>> = read_eigenvalues_binary_chunks(binary.as_slice(), 777)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert!(chunks.iter().all(|c| c.len() <= 777));
+        assert_eq!(chunks.concat(), values);
+        let from_binary =
+            accumulate_chunks(read_eigenvalues_binary_chunks(binary.as_slice(), 777), &acc)
+                .unwrap();
+        assert_eq!(from_binary, expected);
+
+        let mut csv = Vec::new();
+        write_eigenvalues_csv(&mut csv, &values).unwrap();
+        let from_csv =
+            accumulate_chunks(read_eigenvalues_csv_chunks(csv.as_slice(), 1000), &acc).unwrap();
+        assert_eq!(from_csv.count(), values.len() as u64);
+        assert_eq!(read_eigenvalues_csv(csv.as_slice()).unwrap(), values);
+        for (a, b) in from_csv
+            .spectral_moments()
+            .iter()
+            .zip(spectral_moments(&values, 3))
+        {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // Partial statistics from separate files merge into the pooled result.
+        let (first, second) = binary.split_at(8 * 4000);
+        let mut merged =
+            accumulate_chunks(read_eigenvalues_binary_chunks(first, 512), &acc).unwrap();
+        merged
+            .merge(&accumulate_chunks(read_eigenvalues_binary_chunks(second, 512), &acc).unwrap());
+        assert_eq!(merged.count(), expected.count());
+        assert_eq!(merged.density(), expected.density());
+
+        let truncated = &binary[..binary.len() - 3];
+        let err =
+            accumulate_chunks(read_eigenvalues_binary_chunks(truncated, 64), &acc).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = accumulate_chunks(
+            read_eigenvalues_csv_chunks("x\n1\nnope\n".as_bytes(), 8),
+            &acc,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mapped_eigenvalues() {
+        let values = pooled_spectrum();
+        let path = std::env::temp_dir().join(format!("rmt-mmap-{}.f64", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        write_eigenvalues_binary(&mut file, &values).unwrap();
+        drop(file);
+
+        let mapped = MappedEigenvalues::open(&path).unwrap();
+        assert_eq!(mapped.len(), values.len());
+        assert_eq!(mapped.get(1234), Some(values[1234]));
+        assert_eq!(mapped.get(values.len()), None);
+        assert_eq!(mapped.range(100, 130), values[100..130]);
+        assert_eq!(mapped.range(9_990, 20_000).len(), 10);
+
+        let acc = StreamingSpectrum::new(0.0, 2.0, 25).moments(2);
+        let got = mapped.accumulate(&acc, 333);
+        let mut expected = acc.clone();
+        expected.extend(mapped.iter());
+        assert_eq!(got.count(), expected.count());
+        assert_eq!(got.density(), expected.density());
+        for (a, b) in got
+            .spectral_moments()
+            .iter()
+            .zip(expected.spectral_moments())
+        {
+            assert!((a - b).abs() < 1e-12);
+        }
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
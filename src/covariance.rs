@@ -0,0 +1,838 @@
+//! Covariance estimation and cleaning.
+//!
+//! When p/n is not small the sample covariance is a poor estimate: its
+//! eigenvalues spread out according to Marchenko-Pastur even when the truth
+//! is the identity. These estimators pull the spectrum back toward the truth.
+
+use std::f64::consts::PI;
+
+use ndarray::{Array2, Axis};
+use num_complex::Complex64;
+
+use crate::free::cauchy_transform;
+use crate::laws::sample_covariance_support;
+use crate::linalg::symmetric_eigen;
+use crate::marchenko_pastur_support;
+use crate::spectrum::AsEigenvalues;
+
+/// Sample covariance (1/n) X_c^T X_c of column-centered data.
+///
+/// # Arguments
+///
+/// * `data` - n x p data matrix, one observation per row
+///
+/// # Returns
+///
+/// p x p covariance (empty if `data` has no rows)
+pub fn sample_covariance(data: &Array2<f64>) -> Array2<f64> {
+    let n = data.nrows();
+    if n == 0 {
+        return Array2::zeros((0, 0));
+    }
+    let centered = center_columns(data);
+    centered.t().dot(&centered) / n as f64
+}
+
+/// Linearly shrunk covariance estimate.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearShrinkage {
+    /// delta mu I + (1 - delta) S
+    pub covariance: Array2<f64>,
+    /// Shrinkage intensity delta in [0, 1]
+    pub shrinkage: f64,
+    /// Target scale mu = tr(S) / p
+    pub target: f64,
+}
+
+/// Ledoit-Wolf (2004) shrinkage of the sample covariance toward a scaled identity.
+///
+/// With S the sample covariance, mu = tr(S)/p, and ||A||^2 = tr(A A^T)/p:
+///
+/// - d^2 = ||S - mu I||^2 (dispersion of S around the target)
+/// - b^2 = min(d^2, (1/n^2) sum_k ||x_k x_k^T - S||^2) (estimation error of S)
+/// - delta = b^2 / d^2
+///
+/// This is the asymptotically optimal convex combination under Frobenius loss
+/// and needs no tuning.
+///
+/// # Arguments
+///
+/// * `data` - n x p data matrix, one observation per row (columns are centered internally)
+///
+/// # Returns
+///
+/// `None` if n < 2 or p = 0
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::ledoit_wolf;
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let data = ndarray::Array2::from_shape_fn((50, 40), |_| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     z
+/// });
+/// // True covariance is the identity, so heavy shrinkage is expected.
+/// let lw = ledoit_wolf(&data).unwrap();
+/// assert!(lw.shrinkage > 0.5);
+/// ```
+pub fn ledoit_wolf(data: &Array2<f64>) -> Option<LinearShrinkage> {
+    let (n, p) = data.dim();
+    if n < 2 || p == 0 {
+        return None;
+    }
+    let centered = center_columns(data);
+    let s = centered.t().dot(&centered) / n as f64;
+    let (shrinkage, target) = ledoit_wolf_intensity(&centered, &s);
+    let covariance = &s * (1.0 - shrinkage) + &(Array2::<f64>::eye(p) * (shrinkage * target));
+
+    Some(LinearShrinkage {
+        covariance,
+        shrinkage,
+        target,
+    })
+}
+
+/// Covariance estimate with cleaned eigenvalues and the sample eigenvectors.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CleanedCovariance {
+    /// Cleaned eigenvalues, paired with the ascending sample eigenvalues
+    pub eigenvalues: Vec<f64>,
+    /// U diag(eigenvalues) U^T with U the sample eigenvectors
+    pub covariance: Array2<f64>,
+}
+
+/// Ledoit-Wolf (2020) analytical nonlinear shrinkage of sample eigenvalues.
+///
+/// Each sample eigenvalue lambda_i is mapped to
+///
+/// d_i = lambda_i / ((pi c lambda_i f(lambda_i))^2 + (1 - c - pi c lambda_i Hf(lambda_i))^2)
+///
+/// with c = p/n, f a kernel estimate of the sample spectral density, and Hf
+/// its Hilbert transform. The Epanechnikov kernel with locally adaptive
+/// bandwidth h lambda_j, h = n^{-1/3}, gives both in closed form. For p > n
+/// the p - n null eigenvalues share one value from the Hilbert transform at 0.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - All p sample covariance eigenvalues in any order
+/// * `n` - Effective sample size (n - 1 if the data were demeaned)
+///
+/// # Returns
+///
+/// Shrunk eigenvalues in ascending order of the sample eigenvalues
+/// (empty if n = 0 or the largest min(n, p) eigenvalues are not all positive)
+pub fn nonlinear_shrinkage_eigenvalues<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let p = eigenvalues.len();
+    if p == 0 || n == 0 {
+        return vec![];
+    }
+    let mut sorted = eigenvalues.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let lambda = &sorted[p.saturating_sub(n)..];
+    if lambda.iter().any(|&l| l <= 0.0 || !l.is_finite()) {
+        return vec![];
+    }
+
+    let c = p as f64 / n as f64;
+    let h = (n as f64).powf(-1.0 / 3.0);
+    let root5 = 5.0_f64.sqrt();
+    let m = lambda.len() as f64;
+
+    let shrunk = lambda.iter().map(|&li| {
+        let (mut f, mut hf) = (0.0, 0.0);
+        for &lj in lambda {
+            let width = h * lj;
+            let x = (li - lj) / width;
+            f += 3.0 / (4.0 * root5) * (1.0 - x * x / 5.0).max(0.0) / width;
+            let log_term = if (x.abs() - root5).abs() < 1e-12 {
+                0.0
+            } else {
+                (1.0 - x * x / 5.0) * ((root5 - x) / (root5 + x)).abs().ln()
+            };
+            hf += (-3.0 / (10.0 * PI) * x + 3.0 / (4.0 * root5 * PI) * log_term) / width;
+        }
+        f /= m;
+        hf /= m;
+        if p <= n {
+            li / ((PI * c * li * f).powi(2) + (1.0 - c - PI * c * li * hf).powi(2))
+        } else {
+            li / (PI * PI * li * li * (f * f + hf * hf))
+        }
+    });
+
+    if p <= n {
+        return shrunk.collect();
+    }
+    // Hilbert transform of the kernel density at 0 for the null eigenvalues.
+    let mean_inverse = lambda.iter().map(|l| 1.0 / l).sum::<f64>() / m;
+    let hf0 = (3.0 / (10.0 * h * h)
+        + 3.0 / (4.0 * root5 * h)
+            * (1.0 - 1.0 / (5.0 * h * h))
+            * ((1.0 + root5 * h) / (1.0 - root5 * h)).abs().ln())
+        * mean_inverse
+        / PI;
+    let null = 1.0 / (PI * (c - 1.0) * hf0);
+    std::iter::repeat_n(null, p - n).chain(shrunk).collect()
+}
+
+/// Analytical nonlinear shrinkage (Ledoit-Wolf 2020) of a data set's covariance.
+///
+/// Demeans the columns, diagonalizes the sample covariance, shrinks its
+/// eigenvalues with [`nonlinear_shrinkage_eigenvalues`] (effective sample
+/// size n - 1), and reassembles with the sample eigenvectors. Substantially
+/// better than linear shrinkage when the true spectrum is not flat.
+///
+/// # Arguments
+///
+/// * `data` - n x p data matrix, one observation per row
+///
+/// # Returns
+///
+/// `None` if n < 3, p = 0, or the sample spectrum is degenerate
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::nonlinear_shrinkage;
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+/// let data = ndarray::Array2::from_shape_fn((200, 50), |_| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     z
+/// });
+/// let cleaned = nonlinear_shrinkage(&data).unwrap();
+/// // The sample spectrum spreads over MP's [0.25, 2.25]; shrinkage pulls it toward 1.
+/// let max = cleaned.eigenvalues.iter().cloned().fold(0.0, f64::max);
+/// assert!(max < 1.5);
+/// ```
+pub fn nonlinear_shrinkage(data: &Array2<f64>) -> Option<CleanedCovariance> {
+    CovarianceCleaner::new()
+        .strategy(CleaningStrategy::NonlinearShrinkage)
+        .clean(data)
+}
+
+/// Rotationally invariant estimator (Bun, Bouchaud & Potters 2016) of the population eigenvalues.
+///
+/// The oracle eigenvalue attached to the sample eigenvector of lambda_i is
+///
+/// xi_i = lambda_i / |1 - q + q z_i g(z_i)|^2,  z_i = lambda_i - i eta,
+///
+/// with q = p/n, g the empirical Cauchy transform of the sample spectrum, and
+/// eta = mean(lambda) / sqrt(p). At finite n this systematically underestimates
+/// the smallest eigenvalues; following Bun et al. (2017) each xi_i is
+/// multiplied by max(1, Gamma_i), where Gamma_i is the same ratio evaluated
+/// with the pure-noise MP transform of matching trace, which undoes the bias
+/// on a null bulk.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `n` - Effective sample size
+///
+/// # Returns
+///
+/// Cleaned eigenvalues in ascending order of the sample eigenvalues (empty if
+/// n = 0, p >= n, or the spectrum is not positive)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::rie_eigenvalues;
+///
+/// // A flat-ish sample spectrum is pulled together.
+/// let sample = [0.4, 0.7, 1.0, 1.3, 1.8];
+/// let cleaned = rie_eigenvalues(&sample, 20);
+/// assert!(cleaned[4] - cleaned[0] < sample[4] - sample[0]);
+/// ```
+pub fn rie_eigenvalues<E: AsEigenvalues + ?Sized>(eigenvalues: &E, n: usize) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let p = eigenvalues.len();
+    if p == 0 || p >= n {
+        return vec![];
+    }
+    let mut sorted = eigenvalues.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    if sorted[0] <= 0.0 || !sorted[p - 1].is_finite() {
+        return vec![];
+    }
+
+    let q = p as f64 / n as f64;
+    let mean = sorted.iter().sum::<f64>() / p as f64;
+    let eta = mean / (p as f64).sqrt();
+    // MP with the same trace: mean eigenvalue sigma^2 for a null covariance sigma^2 I.
+    let sigma_sq = mean;
+    let (lo, hi) = marchenko_pastur_support(q, sigma_sq);
+
+    sorted
+        .iter()
+        .map(|&lambda| {
+            let z = Complex64::new(lambda, -eta);
+            let xi = lambda / (1.0 - q + q * z * cauchy_transform(&sorted, z)).norm_sqr();
+            let g_mp = (z - sigma_sq * (1.0 - q) - (z - lo).sqrt() * (z - hi).sqrt())
+                / (2.0 * q * sigma_sq * z);
+            let gamma = sigma_sq * (1.0 - q + q * z * g_mp).norm_sqr() / lambda;
+            xi * gamma.max(1.0)
+        })
+        .collect()
+}
+
+/// Rotationally invariant estimator of a data set's covariance.
+///
+/// Demeans the columns, cleans the sample eigenvalues with [`rie_eigenvalues`]
+/// (effective sample size n - 1), and reassembles with the sample eigenvectors.
+///
+/// # Arguments
+///
+/// * `data` - n x p data matrix, one observation per row
+///
+/// # Returns
+///
+/// `None` unless 0 < p < n - 1 and the sample spectrum is positive
+pub fn rie_covariance(data: &Array2<f64>) -> Option<CleanedCovariance> {
+    CovarianceCleaner::new()
+        .strategy(CleaningStrategy::Rie)
+        .clean(data)
+}
+
+/// Marchenko-Pastur eigenvalue clipping (Laloux et al. 1999).
+///
+/// Eigenvalues at or below the MP upper edge sigma^2 (1 + sqrt(gamma))^2 are
+/// indistinguishable from noise; they are replaced by their average, which
+/// preserves the trace and leaves the eigenvalues above the edge untouched.
+/// For gamma > 1 the p - n null eigenvalues of the rank-deficient sample
+/// covariance (those below 1e-12 times the largest) are not noise: they stay
+/// at zero and are left out of the average.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+///
+/// # Returns
+///
+/// Clipped eigenvalues in the input order
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::clip_eigenvalues;
+///
+/// // Edge for gamma = 0.25, sigma^2 = 1 is 2.25.
+/// let clipped = clip_eigenvalues(&[0.5, 1.0, 1.5, 6.0], 0.25, 1.0);
+/// assert_eq!(clipped, vec![1.0, 1.0, 1.0, 6.0]);
+/// // Edge for gamma = 2 is (1 + sqrt(2))^2 ~ 5.83; the null eigenvalue stays.
+/// let clipped = clip_eigenvalues(&[0.0, 1.0, 3.0, 5.0, 9.0], 2.0, 1.0);
+/// assert_eq!(clipped, vec![0.0, 3.0, 3.0, 3.0, 9.0]);
+/// ```
+pub fn clip_eigenvalues<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let (_, edge) = sample_covariance_support(ratio, sigma_sq);
+    let floor = null_floor(eigenvalues, ratio);
+    let in_bulk = |v: f64| v > floor && v <= edge;
+    let bulk: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|&v| in_bulk(v))
+        .collect();
+    if bulk.is_empty() {
+        return eigenvalues.to_vec();
+    }
+    let average = bulk.iter().sum::<f64>() / bulk.len() as f64;
+    eigenvalues
+        .iter()
+        .map(|&v| if in_bulk(v) { average } else { v })
+        .collect()
+}
+
+/// Eigenvalues at or below this are the p - n structural zeros of a sample
+/// covariance with gamma > 1; -infinity (nothing is null) for gamma <= 1.
+fn null_floor(eigenvalues: &[f64], ratio: f64) -> f64 {
+    if ratio <= 1.0 {
+        return f64::NEG_INFINITY;
+    }
+    let max = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .fold(0.0_f64, f64::max);
+    1e-12 * max
+}
+
+/// Clipped covariance of a data set.
+///
+/// Demeans the columns and clips the sample covariance spectrum with
+/// [`clip_eigenvalues`] at gamma = p/(n - 1). The noise variance is estimated
+/// self-consistently as the mean of the eigenvalues below the edge it
+/// implies, starting from tr(S)/p, so strong factors do not inflate it.
+///
+/// # Arguments
+///
+/// * `data` - n x p data matrix, one observation per row
+///
+/// # Returns
+///
+/// `None` if n < 3 or p = 0
+pub fn clipped_covariance(data: &Array2<f64>) -> Option<CleanedCovariance> {
+    CovarianceCleaner::new()
+        .strategy(CleaningStrategy::Clipping)
+        .clean(data)
+}
+
+/// Eigenvalue cleaning rule applied by [`CovarianceCleaner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CleaningStrategy {
+    /// Keep the sample eigenvalues (plain sample covariance or correlation).
+    Sample,
+    /// MP clipping with a self-consistent noise estimate ([`clip_eigenvalues`]).
+    ///
+    /// Also handles p >= n: the edge is then sigma^2 (1 + sqrt(gamma))^2 with
+    /// gamma > 1, and the null directions of the sample covariance stay null.
+    Clipping,
+    /// Rotationally invariant estimator ([`rie_eigenvalues`]).
+    Rie,
+    /// Ledoit-Wolf (2004) linear shrinkage toward a scaled identity.
+    LinearShrinkage,
+    /// Ledoit-Wolf (2020) analytical nonlinear shrinkage ([`nonlinear_shrinkage_eigenvalues`]).
+    NonlinearShrinkage,
+}
+
+/// Raw data to cleaned covariance in one configurable step.
+///
+/// The pipeline is: optionally demean the columns, optionally standardize
+/// them to unit variance, diagonalize the sample covariance (normalized by
+/// n - 1 when demeaning, n otherwise), clean the eigenvalues, and reassemble
+/// with the sample eigenvectors. When standardizing, the correlation matrix
+/// is cleaned and then rescaled by the sample standard deviations, so the
+/// result is still a covariance; `eigenvalues` then refers to the cleaned
+/// correlation matrix.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{CleaningStrategy, CovarianceCleaner};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+/// let returns = ndarray::Array2::from_shape_fn((250, 40), |(_, j)| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     0.01 * (1.0 + j as f64 / 40.0) * z
+/// });
+/// let cleaned = CovarianceCleaner::new()
+///     .standardize(true)
+///     .strategy(CleaningStrategy::Clipping)
+///     .clean(&returns)
+///     .unwrap();
+/// assert_eq!(cleaned.covariance.dim(), (40, 40));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CovarianceCleaner {
+    center: bool,
+    standardize: bool,
+    strategy: CleaningStrategy,
+}
+
+impl Default for CovarianceCleaner {
+    fn default() -> Self {
+        Self {
+            center: true,
+            standardize: false,
+            strategy: CleaningStrategy::NonlinearShrinkage,
+        }
+    }
+}
+
+impl CovarianceCleaner {
+    /// Demeaning on, standardization off, nonlinear shrinkage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to subtract column means (default true).
+    pub fn center(mut self, center: bool) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Whether to clean the correlation matrix instead of the covariance (default false).
+    pub fn standardize(mut self, standardize: bool) -> Self {
+        self.standardize = standardize;
+        self
+    }
+
+    /// Eigenvalue cleaning rule (default [`CleaningStrategy::NonlinearShrinkage`]).
+    pub fn strategy(mut self, strategy: CleaningStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Run the pipeline on an n x p data matrix (one observation per row).
+    ///
+    /// Returns `None` if the effective sample size is below 2, p = 0, the
+    /// data contain non-finite values, a column is constant while
+    /// standardizing, or the strategy cannot handle the shape (the RIE
+    /// needs p < n; clipping and both shrinkages accept p >= n).
+    pub fn clean(&self, data: &Array2<f64>) -> Option<CleanedCovariance> {
+        let (n, p) = data.dim();
+        let n_eff = if self.center { n.checked_sub(1)? } else { n };
+        if n_eff < 2 || p == 0 || data.iter().any(|v| !v.is_finite()) {
+            return None;
+        }
+
+        let mut x = if self.center {
+            center_columns(data)
+        } else {
+            data.clone()
+        };
+        let scales = if self.standardize {
+            let scales: Vec<f64> = x
+                .columns()
+                .into_iter()
+                .map(|col| (col.iter().map(|v| v * v).sum::<f64>() / n_eff as f64).sqrt())
+                .collect();
+            if scales.contains(&0.0) {
+                return None;
+            }
+            for (mut col, &sd) in x.columns_mut().into_iter().zip(&scales) {
+                col /= sd;
+            }
+            Some(scales)
+        } else {
+            None
+        };
+
+        let s = x.t().dot(&x) / n_eff as f64;
+        let (sample, vectors) = symmetric_eigen(&s);
+        let eigenvalues = match self.strategy {
+            CleaningStrategy::Sample => sample,
+            CleaningStrategy::Clipping => {
+                clip_with_estimated_noise(&sample, p as f64 / n_eff as f64)
+            }
+            CleaningStrategy::Rie => rie_eigenvalues(&sample, n_eff),
+            CleaningStrategy::LinearShrinkage => {
+                let (shrinkage, _) = ledoit_wolf_intensity(&x, &(x.t().dot(&x) / n as f64));
+                let target = sample.iter().sum::<f64>() / p as f64;
+                sample
+                    .iter()
+                    .map(|&l| shrinkage * target + (1.0 - shrinkage) * l)
+                    .collect()
+            }
+            CleaningStrategy::NonlinearShrinkage => nonlinear_shrinkage_eigenvalues(&sample, n_eff),
+        };
+        if eigenvalues.len() != p {
+            return None;
+        }
+
+        let mut covariance = reconstruct(&vectors, &eigenvalues);
+        if let Some(scales) = scales {
+            for ((i, j), v) in covariance.indexed_iter_mut() {
+                *v *= scales[i] * scales[j];
+            }
+        }
+        Some(CleanedCovariance {
+            eigenvalues,
+            covariance,
+        })
+    }
+}
+
+/// Clip at the MP edge with the noise variance estimated from the mean of the eigenvalues below it.
+///
+/// The nonzero MP eigenvalues average sigma^2 max(gamma, 1), so for gamma > 1
+/// the mean of the non-null bulk is divided by gamma.
+fn clip_with_estimated_noise(sample: &[f64], ratio: f64) -> Vec<f64> {
+    let mut sigma_sq = sample.iter().sum::<f64>() / sample.len() as f64;
+    let floor = null_floor(sample, ratio);
+    for _ in 0..50 {
+        let (_, edge) = sample_covariance_support(ratio, sigma_sq);
+        let bulk: Vec<f64> = sample
+            .iter()
+            .copied()
+            .filter(|&v| v > floor && v <= edge)
+            .collect();
+        if bulk.is_empty() {
+            break;
+        }
+        let next = bulk.iter().sum::<f64>() / bulk.len() as f64 / ratio.max(1.0);
+        if (next - sigma_sq).abs() <= 1e-12 * sigma_sq {
+            break;
+        }
+        sigma_sq = next;
+    }
+    clip_eigenvalues(sample, ratio, sigma_sq)
+}
+
+/// Ledoit-Wolf intensity delta and target mu from centered data and S = X^T X / n.
+fn ledoit_wolf_intensity(centered: &Array2<f64>, s: &Array2<f64>) -> (f64, f64) {
+    let n = centered.nrows() as f64;
+    let pf = s.nrows() as f64;
+    let target = s.diag().sum() / pf;
+    let s_norm_sq = s.iter().map(|v| v * v).sum::<f64>();
+    // ||S - mu I||^2 = (||S||_F^2 - 2 mu tr S + p mu^2) / p
+    let d_sq = (s_norm_sq - pf * target * target) / pf;
+
+    // sum_k ||x_k x_k^T - S||_F^2 = sum_k ||x_k||^4 - n ||S||_F^2
+    let fourth: f64 = centered
+        .axis_iter(Axis(0))
+        .map(|row| row.iter().map(|v| v * v).sum::<f64>().powi(2))
+        .sum();
+    let b_bar_sq = (fourth - n * s_norm_sq) / (n * n * pf);
+    let b_sq = b_bar_sq.clamp(0.0, d_sq);
+
+    let shrinkage = if d_sq > 0.0 { b_sq / d_sq } else { 1.0 };
+    (shrinkage, target)
+}
+
+/// U diag(values) U^T.
+pub(crate) fn reconstruct(vectors: &Array2<f64>, values: &[f64]) -> Array2<f64> {
+    let mut scaled = vectors.clone();
+    for (mut col, &v) in scaled.columns_mut().into_iter().zip(values) {
+        col *= v;
+    }
+    scaled.dot(&vectors.t())
+}
+
+pub(crate) fn center_columns(data: &Array2<f64>) -> Array2<f64> {
+    match data.mean_axis(Axis(0)) {
+        Some(means) => data - &means,
+        None => data.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    fn gaussian(seed: u64, n: usize, p: usize) -> Array2<f64> {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
+        Array2::from_shape_fn((n, p), |_| StandardNormal.sample(&mut rng))
+    }
+
+    fn frobenius_distance(a: &Array2<f64>, b: &Array2<f64>) -> f64 {
+        (a - b).iter().map(|v| v * v).sum::<f64>().sqrt()
+    }
+
+    #[test]
+    fn test_ledoit_wolf_improves_on_sample_covariance() {
+        let data = gaussian(31, 100, 50);
+        let lw = ledoit_wolf(&data).unwrap();
+        let truth = Array2::eye(50);
+        let s = sample_covariance(&data);
+        assert!(frobenius_distance(&lw.covariance, &truth) < 0.5 * frobenius_distance(&s, &truth));
+        assert!(lw.shrinkage > 0.0 && lw.shrinkage <= 1.0);
+        assert!((lw.covariance.diag().sum() - s.diag().sum()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ledoit_wolf_little_shrinkage_when_well_estimated() {
+        // Strongly heterogeneous variances and n >> p: S is already accurate.
+        let mut data = gaussian(37, 5000, 5);
+        for (j, mut col) in data.columns_mut().into_iter().enumerate() {
+            col *= (j + 1) as f64 * 2.0;
+        }
+        let lw = ledoit_wolf(&data).unwrap();
+        assert!(lw.shrinkage < 0.05, "{}", lw.shrinkage);
+    }
+
+    #[test]
+    fn test_nonlinear_shrinkage_beats_linear_on_two_level_spectrum() {
+        // True spectrum: half the variances 1, half 4.
+        let (n, p) = (400, 100);
+        let mut data = gaussian(41, n, p);
+        for (j, mut col) in data.columns_mut().into_iter().enumerate() {
+            if j % 2 == 1 {
+                col *= 2.0;
+            }
+        }
+        let truth = Array2::from_diag(&ndarray::Array1::from_shape_fn(p, |j| {
+            if j % 2 == 1 {
+                4.0
+            } else {
+                1.0
+            }
+        }));
+        let nonlinear = nonlinear_shrinkage(&data).unwrap();
+        let linear = ledoit_wolf(&data).unwrap();
+        let sample = sample_covariance(&data);
+        let e_nl = frobenius_distance(&nonlinear.covariance, &truth);
+        let e_lw = frobenius_distance(&linear.covariance, &truth);
+        let e_s = frobenius_distance(&sample, &truth);
+        assert!(e_nl < e_lw && e_lw < e_s, "{} {} {}", e_nl, e_lw, e_s);
+    }
+
+    #[test]
+    fn test_nonlinear_shrinkage_more_variables_than_samples() {
+        let data = gaussian(43, 60, 100);
+        let cleaned = nonlinear_shrinkage(&data).unwrap();
+        assert_eq!(cleaned.eigenvalues.len(), 100);
+        assert!(cleaned
+            .eigenvalues
+            .iter()
+            .all(|&d| d > 0.0 && d.is_finite()));
+        let truth = Array2::eye(100);
+        assert!(
+            frobenius_distance(&cleaned.covariance, &truth)
+                < 0.5 * frobenius_distance(&sample_covariance(&data), &truth)
+        );
+    }
+
+    #[test]
+    fn test_rie_cleans_null_and_two_level_spectra() {
+        let data = gaussian(47, 300, 100);
+        let cleaned = rie_covariance(&data).unwrap();
+        // Null spectrum: the RIE collapses the MP bulk [0.18, 2.4] close to 1.
+        let (lo, hi) = cleaned
+            .eigenvalues
+            .iter()
+            .fold((f64::INFINITY, 0.0_f64), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        assert!(lo > 0.6 && hi < 1.4, "{} {}", lo, hi);
+
+        let truth = Array2::eye(100);
+        assert!(
+            frobenius_distance(&cleaned.covariance, &truth)
+                < 0.5 * frobenius_distance(&sample_covariance(&data), &truth)
+        );
+        assert!(rie_covariance(&gaussian(1, 50, 60)).is_none());
+    }
+
+    #[test]
+    fn test_clipped_covariance_keeps_factor() {
+        // One strong factor on top of unit noise.
+        let (n, p) = (500, 100);
+        let mut data = gaussian(53, n, p);
+        let factor = gaussian(59, n, 1);
+        for mut col in data.columns_mut() {
+            col += &(&factor.column(0) * 1.5);
+        }
+        let cleaned = clipped_covariance(&data).unwrap();
+        let sample = symmetric_eigen(&sample_covariance(&data)).0;
+        let top = *cleaned.eigenvalues.last().unwrap();
+        assert!((top - sample[p - 1] * (n as f64 / (n - 1) as f64)).abs() < 1e-9);
+        // Everything else collapses to one value near the unit noise variance.
+        let bulk = cleaned.eigenvalues[0];
+        assert!(cleaned.eigenvalues[..p - 1]
+            .iter()
+            .all(|&v| (v - bulk).abs() < 1e-12));
+        assert!((bulk - 1.0).abs() < 0.15, "{}", bulk);
+        let trace: f64 = cleaned.eigenvalues.iter().sum();
+        assert!((trace - cleaned.covariance.diag().sum()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_clipping_more_variables_than_samples() {
+        // Edge (1 + sqrt(2))^2 ~ 5.83, not the folded (1 + sqrt(1/2))^2 ~ 2.91.
+        assert_eq!(
+            clip_eigenvalues(&[0.5, 1.0, 3.0, 5.0], 2.0, 1.0),
+            vec![2.375; 4]
+        );
+
+        // Pure noise, gamma = p / (n - 1) = 2: the 100 nonzero eigenvalues are
+        // all bulk, averaging gamma sigma^2, and the 100 null ones stay at zero.
+        let (n, p) = (101, 200);
+        let cleaned = clipped_covariance(&gaussian(67, n, p)).unwrap();
+        let (null, bulk) = cleaned.eigenvalues.split_at(p - (n - 1));
+        assert!(null.iter().all(|v| v.abs() < 1e-9));
+        assert!(bulk.iter().all(|&v| (v - bulk[0]).abs() < 1e-9));
+        assert!((bulk[0] - 2.0).abs() < 0.1, "{}", bulk[0]);
+    }
+
+    #[test]
+    fn test_cleaner_strategies_and_validation() {
+        let data = gaussian(61, 200, 40);
+        let truth = Array2::eye(40);
+        let sample = CovarianceCleaner::new()
+            .strategy(CleaningStrategy::Sample)
+            .clean(&data)
+            .unwrap();
+        let s = sample_covariance(&data) * (200.0 / 199.0);
+        assert!(frobenius_distance(&sample.covariance, &s) < 1e-9);
+
+        let e_sample = frobenius_distance(&sample.covariance, &truth);
+        for strategy in [
+            CleaningStrategy::Clipping,
+            CleaningStrategy::Rie,
+            CleaningStrategy::LinearShrinkage,
+            CleaningStrategy::NonlinearShrinkage,
+        ] {
+            let cleaned = CovarianceCleaner::new()
+                .strategy(strategy)
+                .clean(&data)
+                .unwrap();
+            let e = frobenius_distance(&cleaned.covariance, &truth);
+            assert!(e < e_sample, "{:?}: {} vs {}", strategy, e, e_sample);
+        }
+
+        let mut constant = data.clone();
+        constant.column_mut(3).fill(1.0);
+        assert!(CovarianceCleaner::new()
+            .standardize(true)
+            .clean(&constant)
+            .is_none());
+        constant[[0, 0]] = f64::NAN;
+        assert!(CovarianceCleaner::new().clean(&constant).is_none());
+    }
+
+    #[test]
+    fn test_cleaner_clips_wide_correlation() {
+        // p = 120 > n - 1 = 59: the noise bulk of the correlation matrix is
+        // flattened to gamma ~ 2 while the null space stays empty.
+        let (n, p) = (60, 120);
+        let cleaner = CovarianceCleaner::new().standardize(true);
+        let data = gaussian(71, n, p);
+        let clean = |strategy| cleaner.strategy(strategy).clean(&data).unwrap().eigenvalues;
+        let clipped = clean(CleaningStrategy::Clipping);
+        assert_ne!(clipped, clean(CleaningStrategy::Sample));
+        let bulk = &clipped[p - (n - 1)..];
+        assert!(bulk.iter().all(|&v| (v - bulk[0]).abs() < 1e-9));
+        assert!((bulk[0] - p as f64 / (n - 1) as f64).abs() < 1e-6);
+        assert!(clipped[..p - (n - 1)].iter().all(|v| v.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_cleaner_standardize_rescales() {
+        let mut data = gaussian(67, 300, 20);
+        for mut col in data.columns_mut() {
+            col *= 5.0;
+        }
+        let cleaned = CovarianceCleaner::new()
+            .standardize(true)
+            .strategy(CleaningStrategy::Sample)
+            .clean(&data)
+            .unwrap();
+        // Correlation eigenvalues sum to p; the covariance carries the variances back.
+        assert!((cleaned.eigenvalues.iter().sum::<f64>() - 20.0).abs() < 1e-9);
+        assert!((cleaned.covariance[[0, 0]] / 25.0 - 1.0).abs() < 0.25);
+    }
+
+    #[test]
+    fn test_sample_covariance_centers() {
+        assert!(nonlinear_shrinkage_eigenvalues(&[1.0, 0.0], 5).is_empty());
+        let data = ndarray::array![[1.0, 10.0], [3.0, 10.0]];
+        let s = sample_covariance(&data);
+        assert_eq!(s, ndarray::array![[1.0, 0.0], [0.0, 0.0]]);
+        assert!(ledoit_wolf(&data.slice(ndarray::s![..1, ..]).to_owned()).is_none());
+    }
+}
@@ -0,0 +1,539 @@
+//! Low-rank matrix denoising by singular-value thresholding.
+//!
+//! For Y = X + sigma Z with X of low rank and Z an n x p matrix of i.i.d.
+//! N(0, 1) entries, the noise singular values fill the Marchenko-Pastur bulk
+//! [0, sigma (sqrt(n) + sqrt(p))] and signal components only separate from it
+//! beyond the BBP transition. Gavish and Donoho (2014) derived the hard
+//! threshold on the singular values of Y that minimizes the asymptotic
+//! Frobenius error of the truncated SVD; it sits above the bulk edge,
+//! because components just past the edge are too noisy to be worth keeping.
+//!
+//! Shrinking instead of keeping-or-killing does better: each kept singular
+//! value is pulled down to undo the upward bias of the noise and to account
+//! for the misaligned singular vectors. The optimal shrinker depends on the
+//! loss (Gavish and Donoho 2017), see [`ShrinkageLoss`].
+
+use ndarray::Array2;
+
+use crate::marchenko_pastur_quantile;
+use crate::simulation::quantile_sorted;
+use crate::symmetric_eigen;
+
+/// Gavish-Donoho hard-threshold coefficient lambda*(beta) for known noise.
+///
+/// lambda*(beta) = sqrt(2 (beta + 1) + 8 beta / (beta + 1 + sqrt(beta^2 + 14 beta + 1)));
+/// the optimal threshold is lambda*(beta) sqrt(max(n, p)) sigma. It equals
+/// 4 / sqrt(3) for square matrices and tends to sqrt(2) as beta -> 0.
+///
+/// # Arguments
+///
+/// * `beta` - Aspect ratio min(n, p) / max(n, p) in (0, 1]
+///
+/// # Returns
+///
+/// lambda*(beta), or NaN for beta outside (0, 1]
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::optimal_hard_threshold_coefficient;
+///
+/// assert!((optimal_hard_threshold_coefficient(1.0) - 4.0 / 3.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn optimal_hard_threshold_coefficient(beta: f64) -> f64 {
+    if !(beta > 0.0 && beta <= 1.0) {
+        return f64::NAN;
+    }
+    let root = (beta * beta + 14.0 * beta + 1.0).sqrt();
+    (2.0 * (beta + 1.0) + 8.0 * beta / (beta + 1.0 + root)).sqrt()
+}
+
+/// Gavish-Donoho hard-threshold coefficient omega(beta) for unknown noise.
+///
+/// The threshold omega(beta) * median singular value replaces sigma by
+/// the estimate median / sqrt(max(n, p) mu_beta), with mu_beta the median of
+/// the Marchenko-Pastur law at ratio beta, so omega(beta) =
+/// lambda*(beta) / sqrt(mu_beta). It is about 2.858 for square matrices.
+///
+/// # Arguments
+///
+/// * `beta` - Aspect ratio min(n, p) / max(n, p) in (0, 1]
+///
+/// # Returns
+///
+/// omega(beta), or NaN for beta outside (0, 1]
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::median_hard_threshold_coefficient;
+///
+/// assert!((median_hard_threshold_coefficient(1.0) - 2.858).abs() < 1e-3);
+/// ```
+pub fn median_hard_threshold_coefficient(beta: f64) -> f64 {
+    optimal_hard_threshold_coefficient(beta) / marchenko_pastur_quantile(0.5, beta, 1.0).sqrt()
+}
+
+/// Optimal hard threshold for the singular values of an n x p noisy matrix.
+///
+/// With a known noise level this is lambda*(beta) sqrt(max(n, p)) sigma
+/// (see [`optimal_hard_threshold_coefficient`]); without one it is
+/// omega(beta) times the median singular value (see
+/// [`median_hard_threshold_coefficient`]), which stays accurate as long as
+/// the rank is small compared to min(n, p).
+///
+/// # Arguments
+///
+/// * `singular_values` - All min(n, p) singular values of Y, in any order
+/// * `n` - Rows of Y
+/// * `p` - Columns of Y
+/// * `sigma` - Noise standard deviation per entry, or `None` to estimate it
+///
+/// # Returns
+///
+/// Threshold on the singular values, or NaN if n or p is zero, `sigma` is
+/// not positive, or `singular_values` is empty while sigma is unknown
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::optimal_hard_threshold;
+///
+/// // 100 x 100 with unit noise: 4 / sqrt(3) * 10.
+/// let tau = optimal_hard_threshold(&[], 100, 100, Some(1.0));
+/// assert!((tau - 23.094).abs() < 1e-3);
+/// ```
+pub fn optimal_hard_threshold(
+    singular_values: &[f64],
+    n: usize,
+    p: usize,
+    sigma: Option<f64>,
+) -> f64 {
+    if n == 0 || p == 0 {
+        return f64::NAN;
+    }
+    let (small, large) = (n.min(p) as f64, n.max(p) as f64);
+    let beta = small / large;
+    match sigma {
+        Some(sigma) if sigma > 0.0 => {
+            optimal_hard_threshold_coefficient(beta) * large.sqrt() * sigma
+        }
+        Some(_) => f64::NAN,
+        None => {
+            let mut sorted = singular_values.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            median_hard_threshold_coefficient(beta) * quantile_sorted(&sorted, 0.5)
+        }
+    }
+}
+
+/// Low-rank estimate of the signal in a noisy matrix.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DenoisedMatrix {
+    /// Estimate of the signal X
+    pub matrix: Array2<f64>,
+    /// Singular values of the estimate, descending; their count is its rank
+    pub singular_values: Vec<f64>,
+    /// Noise level per entry, given or estimated
+    pub sigma: f64,
+}
+
+/// Truncate the SVD of a noisy matrix at the optimal hard threshold.
+///
+/// Keeps the singular components of `y` whose singular value exceeds
+/// [`optimal_hard_threshold`] and drops the rest.
+///
+/// # Arguments
+///
+/// * `y` - n x p noisy matrix
+/// * `sigma` - Noise standard deviation per entry, or `None` to estimate it
+///   as median singular value / sqrt(max(n, p) mu_beta)
+///
+/// # Returns
+///
+/// `None` if `y` is empty or has non-finite entries, or the given or
+/// estimated `sigma` is not positive (the estimate is zero when at least half
+/// the singular values vanish)
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::hard_threshold_denoise;
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+/// // Rank-one signal with singular value 40 in 80 x 60 unit noise.
+/// let scale = 40.0 / (80.0_f64 * 60.0).sqrt();
+/// let y = ndarray::Array2::from_shape_fn((80, 60), |_| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     scale + z
+/// });
+/// let estimate = hard_threshold_denoise(&y, None).unwrap();
+/// assert_eq!(estimate.singular_values.len(), 1);
+/// assert!((estimate.sigma - 1.0).abs() < 0.1);
+/// ```
+pub fn hard_threshold_denoise(y: &Array2<f64>, sigma: Option<f64>) -> Option<DenoisedMatrix> {
+    let svd = GramSvd::new(y, sigma)?;
+    let threshold =
+        optimal_hard_threshold(&svd.singular_values, y.nrows(), y.ncols(), Some(svd.sigma));
+    Some(svd.reconstruct(y, |s| if s > threshold { s } else { 0.0 }))
+}
+
+/// Loss that [`optimal_shrinker`] minimizes between the estimate and the signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShrinkageLoss {
+    /// Frobenius norm; shrinks to l c c', the best multiple of the
+    /// misaligned singular vectors
+    Frobenius,
+    /// Operator (spectral) norm; shrinks to the debiased signal value l
+    Operator,
+    /// Nuclear norm; shrinks to l (c c' - s s')_+, the most aggressive of
+    /// the three, which also kills weak components beyond the bulk edge
+    Nuclear,
+}
+
+/// Optimal singular-value shrinker in noise units (Gavish and Donoho 2017).
+///
+/// The singular value y of the noisy matrix is measured in units of
+/// sigma sqrt(max(n, p)), so the noise bulk ends at 1 + sqrt(beta). Above
+/// the edge, y comes from a signal singular value
+///
+/// l(y) = sqrt((y^2 - beta - 1 + sqrt((y^2 - beta - 1)^2 - 4 beta)) / 2)
+///
+/// whose singular vectors have cosines c, c' with the observed ones, c^2 =
+/// (l^4 - beta) / (l^4 + beta l^2) and c'^2 = (l^4 - beta) / (l^4 + l^2);
+/// s and s' are the matching sines. The shrinker for the chosen loss
+/// (see [`ShrinkageLoss`]) is evaluated from these and is 0 on the bulk.
+///
+/// # Arguments
+///
+/// * `y` - Noisy singular value in units of sigma sqrt(max(n, p))
+/// * `beta` - Aspect ratio min(n, p) / max(n, p) in (0, 1]
+/// * `loss` - Loss to minimize
+///
+/// # Returns
+///
+/// Shrunk singular value in the same units, or NaN for beta outside (0, 1]
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{optimal_shrinker, ShrinkageLoss};
+///
+/// // Square matrix, y = 3: l = 2.618 and the Frobenius shrinker is sqrt(45) / 3.
+/// assert!((optimal_shrinker(3.0, 1.0, ShrinkageLoss::Operator) - 2.618).abs() < 1e-3);
+/// assert!((optimal_shrinker(3.0, 1.0, ShrinkageLoss::Frobenius) - 45.0_f64.sqrt() / 3.0).abs() < 1e-12);
+/// assert_eq!(optimal_shrinker(1.9, 1.0, ShrinkageLoss::Frobenius), 0.0);
+/// ```
+pub fn optimal_shrinker(y: f64, beta: f64, loss: ShrinkageLoss) -> f64 {
+    if !(beta > 0.0 && beta <= 1.0) {
+        return f64::NAN;
+    }
+    if y <= 1.0 + beta.sqrt() {
+        return 0.0;
+    }
+    let excess = y * y - beta - 1.0;
+    let discriminant = (excess * excess - 4.0 * beta).max(0.0).sqrt();
+    if loss == ShrinkageLoss::Frobenius {
+        // l c c' in closed form.
+        return discriminant / y;
+    }
+    let l2 = 0.5 * (excess + discriminant);
+    let l = l2.sqrt();
+    if loss == ShrinkageLoss::Operator {
+        return l;
+    }
+    let l4 = l2 * l2;
+    let c2 = ((l4 - beta) / (l4 + beta * l2)).clamp(0.0, 1.0);
+    let c2_right = ((l4 - beta) / (l4 + l2)).clamp(0.0, 1.0);
+    let cosines = (c2 * c2_right).sqrt();
+    let sines = ((1.0 - c2) * (1.0 - c2_right)).sqrt();
+    (l * (cosines - sines)).max(0.0)
+}
+
+/// Apply the optimal shrinker to the singular values of an n x p noisy matrix.
+///
+/// # Arguments
+///
+/// * `singular_values` - All min(n, p) singular values of Y, in any order
+/// * `n` - Rows of Y
+/// * `p` - Columns of Y
+/// * `sigma` - Noise standard deviation per entry, or `None` to estimate it
+///   from the median singular value as in [`hard_threshold_denoise`]
+/// * `loss` - Loss to minimize
+///
+/// # Returns
+///
+/// Shrunk singular values in the input order; empty if n or p is zero or
+/// `sigma` is not positive
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{shrink_singular_values, ShrinkageLoss};
+///
+/// // 100 x 100 with unit noise: the bulk ends at 20, 30 is y = 3 in noise units.
+/// let shrunk = shrink_singular_values(&[30.0, 15.0], 100, 100, Some(1.0), ShrinkageLoss::Frobenius);
+/// assert!((shrunk[0] - 10.0 * 45.0_f64.sqrt() / 3.0).abs() < 1e-10);
+/// assert_eq!(shrunk[1], 0.0);
+/// ```
+pub fn shrink_singular_values(
+    singular_values: &[f64],
+    n: usize,
+    p: usize,
+    sigma: Option<f64>,
+    loss: ShrinkageLoss,
+) -> Vec<f64> {
+    if n == 0 || p == 0 {
+        return vec![];
+    }
+    let sigma = match sigma {
+        Some(sigma) if sigma > 0.0 => sigma,
+        Some(_) => return vec![],
+        None => median_noise_level(singular_values, n, p),
+    };
+    let (small, large) = (n.min(p) as f64, n.max(p) as f64);
+    let unit = sigma * large.sqrt();
+    singular_values
+        .iter()
+        .map(|&s| unit * optimal_shrinker(s / unit, small / large, loss))
+        .collect()
+}
+
+/// Denoise a matrix by optimal shrinkage of its singular values.
+///
+/// Replaces each singular value of `y` by [`optimal_shrinker`] for the
+/// chosen loss and keeps the singular vectors. Under Frobenius loss this
+/// beats the optimal hard threshold of [`hard_threshold_denoise`] at every
+/// signal strength.
+///
+/// # Arguments
+///
+/// * `y` - n x p noisy matrix
+/// * `sigma` - Noise standard deviation per entry, or `None` to estimate it
+/// * `loss` - Loss to minimize
+///
+/// # Returns
+///
+/// `None` under the same conditions as [`hard_threshold_denoise`]
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{shrinkage_denoise, ShrinkageLoss};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+/// // Rank-one signal with singular value 30 in 80 x 60 unit noise.
+/// let scale = 30.0 / (80.0_f64 * 60.0).sqrt();
+/// let y = ndarray::Array2::from_shape_fn((80, 60), |_| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     scale + z
+/// });
+/// let estimate = shrinkage_denoise(&y, Some(1.0), ShrinkageLoss::Operator).unwrap();
+/// assert_eq!(estimate.singular_values.len(), 1);
+/// // The operator shrinker debiases the top singular value back toward 30.
+/// assert!((estimate.singular_values[0] - 30.0).abs() < 3.0);
+/// ```
+pub fn shrinkage_denoise(
+    y: &Array2<f64>,
+    sigma: Option<f64>,
+    loss: ShrinkageLoss,
+) -> Option<DenoisedMatrix> {
+    let svd = GramSvd::new(y, sigma)?;
+    let (n, p) = y.dim();
+    let (small, large) = (n.min(p) as f64, n.max(p) as f64);
+    let unit = svd.sigma * large.sqrt();
+    Some(svd.reconstruct(y, |s| {
+        unit * optimal_shrinker(s / unit, small / large, loss)
+    }))
+}
+
+/// Noise level from the median singular value: noise-only singular values
+/// squared over max(n, p) follow MP(beta, sigma^2), so match the medians.
+fn median_noise_level(singular_values: &[f64], n: usize, p: usize) -> f64 {
+    let mut sorted = singular_values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let (small, large) = (n.min(p) as f64, n.max(p) as f64);
+    let mu = marchenko_pastur_quantile(0.5, small / large, 1.0);
+    quantile_sorted(&sorted, 0.5) / (large * mu).sqrt()
+}
+
+/// Singular values and the singular vectors of the smaller side of a matrix.
+///
+/// Like [`crate::singular_values`] this diagonalizes the smaller Gram
+/// matrix, so singular values far below the largest lose relative accuracy;
+/// the shrinkers here set those to zero anyway.
+struct GramSvd {
+    /// Descending singular values
+    singular_values: Vec<f64>,
+    /// Matching right (p <= n) or left (p > n) singular vectors as columns
+    vectors: Array2<f64>,
+    /// Given or estimated noise level
+    sigma: f64,
+}
+
+impl GramSvd {
+    fn new(y: &Array2<f64>, sigma: Option<f64>) -> Option<Self> {
+        let (n, p) = y.dim();
+        if n == 0 || p == 0 || y.iter().any(|v| !v.is_finite()) {
+            return None;
+        }
+        let gram = if p <= n { y.t().dot(y) } else { y.dot(&y.t()) };
+        let (values, vectors) = symmetric_eigen(&gram);
+        let k = values.len();
+        let singular_values: Vec<f64> = values.iter().rev().map(|v| v.max(0.0).sqrt()).collect();
+        let vectors = Array2::from_shape_fn((k, k), |(i, j)| vectors[[i, k - 1 - j]]);
+        let sigma = match sigma.unwrap_or_else(|| median_noise_level(&singular_values, n, p)) {
+            sigma if sigma > 0.0 => sigma,
+            _ => return None,
+        };
+        Some(Self {
+            singular_values,
+            vectors,
+            sigma,
+        })
+    }
+
+    /// Sum of shrink(s_k) u_k v_k^T over the components with a positive
+    /// shrunk value, written as Y V diag(shrink(s) / s) V^T (or its
+    /// transpose analogue) so the other side's vectors are never formed.
+    fn reconstruct<F: Fn(f64) -> f64>(self, y: &Array2<f64>, shrink: F) -> DenoisedMatrix {
+        let (n, p) = y.dim();
+        let kept: Vec<(usize, f64)> = self
+            .singular_values
+            .iter()
+            .enumerate()
+            .map(|(k, &s)| (k, if s > 0.0 { shrink(s) } else { 0.0 }))
+            .filter(|&(_, value)| value > 0.0)
+            .collect();
+        let k = self.vectors.nrows();
+        let basis = Array2::from_shape_fn((k, kept.len()), |(i, j)| self.vectors[[i, kept[j].0]]);
+        let weighted = Array2::from_shape_fn((k, kept.len()), |(i, j)| {
+            let (index, value) = kept[j];
+            basis[[i, j]] * value / self.singular_values[index]
+        });
+        let projector = weighted.dot(&basis.t());
+        let matrix = if p <= n {
+            y.dot(&projector)
+        } else {
+            projector.dot(y)
+        };
+        DenoisedMatrix {
+            matrix,
+            singular_values: kept.iter().map(|&(_, value)| value).collect(),
+            sigma: self.sigma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::singular_values;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_hard_threshold_recovers_low_rank_signal() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(6);
+        for (n, p) in [(120, 80), (60, 150)] {
+            // Rank two with singular values 60 and 35; the bulk edge is about
+            // sqrt(n) + sqrt(p) = 20 and the threshold about 25.
+            let (large, small) = (n.max(p) as f64, n.min(p) as f64);
+            let signal = Array2::from_shape_fn((n, p), |(i, j)| {
+                let u1 = if i % 2 == 0 { 1.0 } else { -1.0 };
+                let v1 = if j % 2 == 0 { 1.0 } else { -1.0 };
+                let u2 = if (i / 2) % 2 == 0 { 1.0 } else { -1.0 };
+                let v2 = if (j / 2) % 2 == 0 { 1.0 } else { -1.0 };
+                (60.0 * u1 * v1 + 35.0 * u2 * v2) / (large * small).sqrt()
+            });
+            let y = &signal
+                + &Array2::from_shape_fn((n, p), |_| {
+                    let z: f64 = StandardNormal.sample(&mut rng);
+                    z
+                });
+
+            let tau = optimal_hard_threshold(&singular_values(&y), n, p, Some(1.0));
+            let tau_unknown = optimal_hard_threshold(&singular_values(&y), n, p, None);
+            assert!((tau - tau_unknown).abs() < 0.05 * tau);
+
+            let estimate = hard_threshold_denoise(&y, None).unwrap();
+            assert_eq!(estimate.singular_values.len(), 2);
+            assert!((estimate.sigma - 1.0).abs() < 0.05);
+            // The truncated SVD keeps the signal and most of the noise goes.
+            let error = (&estimate.matrix - &signal).mapv(|v| v * v).sum().sqrt();
+            let raw_error = (&y - &signal).mapv(|v| v * v).sum().sqrt();
+            assert!(error < 0.3 * raw_error);
+            let kept = singular_values(&estimate.matrix);
+            assert!((kept[0] - estimate.singular_values[0]).abs() < 1e-8);
+            assert!(kept[2] < 1e-5 * kept[0]);
+        }
+        assert!(hard_threshold_denoise(&Array2::zeros((0, 3)), None).is_none());
+        // Rank one: the median singular value, and so the estimated sigma, is zero.
+        let mut rank_one = Array2::zeros((10, 8));
+        rank_one[[0, 0]] = 5.0;
+        assert!(hard_threshold_denoise(&rank_one, None).is_none());
+        assert!(shrinkage_denoise(&rank_one, None, ShrinkageLoss::Frobenius).is_none());
+        assert!(hard_threshold_denoise(&rank_one, Some(0.1)).is_some());
+        assert!(optimal_hard_threshold(&[1.0], 3, 3, Some(0.0)).is_nan());
+    }
+
+    #[test]
+    fn test_optimal_shrinkers() {
+        let beta = 0.5_f64;
+        let edge = 1.0 + beta.sqrt();
+        for y in [1.8, 2.0, 3.0, 6.0] {
+            let operator = optimal_shrinker(y, beta, ShrinkageLoss::Operator);
+            let frobenius = optimal_shrinker(y, beta, ShrinkageLoss::Frobenius);
+            let nuclear = optimal_shrinker(y, beta, ShrinkageLoss::Nuclear);
+            assert!(nuclear <= frobenius && frobenius <= operator && operator < y);
+            // l(y) inverts the BBP map y^2 = (1 + l^2) (beta + l^2) / l^2.
+            let l2 = operator * operator;
+            assert!(((1.0 + l2) * (beta + l2) / l2 - y * y).abs() < 1e-10);
+        }
+        for loss in [
+            ShrinkageLoss::Operator,
+            ShrinkageLoss::Frobenius,
+            ShrinkageLoss::Nuclear,
+        ] {
+            assert_eq!(optimal_shrinker(edge, beta, loss), 0.0);
+            assert!(optimal_shrinker(3.0, 0.0, loss).is_nan());
+        }
+        // Nuclear loss kills components just past the edge.
+        assert_eq!(
+            optimal_shrinker(edge + 0.05, beta, ShrinkageLoss::Nuclear),
+            0.0
+        );
+
+        // Frobenius shrinkage beats the optimal hard threshold in Frobenius error.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(8);
+        let (n, p) = (100, 50);
+        let scale = ((n * p) as f64).sqrt();
+        let signal = Array2::from_shape_fn((n, p), |(i, j)| {
+            let u = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let v = if j % 3 == 0 { 1.0 } else { -0.5 };
+            22.0 * u * v / (scale * 0.75_f64.sqrt())
+        });
+        let y = &signal
+            + &Array2::from_shape_fn((n, p), |_| {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                z
+            });
+        let error = |estimate: &DenoisedMatrix| (&estimate.matrix - &signal).mapv(|v| v * v).sum();
+        let hard = hard_threshold_denoise(&y, Some(1.0)).unwrap();
+        let shrunk = shrinkage_denoise(&y, Some(1.0), ShrinkageLoss::Frobenius).unwrap();
+        assert_eq!(shrunk.singular_values.len(), 1);
+        assert!(error(&shrunk) < error(&hard));
+        let values = singular_values(&y);
+        let expected = shrink_singular_values(&values, n, p, Some(1.0), ShrinkageLoss::Frobenius);
+        assert!((expected[0] - shrunk.singular_values[0]).abs() < 1e-8);
+        assert!(
+            shrink_singular_values(&values, n, p, Some(-1.0), ShrinkageLoss::Nuclear).is_empty()
+        );
+    }
+}
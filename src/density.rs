@@ -0,0 +1,606 @@
+//! Spectral density estimates beyond the fixed-bin histogram.
+//!
+//! Histograms put a bin's worth of bias right where the interesting structure
+//! is: the square-root vanishing at the MP and semicircle edges. A Gaussian
+//! KDE with a data-driven bandwidth is smoother, and reflecting the kernel
+//! mass that spills past a known support boundary removes the factor-of-two
+//! underestimate a plain KDE has at the edge. When a histogram is wanted,
+//! [`BinRule`] picks the bin count from the data. [`EmpiricalCdf`] needs no
+//! smoothing parameter at all.
+
+use std::f64::consts::PI;
+
+use crate::empirical_spectral_density;
+use crate::gof::ks_distance_sorted;
+use crate::simulation::quantile_sorted;
+use crate::spectrum::AsEigenvalues;
+
+/// Bin-count rule for [`empirical_spectral_density_auto`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BinRule {
+    /// ceil(log2 n) + 1 bins; too few for large spectra
+    Sturges,
+    /// Bin width 3.49 sd n^(-1/3), optimal for Gaussian data
+    Scott,
+    /// Bin width 2 IQR n^(-1/3), robust to outliers such as spikes
+    FreedmanDiaconis,
+}
+
+/// Number of histogram bins for a spectrum under the given rule.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum in any order
+/// * `rule` - Bin-count rule
+///
+/// # Returns
+///
+/// Bin count in 1..=n (0 for an empty spectrum)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{histogram_bins, BinRule};
+///
+/// let values: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+/// assert_eq!(histogram_bins(&values, BinRule::Sturges), 11);
+/// ```
+pub fn histogram_bins<E: AsEigenvalues + ?Sized>(eigenvalues: &E, rule: BinRule) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mut sorted: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .collect();
+    if sorted.is_empty() {
+        return 0;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+    let range = sorted[sorted.len() - 1] - sorted[0];
+    let width = match rule {
+        BinRule::Sturges => return (n.log2().ceil() as usize + 1).min(sorted.len()),
+        BinRule::Scott => {
+            let mean = sorted.iter().sum::<f64>() / n;
+            let var = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+            3.49 * var.sqrt() * n.powf(-1.0 / 3.0)
+        }
+        BinRule::FreedmanDiaconis => {
+            let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+            2.0 * iqr * n.powf(-1.0 / 3.0)
+        }
+    };
+    if width <= 0.0 || range <= 0.0 {
+        return 1;
+    }
+    ((range / width).ceil() as usize).clamp(1, sorted.len())
+}
+
+/// [`empirical_spectral_density`] with the bin count chosen by a [`BinRule`].
+///
+/// # Returns
+///
+/// (bin_centers, densities)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{empirical_spectral_density_auto, BinRule};
+///
+/// let values: Vec<f64> = (0..1000).map(|i| (i as f64 / 1000.0).sqrt()).collect();
+/// let (centers, density) = empirical_spectral_density_auto(&values, BinRule::FreedmanDiaconis);
+/// assert_eq!(centers.len(), density.len());
+/// assert!(centers.len() > 10);
+/// ```
+pub fn empirical_spectral_density_auto<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    rule: BinRule,
+) -> (Vec<f64>, Vec<f64>) {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    empirical_spectral_density(eigenvalues, histogram_bins(eigenvalues, rule))
+}
+
+/// Bandwidth rule for [`kernel_spectral_density`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bandwidth {
+    /// Silverman's rule of thumb 0.9 min(sd, IQR / 1.34) n^(-1/5)
+    Silverman,
+    /// Sheather & Jones (1991) solve-the-equation plug-in; O(n^2) per iteration
+    SheatherJones,
+    /// Fixed bandwidth
+    Fixed(f64),
+}
+
+/// Histogram of a positive spectrum on log-spaced bins.
+///
+/// Bin edges are geometric between the smallest and largest positive
+/// eigenvalue, so a heavy tail spanning decades gets as many bins per decade
+/// as the bulk instead of a handful of nearly empty linear bins. Densities are
+/// count / (n width) with the linear bin width, so they estimate the same
+/// rho(lambda) as [`empirical_spectral_density`]; non-positive eigenvalues
+/// count towards n but fall in no bin.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum in any order
+/// * `bins` - Number of bins
+///
+/// # Returns
+///
+/// (geometric bin centers, densities); empty without a positive eigenvalue
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::log_spectral_density;
+///
+/// let values = [1.0, 10.0, 100.0, 1000.0];
+/// let (centers, _) = log_spectral_density(&values, 3);
+/// assert!((centers[0] - 10.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn log_spectral_density<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let positive: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .collect();
+    if positive.is_empty() || bins == 0 {
+        return (vec![], vec![]);
+    }
+    let lo = positive.iter().copied().fold(f64::INFINITY, f64::min).ln();
+    let hi = positive.iter().copied().fold(0.0, f64::max).ln();
+    if hi - lo < 1e-10 {
+        return (vec![lo.exp()], vec![1.0]);
+    }
+
+    let step = (hi - lo) / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for v in &positive {
+        let idx = ((v.ln() - lo) / step).floor() as usize;
+        counts[idx.min(bins - 1)] += 1;
+    }
+
+    let n = eigenvalues.len() as f64;
+    (0..bins)
+        .map(|i| {
+            let left = (lo + i as f64 * step).exp();
+            let right = (lo + (i + 1) as f64 * step).exp();
+            (
+                (left * right).sqrt(),
+                counts[i] as f64 / (n * (right - left)),
+            )
+        })
+        .unzip()
+}
+
+/// log10-log10 ESD for tail inspection: [`log_spectral_density`] with empty bins dropped.
+///
+/// A power-law density rho(lambda) ~ lambda^(-alpha) is a straight line of
+/// slope -alpha in these coordinates.
+///
+/// # Returns
+///
+/// (log10 bin centers, log10 densities) of the non-empty bins
+pub fn log_log_spectral_density<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let (centers, densities) = log_spectral_density(eigenvalues, bins);
+    centers
+        .into_iter()
+        .zip(densities)
+        .filter(|&(_, d)| d > 0.0)
+        .map(|(c, d)| (c.log10(), d.log10()))
+        .unzip()
+}
+
+/// Empirical CDF of a spectrum: a step function backed by the sorted eigenvalues.
+///
+/// Sorts once, then answers CDF, quantile, KS, and Q-Q queries in O(log n)
+/// or O(n) without re-sorting.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmpiricalCdf {
+    sorted: Vec<f64>,
+}
+
+impl EmpiricalCdf {
+    /// Build from eigenvalues in any order; NaNs are dropped.
+    pub fn new(eigenvalues: &[f64]) -> Self {
+        let mut sorted: Vec<f64> = eigenvalues
+            .iter()
+            .copied()
+            .filter(|v| !v.is_nan())
+            .collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        Self { sorted }
+    }
+
+    /// Wrap eigenvalues that are already sorted ascending and NaN-free.
+    pub(crate) fn from_sorted(sorted: Vec<f64>) -> Self {
+        Self { sorted }
+    }
+
+    /// F_n(x) = #{lambda_i <= x} / n (0 for an empty spectrum).
+    pub fn eval(&self, x: f64) -> f64 {
+        if self.sorted.is_empty() {
+            return 0.0;
+        }
+        self.sorted.partition_point(|&v| v <= x) as f64 / self.sorted.len() as f64
+    }
+
+    /// Left-continuous inverse: the smallest eigenvalue with F_n >= q.
+    ///
+    /// Returns NaN for an empty spectrum or q outside [0, 1].
+    pub fn quantile(&self, q: f64) -> f64 {
+        step_quantile(&self.sorted, q)
+    }
+
+    /// Kolmogorov-Smirnov distance sup |F_n(x) - F(x)| to a continuous CDF.
+    pub fn ks_distance<F: Fn(f64) -> f64>(&self, cdf: F) -> f64 {
+        ks_distance_sorted(&self.sorted, cdf)
+    }
+
+    /// Q-Q plot points (Q((i + 1/2) / n), lambda_(i)) against a reference quantile function.
+    pub fn qq_points<Q: Fn(f64) -> f64>(&self, quantile: Q) -> Vec<(f64, f64)> {
+        let n = self.sorted.len() as f64;
+        self.sorted
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (quantile((i as f64 + 0.5) / n), x))
+            .collect()
+    }
+
+    /// Sorted eigenvalues (the jump points of the step function).
+    pub fn values(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    /// Number of eigenvalues.
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Whether the spectrum is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+}
+
+/// Left-continuous inverse of the empirical CDF of sorted data.
+pub(crate) fn step_quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() || !(0.0..=1.0).contains(&q) {
+        return f64::NAN;
+    }
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+/// Empirical spectral CDF of a set of eigenvalues.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::empirical_spectral_cdf;
+///
+/// let cdf = empirical_spectral_cdf(&[3.0, 1.0, 2.0, 4.0]);
+/// assert_eq!(cdf.eval(2.5), 0.5);
+/// assert_eq!(cdf.quantile(0.5), 2.0);
+/// ```
+pub fn empirical_spectral_cdf<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> EmpiricalCdf {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    EmpiricalCdf::new(eigenvalues)
+}
+
+/// Bandwidth of a Gaussian KDE of the given values under the given rule.
+///
+/// # Arguments
+///
+/// * `values` - Samples in any order
+/// * `rule` - Bandwidth rule
+///
+/// # Returns
+///
+/// The bandwidth, or NaN for fewer than 2 finite values or a constant sample
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{kde_bandwidth, Bandwidth};
+///
+/// let values: Vec<f64> = (0..100).map(|i| i as f64).collect();
+/// let h = kde_bandwidth(&values, Bandwidth::Silverman);
+/// assert!(h > 5.0 && h < 15.0);
+/// ```
+pub fn kde_bandwidth<E: AsEigenvalues + ?Sized>(values: &E, rule: Bandwidth) -> f64 {
+    let values: &[f64] = &values.as_eigenvalues();
+    if let Bandwidth::Fixed(h) = rule {
+        return h;
+    }
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.len() < 2 {
+        return f64::NAN;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let silverman = silverman_bandwidth(&sorted);
+    if silverman.is_nan() || silverman <= 0.0 {
+        return f64::NAN;
+    }
+    match rule {
+        Bandwidth::SheatherJones => sheather_jones_bandwidth(&sorted, silverman),
+        _ => silverman,
+    }
+}
+
+/// Gaussian kernel density estimate of a spectrum on a grid.
+///
+/// f(x) = (1 / (n h)) sum_i phi((x - lambda_i) / h). With a `support`
+/// [a, b] the kernel mass beyond each boundary is reflected back into it
+/// (images 2a - lambda_i and 2b - lambda_i), so the estimate integrates to
+/// one over [a, b] and is zero outside; pass the MP support, or
+/// `(0.0, f64::INFINITY)` for a nonnegative spectrum.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum in any order
+/// * `grid` - Points at which to evaluate the density
+/// * `bandwidth` - Bandwidth rule (see [`kde_bandwidth`])
+/// * `support` - Optional support [a, b] for boundary reflection
+///
+/// # Returns
+///
+/// Density at each grid point (all zeros if the bandwidth cannot be computed)
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{
+///     kernel_spectral_density, marchenko_pastur_density, marchenko_pastur_support,
+///     sample_wishart_with, symmetric_eigenvalues, Bandwidth,
+/// };
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let eigenvalues = symmetric_eigenvalues(&(sample_wishart_with(&mut rng, 800, 200) / 800.0));
+/// let support = marchenko_pastur_support(0.25, 1.0);
+/// let kde = kernel_spectral_density(&eigenvalues, &[1.0], Bandwidth::Silverman, Some(support));
+/// assert!((kde[0] - marchenko_pastur_density(1.0, 0.25, 1.0)).abs() < 0.1);
+/// ```
+pub fn kernel_spectral_density<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    grid: &[f64],
+    bandwidth: Bandwidth,
+    support: Option<(f64, f64)>,
+) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let h = kde_bandwidth(eigenvalues, bandwidth);
+    let values: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .collect();
+    if values.is_empty() || h.is_nan() || h <= 0.0 {
+        return vec![0.0; grid.len()];
+    }
+    let norm = 1.0 / (values.len() as f64 * h * (2.0 * PI).sqrt());
+    let kernel = |x: f64, center: f64| (-0.5 * ((x - center) / h).powi(2)).exp();
+
+    grid.iter()
+        .map(|&x| match support {
+            None => norm * values.iter().map(|&v| kernel(x, v)).sum::<f64>(),
+            Some((a, b)) if x < a || x > b => 0.0,
+            Some((a, b)) => {
+                norm * values
+                    .iter()
+                    .map(|&v| {
+                        let mut k = kernel(x, v);
+                        if a.is_finite() {
+                            k += kernel(x, 2.0 * a - v);
+                        }
+                        if b.is_finite() {
+                            k += kernel(x, 2.0 * b - v);
+                        }
+                        k
+                    })
+                    .sum::<f64>()
+            }
+        })
+        .collect()
+}
+
+fn silverman_bandwidth(sorted: &[f64]) -> f64 {
+    let n = sorted.len() as f64;
+    let mean = sorted.iter().sum::<f64>() / n;
+    let sd = (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let iqr = quantile_sorted(sorted, 0.75) - quantile_sorted(sorted, 0.25);
+    let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+    0.9 * spread * n.powf(-0.2)
+}
+
+/// Sheather-Jones bandwidth: root of h = (R(K) / (n psi_4(g(h))))^(1/5).
+///
+/// The pilot bandwidth g(h) = 1.357 (S(a) / T(b))^(1/7) h^(5/7) uses the
+/// normal-scale pilots a = 0.920 IQR n^(-1/7) and b = 0.912 IQR n^(-1/9) for
+/// the density functionals S = psi_4 and T = -psi_6.
+fn sheather_jones_bandwidth(sorted: &[f64], silverman: f64) -> f64 {
+    let n = sorted.len() as f64;
+    let iqr = quantile_sorted(sorted, 0.75) - quantile_sorted(sorted, 0.25);
+    let scale = if iqr > 0.0 { iqr } else { silverman };
+    let a = 0.920 * scale * n.powf(-1.0 / 7.0);
+    let b = 0.912 * scale * n.powf(-1.0 / 9.0);
+    let s = psi(sorted, a, 4);
+    let t = -psi(sorted, b, 6);
+    if s.is_nan() || t.is_nan() || s <= 0.0 || t <= 0.0 {
+        return silverman;
+    }
+    let pilot = 1.357 * (s / t).powf(1.0 / 7.0);
+    let roughness = 1.0 / (2.0 * PI.sqrt());
+
+    let equation = |h: f64| {
+        let psi4 = psi(sorted, pilot * h.powf(5.0 / 7.0), 4);
+        h - (roughness / (n * psi4)).powf(0.2)
+    };
+    let (mut lo, mut hi) = (0.01 * silverman, 10.0 * silverman);
+    let (f_lo, f_hi) = (equation(lo), equation(hi));
+    if f_lo.is_nan() || f_hi.is_nan() || f_lo >= 0.0 || f_hi <= 0.0 {
+        return silverman;
+    }
+    for _ in 0..60 {
+        let mid = 0.5 * (lo + hi);
+        if equation(mid) < 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Density functional psi_r = (1 / (n (n - 1) g^(r+1))) sum_{i, j} phi^(r)((x_i - x_j) / g) for r = 4, 6.
+fn psi(values: &[f64], g: f64, r: u32) -> f64 {
+    let n = values.len() as f64;
+    let hermite = |u: f64| {
+        let u2 = u * u;
+        match r {
+            4 => u2 * u2 - 6.0 * u2 + 3.0,
+            _ => u2 * u2 * u2 - 15.0 * u2 * u2 + 45.0 * u2 - 15.0,
+        }
+    };
+    let phi = |u: f64| (-0.5 * u * u).exp() / (2.0 * PI).sqrt();
+    let mut sum = 0.0;
+    for (i, &xi) in values.iter().enumerate() {
+        for &xj in &values[i + 1..] {
+            let u = (xi - xj) / g;
+            sum += 2.0 * hermite(u) * phi(u);
+        }
+    }
+    // The diagonal i == j is included, as in Sheather & Jones.
+    sum += n * hermite(0.0) * phi(0.0);
+    sum / (n * (n - 1.0) * g.powi(r as i32 + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{marchenko_pastur_density, marchenko_pastur_support, symmetric_eigenvalues};
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_bin_rules() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(163);
+        let mut values: Vec<f64> = (0..8000).map(|_| StandardNormal.sample(&mut rng)).collect();
+        let scott = histogram_bins(&values, BinRule::Scott);
+        let fd = histogram_bins(&values, BinRule::FreedmanDiaconis);
+        assert_eq!(histogram_bins(&values, BinRule::Sturges), 14);
+        assert!(scott > 30 && fd > 30, "{} {}", scott, fd);
+
+        // A far outlier stretches the range: FD keeps the bin width, so the count grows.
+        values.push(1000.0);
+        assert!(histogram_bins(&values, BinRule::FreedmanDiaconis) > 10 * fd);
+        assert_eq!(histogram_bins(&[2.0; 5], BinRule::Scott), 1);
+        assert_eq!(histogram_bins(&[], BinRule::Sturges), 0);
+    }
+
+    #[test]
+    fn test_log_log_slope_of_pareto() {
+        // Exact Pareto quantiles with density exponent alpha = 3.
+        let values: Vec<f64> = (1..=20_000).map(|i| (20_000.0 / i as f64).sqrt()).collect();
+        let (x, y) = log_log_spectral_density(&values, 20);
+        let slope = (y[8] - y[1]) / (x[8] - x[1]);
+        assert!((slope + 3.0).abs() < 0.1, "{}", slope);
+
+        // Log bins from 1 to values[0]: the bin around c has width 2 c sinh(step / 2).
+        let (centers, densities) = log_spectral_density(&values, 20);
+        let step = values[0].ln() / 20.0;
+        let mass: f64 = centers
+            .iter()
+            .zip(&densities)
+            .map(|(c, d)| d * 2.0 * c * (0.5 * step).sinh())
+            .sum();
+        assert!((mass - 1.0).abs() < 1e-9, "{}", mass);
+        assert_eq!(log_spectral_density(&[-1.0, 0.0], 5), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_empirical_cdf() {
+        let cdf = empirical_spectral_cdf(&[0.5, f64::NAN, 0.1, 0.9, 0.3]);
+        assert_eq!(cdf.len(), 4);
+        assert_eq!(cdf.values(), &[0.1, 0.3, 0.5, 0.9]);
+        assert_eq!(
+            (cdf.eval(0.0), cdf.eval(0.3), cdf.eval(1.0)),
+            (0.0, 0.5, 1.0)
+        );
+        assert_eq!(
+            (cdf.quantile(0.0), cdf.quantile(0.26), cdf.quantile(1.0)),
+            (0.1, 0.3, 0.9)
+        );
+        assert!(cdf.quantile(1.5).is_nan());
+        // Uniform reference: D = max over the steps.
+        assert!((cdf.ks_distance(|x| x.clamp(0.0, 1.0)) - 0.25).abs() < 1e-12);
+        assert_eq!(cdf.qq_points(|u| u)[0], (0.125, 0.1));
+        assert!(empirical_spectral_cdf(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_sheather_jones_on_normal_sample() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(151);
+        let values: Vec<f64> = (0..1000).map(|_| StandardNormal.sample(&mut rng)).collect();
+        // Normal reference: 1.06 n^(-1/5) = 0.266.
+        let h = kde_bandwidth(&values, Bandwidth::SheatherJones);
+        assert!(h > 0.2 && h < 0.35, "{}", h);
+        assert!(kde_bandwidth(&[1.0, 1.0], Bandwidth::Silverman).is_nan());
+        assert_eq!(kde_bandwidth(&[], Bandwidth::Fixed(0.5)), 0.5);
+    }
+
+    #[test]
+    fn test_reflection_fixes_mp_edge() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(157);
+        let (n, p) = (1600, 400);
+        let x = ndarray::Array2::from_shape_fn((n, p), |_| StandardNormal.sample(&mut rng));
+        let eigenvalues = symmetric_eigenvalues(&(x.t().dot(&x) / n as f64));
+        let (lo, hi) = marchenko_pastur_support(0.25, 1.0);
+
+        let grid: Vec<f64> = (0..=400)
+            .map(|i| lo + (hi - lo) * i as f64 / 400.0)
+            .collect();
+        let step = (hi - lo) / 400.0;
+        let reflected = kernel_spectral_density(
+            &eigenvalues,
+            &grid,
+            Bandwidth::SheatherJones,
+            Some((lo, hi)),
+        );
+        let plain = kernel_spectral_density(&eigenvalues, &grid, Bandwidth::SheatherJones, None);
+        let mass: f64 = reflected.iter().sum::<f64>() * step;
+        assert!((mass - 1.0).abs() < 0.03, "{}", mass);
+
+        // Mid-bulk both agree with MP; the plain estimate leaks mass out of the support.
+        let mid = 200;
+        let exact = marchenko_pastur_density(grid[mid], 0.25, 1.0);
+        assert!(
+            (reflected[mid] - exact).abs() < 0.05,
+            "{} vs {}",
+            reflected[mid],
+            exact
+        );
+        let plain_mass: f64 = plain.iter().sum::<f64>() * step;
+        assert!(plain_mass < mass);
+        assert_eq!(
+            kernel_spectral_density(
+                &eigenvalues,
+                &[hi + 1.0],
+                Bandwidth::Silverman,
+                Some((lo, hi))
+            ),
+            vec![0.0]
+        );
+    }
+}
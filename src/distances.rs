@@ -0,0 +1,301 @@
+//! Distances between spectra and between a spectrum and a limiting law.
+//!
+//! Goodness-of-fit p-values answer "could this come from the law?"; these
+//! answer "how far apart are they?", which is what model comparison and
+//! tracking a spectrum over training need.
+
+use crate::special::gauss_legendre;
+use crate::spectrum::AsEigenvalues;
+use crate::{marchenko_pastur_density, wigner_semicircle_density};
+
+/// Wasserstein distance W_p between two eigenvalue sets.
+///
+/// In one dimension the optimal coupling matches quantiles, so
+/// W_p^p = int_0^1 |F_a^{-1}(u) - F_b^{-1}(u)|^p du. The empirical quantile
+/// functions are step functions, and the integral is evaluated exactly over
+/// the merged breakpoints i/n and j/m, so the sets may differ in size.
+///
+/// # Arguments
+///
+/// * `a`, `b` - Eigenvalues in any order
+/// * `order` - p >= 1 (1 for the earth mover's distance, 2 for the quadratic one)
+///
+/// # Returns
+///
+/// W_p, or NaN if either set is empty or `order` < 1
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spectral_wasserstein;
+///
+/// // Shifting every eigenvalue by 0.5 moves the spectrum by exactly 0.5.
+/// let a = [0.1, 0.7, 1.3, 2.0];
+/// let b: Vec<f64> = a.iter().map(|x| x + 0.5).collect();
+/// assert!((spectral_wasserstein(&a, &b, 1.0) - 0.5).abs() < 1e-12);
+/// assert!((spectral_wasserstein(&a, &b, 2.0) - 0.5).abs() < 1e-12);
+/// ```
+pub fn spectral_wasserstein<A, B>(a: &A, b: &B, order: f64) -> f64
+where
+    A: AsEigenvalues + ?Sized,
+    B: AsEigenvalues + ?Sized,
+{
+    let (a, b) = (a.as_eigenvalues(), b.as_eigenvalues());
+    if a.is_empty() || b.is_empty() || order < 1.0 {
+        return f64::NAN;
+    }
+    let a = sorted_copy(&a);
+    let b = sorted_copy(&b);
+    let (n, m) = (a.len(), b.len());
+
+    // Walk the merged breakpoints i/n and j/m with integer cross-multiplication.
+    let (mut i, mut j) = (0, 0);
+    let mut previous = 0.0;
+    let mut total = 0.0;
+    while i < n && j < m {
+        let next_a = (i + 1) * m;
+        let next_b = (j + 1) * n;
+        let next = next_a.min(next_b) as f64 / (n * m) as f64;
+        total += (next - previous) * (a[i] - b[j]).abs().powf(order);
+        previous = next;
+        if next_a <= next_b {
+            i += 1;
+        }
+        if next_b <= next_a {
+            j += 1;
+        }
+    }
+    total.powf(1.0 / order)
+}
+
+/// Wasserstein distance W_p between an eigenvalue set and a law given by its quantile function.
+///
+/// Integrates |F_n^{-1}(u) - Q(u)|^p over each block (i/n, (i + 1)/n) where
+/// the empirical quantile is constant, with Gauss-Legendre nodes (which
+/// avoid the endpoints u = 0 and u = 1, where Q may be infinite).
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues in any order
+/// * `quantile` - Quantile function Q(u) of the reference law on (0, 1)
+/// * `order` - p >= 1
+///
+/// # Returns
+///
+/// W_p, or NaN if the set is empty or `order` < 1
+pub fn spectral_wasserstein_to<Q: Fn(f64) -> f64, E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    quantile: Q,
+    order: f64,
+) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() || order < 1.0 {
+        return f64::NAN;
+    }
+    let sorted = sorted_copy(eigenvalues);
+    let n = sorted.len() as f64;
+    let total: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let (nodes, weights) = gauss_legendre(8, i as f64 / n, (i + 1) as f64 / n);
+            nodes
+                .iter()
+                .zip(&weights)
+                .map(|(&u, &w)| w * (x - quantile(u)).abs().powf(order))
+                .sum::<f64>()
+        })
+        .sum();
+    total.powf(1.0 / order)
+}
+
+/// Divergences between a binned spectrum and a reference density.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectralDivergences {
+    /// KL(empirical || reference) in nats (infinite if the reference has no mass where eigenvalues fall)
+    pub kullback_leibler: f64,
+    /// Pearson chi-square sum (p - q)^2 / q
+    pub chi_square: f64,
+    /// Total variation distance (1/2) sum |p - q|
+    pub total_variation: f64,
+    /// Number of histogram bins used
+    pub bins: usize,
+}
+
+/// KL, chi-square, and total-variation divergences of a spectrum from a reference density.
+///
+/// The eigenvalues are binned into ceil(sqrt(n)) equal-width bins spanning
+/// their range, and the reference mass of each bin is integrated from
+/// `density` on the same bins. Reference mass outside the sample range forms
+/// one extra cell with empirical mass 0, so both sides remain probability
+/// vectors and misfit in the tails is not hidden by renormalization.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues in any order
+/// * `density` - Reference probability density
+///
+/// # Returns
+///
+/// `None` for fewer than two distinct eigenvalues
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spectral_divergences;
+///
+/// let samples: Vec<f64> = (0..400).map(|i| (i as f64 + 0.5) / 400.0).collect();
+/// let uniform = |x: f64| if (0.0..=1.0).contains(&x) { 1.0 } else { 0.0 };
+/// let d = spectral_divergences(&samples, uniform).unwrap();
+/// assert!(d.total_variation < 0.01);
+/// ```
+pub fn spectral_divergences<F: Fn(f64) -> f64, E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    density: F,
+) -> Option<SpectralDivergences> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let sorted = sorted_copy(eigenvalues);
+    let (&lo, &hi) = (sorted.first()?, sorted.last()?);
+    if hi <= lo {
+        return None;
+    }
+    let n = sorted.len();
+    let bins = (n as f64).sqrt().ceil() as usize;
+    let width = (hi - lo) / bins as f64;
+
+    let mut counts = vec![0usize; bins];
+    for &x in &sorted {
+        counts[(((x - lo) / width) as usize).min(bins - 1)] += 1;
+    }
+    let empirical = counts.iter().map(|&c| c as f64 / n as f64);
+    let reference: Vec<f64> = (0..bins)
+        .map(|k| {
+            let a = lo + k as f64 * width;
+            let (nodes, weights) = gauss_legendre(8, a, a + width);
+            nodes
+                .iter()
+                .zip(&weights)
+                .map(|(&x, &w)| w * density(x).max(0.0))
+                .sum()
+        })
+        .collect();
+    let outside = (1.0 - reference.iter().sum::<f64>()).max(0.0);
+
+    let mut kullback_leibler = 0.0;
+    let mut chi_square = outside;
+    let mut total_variation = outside;
+    for (p, &q) in empirical.zip(&reference) {
+        if p > 0.0 {
+            kullback_leibler += p * (p / q).ln();
+        }
+        chi_square += if q > 0.0 {
+            (p - q).powi(2) / q
+        } else if p > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        total_variation += (p - q).abs();
+    }
+
+    Some(SpectralDivergences {
+        kullback_leibler,
+        chi_square,
+        total_variation: 0.5 * total_variation,
+        bins,
+    })
+}
+
+/// Divergences of a spectrum from the Marchenko-Pastur law.
+pub fn spectral_divergences_mp<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> Option<SpectralDivergences> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    spectral_divergences(eigenvalues, |x| {
+        marchenko_pastur_density(x, ratio, sigma_sq)
+    })
+}
+
+/// Divergences of a spectrum from the Wigner semicircle.
+pub fn spectral_divergences_semicircle<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    sigma: f64,
+) -> Option<SpectralDivergences> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    spectral_divergences(eigenvalues, |x| wigner_semicircle_density(x, sigma))
+}
+
+fn sorted_copy(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sample_goe_with, sample_wishart_with, symmetric_eigenvalues};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_wasserstein_unequal_sizes() {
+        // a = {0, 1} vs b = {0, 0.5, 1}: quantile blocks
+        // (0, 1/3): 0 vs 0, (1/3, 1/2): 0 vs 0.5, (1/2, 2/3): 1 vs 0.5, (2/3, 1): 1 vs 1.
+        let w1 = spectral_wasserstein(&[1.0, 0.0], &[0.5, 0.0, 1.0], 1.0);
+        assert!((w1 - (1.0 / 6.0) * 0.5 * 2.0).abs() < 1e-12);
+        assert!(
+            (spectral_wasserstein(&[0.0, 1.0], &[0.0, 1.0], 2.0)).abs() < 1e-15,
+            "identical sets"
+        );
+    }
+
+    #[test]
+    fn test_wasserstein_to_uniform() {
+        // Midpoints (i + 1/2)/n of a uniform grid: W_1 = 1/(4n).
+        let n = 50;
+        let samples: Vec<f64> = (0..n).map(|i| (i as f64 + 0.5) / n as f64).collect();
+        let w1 = spectral_wasserstein_to(&samples, |u| u, 1.0);
+        // The kink of |x - u| inside each block limits Gauss-Legendre accuracy.
+        assert!((w1 * n as f64 - 0.25).abs() < 1e-2, "{}", w1);
+
+        // Shift by 1: W_p = 1 for every p up to the discretization error.
+        let shifted: Vec<f64> = samples.iter().map(|x| x + 1.0).collect();
+        let w2 = spectral_wasserstein_to(&shifted, |u| u, 2.0);
+        assert!((w2 - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_wasserstein_invalid() {
+        assert!(spectral_wasserstein(&[], &[1.0], 1.0).is_nan());
+        assert!(spectral_wasserstein(&[1.0], &[1.0], 0.5).is_nan());
+        assert!(spectral_wasserstein_to(&[], |u| u, 1.0).is_nan());
+    }
+
+    #[test]
+    fn test_divergences_prefer_correct_law() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(23);
+        let (n, p) = (800, 200);
+        let cov = sample_wishart_with(&mut rng, n, p) / n as f64;
+        let eigenvalues = symmetric_eigenvalues(&cov);
+        let right = spectral_divergences_mp(&eigenvalues, 0.25, 1.0).unwrap();
+        let wrong = spectral_divergences_mp(&eigenvalues, 0.25, 1.5).unwrap();
+        assert!(right.total_variation < 0.15, "{:?}", right);
+        assert!(right.total_variation < wrong.total_variation);
+        assert!(right.chi_square < wrong.chi_square);
+        assert!(right.kullback_leibler < wrong.kullback_leibler);
+        assert_eq!(right.bins, 15);
+    }
+
+    #[test]
+    fn test_divergences_semicircle_and_degenerate() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(29);
+        let eigenvalues = symmetric_eigenvalues(&sample_goe_with(&mut rng, 300));
+        let d = spectral_divergences_semicircle(&eigenvalues, 1.0).unwrap();
+        assert!(d.total_variation < 0.15, "{:?}", d);
+        assert!(spectral_divergences(&[1.0, 1.0], |_| 1.0).is_none());
+        assert!(spectral_divergences(&[], |_| 1.0).is_none());
+    }
+}
@@ -0,0 +1,656 @@
+//! Limiting spectral laws as distribution objects.
+//!
+//! Each type fixes its parameters once and offers `pdf`, `cdf`, and
+//! `quantile` with the same argument order, plus a
+//! [`rand_distr::Distribution`] implementation that samples by inverting the
+//! CDF. Constructors validate their parameters and return [`Error`]; the free
+//! functions ([`crate::marchenko_pastur_density`] and friends) remain the
+//! unchecked primitive layer these delegate to.
+
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand_distr::Distribution;
+
+use crate::error::Error;
+use crate::special::gauss_legendre;
+use crate::{
+    marchenko_pastur_cdf, marchenko_pastur_density, marchenko_pastur_quantile,
+    marchenko_pastur_support, tracy_widom_cdf, wigner_semicircle_cdf, wigner_semicircle_density,
+};
+
+/// Marchenko-Pastur law of the eigenvalues of (1/n) X^T X.
+///
+/// The ratio is folded via min(gamma, 1/gamma) like [`crate::marchenko_pastur_density`],
+/// i.e. this is the law of the nonzero eigenvalues.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::MarchenkoPastur;
+///
+/// let mp = MarchenkoPastur::new(0.25, 1.0).unwrap();
+/// let (lo, hi) = mp.support();
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let x: f64 = mp.sample(&mut rng);
+/// assert!(x >= lo && x <= hi);
+/// assert!((mp.cdf(mp.quantile(0.3)) - 0.3).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MarchenkoPasturParams"))]
+pub struct MarchenkoPastur {
+    ratio: f64,
+    sigma_sq: f64,
+}
+
+impl MarchenkoPastur {
+    /// MP law with aspect ratio gamma = p/n and entry variance sigma^2.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless both are positive and finite.
+    pub fn new(ratio: f64, sigma_sq: f64) -> Result<Self, Error> {
+        Ok(Self {
+            ratio: Error::check_positive("ratio", ratio)?,
+            sigma_sq: Error::check_positive("sigma_sq", sigma_sq)?,
+        })
+    }
+
+    /// Aspect ratio gamma = p/n.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Entry variance sigma^2.
+    pub fn sigma_sq(&self) -> f64 {
+        self.sigma_sq
+    }
+
+    /// Support [lambda_-, lambda_+].
+    pub fn support(&self) -> (f64, f64) {
+        marchenko_pastur_support(self.ratio, self.sigma_sq)
+    }
+
+    /// Density at x.
+    pub fn pdf(&self, x: f64) -> f64 {
+        marchenko_pastur_density(x, self.ratio, self.sigma_sq)
+    }
+
+    /// CDF at x.
+    pub fn cdf(&self, x: f64) -> f64 {
+        marchenko_pastur_cdf(x, self.ratio, self.sigma_sq)
+    }
+
+    /// Quantile function (NaN for q outside [0, 1]).
+    pub fn quantile(&self, q: f64) -> f64 {
+        marchenko_pastur_quantile(q, self.ratio, self.sigma_sq)
+    }
+}
+
+impl Distribution<f64> for MarchenkoPastur {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Wigner semicircle law on [-2 sigma, 2 sigma].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "WignerSemicircleParams"))]
+pub struct WignerSemicircle {
+    sigma: f64,
+}
+
+impl WignerSemicircle {
+    /// Semicircle of radius 2 sigma.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless sigma is positive and finite.
+    pub fn new(sigma: f64) -> Result<Self, Error> {
+        Ok(Self {
+            sigma: Error::check_positive("sigma", sigma)?,
+        })
+    }
+
+    /// Scale sigma.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Support [-2 sigma, 2 sigma].
+    pub fn support(&self) -> (f64, f64) {
+        (-2.0 * self.sigma, 2.0 * self.sigma)
+    }
+
+    /// Density at x.
+    pub fn pdf(&self, x: f64) -> f64 {
+        wigner_semicircle_density(x, self.sigma)
+    }
+
+    /// CDF at x.
+    pub fn cdf(&self, x: f64) -> f64 {
+        wigner_semicircle_cdf(x, self.sigma)
+    }
+
+    /// Quantile function (NaN for q outside [0, 1]).
+    pub fn quantile(&self, q: f64) -> f64 {
+        let (lo, hi) = self.support();
+        invert_cdf(|x| self.cdf(x), q, lo, hi)
+    }
+}
+
+impl Distribution<f64> for WignerSemicircle {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Tracy-Widom law F_beta of the rescaled largest eigenvalue, for beta = 1, 2, or 4.
+///
+/// The CDF is interpolated from tables (see [`crate::tracy_widom_cdf`]); the
+/// density is its numerical derivative, and quantiles and samples cost
+/// about 60 CDF evaluations each. For other beta, sample with
+/// [`crate::StochasticAiry`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "TracyWidomParams"))]
+pub struct TracyWidom {
+    beta: f64,
+}
+
+impl TracyWidom {
+    /// Tracy-Widom law for Dyson index beta.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless beta is 1, 2, or 4.
+    pub fn new(beta: f64) -> Result<Self, Error> {
+        if beta == 1.0 || beta == 2.0 || beta == 4.0 {
+            Ok(Self { beta })
+        } else {
+            Err(Error::InvalidParameter {
+                name: "beta",
+                value: beta,
+                expected: "1, 2, or 4",
+            })
+        }
+    }
+
+    /// Dyson index beta.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Density at s (central difference of the CDF).
+    pub fn pdf(&self, s: f64) -> f64 {
+        let h = 1e-4;
+        ((self.cdf(s + h) - self.cdf(s - h)) / (2.0 * h)).max(0.0)
+    }
+
+    /// CDF at s.
+    pub fn cdf(&self, s: f64) -> f64 {
+        tracy_widom_cdf(s, self.beta)
+    }
+
+    /// Quantile function (NaN for q outside [0, 1]); saturates at -12 and 12 in the extreme tails.
+    pub fn quantile(&self, q: f64) -> f64 {
+        invert_cdf(|s| self.cdf(s), q, -12.0, 12.0)
+    }
+}
+
+impl Distribution<f64> for TracyWidom {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Kesten-McKay law: limiting adjacency spectrum of random d-regular graphs.
+///
+/// rho(x) = d sqrt(4 (d - 1) - x^2) / (2 pi (d^2 - x^2)) on |x| <= 2 sqrt(d - 1).
+/// As d grows, x / sqrt(d - 1) tends to the semicircle on [-2, 2].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "KestenMcKayParams"))]
+pub struct KestenMcKay {
+    degree: f64,
+}
+
+impl KestenMcKay {
+    /// Kesten-McKay law for degree d.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless d >= 2 is finite.
+    pub fn new(degree: f64) -> Result<Self, Error> {
+        if degree.is_finite() && degree >= 2.0 {
+            Ok(Self { degree })
+        } else {
+            Err(Error::InvalidParameter {
+                name: "degree",
+                value: degree,
+                expected: "finite and at least 2",
+            })
+        }
+    }
+
+    /// Degree d.
+    pub fn degree(&self) -> f64 {
+        self.degree
+    }
+
+    /// Support [-2 sqrt(d - 1), 2 sqrt(d - 1)].
+    pub fn support(&self) -> (f64, f64) {
+        let edge = self.edge();
+        (-edge, edge)
+    }
+
+    /// Density at x.
+    pub fn pdf(&self, x: f64) -> f64 {
+        let edge = self.edge();
+        if x.abs() >= edge {
+            return 0.0;
+        }
+        let d = self.degree;
+        d * (edge * edge - x * x).sqrt() / (2.0 * PI * (d * d - x * x))
+    }
+
+    /// CDF at x, by Gauss-Legendre quadrature in the angle x = -2 sqrt(d - 1) cos t.
+    ///
+    /// The substitution removes the square-root edge singularity, leaving the
+    /// smooth integrand d r^2 sin^2 t / (2 pi (d^2 - r^2 cos^2 t)).
+    pub fn cdf(&self, x: f64) -> f64 {
+        let r = self.edge();
+        if x <= -r {
+            return 0.0;
+        }
+        if x >= r {
+            return 1.0;
+        }
+        let d = self.degree;
+        let upper = (-x / r).clamp(-1.0, 1.0).acos();
+        let (nodes, weights) = gauss_legendre(64, 0.0, upper);
+        let value: f64 = nodes
+            .iter()
+            .zip(&weights)
+            .map(|(&t, &w)| {
+                let (s, c) = t.sin_cos();
+                w * d * r * r * s * s / (2.0 * PI * (d * d - r * r * c * c))
+            })
+            .sum();
+        value.clamp(0.0, 1.0)
+    }
+
+    /// Quantile function (NaN for q outside [0, 1]).
+    pub fn quantile(&self, q: f64) -> f64 {
+        let (lo, hi) = self.support();
+        invert_cdf(|x| self.cdf(x), q, lo, hi)
+    }
+
+    fn edge(&self) -> f64 {
+        2.0 * (self.degree - 1.0).sqrt()
+    }
+}
+
+impl Distribution<f64> for KestenMcKay {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Edelman's limit law of kappa / n, the scaled condition number of a square Gaussian matrix.
+///
+/// For an n x n matrix with i.i.d. N(0, 1) entries (beta = 1) or standard
+/// complex Gaussian entries (beta = 2), kappa = s_max / s_min (2-norm)
+/// grows like n, and kappa / n converges in law to (Edelman 1988)
+///
+/// - real: P(kappa / n <= x) = exp(-2 / x - 2 / x^2), density (2 x + 4) / x^3 exp(-2 / x - 2 / x^2);
+/// - complex: P(kappa / n <= x) = exp(-4 / x^2), density 8 / x^3 exp(-4 / x^2).
+///
+/// Both follow from s_max ~ 2 sqrt(n) and the hard-edge law of n s_min^2
+/// (see [`crate::hard_edge_cdf`]). The tails are heavy: P(kappa / n > x)
+/// ~ 2 / x for real matrices, so kappa / n has no mean, while
+/// E[ln kappa] = ln n + 1.537 (real) and ln n + ln 2 + gamma / 2 = ln n + 0.982
+/// (complex). Convergence is fast enough for the limit to serve as a
+/// yardstick for random test matrices of moderate size: a Gaussian
+/// 1000 x 1000 matrix has kappa above 10^6 with probability about 0.002.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{condition_number, singular_values, EdelmanConditionNumber};
+///
+/// let law = EdelmanConditionNumber::new(1.0).unwrap();
+/// // Median kappa of a real Gaussian 100 x 100 matrix is about 100 * 3.67.
+/// assert!((law.quantile(0.5) - 3.67).abs() < 0.01);
+/// // A condition number of 10^5 at n = 100 is unusual for a random matrix.
+/// assert!(law.sf(1e5 / 100.0) < 0.01);
+/// // Measure one with `condition_number(&singular_values(&a))`.
+/// assert_eq!(condition_number(&[4.0, 2.0, 0.5]), 8.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "EdelmanConditionNumberParams"))]
+pub struct EdelmanConditionNumber {
+    beta: f64,
+}
+
+impl EdelmanConditionNumber {
+    /// Limit law for real (beta = 1) or complex (beta = 2) matrices.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless beta is 1 or 2.
+    pub fn new(beta: f64) -> Result<Self, Error> {
+        if beta == 1.0 || beta == 2.0 {
+            Ok(Self { beta })
+        } else {
+            Err(Error::InvalidParameter {
+                name: "beta",
+                value: beta,
+                expected: "1 or 2",
+            })
+        }
+    }
+
+    /// Dyson index beta.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Density of kappa / n at x.
+    pub fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let y = 1.0 / x;
+        if self.beta == 1.0 {
+            (2.0 * y * y + 4.0 * y * y * y) * (-2.0 * y - 2.0 * y * y).exp()
+        } else {
+            8.0 * y * y * y * (-4.0 * y * y).exp()
+        }
+    }
+
+    /// CDF of kappa / n at x.
+    pub fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        (-self.exponent(x)).exp()
+    }
+
+    /// Tail probability P(kappa / n > x), accurate far into the tail.
+    pub fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+        -(-self.exponent(x)).exp_m1()
+    }
+
+    /// Quantile function, in closed form (NaN for q outside [0, 1]).
+    pub fn quantile(&self, q: f64) -> f64 {
+        if !(0.0..=1.0).contains(&q) {
+            return f64::NAN;
+        }
+        // Solve exponent(1 / y) = -ln q for y = n / kappa.
+        let t = -q.ln();
+        let y = if self.beta == 1.0 {
+            0.5 * ((1.0 + 2.0 * t).sqrt() - 1.0)
+        } else {
+            0.5 * t.sqrt()
+        };
+        1.0 / y
+    }
+
+    /// -ln P(kappa / n <= x) for x > 0.
+    fn exponent(&self, x: f64) -> f64 {
+        let y = 1.0 / x;
+        if self.beta == 1.0 {
+            2.0 * y + 2.0 * y * y
+        } else {
+            4.0 * y * y
+        }
+    }
+}
+
+impl Distribution<f64> for EdelmanConditionNumber {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Bisection for the smallest x in [lo, hi] with cdf(x) >= q.
+pub(crate) fn invert_cdf<F: Fn(f64) -> f64>(cdf: F, q: f64, mut lo: f64, mut hi: f64) -> f64 {
+    if !(0.0..=1.0).contains(&q) {
+        return f64::NAN;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if cdf(mid) < q {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= 1e-14 * hi.abs().max(1.0) {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+// Deserialization goes through the validating constructors, so a stored
+// law with out-of-range parameters is rejected instead of silently built.
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct MarchenkoPasturParams {
+    ratio: f64,
+    sigma_sq: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MarchenkoPasturParams> for MarchenkoPastur {
+    type Error = Error;
+
+    fn try_from(params: MarchenkoPasturParams) -> Result<Self, Error> {
+        Self::new(params.ratio, params.sigma_sq)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct WignerSemicircleParams {
+    sigma: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<WignerSemicircleParams> for WignerSemicircle {
+    type Error = Error;
+
+    fn try_from(params: WignerSemicircleParams) -> Result<Self, Error> {
+        Self::new(params.sigma)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TracyWidomParams {
+    beta: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TracyWidomParams> for TracyWidom {
+    type Error = Error;
+
+    fn try_from(params: TracyWidomParams) -> Result<Self, Error> {
+        Self::new(params.beta)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct KestenMcKayParams {
+    degree: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<KestenMcKayParams> for KestenMcKay {
+    type Error = Error;
+
+    fn try_from(params: KestenMcKayParams) -> Result<Self, Error> {
+        Self::new(params.degree)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EdelmanConditionNumberParams {
+    beta: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<EdelmanConditionNumberParams> for EdelmanConditionNumber {
+    type Error = Error;
+
+    fn try_from(params: EdelmanConditionNumberParams) -> Result<Self, Error> {
+        Self::new(params.beta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_kesten_mckay_normalized_and_consistent() {
+        let km = KestenMcKay::new(3.0).unwrap();
+        let (lo, hi) = km.support();
+        let (nodes, weights) = gauss_legendre(400, lo, hi);
+        let mass: f64 = nodes
+            .iter()
+            .zip(&weights)
+            .map(|(&x, &w)| w * km.pdf(x))
+            .sum();
+        assert!((mass - 1.0).abs() < 1e-3, "{}", mass);
+        assert!((km.cdf(0.0) - 0.5).abs() < 1e-12);
+        let x = km.quantile(0.8);
+        assert!((km.cdf(x) - 0.8).abs() < 1e-10);
+        // d = 2 is the arcsine law on [-2, 2].
+        let arcsine = KestenMcKay::new(2.0).unwrap();
+        assert!((arcsine.cdf(1.0) - (0.5 + 0.5_f64.asin() / PI)).abs() < 1e-10);
+        assert!(KestenMcKay::new(1.5).is_err());
+    }
+
+    #[test]
+    fn test_samples_follow_their_laws() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(167);
+        let semicircle = WignerSemicircle::new(1.0).unwrap();
+        let samples: Vec<f64> = (0..2000).map(|_| semicircle.sample(&mut rng)).collect();
+        let ks = crate::ks_test(&samples, |x| semicircle.cdf(x)).unwrap();
+        assert!(ks.p_value > 0.01, "{:?}", ks);
+
+        let km = KestenMcKay::new(4.0).unwrap();
+        let samples: Vec<f64> = (0..2000).map(|_| km.sample(&mut rng)).collect();
+        let ks = crate::ks_test(&samples, |x| km.cdf(x)).unwrap();
+        assert!(ks.p_value > 0.01, "{:?}", ks);
+    }
+
+    #[test]
+    fn test_edelman_condition_number() {
+        for beta in [1.0, 2.0] {
+            let law = EdelmanConditionNumber::new(beta).unwrap();
+            // Integrate in y = 1 / x, where the density is smooth and light-tailed.
+            let (nodes, weights) = gauss_legendre(200, 0.0, 12.0);
+            let (mass, log_mean) =
+                nodes
+                    .iter()
+                    .zip(&weights)
+                    .fold((0.0, 0.0), |(m, l), (&y, &w)| {
+                        let density = law.pdf(1.0 / y) / (y * y);
+                        (m + w * density, l - w * density * y.ln())
+                    });
+            assert!((mass - 1.0).abs() < 1e-10);
+            let expected = if beta == 1.0 {
+                1.537
+            } else {
+                2f64.ln() + 0.5 * 0.5772156649
+            };
+            assert!(
+                (log_mean - expected).abs() < 1e-3,
+                "beta = {beta}: {log_mean}"
+            );
+            for q in [0.01, 0.5, 0.99] {
+                let x = law.quantile(q);
+                assert!((law.cdf(x) - q).abs() < 1e-12);
+                assert!((law.sf(x) - (1.0 - q)).abs() < 1e-12);
+            }
+        }
+        let real = EdelmanConditionNumber::new(1.0).unwrap();
+        assert!((real.sf(1e8) * 1e8 / 2.0 - 1.0).abs() < 1e-6);
+
+        // kappa / n of real Gaussian 30 x 30 matrices.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(647);
+        let n = 30;
+        let samples: Vec<f64> = (0..300)
+            .map(|_| {
+                let a = ndarray::Array2::from_shape_fn((n, n), |_| {
+                    rand_distr::StandardNormal.sample(&mut rng)
+                });
+                crate::condition_number(&crate::singular_values(&a)) / n as f64
+            })
+            .collect();
+        let ks = crate::ks_test(&samples, |x| real.cdf(x)).unwrap();
+        assert!(ks.p_value > 0.01, "{ks:?}");
+        assert!(EdelmanConditionNumber::new(4.0).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mp = MarchenkoPastur::new(0.25, 2.0).unwrap();
+        let json = serde_json::to_string(&mp).unwrap();
+        assert_eq!(json, r#"{"ratio":0.25,"sigma_sq":2.0}"#);
+        assert_eq!(serde_json::from_str::<MarchenkoPastur>(&json).unwrap(), mp);
+        // Parameters that the constructors reject do not deserialize either.
+        assert!(
+            serde_json::from_str::<MarchenkoPastur>(r#"{"ratio":-1.0,"sigma_sq":1.0}"#).is_err()
+        );
+        assert!(serde_json::from_str::<WignerSemicircle>(r#"{"sigma":0.0}"#).is_err());
+        assert!(serde_json::from_str::<TracyWidom>(r#"{"beta":3.0}"#).is_err());
+        assert!(serde_json::from_str::<KestenMcKay>(r#"{"degree":1.0}"#).is_err());
+        assert!(serde_json::from_str::<EdelmanConditionNumber>(r#"{"beta":4.0}"#).is_err());
+        let edelman = EdelmanConditionNumber::new(2.0).unwrap();
+        let json = serde_json::to_string(&edelman).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EdelmanConditionNumber>(&json).unwrap(),
+            edelman
+        );
+    }
+
+    #[test]
+    fn test_tracy_widom_distribution() {
+        let tw = TracyWidom::new(1.0).unwrap();
+        // Mean of TW1 is about -1.2065; the density peaks near it.
+        assert!(tw.pdf(-1.3) > tw.pdf(1.0));
+        assert!((tw.quantile(0.95) - 0.9793).abs() < 1e-3);
+        assert!(TracyWidom::new(3.0).is_err());
+        assert_eq!(
+            MarchenkoPastur::new(0.5, -1.0),
+            Err(Error::InvalidParameter {
+                name: "sigma_sq",
+                value: -1.0,
+                expected: "positive and finite",
+            })
+        );
+        assert!(MarchenkoPastur::new(0.0, 1.0).is_err());
+        assert!(WignerSemicircle::new(f64::NAN).is_err());
+        assert!(MarchenkoPastur::new(0.5, 1.0)
+            .unwrap()
+            .quantile(2.0)
+            .is_nan());
+    }
+}
@@ -0,0 +1,259 @@
+//! Sampling determinantal point processes on a finite ground set.
+//!
+//! A DPP on {0, ..., n - 1} with marginal kernel K (symmetric, spectrum in
+//! [0, 1]) draws subsets Y with P(S in Y) = det K_S for every S. Like
+//! eigenvalues, the points repel: P(i, j in Y) = K_ii K_jj - K_ij^2 never
+//! exceeds the independent value. Sampling uses the spectral algorithm of
+//! Hough, Krishnapur, Peres & Virag (2006): keep each eigenvector
+//! independently with probability equal to its eigenvalue, then sample the
+//! projection DPP spanned by the kept vectors one point at a time.
+
+use ndarray::Array2;
+use rand::Rng;
+use rand_distr::Distribution;
+
+use crate::error::Error;
+use crate::linalg::symmetric_eigen;
+
+/// Spectra of kernels built numerically may stray this far outside [0, 1].
+const SPECTRUM_TOLERANCE: f64 = 1e-10;
+
+/// Determinantal point process given by the eigendecomposition of its kernel.
+///
+/// K = V diag(lambda) V^T with orthonormal columns V and eigenvalues in
+/// [0, 1]. The sample size is a sum of independent Bernoulli(lambda_i)
+/// variables, so it has mean sum lambda_i; for a projection kernel (all
+/// lambda_i = 1) it is always the rank. A sample costs O(n k^3) for k
+/// points, dominated by re-orthonormalizing the kept vectors after each
+/// point.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::DeterminantalProcess;
+///
+/// // Projection onto the 3 lowest Fourier modes on a 12-point circle.
+/// let n = 12;
+/// let modes = Array2::from_shape_fn((n, 3), |(i, k)| {
+///     let theta = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+///     let norm = (2.0 / n as f64).sqrt();
+///     match k {
+///         0 => (1.0 / n as f64).sqrt(),
+///         1 => norm * theta.cos(),
+///         _ => norm * theta.sin(),
+///     }
+/// });
+/// let dpp = DeterminantalProcess::projection(modes).unwrap();
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let points = dpp.sample(&mut rng);
+/// assert_eq!(points.len(), 3);
+/// assert!(points.windows(2).all(|w| w[0] < w[1]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeterminantalProcess {
+    eigenvalues: Vec<f64>,
+    eigenvectors: Array2<f64>,
+}
+
+impl DeterminantalProcess {
+    /// DPP with kernel V diag(eigenvalues) V^T.
+    ///
+    /// The columns of `eigenvectors` must be orthonormal; this is not
+    /// checked.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless every eigenvalue is in [0, 1].
+    ///
+    /// # Panics
+    ///
+    /// If the number of eigenvalues differs from the number of columns.
+    pub fn new(eigenvalues: Vec<f64>, eigenvectors: Array2<f64>) -> Result<Self, Error> {
+        assert_eq!(
+            eigenvalues.len(),
+            eigenvectors.ncols(),
+            "one eigenvalue per eigenvector column"
+        );
+        if let Some(&value) = eigenvalues.iter().find(|&&l| !(0.0..=1.0).contains(&l)) {
+            return Err(Error::InvalidParameter {
+                name: "eigenvalues",
+                value,
+                expected: "in [0, 1]",
+            });
+        }
+        Ok(Self {
+            eigenvalues,
+            eigenvectors,
+        })
+    }
+
+    /// Projection DPP onto the span of orthonormal columns; every sample has `ncols` points.
+    ///
+    /// # Errors
+    ///
+    /// Never in practice; the signature matches [`DeterminantalProcess::new`].
+    pub fn projection(eigenvectors: Array2<f64>) -> Result<Self, Error> {
+        Self::new(vec![1.0; eigenvectors.ncols()], eigenvectors)
+    }
+
+    /// DPP with a symmetric marginal kernel, via its eigendecomposition.
+    ///
+    /// Eigenvalues within 1e-10 of [0, 1] are clamped into it, absorbing
+    /// rounding in kernels that are projections in exact arithmetic.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] if an eigenvalue of `kernel` lies
+    /// outside [0, 1] beyond that tolerance.
+    pub fn from_kernel(kernel: &Array2<f64>) -> Result<Self, Error> {
+        let (values, vectors) = symmetric_eigen(kernel);
+        let clamped = values
+            .into_iter()
+            .map(|l| {
+                if (-SPECTRUM_TOLERANCE..=1.0 + SPECTRUM_TOLERANCE).contains(&l) {
+                    l.clamp(0.0, 1.0)
+                } else {
+                    l
+                }
+            })
+            .collect();
+        Self::new(clamped, vectors)
+    }
+
+    /// Size n of the ground set.
+    pub fn ground_set_size(&self) -> usize {
+        self.eigenvectors.nrows()
+    }
+
+    /// Expected number of points, the trace of K.
+    pub fn expected_size(&self) -> f64 {
+        self.eigenvalues.iter().sum()
+    }
+
+    /// Inclusion probabilities P(i in Y) = K_ii.
+    pub fn marginals(&self) -> Vec<f64> {
+        self.eigenvectors
+            .rows()
+            .into_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.eigenvalues)
+                    .map(|(v, l)| l * v * v)
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+impl Distribution<Vec<usize>> for DeterminantalProcess {
+    /// Sorted indices of one sample.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<usize> {
+        let n = self.ground_set_size();
+        let mut basis: Vec<Vec<f64>> = self
+            .eigenvalues
+            .iter()
+            .enumerate()
+            .filter(|&(_, &l)| rng.random::<f64>() < l)
+            .map(|(k, _)| self.eigenvectors.column(k).to_vec())
+            .collect();
+        let mut points = Vec::with_capacity(basis.len());
+        while !basis.is_empty() {
+            // P(i) = sum_k v_k(i)^2 / k, the diagonal of the current projection.
+            let weight = |i: usize| basis.iter().map(|v| v[i] * v[i]).sum::<f64>();
+            let total: f64 = (0..n).map(weight).sum();
+            let mut u = rng.random::<f64>() * total;
+            let mut point = n - 1;
+            for i in 0..n {
+                u -= weight(i);
+                if u < 0.0 {
+                    point = i;
+                    break;
+                }
+            }
+            points.push(point);
+            // Restrict to vectors vanishing at `point`: eliminate it with
+            // the vector largest there, then re-orthonormalize.
+            let pivot = (0..basis.len())
+                .max_by(|&a, &b| basis[a][point].abs().total_cmp(&basis[b][point].abs()))
+                .expect("basis is non-empty");
+            let pivot_vector = basis.swap_remove(pivot);
+            for v in &mut basis {
+                let c = v[point] / pivot_vector[point];
+                v.iter_mut()
+                    .zip(&pivot_vector)
+                    .for_each(|(x, p)| *x -= c * p);
+            }
+            for k in 0..basis.len() {
+                for j in 0..k {
+                    let dot: f64 = basis[k].iter().zip(&basis[j]).map(|(a, b)| a * b).sum();
+                    let (done, rest) = basis.split_at_mut(k);
+                    rest[0]
+                        .iter_mut()
+                        .zip(&done[j])
+                        .for_each(|(x, b)| *x -= dot * b);
+                }
+                let norm = basis[k].iter().map(|x| x * x).sum::<f64>().sqrt();
+                basis[k].iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+        points.sort_unstable();
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_dpp_inclusion_probabilities() {
+        // K = Q diag(lambda) Q^T for a rotation Q of R^5.
+        let q = symmetric_eigen(&Array2::from_shape_fn((5, 5), |(i, j)| {
+            ((i * 7 + j * 3) % 5) as f64 + ((j * 7 + i * 3) % 5) as f64
+        }))
+        .1;
+        let lambda = [0.9, 0.6, 0.5, 0.2, 0.0];
+        let kernel = Array2::from_shape_fn((5, 5), |(i, j)| {
+            (0..5)
+                .map(|k| lambda[k] * q[[i, k]] * q[[j, k]])
+                .sum::<f64>()
+        });
+        let dpp = DeterminantalProcess::from_kernel(&kernel).unwrap();
+        assert!((dpp.expected_size() - 2.2).abs() < 1e-12);
+        for (m, i) in dpp.marginals().iter().zip(0..5) {
+            assert!((m - kernel[[i, i]]).abs() < 1e-12);
+        }
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        let trials = 20_000;
+        let mut single = [0usize; 5];
+        let mut pair = 0usize;
+        let mut size = 0usize;
+        for _ in 0..trials {
+            let y = dpp.sample(&mut rng);
+            size += y.len();
+            y.iter().for_each(|&i| single[i] += 1);
+            pair += usize::from(y.contains(&0) && y.contains(&1));
+        }
+        let freq = |c: usize| c as f64 / trials as f64;
+        assert!((freq(size) - 2.2).abs() < 0.03);
+        for i in 0..5 {
+            assert!((freq(single[i]) - kernel[[i, i]]).abs() < 0.02, "K_{i}{i}");
+        }
+        let det = kernel[[0, 0]] * kernel[[1, 1]] - kernel[[0, 1]] * kernel[[0, 1]];
+        assert!((freq(pair) - det).abs() < 0.02);
+
+        // Projections always give exactly rank-many distinct points.
+        let dpp =
+            DeterminantalProcess::projection(q.slice(ndarray::s![.., ..3]).to_owned()).unwrap();
+        for _ in 0..50 {
+            assert_eq!(dpp.sample(&mut rng).len(), 3);
+        }
+        assert!(DeterminantalProcess::new(vec![1.5], Array2::eye(1)).is_err());
+        assert!(DeterminantalProcess::from_kernel(&(Array2::eye(3) * 2.0)).is_err());
+    }
+}
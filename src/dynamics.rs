@@ -0,0 +1,463 @@
+//! Dyson Brownian motion.
+//!
+//! The eigenvalues of a symmetric matrix whose entries diffuse independently
+//! move as interacting particles:
+//!
+//! ```text
+//! d lambda_i = sqrt(2 / (beta n)) dB_i
+//!              + (1/n) sum_{j != i} dt / (lambda_i - lambda_j) - k lambda_i dt
+//! ```
+//!
+//! The log-repulsion keeps them ordered, and the confinement k > 0
+//! (Ornstein-Uhlenbeck drift) pulls them towards the Gaussian beta ensemble,
+//! with joint density proportional to
+//! prod |lambda_i - lambda_j|^beta exp(-beta n k sum lambda_i^2 / 2)
+//! and a semicircle of radius sqrt(2 / k). The default k = 1/2 matches
+//! [`crate::sample_goe`]: radius 2. With k = 0 the motion is free and a
+//! spectrum started at 0 is a semicircle of radius 2 sqrt(t) at time t.
+//! The global density relaxes in O(1) time, local statistics (e.g. spacing
+//! ratios) after O(1/n), which is what makes the process a tool for local
+//! universality.
+//!
+//! [`track_eigenvalues`] follows the deterministic counterpart: the spectrum
+//! of A + t B along a grid of t, with branches matched by eigenvector
+//! continuity so that crossings, avoided crossings, and emerging spikes can
+//! be plotted as curves.
+
+use ndarray::Array2;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::error::Error;
+use crate::linalg::symmetric_eigen;
+use crate::spectrum::AsEigenvalues;
+
+/// Fraction of the squared smallest gap (times n) allowed per substep.
+const GAP_SAFETY: f64 = 1.0 / 32.0;
+/// Shortest substep as a fraction of `dt`, so near-collisions cannot stall a step.
+const MIN_SUBSTEP: f64 = 1e-4;
+
+/// Euler-Maruyama integrator for Dyson Brownian motion with gap-adaptive substeps.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{spectral_moments, DysonBrownianMotion};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let dbm = DysonBrownianMotion::new(1.0, 1e-3).unwrap();
+/// // Start from an equally spaced spectrum on [-0.5, 0.5] and relax.
+/// let initial: Vec<f64> = (0..30).map(|i| i as f64 / 29.0 - 0.5).collect();
+/// let path = dbm.simulate(&mut rng, &initial, 4000, 1000);
+/// assert_eq!(path.times.len(), 5);
+/// // The equilibrium semicircle of radius 2 has second moment 1.
+/// let last = path.eigenvalues.row(4).to_vec();
+/// assert!((spectral_moments(&last, 2)[1] - 1.0).abs() < 0.25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "DysonBrownianMotionParams"))]
+pub struct DysonBrownianMotion {
+    beta: f64,
+    dt: f64,
+    confinement: f64,
+}
+
+/// Snapshots of a Dyson Brownian motion.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DysonTrajectory {
+    /// Time of each snapshot, starting at 0
+    pub times: Vec<f64>,
+    /// One row per snapshot, eigenvalues ascending; column i traces the
+    /// i-th smallest eigenvalue
+    pub eigenvalues: Array2<f64>,
+}
+
+impl DysonBrownianMotion {
+    /// Dyson index `beta` and time step `dt`, with confinement 1/2.
+    ///
+    /// Any beta > 0 is allowed (beta = 1, 2, 4 are the GOE, GUE, GSE
+    /// processes). Each step of length `dt` is subdivided internally where
+    /// eigenvalues come close, so `dt` mainly sets the time resolution of
+    /// [`DysonBrownianMotion::simulate`].
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless both are positive and finite.
+    pub fn new(beta: f64, dt: f64) -> Result<Self, Error> {
+        Ok(Self {
+            beta: Error::check_positive("beta", beta)?,
+            dt: Error::check_positive("dt", dt)?,
+            confinement: 0.5,
+        })
+    }
+
+    /// Ornstein-Uhlenbeck rate k of the drift -k lambda dt (0 for free motion).
+    pub fn confinement(mut self, confinement: f64) -> Self {
+        self.confinement = confinement;
+        self
+    }
+
+    /// Dyson index.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Time step.
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    /// Advance sorted `eigenvalues` by one time step in place.
+    ///
+    /// Close pairs feel a repulsion of order 1/(n gap), which a plain
+    /// Euler-Maruyama step overshoots; the step is therefore split into
+    /// substeps short enough that neither the drift nor the noise moves an
+    /// eigenvalue by more than a fraction of the smallest gap. The rare
+    /// crossing that still happens is undone by re-sorting.
+    pub fn step<R: Rng>(&self, rng: &mut R, eigenvalues: &mut [f64]) {
+        let n = eigenvalues.len();
+        if n == 0 {
+            return;
+        }
+        let inv_n = 1.0 / n as f64;
+        // Substep bound h <= GAP_SAFETY n min(1, beta) gap^2.
+        let scale = GAP_SAFETY * n as f64 * self.beta.min(1.0);
+        let mut drift = vec![0.0; n];
+        let mut remaining = self.dt;
+        while remaining > 0.0 {
+            for (d, &x) in drift.iter_mut().zip(eigenvalues.iter()) {
+                *d = -self.confinement * x;
+            }
+            for i in 0..n {
+                for j in i + 1..n {
+                    let force = inv_n / (eigenvalues[i] - eigenvalues[j]);
+                    drift[i] += force;
+                    drift[j] -= force;
+                }
+            }
+            let gap = eigenvalues
+                .windows(2)
+                .map(|w| w[1] - w[0])
+                .fold(f64::INFINITY, f64::min);
+            let h = remaining.min(scale * gap * gap).max(MIN_SUBSTEP * self.dt);
+            let noise = (2.0 * h * inv_n / self.beta).sqrt();
+            for (x, d) in eigenvalues.iter_mut().zip(&drift) {
+                let z: f64 = StandardNormal.sample(rng);
+                *x += d * h + noise * z;
+            }
+            if eigenvalues.windows(2).any(|w| w[0] > w[1]) {
+                eigenvalues.sort_by(|a, b| a.total_cmp(b));
+            }
+            remaining -= h;
+        }
+    }
+
+    /// Run `steps` steps from `initial`, recording every `record_every`-th state.
+    ///
+    /// The initial state is always recorded, so there are
+    /// 1 + steps / record_every snapshots (`record_every` of 0 is treated
+    /// as 1). Repeated initial values are infinitely repelled; spread them
+    /// slightly first.
+    pub fn simulate<R: Rng, E: AsEigenvalues + ?Sized>(
+        &self,
+        rng: &mut R,
+        initial: &E,
+        steps: usize,
+        record_every: usize,
+    ) -> DysonTrajectory {
+        let mut state = initial.as_eigenvalues().into_owned();
+        state.sort_by(|a, b| a.total_cmp(b));
+        let record_every = record_every.max(1);
+        let snapshots = 1 + steps / record_every;
+        let mut eigenvalues = Array2::zeros((snapshots, state.len()));
+        let mut times = Vec::with_capacity(snapshots);
+        eigenvalues
+            .row_mut(0)
+            .assign(&ndarray::ArrayView1::from(&state));
+        times.push(0.0);
+        for k in 1..=steps {
+            self.step(rng, &mut state);
+            if k % record_every == 0 {
+                eigenvalues
+                    .row_mut(times.len())
+                    .assign(&ndarray::ArrayView1::from(&state));
+                times.push(k as f64 * self.dt);
+            }
+        }
+        DysonTrajectory { times, eigenvalues }
+    }
+}
+
+/// Eigenvalue branches of A + t B over a grid of t.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EigenvalueTracks {
+    /// Grid of perturbation strengths, as given
+    pub t: Vec<f64>,
+    /// One row per t; column k follows branch k, which starts as the k-th
+    /// smallest eigenvalue at the first grid point
+    pub eigenvalues: Array2<f64>,
+    /// Per t, a matrix whose column k is the unit eigenvector of branch k,
+    /// signed to agree with the previous grid point
+    /// ([`track_eigenpairs`] only)
+    pub eigenvectors: Option<Vec<Array2<f64>>>,
+    /// Per t, the smallest |<v(t_prev), v(t)>| over matched branches (1 at
+    /// the first point); a dip well below 1 marks an avoided crossing
+    /// narrower than the grid step
+    pub min_overlap: Vec<f64>,
+}
+
+/// Follow the eigenvalues of the symmetric matrix A + t B as t moves along a grid.
+///
+/// At each grid point the eigenvectors are matched to those at the previous
+/// point by largest overlap |<v_i(t_prev), v_j(t)>| (greedily, best pairs
+/// first), so a branch keeps its identity through exact crossings, where
+/// sorting would swap labels. At an avoided crossing the outcome depends on
+/// resolution: a grid finer than the gap follows the adiabatic branches
+/// that repel, a coarser one jumps across to the diabatic ones, and
+/// [`EigenvalueTracks::min_overlap`] flags the step where that happened.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric n x n base matrix
+/// * `b` - Symmetric n x n perturbation direction
+/// * `t` - Grid of perturbation strengths (usually increasing)
+///
+/// # Returns
+///
+/// Empty tracks if `a` and `b` are not square matrices of the same size
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::track_eigenvalues;
+///
+/// // Two levels cross at t = 1 and the perturbation does not couple them.
+/// let a = array![[-1.0, 0.0], [0.0, 1.0]];
+/// let b = array![[1.0, 0.0], [0.0, -1.0]];
+/// let t: Vec<f64> = (0..=20).map(|k| 0.1 * k as f64 + 0.05).collect();
+/// let tracks = track_eigenvalues(&a, &b, &t);
+/// // Branch 0 rises through the crossing instead of staying lowest.
+/// assert!((tracks.eigenvalues[[20, 0]] - 1.05).abs() < 1e-12);
+/// ```
+pub fn track_eigenvalues(a: &Array2<f64>, b: &Array2<f64>, t: &[f64]) -> EigenvalueTracks {
+    track(a, b, t, false)
+}
+
+/// [`track_eigenvalues`] also returning the eigenvectors of each branch.
+pub fn track_eigenpairs(a: &Array2<f64>, b: &Array2<f64>, t: &[f64]) -> EigenvalueTracks {
+    track(a, b, t, true)
+}
+
+fn track(a: &Array2<f64>, b: &Array2<f64>, t: &[f64], keep_vectors: bool) -> EigenvalueTracks {
+    let n = a.nrows();
+    if a.ncols() != n || b.dim() != (n, n) {
+        return EigenvalueTracks {
+            t: vec![],
+            eigenvalues: Array2::zeros((0, 0)),
+            eigenvectors: keep_vectors.then(Vec::new),
+            min_overlap: vec![],
+        };
+    }
+    let mut eigenvalues = Array2::zeros((t.len(), n));
+    let mut eigenvectors = Vec::new();
+    let mut min_overlap = Vec::with_capacity(t.len());
+    let mut previous: Option<Array2<f64>> = None;
+    for (row, &tk) in t.iter().enumerate() {
+        let (values, vectors) = symmetric_eigen(&(a + &(b * tk)));
+        let (order, worst) = match &previous {
+            Some(prev) => match_branches(prev, &vectors),
+            None => ((0..n).collect(), 1.0),
+        };
+        let mut current = Array2::zeros((n, n));
+        for (branch, &j) in order.iter().enumerate() {
+            eigenvalues[[row, branch]] = values[j];
+            let mut column = vectors.column(j).to_owned();
+            if let Some(prev) = &previous {
+                if prev.column(branch).dot(&column) < 0.0 {
+                    column.mapv_inplace(|x| -x);
+                }
+            }
+            current.column_mut(branch).assign(&column);
+        }
+        min_overlap.push(worst);
+        if keep_vectors {
+            eigenvectors.push(current.clone());
+        }
+        previous = Some(current);
+    }
+    EigenvalueTracks {
+        t: t.to_vec(),
+        eigenvalues,
+        eigenvectors: keep_vectors.then_some(eigenvectors),
+        min_overlap,
+    }
+}
+
+/// For each previous branch, the index of the new eigenvector it continues
+/// into, and the smallest matched overlap.
+fn match_branches(previous: &Array2<f64>, vectors: &Array2<f64>) -> (Vec<usize>, f64) {
+    let n = previous.ncols();
+    let overlaps = previous.t().dot(vectors).mapv(f64::abs);
+    let mut pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).collect();
+    pairs.sort_by(|&p, &q| overlaps[[q.0, q.1]].total_cmp(&overlaps[[p.0, p.1]]));
+    let mut order = vec![usize::MAX; n];
+    let mut taken = vec![false; n];
+    let mut worst = 1.0f64;
+    for (i, j) in pairs {
+        if order[i] == usize::MAX && !taken[j] {
+            order[i] = j;
+            taken[j] = true;
+            worst = worst.min(overlaps[[i, j]]);
+        }
+    }
+    (order, worst)
+}
+
+// Deserialization goes through the validating constructor.
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct DysonBrownianMotionParams {
+    beta: f64,
+    dt: f64,
+    confinement: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<DysonBrownianMotionParams> for DysonBrownianMotion {
+    type Error = Error;
+
+    fn try_from(params: DysonBrownianMotionParams) -> Result<Self, Error> {
+        Ok(Self::new(params.beta, params.dt)?.confinement(params.confinement))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mean_spacing_ratio, spectral_moments};
+    use ndarray::array;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_dyson_free_motion_spreads_as_semicircle() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(628);
+        let n = 40;
+        let initial: Vec<f64> = (0..n).map(|i| 0.01 * (i as f64 - 19.5)).collect();
+        let m2_initial = spectral_moments(&initial, 2)[1];
+        let dbm = DysonBrownianMotion::new(2.0, 0.01)
+            .unwrap()
+            .confinement(0.0);
+        let path = dbm.simulate(&mut rng, &initial, 100, 50);
+        assert_eq!(path.times, vec![0.0, 0.5, 1.0]);
+        // For beta = 2, d E[m2] = dt exactly, and the mean is a martingale.
+        for (row, &t) in path.eigenvalues.rows().into_iter().zip(&path.times).skip(1) {
+            let m = spectral_moments(&row.to_vec(), 2);
+            assert!(m[0].abs() < 0.1, "mean {} at t = {t}", m[0]);
+            let spread = m[1] - m[0] * m[0] - m2_initial;
+            assert!(
+                (spread / t - 1.0).abs() < 0.15,
+                "variance {spread} at t = {t}"
+            );
+        }
+        for row in path.eigenvalues.rows() {
+            assert!(row.windows(2).into_iter().all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn test_dyson_relaxes_to_equilibrium_statistics() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(629);
+        let n = 60;
+        // Poisson-like start: uniform points have no level repulsion.
+        let mut state: Vec<f64> = (0..n).map(|_| rng.random_range(-2.0..2.0)).collect();
+        state.sort_by(|a, b| a.total_cmp(b));
+        let dbm = DysonBrownianMotion::new(1.0, 0.01).unwrap();
+        let mut ratios = 0.0;
+        let mut trials = 0.0;
+        for k in 0..150 {
+            dbm.step(&mut rng, &mut state);
+            if k >= 50 && k % 10 == 0 {
+                ratios += mean_spacing_ratio(&state);
+                trials += 1.0;
+            }
+        }
+        // GOE mean spacing ratio ~0.5307 vs Poisson 0.386.
+        assert!(
+            (ratios / trials - 0.5307).abs() < 0.04,
+            "{}",
+            ratios / trials
+        );
+        assert!(DysonBrownianMotion::new(0.0, 1e-3).is_err());
+        assert!(DysonBrownianMotion::new(1.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_track_eigenvalues_through_crossings() {
+        // Levels -1 + t and 1 - t coupled by eps: an avoided crossing at t = 1 with gap 2 eps.
+        let a = array![[-1.0, 0.0], [0.0, 1.0]];
+        let coupled = |eps: f64| array![[1.0, eps], [eps, -1.0]];
+        let grid = |step: f64| -> Vec<f64> {
+            (0..=(2.0 / step) as usize)
+                .map(|k| 0.5 * step + k as f64 * step)
+                .collect()
+        };
+
+        // Grid far coarser than the gap: the branches jump across.
+        let coarse = track_eigenvalues(&a, &coupled(1e-4), &grid(0.1));
+        let last = coarse.t.len() - 1;
+        assert!(coarse.eigenvalues[[last, 0]] > 0.9);
+        assert!(coarse.eigenvalues[[last, 1]] < -0.9);
+        assert!(coarse.eigenvectors.is_none());
+
+        // Grid resolving the gap: adiabatic branches repel and stay ordered.
+        let fine = track_eigenpairs(&a, &coupled(0.3), &grid(0.01));
+        for row in fine.eigenvalues.rows() {
+            assert!(row[0] < row[1]);
+        }
+        assert!(fine.min_overlap.iter().all(|&o| o > 0.9));
+        let vectors = fine.eigenvectors.as_ref().unwrap();
+        for pair in vectors.windows(2) {
+            for k in 0..2 {
+                assert!(pair[0].column(k).dot(&pair[1].column(k)) > 0.9);
+            }
+        }
+
+        // A spike emerging from a GOE bulk: the top branch ends near t + 1/t.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(629);
+        let n = 80;
+        let goe = crate::sample_goe_with(&mut rng, n);
+        let mut spike = Array2::zeros((n, n));
+        spike[[0, 0]] = 1.0;
+        let t: Vec<f64> = (0..=30).map(|k| 0.1 * k as f64).collect();
+        let tracks = track_eigenvalues(&goe, &spike, &t);
+        let top = tracks
+            .eigenvalues
+            .row(30)
+            .fold(f64::NEG_INFINITY, |m, &v| m.max(v));
+        assert!((top - (3.0 + 1.0 / 3.0)).abs() < 0.15, "{top}");
+
+        let empty = track_eigenvalues(&a, &Array2::zeros((3, 3)), &[0.0]);
+        assert!(empty.t.is_empty() && empty.eigenvalues.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates() {
+        let process = DysonBrownianMotion::new(2.0, 0.01)
+            .unwrap()
+            .confinement(0.0);
+        let json = serde_json::to_string(&process).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DysonBrownianMotion>(&json).unwrap(),
+            process
+        );
+        let bad = r#"{"beta":2.0,"dt":0.0,"confinement":0.5}"#;
+        assert!(serde_json::from_str::<DysonBrownianMotion>(bad).is_err());
+    }
+}
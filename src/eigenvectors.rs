@@ -0,0 +1,267 @@
+//! Eigenvector statistics: localization and delocalization measures.
+//!
+//! Eigenvalue statistics alone cannot distinguish a delocalized (ergodic)
+//! phase from a localized one with similar densities. These functions treat
+//! the columns of a matrix as eigenvectors and summarize how their mass
+//! p_i = |v_i|^2 / ||v||^2 is spread across coordinates.
+
+use ndarray::Array2;
+
+use crate::special::{ln_gamma, EULER_GAMMA};
+
+/// Inverse participation ratio of each eigenvector (column).
+///
+/// IPR = sum_i |v_i|^4 / (sum_i |v_i|^2)^2. A vector spread evenly over n
+/// coordinates has IPR = 1/n; a vector on a single coordinate has IPR = 1.
+/// Haar-random (GOE) eigenvectors have IPR ~ 3/n.
+///
+/// # Arguments
+///
+/// * `eigenvectors` - n x k matrix whose columns are eigenvectors (need not be normalized)
+///
+/// # Returns
+///
+/// One IPR per column (NaN for an all-zero column)
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::inverse_participation_ratios;
+///
+/// let v = array![[1.0, 0.5], [0.0, 0.5], [0.0, 0.5], [0.0, 0.5]];
+/// let ipr = inverse_participation_ratios(&v);
+/// assert!((ipr[0] - 1.0).abs() < 1e-12);
+/// assert!((ipr[1] - 0.25).abs() < 1e-12);
+/// ```
+pub fn inverse_participation_ratios(eigenvectors: &Array2<f64>) -> Vec<f64> {
+    eigenvectors
+        .columns()
+        .into_iter()
+        .map(|col| {
+            let norm_sq: f64 = col.iter().map(|v| v * v).sum();
+            if norm_sq == 0.0 {
+                return f64::NAN;
+            }
+            col.iter().map(|v| v.powi(4)).sum::<f64>() / (norm_sq * norm_sq)
+        })
+        .collect()
+}
+
+/// Participation ratio 1 / IPR of each eigenvector: the effective number of occupied coordinates.
+pub fn participation_ratios(eigenvectors: &Array2<f64>) -> Vec<f64> {
+    inverse_participation_ratios(eigenvectors)
+        .into_iter()
+        .map(|ipr| 1.0 / ipr)
+        .collect()
+}
+
+/// Shannon participation entropy S = -sum_i p_i ln p_i of each eigenvector.
+///
+/// S ranges from 0 (fully localized) to ln n (uniform). For Haar-random real
+/// vectors, S ~ ln(n) - (2 - ln 2 - gamma_E) ~ ln(n) - 0.7296.
+///
+/// # Arguments
+///
+/// * `eigenvectors` - n x k matrix whose columns are eigenvectors
+///
+/// # Returns
+///
+/// One entropy per column (NaN for an all-zero column)
+pub fn participation_entropies(eigenvectors: &Array2<f64>) -> Vec<f64> {
+    eigenvectors
+        .columns()
+        .into_iter()
+        .map(|col| {
+            let norm_sq: f64 = col.iter().map(|v| v * v).sum();
+            if norm_sq == 0.0 {
+                return f64::NAN;
+            }
+            -col.iter()
+                .map(|v| v * v / norm_sq)
+                .filter(|&p| p > 0.0)
+                .map(|p| p * p.ln())
+                .sum::<f64>()
+        })
+        .collect()
+}
+
+/// Ratio ||v||_inf / ||v||_2 of each eigenvector (column).
+///
+/// Ranges from 1/sqrt(n) (perfectly flat) to 1 (a single coordinate).
+/// Delocalized eigenvectors satisfy ||v||_inf <~ sqrt(2 ln n / n); compare with
+/// [`haar_max_entry_ratio`].
+///
+/// # Returns
+///
+/// One ratio per column (NaN for an all-zero column)
+pub fn max_entry_ratios(eigenvectors: &Array2<f64>) -> Vec<f64> {
+    eigenvectors
+        .columns()
+        .into_iter()
+        .map(|col| {
+            let norm = col.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                return f64::NAN;
+            }
+            col.iter().fold(0.0_f64, |m, v| m.max(v.abs())) / norm
+        })
+        .collect()
+}
+
+/// Expected ||v||_inf / ||v||_2 for a Haar-random unit vector in R^n (large-n Gumbel approximation).
+///
+/// Treats the coordinates as n i.i.d. N(0, 1/n) entries, whose maximum absolute
+/// value concentrates at sqrt(2 ln(2n) / n) with Gumbel fluctuations of order
+/// 1 / sqrt(n ln n).
+pub fn haar_max_entry_ratio(n: usize) -> f64 {
+    if n < 2 {
+        return 1.0;
+    }
+    // Max of m = 2n half-normal tails: a_m + gamma_E b_m.
+    let m = 2.0 * n as f64;
+    let l = (2.0 * m.ln()).sqrt();
+    let location = l - (m.ln().ln() + (4.0 * std::f64::consts::PI).ln()) / (2.0 * l);
+    (location + EULER_GAMMA / l) / (n as f64).sqrt()
+}
+
+/// Squared overlaps |<v_k, u>|^2 / (||v_k||^2 ||u||^2) of each eigenvector with a fixed direction.
+///
+/// For Haar-distributed eigenvectors, n times the overlap follows the
+/// Porter-Thomas law (see [`porter_thomas_density`]) with mean 1.
+///
+/// # Arguments
+///
+/// * `eigenvectors` - n x k matrix whose columns are eigenvectors
+/// * `direction` - Fixed direction u of length n
+///
+/// # Returns
+///
+/// One squared overlap per column (empty if the dimensions do not match)
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::eigenvector_overlaps;
+///
+/// let v = array![[1.0, 1.0], [0.0, 1.0]];
+/// let overlaps = eigenvector_overlaps(&v, &[1.0, 0.0]);
+/// assert!((overlaps[0] - 1.0).abs() < 1e-12);
+/// assert!((overlaps[1] - 0.5).abs() < 1e-12);
+/// ```
+pub fn eigenvector_overlaps(eigenvectors: &Array2<f64>, direction: &[f64]) -> Vec<f64> {
+    if eigenvectors.nrows() != direction.len() {
+        return vec![];
+    }
+    let u_norm_sq: f64 = direction.iter().map(|u| u * u).sum();
+
+    eigenvectors
+        .columns()
+        .into_iter()
+        .map(|col| {
+            let v_norm_sq: f64 = col.iter().map(|v| v * v).sum();
+            let dot: f64 = col.iter().zip(direction).map(|(v, u)| v * u).sum();
+            dot * dot / (v_norm_sq * u_norm_sq)
+        })
+        .collect()
+}
+
+/// Porter-Thomas density of y = n |<v, u>|^2 for Haar-random eigenvectors.
+///
+/// A chi-square law with beta degrees of freedom scaled to unit mean:
+/// p(y) = (beta/2)^(beta/2) y^(beta/2 - 1) exp(-beta y / 2) / Gamma(beta/2).
+/// beta = 1 for real (GOE) and beta = 2 for complex (GUE) eigenvectors.
+pub fn porter_thomas_density(y: f64, beta: f64) -> f64 {
+    if y <= 0.0 || beta <= 0.0 {
+        return 0.0;
+    }
+    let half = beta / 2.0;
+    (half * half.ln() + (half - 1.0) * y.ln() - half * y - ln_gamma(half)).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_ipr_gaussian_vectors() {
+        // Gaussian vectors are the large-n proxy for Haar eigenvectors: IPR ~ 3/n.
+        let n = 2000;
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        let v = Array2::from_shape_fn((n, 4), |_| StandardNormal.sample(&mut rng));
+        for ipr in inverse_participation_ratios(&v) {
+            assert!(
+                (ipr * n as f64 - 3.0).abs() < 0.5,
+                "n * IPR = {}",
+                ipr * n as f64
+            );
+        }
+        let entropy_gap = 2.0 - 2.0_f64.ln() - EULER_GAMMA;
+        for s in participation_entropies(&v) {
+            assert!(((n as f64).ln() - s - entropy_gap).abs() < 0.1, "S = {}", s);
+        }
+    }
+
+    #[test]
+    fn test_max_entry_and_overlaps_gaussian() {
+        let n = 4000;
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+        let v = Array2::from_shape_fn((n, 200), |_| StandardNormal.sample(&mut rng));
+
+        let ratios = max_entry_ratios(&v);
+        let mean_ratio = ratios.iter().sum::<f64>() / ratios.len() as f64;
+        let expected = haar_max_entry_ratio(n);
+        assert!(
+            (mean_ratio - expected).abs() / expected < 0.05,
+            "{} vs {}",
+            mean_ratio,
+            expected
+        );
+
+        let mut direction = vec![0.0; n];
+        direction[0] = 1.0;
+        let overlaps = eigenvector_overlaps(&v, &direction);
+        let mean_scaled =
+            overlaps.iter().map(|o| o * n as f64).sum::<f64>() / overlaps.len() as f64;
+        assert!(
+            (mean_scaled - 1.0).abs() < 0.3,
+            "mean n * overlap {}",
+            mean_scaled
+        );
+    }
+
+    #[test]
+    fn test_porter_thomas_normalized() {
+        for &beta in &[1.0, 2.0] {
+            // Substitute y = t^2 to remove the beta = 1 singularity at 0.
+            let dt = 1e-3;
+            let mass: f64 = (0..10000)
+                .map(|i| {
+                    let t = (i as f64 + 0.5) * dt;
+                    porter_thomas_density(t * t, beta) * 2.0 * t * dt
+                })
+                .sum();
+            assert!((mass - 1.0).abs() < 1e-4, "beta {} mass {}", beta, mass);
+        }
+        assert!((porter_thomas_density(1.0, 2.0) - (-1.0_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_localized_vector() {
+        let mut v = Array2::zeros((5, 1));
+        v[[2, 0]] = -3.0;
+        assert_eq!(inverse_participation_ratios(&v), vec![1.0]);
+        assert_eq!(participation_ratios(&v), vec![1.0]);
+        assert_eq!(participation_entropies(&v), vec![0.0]);
+    }
+
+    #[test]
+    fn test_zero_column() {
+        let v = Array2::zeros((3, 1));
+        assert!(inverse_participation_ratios(&v)[0].is_nan());
+        assert!(participation_entropies(&v)[0].is_nan());
+    }
+}
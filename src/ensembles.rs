@@ -0,0 +1,478 @@
+//! Random matrix ensembles behind one trait.
+//!
+//! [`Ensemble`] pairs a sampler of real symmetric matrices with the limiting
+//! density of its eigenvalues, so Monte Carlo harnesses and tests can be
+//! written once and run over every ensemble. The free samplers
+//! ([`crate::sample_goe_with`], [`crate::sample_wishart_with`]) remain the
+//! primitive layer.
+//!
+//! Complex Hermitian ensembles ([`Gue`], [`Wishart::complex`]) are sampled
+//! through the real 2n x 2n embedding [[A, -B], [B, A]] of A + iB, which has
+//! the same eigenvalues, each doubled.
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::covariance::reconstruct;
+use crate::linalg::{symmetric_eigen, symmetric_eigenvalues};
+use crate::{
+    marchenko_pastur_density, sample_goe_with, sample_wishart_with, wigner_semicircle_density,
+};
+
+/// A random matrix ensemble of real symmetric matrices with a known limiting spectral density.
+///
+/// The eigenvalues are real and the limiting law is a density on the line,
+/// which rules out non-Hermitian ensembles such as Ginibre: their eigenvalues
+/// fill a region of the complex plane and no real symmetric matrix carries
+/// them. Those are sampled directly and analysed with
+/// [`crate::general_eigenvalues`] and [`crate::circular_law_test`].
+///
+/// # Example
+///
+/// ```rust
+/// use rand::{Rng, SeedableRng};
+/// use rmt::{Ensemble, Goe, Gue, Wishart};
+///
+/// fn top_eigenvalue<E: Ensemble, R: Rng>(ensemble: &E, rng: &mut R) -> f64 {
+///     *ensemble.sample_eigenvalues(rng).last().unwrap()
+/// }
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// assert!((top_eigenvalue(&Goe::new(200), &mut rng) - 2.0).abs() < 0.2);
+/// assert!((top_eigenvalue(&Gue::new(200), &mut rng) - 2.0).abs() < 0.2);
+/// assert!((top_eigenvalue(&Wishart::new(800, 200), &mut rng) - 2.25).abs() < 0.2);
+/// ```
+pub trait Ensemble {
+    /// Draw one matrix.
+    fn sample<R: Rng>(&self, rng: &mut R) -> Array2<f64>;
+
+    /// Limiting (n -> infinity) density of the eigenvalues at x.
+    fn limiting_density(&self, x: f64) -> f64;
+
+    /// Matrix dimension.
+    fn dim(&self) -> usize;
+
+    /// Draw one matrix and return its eigenvalues in ascending order.
+    fn sample_eigenvalues<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        symmetric_eigenvalues(&self.sample(rng))
+    }
+}
+
+/// Gaussian Orthogonal Ensemble scaled so the spectrum fills the semicircle on [-2, 2].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Goe {
+    n: usize,
+}
+
+impl Goe {
+    /// n x n GOE matrices (see [`crate::sample_goe_with`]).
+    pub fn new(n: usize) -> Self {
+        Self { n }
+    }
+}
+
+impl Ensemble for Goe {
+    fn sample<R: Rng>(&self, rng: &mut R) -> Array2<f64> {
+        sample_goe_with(rng, self.n)
+    }
+
+    fn limiting_density(&self, x: f64) -> f64 {
+        wigner_semicircle_density(x, 1.0)
+    }
+
+    fn dim(&self) -> usize {
+        self.n
+    }
+}
+
+/// Gaussian Unitary Ensemble scaled so the spectrum fills the semicircle on [-2, 2].
+///
+/// H is Hermitian with real N(0, 1/n) diagonal entries and complex
+/// off-diagonal entries whose real and imaginary parts are N(0, 1/(2n)).
+/// [`Ensemble::sample`] returns the real 2n x 2n embedding of H and
+/// [`Gue::sample_complex`] returns H itself.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{Ensemble, Gue};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let gue = Gue::new(100);
+/// let h = gue.sample_complex(&mut rng);
+/// assert_eq!(h[[2, 5]], h[[5, 2]].conj());
+/// assert_eq!(gue.sample_eigenvalues(&mut rng).len(), 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gue {
+    n: usize,
+}
+
+impl Gue {
+    /// n x n GUE matrices.
+    pub fn new(n: usize) -> Self {
+        Self { n }
+    }
+
+    /// Draw H as a complex Hermitian matrix.
+    pub fn sample_complex<R: Rng>(&self, rng: &mut R) -> Array2<Complex64> {
+        let (re, im) = self.sample_parts(rng);
+        Array2::from_shape_fn((self.n, self.n), |(i, j)| {
+            Complex64::new(re[[i, j]], im[[i, j]])
+        })
+    }
+
+    /// Real (symmetric) and imaginary (antisymmetric) parts of H.
+    fn sample_parts<R: Rng>(&self, rng: &mut R) -> (Array2<f64>, Array2<f64>) {
+        let n = self.n;
+        let diagonal_std = 1.0 / (n as f64).sqrt();
+        let off_diagonal_std = (0.5 / n as f64).sqrt();
+        let mut re = Array2::zeros((n, n));
+        let mut im = Array2::zeros((n, n));
+        for i in 0..n {
+            let z: f64 = StandardNormal.sample(rng);
+            re[[i, i]] = diagonal_std * z;
+            for j in (i + 1)..n {
+                let x: f64 = StandardNormal.sample(rng);
+                let y: f64 = StandardNormal.sample(rng);
+                re[[i, j]] = off_diagonal_std * x;
+                re[[j, i]] = off_diagonal_std * x;
+                im[[i, j]] = off_diagonal_std * y;
+                im[[j, i]] = -off_diagonal_std * y;
+            }
+        }
+        (re, im)
+    }
+}
+
+impl Ensemble for Gue {
+    fn sample<R: Rng>(&self, rng: &mut R) -> Array2<f64> {
+        let (re, im) = self.sample_parts(rng);
+        real_embedding(&re, &im)
+    }
+
+    fn limiting_density(&self, x: f64) -> f64 {
+        wigner_semicircle_density(x, 1.0)
+    }
+
+    /// 2n, the size of the real embedding.
+    fn dim(&self) -> usize {
+        2 * self.n
+    }
+
+    /// The n eigenvalues of H; each eigenvalue of the embedding appears
+    /// twice and one copy is kept.
+    fn sample_eigenvalues<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        symmetric_eigenvalues(&self.sample(rng))
+            .into_iter()
+            .step_by(2)
+            .collect()
+    }
+}
+
+/// Real 2p x 2p embedding [[A, -B], [B, A]] of the Hermitian matrix A + iB.
+fn real_embedding(re: &Array2<f64>, im: &Array2<f64>) -> Array2<f64> {
+    let p = re.nrows();
+    Array2::from_shape_fn((2 * p, 2 * p), |(i, j)| match (i < p, j < p) {
+        (true, true) => re[[i, j]],
+        (true, false) => -im[[i, j - p]],
+        (false, true) => im[[i - p, j]],
+        (false, false) => re[[i - p, j - p]],
+    })
+}
+
+/// Wishart ensemble W = X^H X of an n x p Gaussian data matrix, configured by builder methods.
+///
+/// Defaults: real entries of variance 1, identity population covariance, and
+/// normalization by n, so the eigenvalues follow Marchenko-Pastur with
+/// gamma = p/n and sigma^2 = 1.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rmt::{Ensemble, Wishart};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let population = Array2::from_diag(&ndarray::Array1::linspace(1.0, 3.0, 50));
+/// let wishart = Wishart::new(500, 50).sigma_sq(2.0).covariance(&population);
+/// let w = wishart.sample(&mut rng);
+/// // E[W] = sigma^2 Sigma, so the trace is near 2 * 100.
+/// assert!((w.diag().sum() - 200.0).abs() < 10.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wishart {
+    n: usize,
+    p: usize,
+    sigma_sq: f64,
+    covariance_root: Option<Array2<f64>>,
+    normalize: bool,
+    complex: bool,
+}
+
+impl Wishart {
+    /// p x p sample covariances of n observations, with the defaults above.
+    pub fn new(n: usize, p: usize) -> Self {
+        Self {
+            n,
+            p,
+            sigma_sq: 1.0,
+            covariance_root: None,
+            normalize: true,
+            complex: false,
+        }
+    }
+
+    /// Variance sigma^2 of the entries of X (default 1).
+    pub fn sigma_sq(mut self, sigma_sq: f64) -> Self {
+        self.sigma_sq = sigma_sq;
+        self
+    }
+
+    /// Population covariance: rows of X are drawn as z^T Sigma^{1/2} (default identity).
+    ///
+    /// `covariance` should be symmetric positive semidefinite; negative
+    /// eigenvalues are clipped to zero when forming the square root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `covariance` is not p x p.
+    pub fn covariance(mut self, covariance: &Array2<f64>) -> Self {
+        assert_eq!(
+            covariance.dim(),
+            (self.p, self.p),
+            "covariance must be p x p"
+        );
+        let (values, vectors) = symmetric_eigen(covariance);
+        let roots: Vec<f64> = values.iter().map(|v| v.max(0.0).sqrt()).collect();
+        self.covariance_root = Some(reconstruct(&vectors, &roots));
+        self
+    }
+
+    /// Divide by n so the spectrum stays O(1) (default true).
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Complex Gaussian entries (beta = 2) instead of real ones (default false).
+    ///
+    /// Real and imaginary parts each have variance sigma^2 / 2. The limiting
+    /// density is unchanged; [`Ensemble::sample`] then returns the real
+    /// 2p x 2p embedding [[A, -B], [B, A]] of W = A + iB, and
+    /// [`Wishart::sample_complex`] returns W itself.
+    pub fn complex(mut self, complex: bool) -> Self {
+        self.complex = complex;
+        self
+    }
+
+    /// Aspect ratio gamma = p/n.
+    pub fn ratio(&self) -> f64 {
+        self.p as f64 / self.n as f64
+    }
+
+    /// Draw W as a complex Hermitian matrix (real entries when not [`Wishart::complex`]).
+    pub fn sample_complex<R: Rng>(&self, rng: &mut R) -> Array2<Complex64> {
+        let (re, im) = self.sample_parts(rng);
+        Array2::from_shape_fn((self.p, self.p), |(i, j)| {
+            Complex64::new(re[[i, j]], im[[i, j]])
+        })
+    }
+
+    /// Real and imaginary parts of W.
+    fn sample_parts<R: Rng>(&self, rng: &mut R) -> (Array2<f64>, Array2<f64>) {
+        let scale = if self.normalize {
+            1.0 / self.n as f64
+        } else {
+            1.0
+        };
+        if !self.complex {
+            // Sigma^{1/2} Z^T Z Sigma^{1/2}, with Z^T Z from the Bartlett sampler.
+            let mut w = sample_wishart_with(rng, self.n, self.p);
+            if let Some(root) = &self.covariance_root {
+                w = root.dot(&w).dot(root);
+            }
+            return (w * (self.sigma_sq * scale), Array2::zeros((self.p, self.p)));
+        }
+
+        let std = (0.5 * self.sigma_sq).sqrt();
+        let mut gaussian = |_| {
+            let z: f64 = StandardNormal.sample(rng);
+            std * z
+        };
+        let mut x_re: Array2<f64> = Array2::from_shape_fn((self.n, self.p), &mut gaussian);
+        let mut x_im: Array2<f64> = Array2::from_shape_fn((self.n, self.p), &mut gaussian);
+        if let Some(root) = &self.covariance_root {
+            x_re = x_re.dot(root);
+            x_im = x_im.dot(root);
+        }
+        let re = (x_re.t().dot(&x_re) + x_im.t().dot(&x_im)) * scale;
+        let im = (x_re.t().dot(&x_im) - x_im.t().dot(&x_re)) * scale;
+        (re, im)
+    }
+}
+
+impl Ensemble for Wishart {
+    fn sample<R: Rng>(&self, rng: &mut R) -> Array2<f64> {
+        let (re, im) = self.sample_parts(rng);
+        if !self.complex {
+            return re;
+        }
+        real_embedding(&re, &im)
+    }
+
+    /// Marchenko-Pastur density (rescaled by n when not normalized).
+    ///
+    /// For p > n, W has p - n zero eigenvalues; the density is then that of
+    /// the other n, which fill sigma^2 [(sqrt(gamma) - 1)^2, (sqrt(gamma) + 1)^2],
+    /// and integrates to n/p rather than 1.
+    ///
+    /// There is no closed form with a general population covariance, so this
+    /// returns NaN once [`Wishart::covariance`] is set.
+    fn limiting_density(&self, x: f64) -> f64 {
+        if self.covariance_root.is_some() {
+            return f64::NAN;
+        }
+        let unit = if self.normalize { 1.0 } else { self.n as f64 };
+        let (x, ratio) = (x / unit, self.ratio());
+        let density = if ratio <= 1.0 {
+            marchenko_pastur_density(x, ratio, self.sigma_sq)
+        } else {
+            // The nonzero eigenvalues are gamma times those of X X^T / p ~ MP(1 / gamma),
+            // the law marchenko_pastur_density folds gamma onto.
+            marchenko_pastur_density(x / ratio, ratio, self.sigma_sq) / (ratio * ratio)
+        };
+        density / unit
+    }
+
+    /// p, or 2p for the real embedding of a complex ensemble.
+    fn dim(&self) -> usize {
+        if self.complex {
+            2 * self.p
+        } else {
+            self.p
+        }
+    }
+
+    /// The p eigenvalues of W; for a complex ensemble each eigenvalue of the
+    /// embedding appears twice and one copy is kept.
+    fn sample_eigenvalues<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        let eigenvalues = symmetric_eigenvalues(&self.sample(rng));
+        if self.complex {
+            eigenvalues.into_iter().step_by(2).collect()
+        } else {
+            eigenvalues
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    /// Mean absolute gap between a pooled histogram and the limiting density.
+    fn density_error<E: Ensemble>(ensemble: &E, trials: usize) -> f64 {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(173);
+        let eigenvalues: Vec<f64> = (0..trials)
+            .flat_map(|_| ensemble.sample_eigenvalues(&mut rng))
+            .collect();
+        assert_eq!(eigenvalues.len(), trials * ensemble.dim());
+        histogram_error(ensemble, &eigenvalues)
+    }
+
+    /// [`density_error`] for ensembles whose `sample_eigenvalues` returns `per_trial` values.
+    fn density_error_pooled<E: Ensemble>(ensemble: &E, trials: usize, per_trial: usize) -> f64 {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(181);
+        let eigenvalues: Vec<f64> = (0..trials)
+            .flat_map(|_| ensemble.sample_eigenvalues(&mut rng))
+            .collect();
+        assert_eq!(eigenvalues.len(), trials * per_trial);
+        histogram_error(ensemble, &eigenvalues)
+    }
+
+    fn histogram_error<E: Ensemble>(ensemble: &E, eigenvalues: &[f64]) -> f64 {
+        let (centers, density) = crate::empirical_spectral_density(eigenvalues, 30);
+        centers
+            .iter()
+            .zip(&density)
+            .map(|(&x, &d)| (d - ensemble.limiting_density(x)).abs())
+            .sum::<f64>()
+            / centers.len() as f64
+    }
+
+    #[test]
+    fn test_ensembles_match_limiting_densities() {
+        assert!(density_error(&Goe::new(200), 5) < 0.03);
+        assert!(density_error_pooled(&Gue::new(200), 5, 200) < 0.03);
+        assert!(density_error(&Wishart::new(400, 100), 10) < 0.05);
+    }
+
+    #[test]
+    fn test_wishart_builder_variants() {
+        let scaled = Wishart::new(400, 100).sigma_sq(2.0);
+        assert!(density_error(&scaled, 5) < 0.03);
+
+        // Complex entries: same MP law, p eigenvalues from the 2p embedding.
+        let complex = Wishart::new(400, 100).complex(true);
+        assert_eq!(complex.dim(), 200);
+        assert!(density_error_pooled(&complex, 5, 100) < 0.05);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(179);
+        let w = complex.sample_complex(&mut rng);
+        assert!((w[[3, 7]] - w[[7, 3]].conj()).norm() < 1e-12);
+        assert!(w[[3, 7]].im != 0.0);
+
+        let raw = Wishart::new(400, 100).normalize(false);
+        let top = *raw.sample_eigenvalues(&mut rng).last().unwrap();
+        assert!((top / 400.0 - 2.25).abs() < 0.2);
+        assert!(
+            (raw.limiting_density(400.0) - Wishart::new(400, 100).limiting_density(1.0) / 400.0)
+                .abs()
+                < 1e-15
+        );
+
+        let cov = Array2::from_diag(&ndarray::Array1::from_elem(100, 3.0));
+        let with_cov = Wishart::new(400, 100).covariance(&cov);
+        assert!(with_cov.limiting_density(1.0).is_nan());
+        let eigenvalues = with_cov.sample_eigenvalues(&mut rng);
+        let mean = eigenvalues.iter().sum::<f64>() / 100.0;
+        assert!((mean - 3.0).abs() < 0.1, "{}", mean);
+    }
+
+    #[test]
+    fn test_wishart_more_variables_than_samples() {
+        // gamma = 2: half the eigenvalues are zero and the rest follow the
+        // continuous part of the density, of mass 1/2.
+        let wishart = Wishart::new(100, 200);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(191);
+        let nonzero: Vec<f64> = (0..10)
+            .flat_map(|_| wishart.sample_eigenvalues(&mut rng))
+            .filter(|&v| v > 1e-9)
+            .collect();
+        assert_eq!(nonzero.len(), 10 * 100);
+        let (centers, density) = crate::empirical_spectral_density(&nonzero, 30);
+        let error = centers
+            .iter()
+            .zip(&density)
+            .map(|(&x, &d)| (0.5 * d - wishart.limiting_density(x)).abs())
+            .sum::<f64>()
+            / centers.len() as f64;
+        assert!(error < 0.01, "{}", error);
+        let edge = (1.0 + 2.0_f64.sqrt()).powi(2);
+        assert_eq!(wishart.limiting_density(edge + 0.1), 0.0);
+        assert!(wishart.limiting_density(edge - 0.5) > 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "covariance must be p x p")]
+    fn test_wishart_rejects_misshapen_covariance() {
+        let _ = Wishart::new(400, 100).covariance(&Array2::eye(50));
+    }
+}
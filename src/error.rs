@@ -0,0 +1,130 @@
+//! Error type for validated constructors and checked entry points.
+//!
+//! The free functions of this crate are unchecked: nonsensical parameters
+//! yield 0, NaN, or an empty result rather than an error, and non-finite
+//! eigenvalues propagate. Types such as [`crate::MarchenkoPastur`] validate
+//! once at construction instead, and the `checked_*` functions scan their
+//! input with [`check_finite`] before delegating.
+
+use std::fmt;
+
+use crate::spectrum::AsEigenvalues;
+
+/// Errors reported by validating constructors and `checked_*` functions.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A parameter is outside its domain.
+    InvalidParameter {
+        /// Parameter name as it appears in the constructor signature
+        name: &'static str,
+        /// Offending value
+        value: f64,
+        /// Human-readable domain, e.g. "positive and finite"
+        expected: &'static str,
+    },
+    /// An input value is NaN or infinite.
+    NonFinite {
+        /// Position of the first non-finite value
+        index: usize,
+        /// The value itself
+        value: f64,
+    },
+}
+
+impl Error {
+    /// Validates that `value` is positive and finite.
+    pub(crate) fn check_positive(name: &'static str, value: f64) -> Result<f64, Error> {
+        if value.is_finite() && value > 0.0 {
+            Ok(value)
+        } else {
+            Err(Error::InvalidParameter {
+                name,
+                value,
+                expected: "positive and finite",
+            })
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidParameter {
+                name,
+                value,
+                expected,
+            } => write!(f, "invalid parameter {name} = {value}: expected {expected}"),
+            Error::NonFinite { index, value } => {
+                write!(f, "non-finite input {value} at index {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Checks that every value is finite.
+///
+/// # Errors
+///
+/// [`Error::NonFinite`] for the first NaN or infinite value.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{check_finite, Error};
+///
+/// assert!(check_finite(&[1.0, 2.0]).is_ok());
+/// assert!(matches!(
+///     check_finite(&[1.0, f64::NAN]),
+///     Err(Error::NonFinite { index: 1, .. })
+/// ));
+/// ```
+pub fn check_finite<E: AsEigenvalues + ?Sized>(values: &E) -> Result<(), Error> {
+    let values: &[f64] = &values.as_eigenvalues();
+    match values.iter().position(|v| !v.is_finite()) {
+        Some(index) => Err(Error::NonFinite {
+            index,
+            value: values[index],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// The finite values, in their original order.
+///
+/// The filtering counterpart of [`check_finite`], for inputs where NaN or
+/// infinite entries (e.g. failed eigensolver outputs) should be dropped
+/// rather than reported.
+pub fn retain_finite<E: AsEigenvalues + ?Sized>(values: &E) -> Vec<f64> {
+    let values: &[f64] = &values.as_eigenvalues();
+    values.iter().copied().filter(|v| v.is_finite()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_finite_reporting() {
+        let values = [0.5, 1.0, f64::INFINITY, f64::NAN];
+        assert_eq!(
+            check_finite(&values),
+            Err(Error::NonFinite {
+                index: 2,
+                value: f64::INFINITY
+            })
+        );
+        assert_eq!(retain_finite(&values), vec![0.5, 1.0]);
+        assert_eq!(
+            Error::NonFinite {
+                index: 2,
+                value: f64::INFINITY
+            }
+            .to_string(),
+            "non-finite input inf at index 2"
+        );
+        assert!(check_finite(&[]).is_ok());
+    }
+}
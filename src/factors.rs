@@ -0,0 +1,355 @@
+//! Number-of-factors estimators from econometrics.
+//!
+//! Thresholding at the MP edge needs the noise variance and misses weak
+//! factors whose eigenvalues sit just above the bulk. These estimators use
+//! only the shape of the top of the spectrum and are consistent under weaker
+//! assumptions (correlated or heteroskedastic noise).
+//!
+//! The signal-processing estimators ([`factor_count_kn`] and the
+//! information criteria) instead assume white noise of unknown variance and
+//! use the sample size n explicitly.
+
+use crate::linalg::least_squares;
+use crate::spectrum::AsEigenvalues;
+use crate::tracy_widom::{tracy_widom_sf, tw_centering_scaling};
+
+/// Onatski (2010) edge-distribution estimator of the number of factors.
+///
+/// Finds the largest k <= `k_max` whose eigenvalue gap lambda_k - lambda_{k+1}
+/// exceeds delta, where delta = 2 |beta| and beta is the slope of a regression
+/// of lambda_j, ..., lambda_{j+4} on (j - 1)^(2/3), ..., (j + 3)^(2/3). The
+/// regression tracks the Tracy-Widom-scale spacing of the bulk edge and is
+/// iterated with j = k + 1 until the count stabilizes.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `k_max` - Largest number of factors considered
+///
+/// # Returns
+///
+/// Estimated number of factors (0 if fewer than `k_max + 5` positive eigenvalues)
+pub fn factor_count_onatski<E: AsEigenvalues + ?Sized>(eigenvalues: &E, k_max: usize) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mu = positive_descending(eigenvalues);
+    if mu.len() < k_max + 5 {
+        return 0;
+    }
+
+    // 1-based indices as in the paper: mu[j - 1] is lambda_j.
+    let mut j = k_max + 1;
+    let mut k_hat = 0;
+    for _ in 0..100 {
+        let design: Vec<Vec<f64>> = (0..5)
+            .map(|i| vec![1.0, ((j + i - 1) as f64).powf(2.0 / 3.0)])
+            .collect();
+        let y: Vec<f64> = (0..5).map(|i| mu[j - 1 + i]).collect();
+        let Some(coef) = least_squares(&design, &y) else {
+            return k_hat;
+        };
+        let delta = 2.0 * coef[1].abs();
+
+        let next = (1..=k_max)
+            .rev()
+            .find(|&k| mu[k - 1] - mu[k] >= delta)
+            .unwrap_or(0);
+        if next + 1 == j {
+            return next;
+        }
+        k_hat = next;
+        j = next + 1;
+    }
+    k_hat
+}
+
+/// Ahn & Horenstein (2013) eigenvalue-ratio estimator: argmax_k lambda_k / lambda_{k+1}.
+///
+/// A mock eigenvalue lambda_0 = sum_j lambda_j / ln(m), with m the number of
+/// positive eigenvalues, lets the estimator return 0 for pure noise.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `k_max` - Largest number of factors considered
+///
+/// # Returns
+///
+/// Estimated number of factors in 0..=k_max
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::factor_count_er;
+///
+/// let eigenvalues = [50.0, 20.0, 1.3, 1.2, 1.1, 1.0, 0.9, 0.8, 0.7, 0.6];
+/// assert_eq!(factor_count_er(&eigenvalues, 5), 2);
+/// ```
+pub fn factor_count_er<E: AsEigenvalues + ?Sized>(eigenvalues: &E, k_max: usize) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mu = with_mock_eigenvalue(eigenvalues);
+    argmax_ratio(k_max, mu.len(), |k| mu[k] / mu[k + 1])
+}
+
+/// Ahn & Horenstein (2013) growth-ratio estimator.
+///
+/// GR(k) = ln(V_{k-1} / V_k) / ln(V_k / V_{k+1}) with V_k = sum_{j > k} lambda_j
+/// the residual variance after k factors. Less sensitive than the eigenvalue
+/// ratio to a single dominant factor.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `k_max` - Largest number of factors considered
+///
+/// # Returns
+///
+/// Estimated number of factors in 0..=k_max
+pub fn factor_count_gr<E: AsEigenvalues + ?Sized>(eigenvalues: &E, k_max: usize) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mu = with_mock_eigenvalue(eigenvalues);
+    // tail[k] = V_{k-1} in the paper's indexing (tail[0] includes the mock eigenvalue).
+    let mut tail = vec![0.0; mu.len() + 1];
+    for k in (0..mu.len()).rev() {
+        tail[k] = tail[k + 1] + mu[k];
+    }
+    argmax_ratio(k_max, mu.len().saturating_sub(1), |k| {
+        (tail[k] / tail[k + 1]).ln() / (tail[k + 1] / tail[k + 2]).ln()
+    })
+}
+
+/// Kritchman & Nadler (2009) sequential test for the number of signals in white noise.
+///
+/// For k = 0, 1, ... tests H0: "exactly k signals" by comparing the (k+1)-th
+/// largest eigenvalue with the Tracy-Widom law of the largest eigenvalue of a
+/// p - k dimensional noise bulk, stopping at the first non-significant one.
+/// Unlike [`crate::count_spikes_with`] the noise variance is not supplied but
+/// re-estimated under each H0 with the RMT bias correction for the k
+/// signal eigenvalues, so the decisions stay calibrated when p/n is not small.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - All p sample covariance eigenvalues (p < n) in any order
+/// * `n` - Number of samples
+/// * `alpha` - Per-test significance level
+///
+/// # Returns
+///
+/// Estimated number of signals (0 if p < 2 or n < 2)
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{factor_count_kn, sample_wishart_with, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+/// let cov = sample_wishart_with(&mut rng, 400, 100) * (3.0 / 400.0);
+/// assert_eq!(factor_count_kn(&symmetric_eigenvalues(&cov), 400, 0.01), 0);
+/// ```
+pub fn factor_count_kn<E: AsEigenvalues + ?Sized>(eigenvalues: &E, n: usize, alpha: f64) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mu = finite_descending(eigenvalues);
+    let p = mu.len();
+    if p < 2 || n < 2 {
+        return 0;
+    }
+    (0..p - 1)
+        .take_while(|&k| {
+            let sigma_sq = kn_noise_variance(&mu, k, n);
+            if sigma_sq.is_nan() || sigma_sq <= 0.0 {
+                return false;
+            }
+            let scaling = tw_centering_scaling(n, p - k);
+            let (center, scale) = (scaling.mu, scaling.sigma);
+            let statistic = (n as f64 * mu[k] / sigma_sq - center) / scale;
+            tracy_widom_sf(statistic, 1.0) < alpha
+        })
+        .count()
+}
+
+/// Wax & Kailath (1985) minimum description length estimate of the number of signals.
+///
+/// Minimizes n (p - k) ln(a_k / g_k) + (1/2) d_k ln n over k in 0..=k_max,
+/// where a_k and g_k are the arithmetic and geometric means of the p - k
+/// smallest eigenvalues and d_k = k (2p - k + 1) / 2 counts the free
+/// parameters of a real rank-k spiked covariance. Consistent as n -> inf with
+/// p fixed, but tends to miss weak signals near the BBP threshold when p/n
+/// is not small; prefer [`factor_count_kn`] there.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - All p sample covariance eigenvalues (p < n) in any order
+/// * `n` - Number of samples
+/// * `k_max` - Largest number of signals considered
+///
+/// # Returns
+///
+/// Estimated number of signals in 0..=min(k_max, p - 1)
+pub fn factor_count_mdl<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+    k_max: usize,
+) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    information_criterion(eigenvalues, n, k_max, 0.5 * (n as f64).ln())
+}
+
+/// Akaike-type estimate of the number of signals with Nadler's (2010) doubled penalty.
+///
+/// Same likelihood term as [`factor_count_mdl`] with penalty 2 d_k instead
+/// of (1/2) d_k ln n. The classical Wax-Kailath AIC (penalty d_k) over-counts
+/// badly when p/n is not small because the noise eigenvalues spread over the
+/// MP bulk and inflate a_k / g_k; doubling the penalty keeps the false-alarm
+/// rate low while still detecting weaker signals than MDL.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - All p sample covariance eigenvalues (p < n) in any order
+/// * `n` - Number of samples
+/// * `k_max` - Largest number of signals considered
+///
+/// # Returns
+///
+/// Estimated number of signals in 0..=min(k_max, p - 1)
+pub fn factor_count_aic<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+    k_max: usize,
+) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    information_criterion(eigenvalues, n, k_max, 2.0)
+}
+
+/// argmin over k of n (p - k) ln(a_k / g_k) + penalty * k (2p - k + 1) / 2.
+fn information_criterion(eigenvalues: &[f64], n: usize, k_max: usize, penalty: f64) -> usize {
+    let mu = positive_descending(eigenvalues);
+    let p = mu.len();
+    if p < 2 || n < 2 {
+        return 0;
+    }
+    (0..=k_max.min(p - 1))
+        .map(|k| {
+            let tail = &mu[k..];
+            let m = tail.len() as f64;
+            let arithmetic = tail.iter().sum::<f64>() / m;
+            let log_geometric = tail.iter().map(|v| v.ln()).sum::<f64>() / m;
+            let likelihood = n as f64 * m * (arithmetic.ln() - log_geometric);
+            let params = (k * (2 * p - k + 1)) as f64 / 2.0;
+            (k, likelihood + penalty * params)
+        })
+        .filter(|(_, v)| v.is_finite())
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(k, _)| k)
+        .unwrap_or(0)
+}
+
+/// Kritchman-Nadler noise variance assuming the k largest eigenvalues are signals.
+///
+/// Solves sigma^2 = (sum_{j >= k} mu_j + sum_{j < k} (mu_j - rho_j)) / (p - k) jointly
+/// with the quadratic rho_j^2 - rho_j (mu_j + sigma^2 - sigma^2 (p - k) / n) + mu_j sigma^2 = 0
+/// for the debiased signal eigenvalues rho_j, by fixed-point iteration.
+fn kn_noise_variance(mu: &[f64], k: usize, n: usize) -> f64 {
+    let m = (mu.len() - k) as f64;
+    let tail: f64 = mu[k..].iter().sum();
+    let mut sigma_sq = tail / m;
+    for _ in 0..100 {
+        let excess: f64 = mu[..k]
+            .iter()
+            .map(|&lambda| {
+                let b = lambda + sigma_sq - sigma_sq * m / n as f64;
+                let rho = 0.5 * (b + (b * b - 4.0 * lambda * sigma_sq).max(0.0).sqrt());
+                lambda - rho
+            })
+            .sum();
+        let next = (tail + excess) / m;
+        if (next - sigma_sq).abs() <= 1e-12 * sigma_sq.abs() {
+            return next;
+        }
+        sigma_sq = next;
+    }
+    sigma_sq
+}
+
+/// argmax over k in 0..=min(k_max, limit - 2) of a ratio criterion.
+fn argmax_ratio<F: Fn(usize) -> f64>(k_max: usize, limit: usize, criterion: F) -> usize {
+    if limit < 2 {
+        return 0;
+    }
+    (0..=k_max.min(limit - 2))
+        .map(|k| (k, criterion(k)))
+        .filter(|(_, v)| v.is_finite())
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(k, _)| k)
+        .unwrap_or(0)
+}
+
+/// Positive eigenvalues in descending order, preceded by the mock eigenvalue.
+fn with_mock_eigenvalue(eigenvalues: &[f64]) -> Vec<f64> {
+    let mu = positive_descending(eigenvalues);
+    if mu.len() < 2 {
+        return vec![];
+    }
+    let mock = mu.iter().sum::<f64>() / (mu.len() as f64).ln();
+    std::iter::once(mock).chain(mu).collect()
+}
+
+fn finite_descending(eigenvalues: &[f64]) -> Vec<f64> {
+    let mut mu: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .collect();
+    mu.sort_by(|a, b| b.total_cmp(a));
+    mu
+}
+
+fn positive_descending(eigenvalues: &[f64]) -> Vec<f64> {
+    let mut mu: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .collect();
+    mu.sort_by(|a, b| b.total_cmp(a));
+    mu
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::spiked_covariance_eigenvalues;
+
+    #[test]
+    fn test_factor_counts_recover_three_factors() {
+        let eigenvalues = spiked_covariance_eigenvalues(11, 400, 100, &[100.0, 64.0, 36.0]);
+        assert_eq!(factor_count_onatski(&eigenvalues, 8), 3);
+        assert_eq!(factor_count_er(&eigenvalues, 8), 3);
+        assert_eq!(factor_count_gr(&eigenvalues, 8), 3);
+        assert_eq!(factor_count_kn(&eigenvalues, 400, 0.01), 3);
+        assert_eq!(factor_count_mdl(&eigenvalues, 400, 8), 3);
+        assert_eq!(factor_count_aic(&eigenvalues, 400, 8), 3);
+    }
+
+    #[test]
+    fn test_kn_calibrated_on_pure_noise() {
+        let eigenvalues = spiked_covariance_eigenvalues(13, 200, 100, &[]);
+        assert_eq!(factor_count_kn(&eigenvalues, 200, 0.01), 0);
+        assert_eq!(factor_count_mdl(&eigenvalues, 200, 8), 0);
+        assert_eq!(factor_count_aic(&eigenvalues, 200, 8), 0);
+        assert_eq!(factor_count_kn(&[1.0], 200, 0.01), 0);
+    }
+
+    #[test]
+    fn test_factor_counts_pure_noise() {
+        let eigenvalues = spiked_covariance_eigenvalues(12, 400, 100, &[]);
+        assert_eq!(factor_count_onatski(&eigenvalues, 8), 0);
+        assert_eq!(factor_count_er(&eigenvalues, 8), 0);
+        assert_eq!(factor_count_gr(&eigenvalues, 8), 0);
+    }
+
+    #[test]
+    fn test_factor_counts_degenerate() {
+        assert_eq!(factor_count_onatski(&[1.0, 2.0], 3), 0);
+        assert_eq!(factor_count_er(&[], 3), 0);
+        assert_eq!(factor_count_gr(&[1.0], 3), 0);
+    }
+}
@@ -0,0 +1,656 @@
+//! Exact eigenvalue densities of the classical ensembles at finite n.
+//!
+//! The limiting laws (semicircle, Marchenko-Pastur) are only approached at
+//! rate 1/n in the bulk and n^(-2/3) at the edges, and small matrices show
+//! oscillations (one bump per eigenvalue) and tails beyond the limiting
+//! support. The mean densities here are exact for every n, written with
+//! the orthonormal functions of the ensemble's weight, so Monte Carlo
+//! histograms of small matrices can be checked against the right curve.
+//!
+//! Expected characteristic polynomials are finite-n objects too: E det(x - H)
+//! is a scaled Hermite or Laguerre polynomial whatever the entry
+//! distribution, its roots interlace like eigenvalues and approach the
+//! quantiles of the limiting law, and the finite free convolution of Marcus,
+//! Spielman and Srivastava maps such polynomials of A and B to that of
+//! A + Q B Q^T for a random rotation Q.
+
+use ndarray::Array2;
+
+use crate::special::{gamma_p, ln_gamma};
+use crate::symmetric_eigenvalues;
+
+/// Rescaling of a Hermite-function recurrence once values exceed this.
+const RESCALE: f64 = 1e100;
+
+/// Exact mean eigenvalue density of the n x n GOE (beta = 1) or GUE (beta = 2).
+///
+/// The normalization is that of [`crate::sample_goe_with`]: off-diagonal
+/// entries with E |h_ij|^2 = 1 / n and diagonal entries of variance 2 / n
+/// (GOE) or 1 / n (GUE), so the density tends to the semicircle on
+/// [-2, 2]. In z = x sqrt(n / 2) both ensembles have the Hermite weight of
+/// Mehta (2004), and with the Hermite functions phi_k orthonormal on the
+/// line,
+///
+/// - GUE: rho(z) = (1 / n) sum_{k < n} phi_k(z)^2;
+/// - GOE: the same sum plus sqrt(n / 2) phi_(n-1)(z) int sgn(z - t) phi_n(t) dt / 2,
+///   and phi_(n-1)(z) / int phi_(n-1) for odd n (Mehta, eq. 7.2.32).
+///
+/// The functions are generated by their three-term recurrences with
+/// rescaling, so the cost is O(n) per point and large |x| does not
+/// underflow prematurely.
+///
+/// # Arguments
+///
+/// * `x` - Point at which to evaluate the density
+/// * `n` - Matrix dimension
+/// * `beta` - 1 (GOE) or 2 (GUE)
+///
+/// # Returns
+///
+/// The density (integrating to 1), or NaN for n = 0 or another beta
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{gaussian_ensemble_density, wigner_semicircle_density};
+///
+/// // A 1 x 1 GOE is a single N(0, 2) entry.
+/// let x: f64 = 0.7;
+/// let normal = (-x * x / 4.0).exp() / (4.0 * std::f64::consts::PI).sqrt();
+/// assert!((gaussian_ensemble_density(x, 1, 1.0) - normal).abs() < 1e-14);
+/// // At n = 100 the GUE density is within a few percent of the semicircle in the bulk...
+/// let ratio = gaussian_ensemble_density(0.5, 100, 2.0) / wigner_semicircle_density(0.5, 1.0);
+/// assert!((ratio - 1.0).abs() < 0.05);
+/// // ...but is still far from zero at the edge, where the semicircle vanishes.
+/// assert!(gaussian_ensemble_density(2.0, 100, 2.0) > 0.01);
+/// ```
+pub fn gaussian_ensemble_density(x: f64, n: usize, beta: f64) -> f64 {
+    if n == 0 || !(beta == 1.0 || beta == 2.0) || x.is_nan() {
+        return f64::NAN;
+    }
+    let scale = (0.5 * n as f64).sqrt();
+    let z = (x * scale).abs();
+    let hermite = HermiteSums::new(z, n);
+    let mut density = hermite.kernel;
+    if beta == 1.0 {
+        density += scale * hermite.phi_last * (hermite.integral_n - 0.5 * hermite.total_n);
+        if n % 2 == 1 {
+            density += hermite.phi_last / hermite.total_last;
+        }
+    }
+    (density * scale / n as f64).max(0.0)
+}
+
+/// Exact mean eigenvalue density of a white Wishart matrix W / n at finite (n, p).
+///
+/// W = X^T X with X n x p of i.i.d. N(0, 1) (beta = 1) or standard complex
+/// Gaussian (beta = 2) entries, so the density tends to
+/// [`crate::marchenko_pastur_density`] with ratio p / n and unit variance.
+/// With m = min(n, p), a = |n - p| and the Laguerre functions
+/// psi_k(t) = sqrt(k! / Gamma(k + a + 1)) t^(a/2) e^(-t/2) L_k^(a)(t),
+/// orthonormal on (0, inf), the m nonzero eigenvalues t of W have density
+///
+/// - complex: sum_{k < m} psi_k(t)^2 (the Laguerre kernel);
+/// - real: a Pfaffian kernel built from f_k = psi_k / sqrt(t). Skew
+///   orthogonalization collapses it to O(m) recurrences plus a rank-one
+///   term, f_(m-1)(t) times the integral of f_0 for even m, and
+///   f_(m-1)(t) / int f_(m-1) for odd m, as for the GOE.
+///
+/// For p > n the density is that of the n nonzero eigenvalues, on the
+/// scale of the larger dimension, matching the folding in
+/// [`crate::marchenko_pastur_density`]. The point mass at zero is left out.
+///
+/// # Arguments
+///
+/// * `x` - Eigenvalue of W / n
+/// * `n` - Number of rows (samples)
+/// * `p` - Number of columns (variables)
+/// * `beta` - 1 (real) or 2 (complex)
+///
+/// # Returns
+///
+/// The density (integrating to 1), 0 for x <= 0, or NaN for n = 0, p = 0
+/// or another beta
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{marchenko_pastur_density, wishart_eigenvalue_density};
+///
+/// // With p = 1, W is chi-square with n degrees of freedom (here 4).
+/// let chi_sq = |y: f64| y * (-y / 2.0).exp() / 4.0;
+/// let x = 0.8;
+/// assert!((wishart_eigenvalue_density(x, 4, 1, 1.0) - 4.0 * chi_sq(4.0 * x)).abs() < 1e-12);
+/// // n = 400, p = 100: close to Marchenko-Pastur in the bulk, but not at the edge.
+/// let ratio = wishart_eigenvalue_density(1.0, 400, 100, 1.0) / marchenko_pastur_density(1.0, 0.25, 1.0);
+/// assert!((ratio - 1.0).abs() < 0.02);
+/// assert!(wishart_eigenvalue_density(2.25, 400, 100, 1.0) > 0.01);
+/// ```
+pub fn wishart_eigenvalue_density(x: f64, n: usize, p: usize, beta: f64) -> f64 {
+    if n == 0 || p == 0 || !(beta == 1.0 || beta == 2.0) || x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let (rows, m) = (n.max(p), n.min(p));
+    let t = rows as f64 * x;
+    let laguerre = LaguerreSums::new(t, m, (rows - m) as f64);
+    let density = if beta == 2.0 {
+        laguerre.kernel
+    } else {
+        laguerre.pfaffian
+    };
+    (density * rows as f64 / m as f64).max(0.0)
+}
+
+/// Expected characteristic polynomial E det(x I - M) of a classical random matrix.
+///
+/// Stored through its three-term recurrence
+/// p_(k+1)(x) = (x - a_k) p_k(x) - b_k p_(k-1)(x), p_0 = 1, which is also
+/// the Jacobi matrix whose eigenvalues are the roots. Only means and
+/// variances of the entries enter, so the same polynomial serves the real,
+/// complex and non-Gaussian versions of each ensemble.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpectedCharacteristicPolynomial {
+    /// Recurrence coefficients a_k (diagonal of the Jacobi matrix)
+    diagonal: Vec<f64>,
+    /// Recurrence coefficients b_k, k >= 1 (squared off-diagonal)
+    off_diagonal_sq: Vec<f64>,
+    /// Extra roots at zero (the p - n null eigenvalues of a wide Wishart matrix)
+    zeros: usize,
+}
+
+impl ExpectedCharacteristicPolynomial {
+    /// Wigner matrices in the normalization of [`crate::sample_goe_with`].
+    ///
+    /// With zero-mean entries and E |h_ij|^2 = 1 / n off the diagonal,
+    /// expanding the determinant along the last row gives
+    /// p_k = x p_(k-1) - ((k - 1) / n) p_(k-2), a Hermite polynomial
+    /// He_n(x sqrt(n)) / n^(n/2) whose roots fill the semicircle on [-2, 2].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Matrix dimension
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rmt::ExpectedCharacteristicPolynomial;
+    ///
+    /// // n = 2: E det(x - H) = x^2 - E h_12^2 = x^2 - 1/2.
+    /// let poly = ExpectedCharacteristicPolynomial::hermite(2);
+    /// assert_eq!(poly.coefficients(), vec![-0.5, 0.0, 1.0]);
+    /// let roots = ExpectedCharacteristicPolynomial::hermite(500).roots();
+    /// assert!(roots[0] > -2.0 && roots[499] < 2.0 && roots[0] < -1.9);
+    /// ```
+    pub fn hermite(n: usize) -> Self {
+        Self {
+            diagonal: vec![0.0; n],
+            off_diagonal_sq: (1..n).map(|k| k as f64 / n as f64).collect(),
+            zeros: 0,
+        }
+    }
+
+    /// White Wishart matrices W / n, W = X^T X with X n x p.
+    ///
+    /// For entries of mean 0 and variance 1, E det(y I - X^T X) is the monic
+    /// Laguerre polynomial (-1)^p p! L_p^(n - p)(y); in x = y / n its
+    /// recurrence coefficients are a_k = (2k + n - p + 1) / n and
+    /// b_k = k (k + n - p) / n^2, and the roots fill the Marchenko-Pastur
+    /// support. For p > n the polynomial is x^(p - n) times that of X X^T.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of rows (samples)
+    /// * `p` - Number of columns (variables)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rmt::{marchenko_pastur_support, ExpectedCharacteristicPolynomial};
+    ///
+    /// // The roots sum to E tr(W / n) = p.
+    /// let roots = ExpectedCharacteristicPolynomial::laguerre(400, 100).roots();
+    /// assert!((roots.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    /// let (lo, hi) = marchenko_pastur_support(0.25, 1.0);
+    /// assert!(roots[0] > lo && roots[99] < hi);
+    /// ```
+    pub fn laguerre(n: usize, p: usize) -> Self {
+        let (m, a) = (n.min(p), n.abs_diff(p) as f64);
+        let scale = n as f64;
+        Self {
+            diagonal: (0..m).map(|k| (2.0 * k as f64 + a + 1.0) / scale).collect(),
+            off_diagonal_sq: (1..m)
+                .map(|k| k as f64 * (k as f64 + a) / (scale * scale))
+                .collect(),
+            zeros: p.saturating_sub(n),
+        }
+    }
+
+    /// Degree of the polynomial (the matrix dimension).
+    pub fn degree(&self) -> usize {
+        self.diagonal.len() + self.zeros
+    }
+
+    /// Value at x, by the recurrence.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let (mut previous, mut current) = (0.0, 1.0);
+        for (k, a) in self.diagonal.iter().enumerate() {
+            let b = if k == 0 {
+                0.0
+            } else {
+                self.off_diagonal_sq[k - 1]
+            };
+            (previous, current) = (current, (x - a) * current - b * previous);
+        }
+        current * x.powi(self.zeros as i32)
+    }
+
+    /// Coefficients in ascending powers of x, `result[k]` multiplying x^k.
+    ///
+    /// The leading coefficient is 1. Monomial coefficients grow quickly and
+    /// cancel on evaluation, so prefer [`Self::evaluate`] and [`Self::roots`]
+    /// beyond a few tens of degrees.
+    pub fn coefficients(&self) -> Vec<f64> {
+        let (mut previous, mut current) = (vec![], vec![1.0]);
+        for (k, a) in self.diagonal.iter().enumerate() {
+            let b = if k == 0 {
+                0.0
+            } else {
+                self.off_diagonal_sq[k - 1]
+            };
+            let mut next = vec![0.0; current.len() + 1];
+            for (i, c) in current.iter().enumerate() {
+                next[i + 1] += c;
+                next[i] -= a * c;
+            }
+            for (i, c) in previous.iter().enumerate() {
+                next[i] -= b * c;
+            }
+            (previous, current) = (current, next);
+        }
+        let mut coefficients = vec![0.0; self.zeros];
+        coefficients.extend(current);
+        coefficients
+    }
+
+    /// Roots in ascending order, as eigenvalues of the Jacobi matrix.
+    ///
+    /// All roots are real and simple (apart from the fixed zeros), and the
+    /// empirical measure of the roots approaches the limiting eigenvalue
+    /// law, which makes them a deterministic n-point quadrature of it.
+    pub fn roots(&self) -> Vec<f64> {
+        let m = self.diagonal.len();
+        let jacobi = Array2::from_shape_fn((m, m), |(i, j)| {
+            if i == j {
+                self.diagonal[i]
+            } else if i.abs_diff(j) == 1 {
+                self.off_diagonal_sq[i.min(j)].sqrt()
+            } else {
+                0.0
+            }
+        });
+        let mut roots = vec![0.0; self.zeros];
+        roots.extend(symmetric_eigenvalues(&jacobi));
+        roots.sort_by(f64::total_cmp);
+        roots
+    }
+}
+
+/// Finite free additive convolution of two monic polynomials of degree n.
+///
+/// If p and q are the characteristic polynomials of symmetric A and B, then
+/// p (+)_n q is E det(x I - A - Q B Q^T) for Q Haar orthogonal
+/// (Marcus, Spielman & Srivastava 2022). Writing
+/// p(x) = sum_i (-1)^i a_i x^(n-i), the coefficients combine as
+/// c_k = sum_(i+j=k) (n-i)! (n-j)! / (n! (n-k)!) a_i b_j. Real-rootedness is
+/// preserved, and as n grows the root distribution tends to the free
+/// convolution of the two root distributions.
+///
+/// # Arguments
+///
+/// * `p` - Coefficients of the first polynomial, ascending powers
+/// * `q` - Coefficients of the second polynomial, ascending powers
+///
+/// # Returns
+///
+/// Coefficients of p (+)_n q, ascending powers
+///
+/// # Panics
+///
+/// If the two coefficient vectors differ in length.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{finite_free_convolution, ExpectedCharacteristicPolynomial};
+///
+/// // Two independent Wigner matrices add to one of twice the variance.
+/// let n = 6;
+/// let h = ExpectedCharacteristicPolynomial::hermite(n).coefficients();
+/// let sum = finite_free_convolution(&h, &h);
+/// for k in 0..=n {
+///     assert!((sum[k] - h[k] * 2f64.powf((n - k) as f64 / 2.0)).abs() < 1e-12);
+/// }
+/// ```
+pub fn finite_free_convolution(p: &[f64], q: &[f64]) -> Vec<f64> {
+    assert_eq!(p.len(), q.len(), "polynomials must have the same degree");
+    let Some(n) = p.len().checked_sub(1) else {
+        return vec![];
+    };
+    let ln_factorial = |k: usize| ln_gamma(k as f64 + 1.0);
+    let mut result = vec![0.0; n + 1];
+    for i in 0..=n {
+        for j in 0..=n - i {
+            let k = i + j;
+            let weight =
+                (ln_factorial(n - i) + ln_factorial(n - j) - ln_factorial(n) - ln_factorial(n - k))
+                    .exp();
+            // p[n - i] = (-1)^i a_i, so the signs combine to (-1)^k.
+            result[n - k] += weight * p[n - i] * q[n - j];
+        }
+    }
+    result
+}
+
+/// Hermite-function quantities at z >= 0 for the n-point GUE and GOE kernels.
+///
+/// phi_k(z) = H_k(z) exp(-z^2 / 2) / sqrt(2^k k! sqrt(pi)), orthonormal on the line.
+struct HermiteSums {
+    /// sum_{k < n} phi_k(z)^2
+    kernel: f64,
+    /// phi_(n-1)(z)
+    phi_last: f64,
+    /// int_(-inf)^z phi_n
+    integral_n: f64,
+    /// int phi_n over the line
+    total_n: f64,
+    /// int phi_(n-1) over the line
+    total_last: f64,
+}
+
+impl HermiteSums {
+    fn new(z: f64, n: usize) -> Self {
+        // phi_k = current * exp(log_scale); the kernel sum is kept in the same scale, squared.
+        let pi = std::f64::consts::PI;
+        let mut log_scale = -0.5 * z * z - 0.25 * pi.ln();
+        let (mut previous, mut current) = (0.0, 1.0);
+        let mut kernel = 0.0;
+        let mut phi_last = 0.0;
+        // (I_(k-1), I_k) for I_k(z) = int_(-inf)^z phi_k, and the same for the totals I_k(inf).
+        let total_0 = pi.powf(-0.25) * (2.0 * pi).sqrt();
+        let normal_cdf = 0.5 * (1.0 + gamma_p(0.5, 0.5 * z * z));
+        let (mut integral_previous, mut integral) = (0.0, total_0 * normal_cdf);
+        let (mut total_previous, mut total) = (0.0, total_0);
+        let unscale =
+            |value: f64, log_scale: f64| value.signum() * (value.abs().ln() + log_scale).exp();
+        for k in 0..n {
+            let phi = unscale(current, log_scale);
+            kernel += current * current;
+            if k + 1 == n {
+                phi_last = phi;
+            }
+            let kf = k as f64;
+            let ratio = (kf / (kf + 1.0)).sqrt();
+            let step = (2.0 / (kf + 1.0)).sqrt();
+            // From phi_k' = sqrt(k / 2) phi_(k-1) - sqrt((k + 1) / 2) phi_(k+1).
+            let integral_next = ratio * integral_previous - step * phi;
+            (integral_previous, integral) = (integral, integral_next);
+            (total_previous, total) = (total, ratio * total_previous);
+            let next = step * z * current - ratio * previous;
+            (previous, current) = (current, next);
+            if current.abs() > RESCALE {
+                previous /= RESCALE;
+                current /= RESCALE;
+                kernel /= RESCALE * RESCALE;
+                log_scale += RESCALE.ln();
+            }
+        }
+        Self {
+            kernel: unscale(kernel, 2.0 * log_scale),
+            phi_last,
+            integral_n: integral,
+            total_n: total,
+            total_last: total_previous,
+        }
+    }
+}
+
+/// Laguerre-function sums at t > 0 for the m-point LUE and LOE densities.
+struct LaguerreSums {
+    /// sum_{k < m} psi_k(t)^2, the LUE density
+    kernel: f64,
+    /// The LOE density of eigenvalues of X^T X with n - p = a
+    pfaffian: f64,
+}
+
+impl LaguerreSums {
+    fn new(t: f64, m: usize, a: f64) -> Self {
+        // sigma_k = sqrt(k (k + a)) are the recurrence coefficients of psi_k;
+        // the skew products of the basis below are -4 sigma_k, with sigma_0 read as 1.
+        let sigma = |k: usize| (k as f64 * (k as f64 + a)).sqrt();
+        let skew = |k: usize| if k == 0 { 1.0 } else { sigma(k) };
+        let sqrt_t = t.sqrt();
+        // psi_k = current * exp(log_scale); quadratic sums carry the square of the scale.
+        let mut log_scale = 0.5 * a * t.ln() - 0.5 * t - 0.5 * ln_gamma(a + 1.0);
+        let (mut previous, mut current) = (0.0, 1.0_f64);
+        let (mut f_previous, mut f_before) = (0.0, 0.0);
+        let mut g_previous = 0.0;
+        // Running skew-orthogonalized basis element and its epsilon transform.
+        let (mut r, mut r_epsilon) = (0.0, 0.0);
+        let (mut kernel, mut pfaffian) = (0.0, 0.0);
+        let (mut f_last, mut ln_ratio) = (0.0, 0.0);
+        for k in 0..m {
+            if current.abs() > RESCALE {
+                previous /= RESCALE;
+                current /= RESCALE;
+                f_previous /= RESCALE;
+                f_before /= RESCALE;
+                g_previous /= RESCALE;
+                r /= RESCALE;
+                r_epsilon /= RESCALE;
+                kernel /= RESCALE * RESCALE;
+                pfaffian /= RESCALE * RESCALE;
+                log_scale += RESCALE.ln();
+            }
+            // f_k = psi_k / sqrt(t) spans the LOE polynomials times the weight, and
+            // b_k = (2 sqrt(t) psi_(k-1))' = sigma_k f_k - sigma_(k-1) f_(k-2), with
+            // epsilon b_k = g_(k-1) = 2 sqrt(t) psi_(k-1).
+            let f = current / sqrt_t;
+            let g = 2.0 * sqrt_t * current;
+            let b = if k == 0 {
+                f
+            } else {
+                sigma(k) * f - sigma(k - 1) * f_before
+            };
+            kernel += current * current;
+            if k % 2 == m % 2 {
+                // First element of a pair; orthogonalize against the earlier pairs.
+                if k >= 2 {
+                    let ratio = skew(k - 1) / skew(k - 2);
+                    r = b + ratio * r;
+                    r_epsilon = g_previous + ratio * r_epsilon;
+                } else {
+                    r = b;
+                    r_epsilon = if k == 0 { 0.0 } else { g_previous };
+                }
+            } else if k >= 1 {
+                pfaffian -= (b * r_epsilon - r * g_previous) / (2.0 * skew(k - 1));
+            }
+            ln_ratio += if k % 2 == 1 {
+                skew(k).ln()
+            } else {
+                -skew(k).ln()
+            };
+            if k + 1 == m {
+                f_last = f;
+            }
+            let next =
+                ((2.0 * k as f64 + a + 1.0 - t) * current - sigma(k) * previous) / sigma(k + 1);
+            (f_before, f_previous) = (f_previous, f);
+            g_previous = g;
+            (previous, current) = (current, next);
+        }
+        // The rank-one term pairs f_(m-1) with the constant of the odd-size Pfaffian
+        // (odd m) or with epsilon f_0 (even m); int f_0 = 2^((a+1)/2) Gamma((a+1)/2) / sqrt(Gamma(a+1)).
+        let ln_total =
+            0.5 * (a + 1.0) * 2f64.ln() + ln_gamma(0.5 * (a + 1.0)) - 0.5 * ln_gamma(a + 1.0);
+        let rank_one = if m % 2 == 1 {
+            unscale(f_last, log_scale - ln_total - ln_ratio)
+        } else {
+            // epsilon f_0 = int f_0 (P((a + 1) / 2, t / 2) - 1 / 2)
+            let centered_cdf = gamma_p(0.5 * (a + 1.0), 0.5 * t) - 0.5;
+            -0.5 * centered_cdf * unscale(f_last, log_scale + ln_total + ln_ratio)
+        };
+        Self {
+            kernel: unscale(kernel, 2.0 * log_scale),
+            pfaffian: unscale(pfaffian, 2.0 * log_scale) + rank_one,
+        }
+    }
+}
+
+/// value * exp(log_scale), without overflowing or underflowing the intermediate.
+fn unscale(value: f64, log_scale: f64) -> f64 {
+    value.signum() * (value.abs().ln() + log_scale).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integrate(f: impl Fn(f64) -> f64, lo: f64, hi: f64) -> f64 {
+        let steps = 4000;
+        let h = (hi - lo) / steps as f64;
+        (0..steps).map(|i| f(lo + (i as f64 + 0.5) * h) * h).sum()
+    }
+
+    #[test]
+    fn test_gaussian_ensemble_densities() {
+        // n = 1 GUE is N(0, 1).
+        let x: f64 = 1.3;
+        let normal = (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt();
+        assert!((gaussian_ensemble_density(x, 1, 2.0) - normal).abs() < 1e-14);
+
+        for beta in [1.0, 2.0] {
+            for n in [2, 3, 4, 7, 60] {
+                let density = |x| gaussian_ensemble_density(x, n, beta);
+                let mass = integrate(density, -6.0, 6.0);
+                assert!((mass - 1.0).abs() < 1e-6, "beta {beta} n {n}: {mass}");
+                // E tr H^2 / n = 1 + (2 / beta - 1) / n.
+                let second = integrate(|x| x * x * density(x), -6.0, 6.0);
+                let expected = 1.0 + (2.0 / beta - 1.0) / n as f64;
+                assert!(
+                    (second - expected).abs() < 1e-6,
+                    "beta {beta} n {n}: {second}"
+                );
+                assert!((density(0.4) - density(-0.4)).abs() < 1e-14);
+            }
+        }
+
+        // Large n: no overflow, bulk close to the semicircle, tails vanish.
+        let bulk = gaussian_ensemble_density(0.0, 2000, 1.0);
+        assert!((bulk - 1.0 / std::f64::consts::PI).abs() < 1e-3);
+        let tail = gaussian_ensemble_density(3.0, 2000, 2.0);
+        assert!(tail.is_finite() && tail < 1e-100);
+
+        assert!(gaussian_ensemble_density(0.0, 0, 2.0).is_nan());
+        assert!(gaussian_ensemble_density(0.0, 3, 4.0).is_nan());
+    }
+
+    #[test]
+    fn test_expected_characteristic_polynomials() {
+        use rand::SeedableRng;
+
+        // Monte Carlo E det(x - M) for small GOE and (wide) Wishart matrices.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+        let trials = 20000;
+        let x = 0.7;
+        let det_at = |m: &Array2<f64>| {
+            symmetric_eigenvalues(m)
+                .iter()
+                .map(|l| x - l)
+                .product::<f64>()
+        };
+        let goe: f64 = (0..trials)
+            .map(|_| det_at(&crate::sample_goe_with(&mut rng, 3)))
+            .sum::<f64>()
+            / trials as f64;
+        let hermite = ExpectedCharacteristicPolynomial::hermite(3);
+        assert!((goe - hermite.evaluate(x)).abs() < 0.02, "{goe}");
+        for (n, p) in [(4, 2), (2, 3)] {
+            let wishart: f64 = (0..trials)
+                .map(|_| det_at(&(crate::sample_wishart_with(&mut rng, n, p) / n as f64)))
+                .sum::<f64>()
+                / trials as f64;
+            let laguerre = ExpectedCharacteristicPolynomial::laguerre(n, p);
+            assert_eq!(laguerre.degree(), p);
+            assert!(
+                (wishart - laguerre.evaluate(x)).abs() < 0.03,
+                "({n}, {p}): {wishart}"
+            );
+        }
+
+        // Coefficients, evaluation and roots describe the same polynomial.
+        let poly = ExpectedCharacteristicPolynomial::laguerre(5, 8);
+        let coefficients = poly.coefficients();
+        let horner = coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c);
+        assert!((horner - poly.evaluate(x)).abs() < 1e-12);
+        let roots = poly.roots();
+        assert_eq!(roots.len(), 8);
+        assert!(roots[..3].iter().all(|&r| r == 0.0));
+        for r in &roots[3..] {
+            assert!(poly.evaluate(*r).abs() < 1e-9);
+        }
+
+        // Convolving with (x - c)^n shifts by c.
+        let (n, c) = (5, 0.3_f64);
+        let shift: Vec<f64> = (0..=n)
+            .map(|k| {
+                let binomial = (ln_gamma(n as f64 + 1.0)
+                    - ln_gamma(k as f64 + 1.0)
+                    - ln_gamma((n - k) as f64 + 1.0))
+                .exp();
+                binomial * (-c).powi((n - k) as i32)
+            })
+            .collect();
+        let hermite = ExpectedCharacteristicPolynomial::hermite(n);
+        let shifted = finite_free_convolution(&hermite.coefficients(), &shift);
+        let value = shifted.iter().rev().fold(0.0, |acc, a| acc * x + a);
+        assert!((value - hermite.evaluate(x - c)).abs() < 1e-12);
+        assert!(finite_free_convolution(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_wishart_eigenvalue_densities() {
+        // p = 1 complex: W is Gamma(n, 1).
+        let (x, n) = (1.3, 3);
+        let gamma = |t: f64| t * t * (-t).exp() / 2.0;
+        assert!((wishart_eigenvalue_density(x, n, 1, 2.0) - 3.0 * gamma(3.0 * x)).abs() < 1e-12);
+
+        // Substituting x = u^2 keeps the x^(-1/2) hard edge of the square real case integrable.
+        for beta in [1.0, 2.0] {
+            for (n, p) in [(3, 3), (4, 4), (6, 3), (9, 4), (5, 8), (60, 40)] {
+                let density = |u: f64| 2.0 * u * wishart_eigenvalue_density(u * u, n, p, beta);
+                let mass = integrate(density, 0.0, 4.0);
+                assert!((mass - 1.0).abs() < 1e-4, "beta {beta} ({n}, {p}): {mass}");
+                // E tr W = n p, spread over min(n, p) eigenvalues on the max(n, p) scale.
+                let mean = integrate(|u| u * u * density(u), 0.0, 4.0);
+                assert!((mean - 1.0).abs() < 1e-4, "beta {beta} ({n}, {p}): {mean}");
+            }
+        }
+
+        // Large n: no overflow, and the bulk approaches Marchenko-Pastur.
+        let mp = crate::marchenko_pastur_density(1.0, 0.5, 1.0);
+        for beta in [1.0, 2.0] {
+            let density = wishart_eigenvalue_density(1.0, 4000, 2000, beta);
+            assert!((density / mp - 1.0).abs() < 0.01);
+            assert!(wishart_eigenvalue_density(4.0, 4000, 2000, beta) < 1e-100);
+        }
+
+        assert_eq!(wishart_eigenvalue_density(-1.0, 3, 2, 1.0), 0.0);
+        assert!(wishart_eigenvalue_density(1.0, 0, 2, 1.0).is_nan());
+        assert!(wishart_eigenvalue_density(1.0, 3, 2, 4.0).is_nan());
+    }
+}
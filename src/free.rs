@@ -0,0 +1,608 @@
+//! Free probability: moments, free cumulants, and R-transforms.
+//!
+//! Free cumulants play the role for free (rotationally invariant) matrix sums
+//! that classical cumulants play for sums of independent variables: the free
+//! cumulants of A + B are the sums of those of A and B. They are related to
+//! moments through non-crossing partitions:
+//!
+//! ```text
+//! m_n = sum_{pi in NC(n)} prod_{V in pi} kappa_{|V|}
+//! ```
+//!
+//! which unrolls into the recursion
+//!
+//! ```text
+//! m_n = sum_{s=1}^{n} kappa_s * [z^{n-s}] M(z)^s,   M(z) = 1 + m_1 z + m_2 z^2 + ...
+//! ```
+//!
+//! Sequences here are indexed from order 1: `moments[0] = m_1`, `cumulants[0] = kappa_1`.
+
+use std::f64::consts::PI;
+
+use ndarray::{Array1, Array2};
+use num_complex::Complex64;
+use rand::Rng;
+
+use crate::spectrum::AsEigenvalues;
+
+/// Raw spectral moments m_k = (1/n) sum lambda_i^k for k = 1..=k_max.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalue samples
+/// * `k_max` - Highest moment order
+///
+/// # Returns
+///
+/// Vector of length `k_max` with `result[k - 1] = m_k` (empty if no eigenvalues)
+pub fn spectral_moments<E: AsEigenvalues + ?Sized>(eigenvalues: &E, k_max: usize) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() {
+        return vec![];
+    }
+
+    let mut sums = vec![0.0; k_max];
+    for &ev in eigenvalues {
+        let mut power = 1.0;
+        for s in sums.iter_mut() {
+            power *= ev;
+            *s += power;
+        }
+    }
+
+    let n = eigenvalues.len() as f64;
+    sums.iter().map(|s| s / n).collect()
+}
+
+/// Spectral moments m_k = (1/n) tr A^k, k = 1..=k_max, of a symmetric matrix.
+///
+/// Diagonalizes A once and takes power sums of its eigenvalues, so the cost
+/// is one O(n^3) eigendecomposition plus O(k_max n), independent of how many
+/// moments are requested. For matrices where even that is too expensive, see
+/// [`hutchinson_spectral_moments`].
+///
+/// # Arguments
+///
+/// * `a` - Square symmetric matrix (only the lower triangle is read)
+/// * `k_max` - Highest moment order
+///
+/// # Returns
+///
+/// Vector of length `k_max` with `result[k - 1] = m_k` (empty for a 0 x 0 matrix)
+///
+/// # Panics
+///
+/// If `a` is not square.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{sample_goe_with, spectral_moments_from_matrix};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let h = sample_goe_with(&mut rng, 200);
+/// let moments = spectral_moments_from_matrix(&h, 4);
+/// // Semicircle: m_2 -> 1, m_4 -> 2.
+/// assert!((moments[3] - 2.0).abs() < 0.1);
+/// ```
+pub fn spectral_moments_from_matrix(a: &Array2<f64>, k_max: usize) -> Vec<f64> {
+    assert_eq!(a.nrows(), a.ncols(), "matrix must be square");
+    if a.nrows() == 0 {
+        return vec![];
+    }
+    spectral_moments(&crate::linalg::symmetric_eigenvalues(a), k_max)
+}
+
+/// Stochastic trace estimates of spectral moments, with standard errors.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MomentEstimates {
+    /// Estimates of m_1, ..., m_(k_max)
+    pub moments: Vec<f64>,
+    /// Standard error of each estimate over the probes (NaN for one probe)
+    pub standard_errors: Vec<f64>,
+}
+
+/// Spectral moments of a symmetric matrix from Hutchinson trace estimates.
+///
+/// For Rademacher probe vectors z (i.i.d. +-1 entries), z^T A^k z / n is an
+/// unbiased estimate of m_k = (1/n) tr A^k with variance
+/// 2 sum_(i != j) (A^k)_ij^2 / n^2. Writing z^T A^k z = (A^i z)^T (A^j z)
+/// with i + j = k needs only ceil(k_max / 2) matrix-vector products per
+/// probe, so the cost is O(probes k_max n^2) for a dense A. For Wigner or
+/// Wishart matrices the per-probe variance is O(1 / n), so a few probes
+/// already give moments accurate to a few percent at large n.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator for the probes
+/// * `a` - Symmetric matrix (only A v products are used; symmetry is not checked)
+/// * `k_max` - Highest moment order
+/// * `probes` - Number of probe vectors
+///
+/// # Returns
+///
+/// Moment estimates and their standard errors (empty for a 0 x 0 matrix or
+/// no probes)
+///
+/// # Panics
+///
+/// If `a` is not square.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{hutchinson_spectral_moments, sample_wishart_with};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+/// let (n, p) = (2000, 500);
+/// let w = sample_wishart_with(&mut rng, n, p) / n as f64;
+/// let estimates = hutchinson_spectral_moments(&mut rng, &w, 3, 10);
+/// // Marchenko-Pastur with gamma = 1/4: m_2 = 1 + gamma, m_3 = 1 + 3 gamma + gamma^2.
+/// assert!((estimates.moments[1] - 1.25).abs() < 0.05);
+/// assert!((estimates.moments[2] - 1.8125).abs() < 0.1);
+/// assert!(estimates.standard_errors[2] < 0.05);
+/// ```
+pub fn hutchinson_spectral_moments<R: Rng + ?Sized>(
+    rng: &mut R,
+    a: &Array2<f64>,
+    k_max: usize,
+    probes: usize,
+) -> MomentEstimates {
+    assert_eq!(a.nrows(), a.ncols(), "matrix must be square");
+    let n = a.nrows();
+    if n == 0 || probes == 0 {
+        return MomentEstimates {
+            moments: vec![],
+            standard_errors: vec![],
+        };
+    }
+    let (mut sums, mut squares) = (vec![0.0; k_max], vec![0.0; k_max]);
+    for _ in 0..probes {
+        let z: Array1<f64> = (0..n)
+            .map(|_| if rng.random() { 1.0 } else { -1.0 })
+            .collect();
+        // iterates[j] = A^j z
+        let mut iterates = vec![z];
+        while iterates.len() <= k_max.div_ceil(2) {
+            let next = a.dot(&iterates[iterates.len() - 1]);
+            iterates.push(next);
+        }
+        for k in 1..=k_max {
+            let (i, j) = (k / 2, k - k / 2);
+            let estimate = iterates[i].dot(&iterates[j]) / n as f64;
+            sums[k - 1] += estimate;
+            squares[k - 1] += estimate * estimate;
+        }
+    }
+    let count = probes as f64;
+    let moments: Vec<f64> = sums.iter().map(|s| s / count).collect();
+    let standard_errors = moments
+        .iter()
+        .zip(&squares)
+        .map(|(mean, square)| {
+            let variance = (square - count * mean * mean).max(0.0) / (count - 1.0);
+            (variance / count).sqrt()
+        })
+        .collect();
+    MomentEstimates {
+        moments,
+        standard_errors,
+    }
+}
+
+/// Convert a moment sequence to free cumulants.
+///
+/// Inverts the non-crossing moment-cumulant relation order by order.
+///
+/// # Arguments
+///
+/// * `moments` - Moments m_1, m_2, ..., m_K
+///
+/// # Returns
+///
+/// Free cumulants kappa_1, ..., kappa_K
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::moments_to_free_cumulants;
+///
+/// // Semicircle of variance 1: m_2 = 1, m_4 = 2 (Catalan numbers), odd moments vanish.
+/// let kappa = moments_to_free_cumulants(&[0.0, 1.0, 0.0, 2.0]);
+/// assert!((kappa[1] - 1.0).abs() < 1e-12);
+/// assert!(kappa[3].abs() < 1e-12);
+/// ```
+pub fn moments_to_free_cumulants(moments: &[f64]) -> Vec<f64> {
+    let k_max = moments.len();
+    let powers = moment_series_powers(moments, k_max);
+    let mut cumulants = vec![0.0; k_max];
+
+    for n in 1..=k_max {
+        // The s = n term is kappa_n * [z^0] M(z)^n = kappa_n.
+        let lower: f64 = (1..n).map(|s| cumulants[s - 1] * powers[s][n - s]).sum();
+        cumulants[n - 1] = moments[n - 1] - lower;
+    }
+
+    cumulants
+}
+
+/// Convert free cumulants back to moments.
+///
+/// # Arguments
+///
+/// * `cumulants` - Free cumulants kappa_1, ..., kappa_K
+///
+/// # Returns
+///
+/// Moments m_1, ..., m_K
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::free_cumulants_to_moments;
+///
+/// // Marchenko-Pastur with ratio gamma has kappa_n = gamma^(n-1).
+/// let gamma: f64 = 0.5;
+/// let kappa: Vec<f64> = (0..3).map(|n| gamma.powi(n)).collect();
+/// let m = free_cumulants_to_moments(&kappa);
+/// assert!((m[1] - (1.0 + gamma)).abs() < 1e-12);
+/// ```
+pub fn free_cumulants_to_moments(cumulants: &[f64]) -> Vec<f64> {
+    let k_max = cumulants.len();
+    let mut moments = vec![0.0; k_max];
+
+    for n in 1..=k_max {
+        // M(z)^s only needs moments of order < n for the coefficients used here.
+        let powers = moment_series_powers(&moments[..n - 1], n);
+        moments[n - 1] = (1..=n).map(|s| cumulants[s - 1] * powers[s][n - s]).sum();
+    }
+
+    moments
+}
+
+/// R-transform R(z) = sum_{n >= 1} kappa_n z^{n-1}, truncated to the given cumulants.
+///
+/// The R-transform linearizes free additive convolution: R_{A+B} = R_A + R_B.
+/// The truncated series is only meaningful for |z| inside its radius of convergence.
+pub fn r_transform(cumulants: &[f64], z: f64) -> f64 {
+    cumulants.iter().rev().fold(0.0, |acc, &k| acc * z + k)
+}
+
+/// Free cumulants of the free compression of a measure by a factor t.
+///
+/// If A is n x n and rotationally invariant with spectral law mu, the top-left
+/// corner P A P of size tn has spectral law mu_t with kappa_n(mu_t) = t^(n-1) kappa_n(mu),
+/// i.e. R_{mu_t}(z) = R_mu(t z).
+///
+/// # Arguments
+///
+/// * `cumulants` - Free cumulants of mu
+/// * `t` - Compression fraction in (0, 1]
+pub fn free_compression_cumulants(cumulants: &[f64], t: f64) -> Vec<f64> {
+    let mut scale = 1.0;
+    cumulants
+        .iter()
+        .map(|&k| {
+            let out = k * scale;
+            scale *= t;
+            out
+        })
+        .collect()
+}
+
+/// Spectral density of the free compression P A P of an empirical spectrum.
+///
+/// Given the eigenvalues of a rotationally invariant matrix A, returns the
+/// limiting eigenvalue density of its tn x tn corner, evaluated on `grid`.
+/// This is the spectrum seen after subsampling coordinates or applying a
+/// random orthogonal sketch.
+///
+/// Uses the subordination relation for compression: with G the Cauchy transform
+/// of mu, G_t(z) = G(xi) / t where xi solves xi = z + (1 - t) / G(xi). The
+/// empirical G is evaluated slightly above the real axis, at height
+/// eta = (lambda_max - lambda_min) / n^(2/3), which smooths the discrete input.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum of A
+/// * `t` - Compression fraction in (0, 1]
+/// * `grid` - Points at which to evaluate the compressed density
+///
+/// # Returns
+///
+/// Density of mu_t at each grid point (all zeros for empty input or t outside (0, 1])
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::free_compression;
+///
+/// let eigenvalues: Vec<f64> = (0..200).map(|i| -1.0 + 2.0 * i as f64 / 199.0).collect();
+/// let density = free_compression(&eigenvalues, 0.5, &[0.0]);
+/// assert!(density[0] > 0.0);
+/// ```
+pub fn free_compression<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    t: f64,
+    grid: &[f64],
+) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() || !(t > 0.0 && t <= 1.0) {
+        return vec![0.0; grid.len()];
+    }
+
+    let eta = cauchy_smoothing_height(eigenvalues);
+
+    grid.iter()
+        .map(|&x| {
+            let z = Complex64::new(x, eta);
+            let mut xi = z;
+            for _ in 0..1000 {
+                let next = z + (1.0 - t) / cauchy_transform(eigenvalues, xi);
+                let step = next - xi;
+                // Damped update: the raw map is a contraction only near convergence.
+                xi += 0.5 * step;
+                if step.norm() < 1e-12 * (1.0 + xi.norm()) {
+                    break;
+                }
+            }
+            let g_t = cauchy_transform(eigenvalues, xi) / t;
+            (-g_t.im / PI).max(0.0)
+        })
+        .collect()
+}
+
+/// Spectral density of the free additive convolution mu + semicircle(variance).
+///
+/// mu is the empirical law of `eigenvalues`; the result is the limiting
+/// spectrum of A + W for A with that spectrum and W an independent Wigner
+/// matrix whose semicircle has the given variance (radius 2 sqrt(variance)).
+/// Solves Pastur's equation G(z) = G_mu(z - variance G(z)) by damped fixed-point
+/// iteration just above the real axis; for variance 0 this reduces to the
+/// smoothed empirical density.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum of A
+/// * `variance` - Variance sigma^2 of the semicircle
+/// * `grid` - Points at which to evaluate the density
+///
+/// # Returns
+///
+/// Density at each grid point (all zeros for empty input or negative variance)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{free_semicircle_convolution, wigner_semicircle_density};
+///
+/// // A point mass at 0 convolved with a semicircle is that semicircle.
+/// let density = free_semicircle_convolution(&[0.0], 1.0, &[0.5]);
+/// assert!((density[0] - wigner_semicircle_density(0.5, 1.0)).abs() < 1e-4);
+/// ```
+pub fn free_semicircle_convolution<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    variance: f64,
+    grid: &[f64],
+) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() || variance.is_nan() || variance < 0.0 {
+        return vec![0.0; grid.len()];
+    }
+    let eta = if variance > 0.0 {
+        1e-8 * variance.sqrt()
+    } else {
+        cauchy_smoothing_height(eigenvalues)
+    };
+
+    grid.iter()
+        .map(|&x| {
+            let z = Complex64::new(x, eta);
+            let mut g = cauchy_transform(eigenvalues, Complex64::new(x, variance.sqrt() + eta));
+            for _ in 0..5000 {
+                let next = cauchy_transform(eigenvalues, z - variance * g);
+                let step = next - g;
+                g += 0.5 * step;
+                if step.norm() < 1e-12 * (1.0 + g.norm()) {
+                    break;
+                }
+            }
+            (-g.im / PI).max(0.0)
+        })
+        .collect()
+}
+
+/// Cauchy transform G(z) = (1/n) sum 1/(z - lambda_i).
+pub(crate) fn cauchy_transform(eigenvalues: &[f64], z: Complex64) -> Complex64 {
+    let n = eigenvalues.len() as f64;
+    eigenvalues
+        .iter()
+        .map(|&ev| (z - ev).inv())
+        .sum::<Complex64>()
+        / n
+}
+
+/// Imaginary offset used to regularize empirical Cauchy transforms.
+fn cauchy_smoothing_height(eigenvalues: &[f64]) -> f64 {
+    let min = eigenvalues.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = eigenvalues
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let spread = (max - min).max(1e-3 * (1.0 + max.abs()));
+    spread / (eigenvalues.len() as f64).powf(2.0 / 3.0)
+}
+
+/// Coefficients of M(z)^s for s = 0..=order, truncated at z^order.
+///
+/// `result[s][j]` is [z^j] (1 + m_1 z + m_2 z^2 + ...)^s.
+fn moment_series_powers(moments: &[f64], order: usize) -> Vec<Vec<f64>> {
+    let mut series = vec![0.0; order + 1];
+    series[0] = 1.0;
+    for (j, &m) in moments.iter().enumerate().take(order) {
+        series[j + 1] = m;
+    }
+
+    let mut powers = Vec::with_capacity(order + 1);
+    let mut current = vec![0.0; order + 1];
+    current[0] = 1.0;
+    powers.push(current.clone());
+
+    for _ in 1..=order {
+        let mut next = vec![0.0; order + 1];
+        for (i, &c) in current.iter().enumerate() {
+            if c == 0.0 {
+                continue;
+            }
+            for (j, &m) in series.iter().enumerate().take(order + 1 - i) {
+                next[i + j] += c * m;
+            }
+        }
+        powers.push(next.clone());
+        current = next;
+    }
+
+    powers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_semicircle_cumulants() {
+        // Catalan numbers: m_{2k} = C_k for the unit-variance semicircle.
+        let moments = [0.0, 1.0, 0.0, 2.0, 0.0, 5.0, 0.0, 14.0];
+        let kappa = moments_to_free_cumulants(&moments);
+        for (i, k) in kappa.iter().enumerate() {
+            let expected = if i == 1 { 1.0 } else { 0.0 };
+            assert!((k - expected).abs() < 1e-10, "kappa_{} = {}", i + 1, k);
+        }
+    }
+
+    #[test]
+    fn test_marchenko_pastur_moments() {
+        // MP moments are Narayana polynomials: m_3 = 1 + 3g + g^2.
+        let gamma: f64 = 0.3;
+        let kappa: Vec<f64> = (0..4).map(|n| gamma.powi(n)).collect();
+        let m = free_cumulants_to_moments(&kappa);
+        assert!((m[0] - 1.0).abs() < 1e-12);
+        assert!((m[1] - (1.0 + gamma)).abs() < 1e-12);
+        assert!((m[2] - (1.0 + 3.0 * gamma + gamma * gamma)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let moments = [0.3, 1.2, 0.7, 3.1, 2.0, 9.5];
+        let back = free_cumulants_to_moments(&moments_to_free_cumulants(&moments));
+        for (a, b) in moments.iter().zip(&back) {
+            assert!((a - b).abs() < 1e-10, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_spectral_moments() {
+        let m = spectral_moments(&[1.0, 2.0, 3.0], 2);
+        assert!((m[0] - 2.0).abs() < 1e-12);
+        assert!((m[1] - 14.0 / 3.0).abs() < 1e-12);
+        assert!(spectral_moments(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_spectral_moments_from_matrix() {
+        use rand::SeedableRng;
+
+        // The eigenvalues of [[2, 1], [1, 2]] are 1 and 3.
+        let a = ndarray::array![[2.0, 1.0], [1.0, 2.0]];
+        let m = spectral_moments_from_matrix(&a, 5);
+        for (k, mk) in m.iter().enumerate() {
+            let expected = (1.0 + 3f64.powi(k as i32 + 1)) / 2.0;
+            assert!((mk - expected).abs() < 1e-9, "m_{} = {}", k + 1, mk);
+        }
+        assert!(spectral_moments_from_matrix(&Array2::zeros((0, 0)), 3).is_empty());
+
+        // Hutchinson on a diagonal matrix: z^T D^k z = tr D^k for every +-1 probe.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        let d = Array2::from_diag(&ndarray::array![0.5, -1.0, 2.0]);
+        let exact = spectral_moments_from_matrix(&d, 4);
+        let estimates = hutchinson_spectral_moments(&mut rng, &d, 4, 3);
+        for ((e, x), se) in estimates
+            .moments
+            .iter()
+            .zip(&exact)
+            .zip(&estimates.standard_errors)
+        {
+            assert!((e - x).abs() < 1e-12 && *se < 1e-12);
+        }
+
+        // Off-diagonal mass makes single probes noisy but keeps them unbiased.
+        let h = crate::sample_goe_with(&mut rng, 300);
+        let exact = spectral_moments_from_matrix(&h, 4);
+        let estimates = hutchinson_spectral_moments(&mut rng, &h, 4, 20);
+        for (k, x) in exact.iter().enumerate() {
+            let error = (estimates.moments[k] - x).abs();
+            let bound = 5.0 * estimates.standard_errors[k] + 1e-12;
+            assert!(error < bound, "m_{}: {}", k + 1, error);
+        }
+        assert!(hutchinson_spectral_moments(&mut rng, &h, 4, 0)
+            .moments
+            .is_empty());
+    }
+
+    /// Quantiles of the unit-variance semicircle, as a deterministic "spectrum".
+    fn semicircle_quantiles(n: usize) -> Vec<f64> {
+        let cdf = |x: f64| 0.5 + x * (4.0 - x * x).sqrt() / (4.0 * PI) + (x / 2.0).asin() / PI;
+        (0..n)
+            .map(|i| {
+                let target = (i as f64 + 0.5) / n as f64;
+                let (mut lo, mut hi) = (-2.0, 2.0);
+                for _ in 0..60 {
+                    let mid = 0.5 * (lo + hi);
+                    if cdf(mid) < target {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                0.5 * (lo + hi)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_free_compression_semicircle() {
+        // Compressing a semicircle of variance 1 by t gives a semicircle of variance t.
+        let eigenvalues = semicircle_quantiles(2000);
+        let t = 0.5;
+        let density = free_compression(&eigenvalues, t, &[0.0, 3.0]);
+        let expected = crate::wigner_semicircle_density(0.0, t.sqrt());
+        assert!(
+            (density[0] - expected).abs() / expected < 0.05,
+            "{} vs {}",
+            density[0],
+            expected
+        );
+        assert!(density[1] < 0.01);
+    }
+
+    #[test]
+    fn test_free_compression_invalid() {
+        assert_eq!(free_compression(&[1.0, 2.0], 0.0, &[1.0]), vec![0.0]);
+        assert_eq!(free_compression(&[], 0.5, &[1.0]), vec![0.0]);
+    }
+
+    #[test]
+    fn test_free_compression_cumulants() {
+        let k = free_compression_cumulants(&[1.0, 2.0, 3.0], 0.5);
+        assert_eq!(k, vec![1.0, 1.0, 0.75]);
+    }
+
+    #[test]
+    fn test_r_transform_semicircle() {
+        // R(z) = sigma^2 z for the semicircle.
+        assert!((r_transform(&[0.0, 1.0, 0.0], 0.25) - 0.25).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,422 @@
+//! Goodness-of-fit tests of spectra against limiting laws.
+//!
+//! These compare the empirical CDF of the eigenvalues with a theoretical CDF.
+//! The p-values use the i.i.d. null distributions of the statistics. Eigenvalues
+//! of a matrix drawn from the null ensemble are *more* regular than i.i.d.
+//! samples (level repulsion), so the statistics are typically smaller than the
+//! i.i.d. theory predicts and the p-values are conservative: a small p-value is
+//! strong evidence against the law, while a moderate one says little about
+//! fine structure.
+
+use crate::special::bessel_k;
+use crate::spectrum::AsEigenvalues;
+use crate::{marchenko_pastur_cdf, wigner_semicircle_cdf};
+
+/// Result of a goodness-of-fit test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoodnessOfFit {
+    /// Test statistic (larger means a worse fit)
+    pub statistic: f64,
+    /// Asymptotic p-value under the i.i.d. null
+    pub p_value: f64,
+    /// Number of samples used
+    pub n: usize,
+}
+
+/// One-sample Kolmogorov-Smirnov test against an arbitrary CDF.
+///
+/// D = sup |F_n(x) - F(x)|, with the asymptotic Kolmogorov p-value using
+/// Stephens' finite-n correction sqrt(n) + 0.12 + 0.11 / sqrt(n).
+///
+/// # Arguments
+///
+/// * `samples` - Observations in any order
+/// * `cdf` - Hypothesized continuous CDF
+///
+/// # Returns
+///
+/// `None` for an empty sample
+pub fn ks_test<F: Fn(f64) -> f64, E: AsEigenvalues + ?Sized>(
+    samples: &E,
+    cdf: F,
+) -> Option<GoodnessOfFit> {
+    let samples: &[f64] = &samples.as_eigenvalues();
+    if samples.is_empty() {
+        return None;
+    }
+    let sorted = sorted_copy(samples);
+    let statistic = ks_distance_sorted(&sorted, cdf);
+    let root_n = (sorted.len() as f64).sqrt();
+    let p_value = kolmogorov_survival((root_n + 0.12 + 0.11 / root_n) * statistic);
+    Some(GoodnessOfFit {
+        statistic,
+        p_value,
+        n: sorted.len(),
+    })
+}
+
+/// Kolmogorov-Smirnov test of a spectrum against the Marchenko-Pastur law.
+///
+/// "Does my covariance spectrum look like noise?" in one call.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues of (1/n) X^T X
+/// * `ratio` - gamma = p/n
+/// * `sigma_sq` - Noise variance
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{ks_test_mp, sample_wishart_with, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+/// let (n, p) = (400, 100);
+/// let w = sample_wishart_with(&mut rng, n, p) / n as f64;
+/// let result = ks_test_mp(&symmetric_eigenvalues(&w), p as f64 / n as f64, 1.0).unwrap();
+/// assert!(result.p_value > 0.05);
+/// ```
+pub fn ks_test_mp<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> Option<GoodnessOfFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    ks_test(eigenvalues, |x| marchenko_pastur_cdf(x, ratio, sigma_sq))
+}
+
+/// Kolmogorov-Smirnov test of a spectrum against the Wigner semicircle.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues of a symmetric matrix, scaled so the radius is 2*sigma
+/// * `sigma` - Standard deviation of the semicircle (radius = 2*sigma)
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{ks_test_semicircle, sample_goe_with, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+/// let eigenvalues = symmetric_eigenvalues(&sample_goe_with(&mut rng, 200));
+/// assert!(ks_test_semicircle(&eigenvalues, 1.0).unwrap().p_value > 0.05);
+/// ```
+pub fn ks_test_semicircle<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    sigma: f64,
+) -> Option<GoodnessOfFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    ks_test(eigenvalues, |x| wigner_semicircle_cdf(x, sigma))
+}
+
+/// One-sample Anderson-Darling test against an arbitrary CDF.
+///
+/// A^2 = -n - (1/n) sum_i (2i - 1) [ln F(x_(i)) + ln(1 - F(x_(n+1-i)))] weights
+/// the tails by 1 / (F (1 - F)), so it is far more sensitive than KS to
+/// misfit at the spectral edges, where signal eigenvalues live. The p-value
+/// uses the Marsaglia & Marsaglia (2004) approximation to the asymptotic law.
+///
+/// # Arguments
+///
+/// * `samples` - Observations in any order
+/// * `cdf` - Hypothesized continuous CDF
+///
+/// # Returns
+///
+/// `None` for an empty sample
+pub fn ad_test<F: Fn(f64) -> f64, E: AsEigenvalues + ?Sized>(
+    samples: &E,
+    cdf: F,
+) -> Option<GoodnessOfFit> {
+    let samples: &[f64] = &samples.as_eigenvalues();
+    if samples.is_empty() {
+        return None;
+    }
+    let sorted = sorted_copy(samples);
+    let n = sorted.len();
+    // Samples outside the hypothesized support would give infinite terms.
+    let eps = 1e-15;
+    let u: Vec<f64> = sorted
+        .iter()
+        .map(|&x| cdf(x).clamp(eps, 1.0 - eps))
+        .collect();
+
+    let sum: f64 = (0..n)
+        .map(|i| (2 * i + 1) as f64 * (u[i].ln() + (1.0 - u[n - 1 - i]).ln()))
+        .sum();
+    let statistic = -(n as f64) - sum / n as f64;
+
+    Some(GoodnessOfFit {
+        statistic,
+        p_value: 1.0 - anderson_darling_cdf(statistic),
+        n,
+    })
+}
+
+/// Anderson-Darling test of a spectrum against the Marchenko-Pastur law.
+pub fn ad_test_mp<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> Option<GoodnessOfFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    ad_test(eigenvalues, |x| marchenko_pastur_cdf(x, ratio, sigma_sq))
+}
+
+/// Anderson-Darling test of a spectrum against the Wigner semicircle.
+pub fn ad_test_semicircle<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    sigma: f64,
+) -> Option<GoodnessOfFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    ad_test(eigenvalues, |x| wigner_semicircle_cdf(x, sigma))
+}
+
+/// One-sample Cramer-von Mises test against an arbitrary CDF.
+///
+/// W^2 = 1/(12n) + sum_i (F(x_(i)) - (2i - 1)/(2n))^2 integrates the squared
+/// CDF discrepancy, so it responds to diffuse misfit that KS can miss. The
+/// p-value uses the exact asymptotic distribution (Csorgo & Faraway 1996).
+///
+/// # Arguments
+///
+/// * `samples` - Observations in any order
+/// * `cdf` - Hypothesized continuous CDF
+///
+/// # Returns
+///
+/// `None` for an empty sample
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::cvm_test;
+///
+/// let samples: Vec<f64> = (0..50).map(|i| (i as f64 + 0.5) / 50.0).collect();
+/// let result = cvm_test(&samples, |x| x.clamp(0.0, 1.0)).unwrap();
+/// assert!(result.p_value > 0.99);
+/// ```
+pub fn cvm_test<F: Fn(f64) -> f64, E: AsEigenvalues + ?Sized>(
+    samples: &E,
+    cdf: F,
+) -> Option<GoodnessOfFit> {
+    let samples: &[f64] = &samples.as_eigenvalues();
+    if samples.is_empty() {
+        return None;
+    }
+    let sorted = sorted_copy(samples);
+    let n = sorted.len() as f64;
+    let statistic = 1.0 / (12.0 * n)
+        + sorted
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (cdf(x) - (2 * i + 1) as f64 / (2.0 * n)).powi(2))
+            .sum::<f64>();
+
+    Some(GoodnessOfFit {
+        statistic,
+        p_value: 1.0 - cramer_von_mises_cdf(statistic),
+        n: sorted.len(),
+    })
+}
+
+/// Cramer-von Mises test of a spectrum against the Marchenko-Pastur law.
+pub fn cvm_test_mp<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> Option<GoodnessOfFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    cvm_test(eigenvalues, |x| marchenko_pastur_cdf(x, ratio, sigma_sq))
+}
+
+/// Cramer-von Mises test of a spectrum against the Wigner semicircle.
+pub fn cvm_test_semicircle<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    sigma: f64,
+) -> Option<GoodnessOfFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    cvm_test(eigenvalues, |x| wigner_semicircle_cdf(x, sigma))
+}
+
+/// Asymptotic CDF of the Anderson-Darling statistic (Marsaglia & Marsaglia 2004).
+fn anderson_darling_cdf(z: f64) -> f64 {
+    if z <= 0.0 {
+        return 0.0;
+    }
+    let value = if z < 2.0 {
+        (-1.233_714_1 / z).exp() / z.sqrt()
+            * (2.000_12
+                + (0.247_105
+                    - (0.064_982_1 - (0.034_796_2 - (0.011_672 - 0.001_686_91 * z) * z) * z) * z)
+                    * z)
+    } else {
+        (-(1.077_6
+            - (2.306_95 - (0.434_24 - (0.082_433 - (0.008_056 - 0.000_314_6 * z) * z) * z) * z)
+                * z)
+            .exp())
+        .exp()
+    };
+    value.clamp(0.0, 1.0)
+}
+
+/// Asymptotic CDF of the Cramer-von Mises statistic.
+///
+/// P(W^2 <= x) = 1/(pi sqrt(x)) sum_j Gamma(j + 1/2) / (Gamma(1/2) j!) sqrt(4j + 1)
+///               exp(-u_j) K_{1/4}(u_j),  u_j = (4j + 1)^2 / (16 x)
+fn cramer_von_mises_cdf(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut sum = 0.0;
+    let mut coef = 1.0; // Gamma(j + 1/2) / (Gamma(1/2) j!)
+    for j in 0..200 {
+        if j > 0 {
+            coef *= (j as f64 - 0.5) / j as f64;
+        }
+        let k = 4.0 * j as f64 + 1.0;
+        let u = k * k / (16.0 * x);
+        if u > 700.0 {
+            break;
+        }
+        sum += coef * k.sqrt() * (-u).exp() * bessel_k(0.25, u);
+    }
+    (sum / (std::f64::consts::PI * x.sqrt())).clamp(0.0, 1.0)
+}
+
+/// Kolmogorov survival function Q(t) = 2 sum_{k>=1} (-1)^(k-1) exp(-2 k^2 t^2).
+pub(crate) fn kolmogorov_survival(t: f64) -> f64 {
+    if t <= 0.0 {
+        return 1.0;
+    }
+    if t < 0.2 {
+        // The alternating series converges slowly here and the value is 1 to
+        // double precision anyway.
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for k in 1..=100 {
+        let term = (-2.0 * (k * k) as f64 * t * t).exp();
+        sum += sign * term;
+        if term < 1e-16 {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// sup |F_emp - F| for sorted samples.
+pub(crate) fn ks_distance_sorted<F: Fn(f64) -> f64>(sorted: &[f64], cdf: F) -> f64 {
+    let n = sorted.len() as f64;
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let f = cdf(x);
+            (f - i as f64 / n).abs().max(((i + 1) as f64 / n - f).abs())
+        })
+        .fold(0.0, f64::max)
+}
+
+fn sorted_copy(samples: &[f64]) -> Vec<f64> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{marchenko_pastur_support, sample_wishart_with, symmetric_eigenvalues};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_kolmogorov_survival_reference() {
+        // Q(1.36) ~ 0.05 and Q(1.63) ~ 0.01 are the classical critical values.
+        assert!((kolmogorov_survival(1.358) - 0.05).abs() < 1e-3);
+        assert!((kolmogorov_survival(1.628) - 0.01).abs() < 1e-3);
+        assert_eq!(kolmogorov_survival(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_ks_uniform_grid() {
+        let samples: Vec<f64> = (0..100).map(|i| (i as f64 + 0.5) / 100.0).collect();
+        let result = ks_test(&samples, |x| x.clamp(0.0, 1.0)).unwrap();
+        assert!((result.statistic - 0.005).abs() < 1e-12);
+        assert!(result.p_value > 0.99);
+        assert_eq!(result.n, 100);
+    }
+
+    #[test]
+    fn test_ks_mp_rejects_wrong_ratio() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(4);
+        let (n, p) = (400, 200);
+        let w = sample_wishart_with(&mut rng, n, p) / n as f64;
+        let eigenvalues = symmetric_eigenvalues(&w);
+        let good = ks_test_mp(&eigenvalues, 0.5, 1.0).unwrap();
+        let bad = ks_test_mp(&eigenvalues, 0.1, 1.0).unwrap();
+        assert!(good.p_value > 0.05, "{:?}", good);
+        assert!(bad.p_value < 1e-6, "{:?}", bad);
+        // Sanity: the spectrum actually lives on the MP support.
+        let (lo, hi) = marchenko_pastur_support(0.5, 1.0);
+        assert!(eigenvalues[0] > lo - 0.1 && eigenvalues[p - 1] < hi + 0.2);
+    }
+
+    #[test]
+    fn test_ks_semicircle_rejects_wrong_scale() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(8);
+        let eigenvalues = symmetric_eigenvalues(&crate::sample_goe_with(&mut rng, 300));
+        assert!(ks_test_semicircle(&eigenvalues, 1.0).unwrap().p_value > 0.05);
+        assert!(ks_test_semicircle(&eigenvalues, 2.0).unwrap().p_value < 1e-6);
+    }
+
+    #[test]
+    fn test_asymptotic_critical_values() {
+        // Standard 5% and 1% critical values of A^2 and W^2 (case 0, all parameters known).
+        assert!((anderson_darling_cdf(2.492) - 0.95).abs() < 1e-3);
+        assert!((anderson_darling_cdf(3.857) - 0.99).abs() < 1e-3);
+        assert!((cramer_von_mises_cdf(0.46136) - 0.95).abs() < 1e-3);
+        assert!((cramer_von_mises_cdf(0.74346) - 0.99).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_ad_cvm_edge_sensitivity() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(13);
+        let (n, p) = (400, 100);
+        let w = sample_wishart_with(&mut rng, n, p) / n as f64;
+        let mut eigenvalues = symmetric_eigenvalues(&w);
+        assert!(ad_test_mp(&eigenvalues, 0.25, 1.0).unwrap().p_value > 0.05);
+        assert!(cvm_test_mp(&eigenvalues, 0.25, 1.0).unwrap().p_value > 0.05);
+
+        // Push a handful of eigenvalues well outside the bulk.
+        for ev in eigenvalues.iter_mut().rev().take(5) {
+            *ev += 3.0;
+        }
+        let ad = ad_test_mp(&eigenvalues, 0.25, 1.0).unwrap();
+        let ks = ks_test_mp(&eigenvalues, 0.25, 1.0).unwrap();
+        assert!(ad.p_value < 0.01, "{:?}", ad);
+        assert!(ad.p_value < ks.p_value);
+    }
+
+    #[test]
+    fn test_ad_cvm_semicircle() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(17);
+        let eigenvalues = symmetric_eigenvalues(&crate::sample_goe_with(&mut rng, 200));
+        assert!(ad_test_semicircle(&eigenvalues, 1.0).unwrap().p_value > 0.05);
+        assert!(cvm_test_semicircle(&eigenvalues, 1.0).unwrap().p_value > 0.05);
+        assert!(cvm_test_semicircle(&eigenvalues, 2.0).unwrap().p_value < 1e-3);
+    }
+
+    #[test]
+    fn test_ks_empty() {
+        assert!(ad_test(&[], |x| x).is_none());
+        assert!(cvm_test(&[], |x| x).is_none());
+        assert!(ks_test_mp(&[], 0.5, 1.0).is_none());
+        assert!(ks_test_semicircle(&[], 1.0).is_none());
+    }
+}
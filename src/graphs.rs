@@ -0,0 +1,1192 @@
+//! Random graphs and the spectra of their adjacency and Laplacian matrices.
+//!
+//! A graph on n vertices is represented by its dense symmetric 0/1
+//! adjacency matrix A. From A and the degrees D = diag(A 1) come the
+//! combinatorial Laplacian L = D - A (eigenvalues in [0, 2 max deg], 0 with
+//! multiplicity the number of components) and the normalized Laplacian
+//! I - D^(-1/2) A D^(-1/2) (eigenvalues in [0, 2]). [`graph_spectrum`]
+//! returns the sorted spectrum of any of the three, and the random graph
+//! models pair a sampler with the limiting law of each:
+//!
+//! - [`ErdosRenyi`] G(n, p): semicircular adjacency bulk, a shifted
+//!   semicircle for the normalized Laplacian, and the free convolution of
+//!   the degree distribution with a semicircle for L (Ding & Jiang 2010).
+//! - [`RandomRegular`]: the Kesten-McKay law, which L = d I - A and the
+//!   normalized Laplacian I - A / d inherit by an affine change of variable.
+//!   [`kesten_mckay_test`] scores a regular graph's spectrum against it, and
+//!   [`expander_statistics`] its second eigenvalue against the Ramanujan
+//!   bound 2 sqrt(d - 1).
+//! - [`StochasticBlockModel`]: a finite-rank spike on a semicircular bulk,
+//!   with outliers (and community detection) above the Kesten-Stigum
+//!   threshold, also visible in [`non_backtracking_outliers`].
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use rand::Rng;
+use rand_distr::Distribution;
+
+use crate::error::Error;
+use crate::linalg::{general_eigenvalues, symmetric_eigenvalues};
+use crate::special::ln_gamma;
+use crate::spectrum::AsEigenvalues;
+use crate::{
+    free_semicircle_convolution, ks_test, spectral_divergences, wigner_semicircle_density,
+    GoodnessOfFit, KestenMcKay, SampleSummary, SpectralDivergences,
+};
+
+/// Equal-weight atoms used to represent the binomial degree distribution.
+const DEGREE_ATOMS: usize = 400;
+
+/// Which matrix of a graph to take the spectrum of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphMatrix {
+    /// Adjacency matrix A
+    Adjacency,
+    /// Combinatorial Laplacian D - A
+    Laplacian,
+    /// Normalized Laplacian I - D^(-1/2) A D^(-1/2)
+    NormalizedLaplacian,
+}
+
+/// Vertex degrees, the row sums of the adjacency matrix.
+pub fn degrees(adjacency: &Array2<f64>) -> Vec<f64> {
+    adjacency.rows().into_iter().map(|row| row.sum()).collect()
+}
+
+/// Combinatorial Laplacian L = D - A.
+pub fn laplacian(adjacency: &Array2<f64>) -> Array2<f64> {
+    let mut l = -adjacency;
+    for (i, d) in degrees(adjacency).into_iter().enumerate() {
+        l[[i, i]] += d;
+    }
+    l
+}
+
+/// Normalized Laplacian I - D^(-1/2) A D^(-1/2).
+///
+/// Isolated vertices get a zero row and column (so they contribute
+/// eigenvalue 0, like a component of their own).
+pub fn normalized_laplacian(adjacency: &Array2<f64>) -> Array2<f64> {
+    let scale: Vec<f64> = degrees(adjacency)
+        .into_iter()
+        .map(|d| if d > 0.0 { 1.0 / d.sqrt() } else { 0.0 })
+        .collect();
+    Array2::from_shape_fn(adjacency.dim(), |(i, j)| {
+        let identity = if i == j && scale[i] > 0.0 { 1.0 } else { 0.0 };
+        identity - scale[i] * adjacency[[i, j]] * scale[j]
+    })
+}
+
+/// Eigenvalues of the adjacency or a Laplacian matrix of a graph, in ascending order.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::{graph_spectrum, GraphMatrix};
+///
+/// // The path on three vertices.
+/// let path = array![[0.0, 1.0, 0.0], [1.0, 0.0, 1.0], [0.0, 1.0, 0.0]];
+/// let l = graph_spectrum(&path, GraphMatrix::Laplacian);
+/// assert!(l[0].abs() < 1e-12 && (l[1] - 1.0).abs() < 1e-12 && (l[2] - 3.0).abs() < 1e-12);
+/// ```
+pub fn graph_spectrum(adjacency: &Array2<f64>, matrix: GraphMatrix) -> Vec<f64> {
+    match matrix {
+        GraphMatrix::Adjacency => symmetric_eigenvalues(adjacency),
+        GraphMatrix::Laplacian => symmetric_eigenvalues(&laplacian(adjacency)),
+        GraphMatrix::NormalizedLaplacian => symmetric_eigenvalues(&normalized_laplacian(adjacency)),
+    }
+}
+
+/// Erdos-Renyi random graph G(n, p): each edge present independently with probability p.
+///
+/// With `W = A - E[A]` a Wigner matrix of entry variance p (1 - p), the
+/// limiting laws of the bulk (valid when n p grows faster than log n) are:
+///
+/// - adjacency: semicircle of radius 2 sqrt(n p (1 - p)), plus one outlier
+///   near (n - 2) p + 1 from the mean degree;
+/// - Laplacian: D + p I - W with an eigenvalue 0 split off, i.e. the free
+///   convolution of the Binomial(n - 1, p) degree law shifted by p with a
+///   semicircle of variance n p (1 - p);
+/// - normalized Laplacian: semicircle of radius 2 sqrt((1 - p) / (n p))
+///   centered at 1, plus the eigenvalue 0.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::{graph_spectrum, ErdosRenyi, GraphMatrix};
+///
+/// let er = ErdosRenyi::new(300, 0.1).unwrap();
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let spectrum = graph_spectrum(&er.sample(&mut rng), GraphMatrix::NormalizedLaplacian);
+/// // Bulk inside 1 +- 2 sqrt(0.9 / 30), up to edge fluctuations.
+/// let radius = 2.0 * (0.9_f64 / 30.0).sqrt();
+/// assert!(spectrum[1..].iter().all(|x| (x - 1.0).abs() < 1.1 * radius));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ErdosRenyiParams"))]
+pub struct ErdosRenyi {
+    n: usize,
+    p: f64,
+}
+
+impl ErdosRenyi {
+    /// G(n, p).
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless n >= 1 and p is in [0, 1].
+    pub fn new(n: usize, p: f64) -> Result<Self, Error> {
+        if n == 0 {
+            return Err(Error::InvalidParameter {
+                name: "n",
+                value: 0.0,
+                expected: "at least 1",
+            });
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::InvalidParameter {
+                name: "p",
+                value: p,
+                expected: "in [0, 1]",
+            });
+        }
+        Ok(Self { n, p })
+    }
+
+    /// Number of vertices n.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Edge probability p.
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Limiting bulk density of the chosen matrix at each grid point.
+    ///
+    /// Outliers (the adjacency's top eigenvalue, the Laplacians' zero) carry
+    /// vanishing mass and are not included.
+    pub fn density(&self, matrix: GraphMatrix, grid: &[f64]) -> Vec<f64> {
+        let (n, p) = (self.n as f64, self.p);
+        match matrix {
+            GraphMatrix::Adjacency => {
+                let sigma = (n * p * (1.0 - p)).sqrt();
+                grid.iter()
+                    .map(|&x| wigner_semicircle_density(x, sigma))
+                    .collect()
+            }
+            GraphMatrix::Laplacian => {
+                let shifted: Vec<f64> = binomial_atoms(self.n - 1, p, DEGREE_ATOMS)
+                    .into_iter()
+                    .map(|d| d + p)
+                    .collect();
+                free_semicircle_convolution(&shifted, n * p * (1.0 - p), grid)
+            }
+            GraphMatrix::NormalizedLaplacian => {
+                let sigma = ((1.0 - p) / (n * p)).sqrt();
+                grid.iter()
+                    .map(|&x| wigner_semicircle_density(x - 1.0, sigma))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Distribution<Array2<f64>> for ErdosRenyi {
+    /// Adjacency matrix of one sample.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Array2<f64> {
+        let mut a = Array2::zeros((self.n, self.n));
+        for i in 0..self.n {
+            for j in (i + 1)..self.n {
+                if rng.random::<f64>() < self.p {
+                    a[[i, j]] = 1.0;
+                    a[[j, i]] = 1.0;
+                }
+            }
+        }
+        a
+    }
+}
+
+/// Random d-regular graph on n vertices.
+///
+/// Sampled by the Steger-Wormald pairing algorithm: degree stubs are
+/// paired uniformly at random, skipping pairs that would create a loop or a
+/// multiple edge, and restarting in the rare case that no valid pair
+/// remains. The result is asymptotically uniform over d-regular graphs for
+/// d = o(n^(1/3)), and much faster than rejecting whole configurations.
+///
+/// The adjacency spectrum has the trivial eigenvalue d and, as n grows, a
+/// Kesten-McKay bulk on [-2 sqrt(d - 1), 2 sqrt(d - 1)].
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::{graph_spectrum, GraphMatrix, RandomRegular};
+///
+/// let graph = RandomRegular::new(100, 3).unwrap();
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let a = graph.sample(&mut rng);
+/// assert!(a.rows().into_iter().all(|row| row.sum() == 3.0));
+/// let spectrum = graph_spectrum(&a, GraphMatrix::Adjacency);
+/// assert!((spectrum[99] - 3.0).abs() < 1e-10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RandomRegularParams"))]
+pub struct RandomRegular {
+    n: usize,
+    degree: usize,
+}
+
+impl RandomRegular {
+    /// Random `degree`-regular graph on n vertices.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless 2 <= degree < n and n * degree is even.
+    pub fn new(n: usize, degree: usize) -> Result<Self, Error> {
+        if degree < 2 || degree >= n || (n % 2 == 1 && degree % 2 == 1) {
+            return Err(Error::InvalidParameter {
+                name: "degree",
+                value: degree as f64,
+                expected: "at least 2 and below n, with n * degree even",
+            });
+        }
+        Ok(Self { n, degree })
+    }
+
+    /// Number of vertices n.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Degree d.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Limiting bulk density of the chosen matrix at each grid point.
+    ///
+    /// Kesten-McKay for A, reflected to d - x for L = d I - A, and rescaled
+    /// to d (1 - x) for the normalized Laplacian I - A / d.
+    pub fn density(&self, matrix: GraphMatrix, grid: &[f64]) -> Vec<f64> {
+        let d = self.degree as f64;
+        let Ok(law) = KestenMcKay::new(d) else {
+            return vec![f64::NAN; grid.len()];
+        };
+        grid.iter()
+            .map(|&x| match matrix {
+                GraphMatrix::Adjacency => law.pdf(x),
+                GraphMatrix::Laplacian => law.pdf(d - x),
+                GraphMatrix::NormalizedLaplacian => d * law.pdf(d * (1.0 - x)),
+            })
+            .collect()
+    }
+}
+
+impl Distribution<Array2<f64>> for RandomRegular {
+    /// Adjacency matrix of one sample.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Array2<f64> {
+        'restart: loop {
+            let mut a = Array2::zeros((self.n, self.n));
+            let mut stubs: Vec<usize> = (0..self.n)
+                .flat_map(|v| std::iter::repeat_n(v, self.degree))
+                .collect();
+            while !stubs.is_empty() {
+                let valid = |a: &Array2<f64>, u: usize, v: usize| u != v && a[[u, v]] == 0.0;
+                let mut pair = None;
+                for _ in 0..64 {
+                    let i = rng.random_range(0..stubs.len());
+                    let j = rng.random_range(0..stubs.len());
+                    if valid(&a, stubs[i], stubs[j]) {
+                        pair = Some((i, j));
+                        break;
+                    }
+                }
+                if pair.is_none() {
+                    // Near the end few stubs remain; check exhaustively.
+                    pair = (0..stubs.len())
+                        .flat_map(|i| (0..i).map(move |j| (i, j)))
+                        .find(|&(i, j)| valid(&a, stubs[i], stubs[j]));
+                    if pair.is_none() {
+                        continue 'restart;
+                    }
+                }
+                let (i, j) = pair.expect("checked above");
+                let (u, v) = (stubs[i], stubs[j]);
+                a[[u, v]] = 1.0;
+                a[[v, u]] = 1.0;
+                stubs.swap_remove(i.max(j));
+                stubs.swap_remove(i.min(j));
+            }
+            return a;
+        }
+    }
+}
+
+/// Stochastic block model: k blocks of vertices, each edge present independently
+/// with a probability depending only on the blocks of its endpoints.
+///
+/// Vertices are numbered block by block, so block b holds a contiguous
+/// range (see [`StochasticBlockModel::labels`]). With sizes n_b and edge
+/// probabilities P, `E[A]` has rank at most k, with the eigenvalues theta of
+/// the mean-degree matrix T = P diag(n) (T_ab is the expected number of
+/// neighbors in block b of a vertex in block a). The adjacency is then a
+/// finite-rank spike on a generalized Wigner noise whose rows have variance
+/// sum s, and the spiked-Wigner (BBP) picture applies:
+///
+/// - bulk: semicircle of radius 2 sqrt(s);
+/// - each theta with |theta| > sqrt(s) gives an outlier near theta + s / theta,
+///   whose eigenvector correlates with the blocks; smaller ones are lost in
+///   the bulk.
+///
+/// This is exact as n grows when all vertices have the same variance sum s
+/// (for instance equal block sizes with a symmetric P) and an approximation
+/// otherwise. In the sparse regime (constant mean degree) s is the mean
+/// degree lambda_1, the Perron eigenvalue of T, and the outlier condition
+/// becomes the Kesten-Stigum threshold lambda_2^2 > lambda_1 (Decelle et
+/// al. 2011), below which no efficient method is known to beat chance. There
+/// the adjacency itself is spoiled by high-degree vertices, but the
+/// non-backtracking matrix still reaches the threshold (Bordenave, Lelarge &
+/// Massoulie 2015); see [`non_backtracking_outliers`].
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::{graph_spectrum, GraphMatrix, StochasticBlockModel};
+///
+/// let sbm = StochasticBlockModel::planted_partition(2, 200, 0.1, 0.02).unwrap();
+/// assert!(sbm.is_detectable());
+/// let outliers = sbm.predicted_outliers();
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let spectrum = graph_spectrum(&sbm.sample(&mut rng), GraphMatrix::Adjacency);
+/// // Two eigenvalues leave the bulk, where predicted.
+/// assert!(spectrum[397] < sbm.bulk_edge() + 1.0);
+/// assert!((spectrum[398] - outliers[0]).abs() < 1.0);
+/// assert!((spectrum[399] - outliers[1]).abs() < 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "StochasticBlockModelParams"))]
+pub struct StochasticBlockModel {
+    sizes: Vec<usize>,
+    probabilities: Array2<f64>,
+}
+
+impl StochasticBlockModel {
+    /// SBM with the given block sizes and symmetric k x k edge probabilities.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] without blocks, for an empty block, or
+    /// unless `probabilities` is symmetric with entries in [0, 1].
+    ///
+    /// # Panics
+    ///
+    /// If `probabilities` is not k x k for k = `sizes.len()`.
+    pub fn new(sizes: Vec<usize>, probabilities: Array2<f64>) -> Result<Self, Error> {
+        let k = sizes.len();
+        assert_eq!(
+            probabilities.dim(),
+            (k, k),
+            "one row and column of probabilities per block"
+        );
+        if k == 0 || sizes.contains(&0) {
+            return Err(Error::InvalidParameter {
+                name: "sizes",
+                value: 0.0,
+                expected: "at least one block, each non-empty",
+            });
+        }
+        for ((a, b), &p) in probabilities.indexed_iter() {
+            if !(0.0..=1.0).contains(&p) {
+                return Err(Error::InvalidParameter {
+                    name: "probabilities",
+                    value: p,
+                    expected: "in [0, 1]",
+                });
+            }
+            if p != probabilities[[b, a]] {
+                return Err(Error::InvalidParameter {
+                    name: "probabilities",
+                    value: p,
+                    expected: "a symmetric matrix",
+                });
+            }
+        }
+        Ok(Self {
+            sizes,
+            probabilities,
+        })
+    }
+
+    /// Planted partition: `blocks` blocks of `block_size` vertices, with edge
+    /// probability `p_in` within a block and `p_out` between blocks.
+    ///
+    /// # Errors
+    ///
+    /// As [`StochasticBlockModel::new`].
+    pub fn planted_partition(
+        blocks: usize,
+        block_size: usize,
+        p_in: f64,
+        p_out: f64,
+    ) -> Result<Self, Error> {
+        let probabilities =
+            Array2::from_shape_fn((blocks, blocks), |(a, b)| if a == b { p_in } else { p_out });
+        Self::new(vec![block_size; blocks], probabilities)
+    }
+
+    /// Number of vertices n.
+    pub fn n(&self) -> usize {
+        self.sizes.iter().sum()
+    }
+
+    /// Block sizes.
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Edge probabilities between blocks.
+    pub fn probabilities(&self) -> &Array2<f64> {
+        &self.probabilities
+    }
+
+    /// Block of each vertex.
+    pub fn labels(&self) -> Vec<usize> {
+        self.sizes
+            .iter()
+            .enumerate()
+            .flat_map(|(b, &size)| std::iter::repeat_n(b, size))
+            .collect()
+    }
+
+    /// Eigenvalues of the mean-degree matrix T = P diag(n), in ascending order.
+    ///
+    /// These are the non-zero eigenvalues of `E[A]` (up to the O(1) diagonal
+    /// correction from excluding loops); the largest is the Perron
+    /// eigenvalue lambda_1, the mean degree when all degrees agree.
+    pub fn mean_eigenvalues(&self) -> Vec<f64> {
+        // T is similar to the symmetric diag(n)^(1/2) P diag(n)^(1/2).
+        let root: Vec<f64> = self.sizes.iter().map(|&n| (n as f64).sqrt()).collect();
+        let s = Array2::from_shape_fn(self.probabilities.dim(), |(a, b)| {
+            root[a] * self.probabilities[[a, b]] * root[b]
+        });
+        symmetric_eigenvalues(&s)
+    }
+
+    /// Row sum s of the entry variances of `A - E[A]`, averaged over vertices.
+    pub fn noise_variance(&self) -> f64 {
+        let n = self.n() as f64;
+        self.sizes
+            .iter()
+            .zip(self.probabilities.rows())
+            .map(|(&na, row)| {
+                let row_sum: f64 = self
+                    .sizes
+                    .iter()
+                    .zip(row)
+                    .map(|(&nb, &p)| nb as f64 * p * (1.0 - p))
+                    .sum();
+                na as f64 * row_sum
+            })
+            .sum::<f64>()
+            / n
+    }
+
+    /// Edge 2 sqrt(s) of the semicircular adjacency bulk.
+    pub fn bulk_edge(&self) -> f64 {
+        2.0 * self.noise_variance().sqrt()
+    }
+
+    /// Predicted adjacency outliers theta + s / theta for every |theta| > sqrt(s), in ascending order.
+    pub fn predicted_outliers(&self) -> Vec<f64> {
+        let s = self.noise_variance();
+        self.mean_eigenvalues()
+            .into_iter()
+            .filter(|&theta| theta * theta > s)
+            .map(|theta| theta + s / theta)
+            .collect()
+    }
+
+    /// Kesten-Stigum ratio lambda_2^2 / lambda_1 of the mean-degree matrix.
+    ///
+    /// lambda_1 is the Perron eigenvalue and lambda_2 the largest of the
+    /// others in absolute value (0 for a single block). Community structure
+    /// is spectrally detectable when the ratio exceeds 1.
+    pub fn kesten_stigum_ratio(&self) -> f64 {
+        let mut eigenvalues = self.mean_eigenvalues();
+        let Some(lambda_1) = eigenvalues.pop() else {
+            return 0.0;
+        };
+        let lambda_2 = eigenvalues.iter().fold(0.0, |m: f64, &x| m.max(x.abs()));
+        if lambda_1 > 0.0 {
+            lambda_2 * lambda_2 / lambda_1
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether the blocks are above the Kesten-Stigum threshold.
+    pub fn is_detectable(&self) -> bool {
+        self.kesten_stigum_ratio() > 1.0
+    }
+}
+
+impl Distribution<Array2<f64>> for StochasticBlockModel {
+    /// Adjacency matrix of one sample, with vertices ordered as [`StochasticBlockModel::labels`].
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Array2<f64> {
+        let labels = self.labels();
+        let n = labels.len();
+        let mut a = Array2::zeros((n, n));
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if rng.random::<f64>() < self.probabilities[[labels[i], labels[j]]] {
+                    a[[i, j]] = 1.0;
+                    a[[j, i]] = 1.0;
+                }
+            }
+        }
+        a
+    }
+}
+
+/// Eigenvalues of the non-backtracking matrix of a graph, by the Ihara-Bass formula.
+///
+/// The non-backtracking (Hashimoto) matrix B acts on the 2m directed
+/// edges, with B_(u->v),(v->w) = 1 for w != u. Ihara-Bass gives
+/// det(I - t B) = (1 - t^2)^(m - n) det(I - t A + t^2 (D - I)), so apart
+/// from +-1 (each of multiplicity m - n, omitted here) the spectrum of B is
+/// that of the 2n x 2n matrix `[[A, I - D], [I, 0]]`, computed densely.
+///
+/// # Returns
+///
+/// The 2n eigenvalues, in no particular order
+pub fn non_backtracking_spectrum(adjacency: &Array2<f64>) -> Vec<Complex64> {
+    let n = adjacency.nrows();
+    let degrees = degrees(adjacency);
+    let companion = Array2::from_shape_fn((2 * n, 2 * n), |(i, j)| match (i < n, j < n) {
+        (true, true) => adjacency[[i, j]],
+        (true, false) if j - n == i => 1.0 - degrees[i],
+        (false, true) if i - n == j => 1.0,
+        _ => 0.0,
+    });
+    general_eigenvalues(&companion)
+}
+
+/// Real non-backtracking eigenvalues outside the bulk circle, by decreasing modulus.
+///
+/// For a sparse graph with community structure the non-backtracking
+/// spectrum has the Perron eigenvalue lambda_1, a bulk inside the circle
+/// of radius sqrt(lambda_1), and one real outlier near each eigenvalue of
+/// the mean-degree matrix above the Kesten-Stigum threshold (Krzakala et
+/// al. 2013; Bordenave, Lelarge & Massoulie 2015). Unlike adjacency
+/// outliers these are not created by high-degree vertices, so the number
+/// returned (Perron eigenvalue included) estimates the number of
+/// detectable communities even at constant mean degree.
+///
+/// # Returns
+///
+/// Real eigenvalues with |lambda| > sqrt(lambda_1), starting with lambda_1;
+/// empty for a graph without edges
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::{non_backtracking_outliers, StochasticBlockModel};
+///
+/// // Mean degree 6 split 5 inside, 1 across: lambda = 6, 4 and 16 > 6.
+/// let sbm = StochasticBlockModel::planted_partition(2, 100, 0.05, 0.01).unwrap();
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+/// let outliers = non_backtracking_outliers(&sbm.sample(&mut rng));
+/// assert_eq!(outliers.len(), 2);
+/// assert!((outliers[1] - 4.0).abs() < 1.0);
+/// ```
+pub fn non_backtracking_outliers(adjacency: &Array2<f64>) -> Vec<f64> {
+    let spectrum = non_backtracking_spectrum(adjacency);
+    let lambda_1 = spectrum.iter().map(|z| z.re).fold(0.0, f64::max);
+    if lambda_1 <= 0.0 {
+        return vec![];
+    }
+    let mut outliers: Vec<f64> = spectrum
+        .iter()
+        .filter(|z| z.im.abs() <= 1e-8 * lambda_1 && z.re * z.re > lambda_1)
+        .map(|z| z.re)
+        .collect();
+    outliers.sort_by(|a, b| b.abs().total_cmp(&a.abs()));
+    outliers
+}
+
+/// Comparison of a regular graph's adjacency spectrum with the Kesten-McKay law.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KestenMcKayTest {
+    /// KS test of the non-trivial eigenvalues against the Kesten-McKay CDF
+    pub ks: GoodnessOfFit,
+    /// Binned divergences of the non-trivial eigenvalues from the Kesten-McKay density
+    pub divergences: SpectralDivergences,
+    /// Number of trivial eigenvalues +-d removed (one d per component, one -d per bipartite one)
+    pub trivial: usize,
+    /// Largest non-trivial |eigenvalue|
+    pub second_eigenvalue: f64,
+    /// Fraction of non-trivial eigenvalues outside the bulk [-2 sqrt(d - 1), 2 sqrt(d - 1)]
+    pub outside_fraction: f64,
+}
+
+/// Goodness-of-fit of a d-regular graph's adjacency spectrum against the Kesten-McKay law.
+///
+/// The trivial eigenvalues +-d (within 1e-8 d) are removed first: they
+/// carry no information about expansion and the law gives them no mass.
+/// The rest are compared with the law twice, through the KS distance on
+/// the CDF (as [`crate::ks_test`]) and through binned KL, chi-square, and
+/// total-variation divergences of the density (as
+/// [`crate::spectral_divergences`]). A good expander passes both and has
+/// `second_eigenvalue` close to the bulk edge 2 sqrt(d - 1); a graph with
+/// communities or bottlenecks shows eigenvalues outside the bulk and a
+/// small p-value. As with the other tests, eigenvalues repel, so the
+/// i.i.d. p-value is conservative.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Adjacency eigenvalues of a d-regular graph
+/// * `degree` - Degree d
+///
+/// # Returns
+///
+/// `None` if d < 2 or fewer than two distinct eigenvalues remain
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::Distribution;
+/// use rmt::{graph_spectrum, kesten_mckay_test, GraphMatrix, RandomRegular};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+/// let a = RandomRegular::new(300, 3).unwrap().sample(&mut rng);
+/// let spectrum = graph_spectrum(&a, GraphMatrix::Adjacency);
+/// let fit = kesten_mckay_test(&spectrum, 3.0).unwrap();
+/// assert_eq!(fit.trivial, 1);
+/// assert!(fit.ks.p_value > 0.05);
+/// assert!(fit.divergences.total_variation < 0.15);
+/// // The much wider law for d = 12 is rejected.
+/// assert!(kesten_mckay_test(&spectrum, 12.0).unwrap().ks.p_value < 1e-3);
+/// ```
+pub fn kesten_mckay_test<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    degree: f64,
+) -> Option<KestenMcKayTest> {
+    let law = KestenMcKay::new(degree).ok()?;
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let (bulk, trivial): (Vec<f64>, Vec<f64>) = eigenvalues
+        .iter()
+        .partition(|&&x| (x.abs() - degree).abs() > 1e-8 * degree);
+    let divergences = spectral_divergences(&bulk, |x| law.pdf(x))?;
+    let ks = ks_test(&bulk, |x| law.cdf(x))?;
+    let edge = law.support().1;
+    Some(KestenMcKayTest {
+        ks,
+        divergences,
+        trivial: trivial.len(),
+        second_eigenvalue: bulk.iter().fold(0.0, |m, &x| m.max(x.abs())),
+        outside_fraction: bulk.iter().filter(|&&x| x.abs() > edge).count() as f64
+            / bulk.len() as f64,
+    })
+}
+
+/// Lower bound on lambda_2 of any d-regular graph of the given diameter (Alon-Boppana, in Nilli's form).
+///
+/// A d-regular graph with diameter D >= 4 has
+/// lambda_2 >= 2 sqrt(d - 1) - (2 sqrt(d - 1) - 1) / floor(D / 2) (Nilli 1991),
+/// so as n grows (and with it D >= log_(d-1) n) no family beats
+/// 2 sqrt(d - 1) - o(1). Below diameter 4 the bound is vacuous and this
+/// returns -1, which lambda_2 of any graph with an edge exceeds.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::alon_boppana_bound;
+///
+/// // Diameter 10 leaves a fifth of the gap between 1 and 2 sqrt(2).
+/// let edge = 2.0 * 2.0_f64.sqrt();
+/// assert!((alon_boppana_bound(3.0, 10) - (edge - (edge - 1.0) / 5.0)).abs() < 1e-12);
+/// assert_eq!(alon_boppana_bound(3.0, 3), -1.0);
+/// ```
+pub fn alon_boppana_bound(degree: f64, diameter: usize) -> f64 {
+    if diameter < 4 {
+        return -1.0;
+    }
+    let edge = 2.0 * (degree - 1.0).sqrt();
+    edge - (edge - 1.0) / (diameter / 2) as f64
+}
+
+/// Spectral expansion of a regular graph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpanderStatistics {
+    /// Degree d
+    pub degree: f64,
+    /// Second-largest adjacency eigenvalue lambda_2
+    pub lambda_2: f64,
+    /// Smallest adjacency eigenvalue lambda_n
+    pub lambda_min: f64,
+    /// lambda(G) = max(|lambda_2|, |lambda_n|), d for bipartite or disconnected graphs
+    pub second_eigenvalue: f64,
+    /// Spectral gap d - lambda_2
+    pub spectral_gap: f64,
+    /// lambda(G) - 2 sqrt(d - 1); the graph is Ramanujan when this is <= 0
+    pub ramanujan_excess: f64,
+    /// Graph diameter (`None` if disconnected)
+    pub diameter: Option<usize>,
+    /// Alon-Boppana lower bound on lambda_2 for this diameter (2 sqrt(d - 1) if disconnected)
+    pub alon_boppana: f64,
+}
+
+/// Second eigenvalue, spectral gap, and the Ramanujan and Alon-Boppana comparisons of a regular graph.
+///
+/// Small lambda(G) means fast mixing and good expansion (the expander
+/// mixing lemma bounds edge-count deviations by lambda(G)). Alon-Boppana
+/// says lambda_2 cannot fall much below 2 sqrt(d - 1), and Friedman's
+/// theorem (2008) says a random d-regular graph comes within any epsilon of
+/// it with high probability: lambda(G) <= 2 sqrt(d - 1) + epsilon. See
+/// [`expander_monte_carlo`] for the distribution over random graphs.
+///
+/// # Returns
+///
+/// `None` unless the graph has at least two vertices and all degrees agree
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rmt::expander_statistics;
+///
+/// // Petersen graph: outer 5-cycle, inner pentagram, spokes. Spectrum 3, 1, -2.
+/// let a = Array2::from_shape_fn((10, 10), |(i, j)| {
+///     let (outer_i, outer_j) = (i < 5, j < 5);
+///     let adjacent = match (outer_i, outer_j) {
+///         (true, true) => (i + 5 - j) % 5 == 1 || (j + 5 - i) % 5 == 1,
+///         (false, false) => (i + 5 - j) % 5 == 2 || (j + 5 - i) % 5 == 2,
+///         _ => i % 5 == j % 5,
+///     };
+///     if adjacent { 1.0 } else { 0.0 }
+/// });
+/// let stats = expander_statistics(&a).unwrap();
+/// assert!((stats.lambda_2 - 1.0).abs() < 1e-10 && (stats.lambda_min + 2.0).abs() < 1e-10);
+/// assert!(stats.ramanujan_excess < 0.0);
+/// assert_eq!(stats.diameter, Some(2));
+/// ```
+pub fn expander_statistics(adjacency: &Array2<f64>) -> Option<ExpanderStatistics> {
+    let degrees = degrees(adjacency);
+    let degree = *degrees.first()?;
+    if degrees.len() < 2 || degrees.iter().any(|&d| d != degree) {
+        return None;
+    }
+    let spectrum = symmetric_eigenvalues(adjacency);
+    let lambda_2 = spectrum[spectrum.len() - 2];
+    let lambda_min = spectrum[0];
+    let second_eigenvalue = lambda_2.abs().max(lambda_min.abs());
+    let edge = 2.0 * (degree - 1.0).sqrt();
+    let diameter = diameter(adjacency);
+    Some(ExpanderStatistics {
+        degree,
+        lambda_2,
+        lambda_min,
+        second_eigenvalue,
+        spectral_gap: degree - lambda_2,
+        ramanujan_excess: second_eigenvalue - edge,
+        diameter,
+        alon_boppana: diameter.map_or(edge, |d| alon_boppana_bound(degree, d)),
+    })
+}
+
+/// Monte Carlo distribution of the expansion of random regular graphs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpanderMonteCarlo {
+    /// Number of sampled graphs
+    pub trials: usize,
+    /// Second-largest eigenvalue lambda_2
+    pub lambda_2: SampleSummary,
+    /// lambda(G) = max(|lambda_2|, |lambda_n|)
+    pub second_eigenvalue: SampleSummary,
+    /// lambda(G) - 2 sqrt(d - 1)
+    pub ramanujan_excess: SampleSummary,
+    /// Fraction of samples that are Ramanujan (excess <= 0)
+    pub ramanujan_fraction: f64,
+    /// Alon-Boppana bound from each sample's diameter
+    pub alon_boppana: SampleSummary,
+}
+
+/// Samples `trials` random regular graphs and summarizes their [`expander_statistics`].
+///
+/// Friedman's theorem predicts the excess over 2 sqrt(d - 1) shrinks to 0
+/// as n grows; at finite n it fluctuates on the Tracy-Widom scale
+/// n^(-2/3), and a constant fraction of graphs (about 69% for large n,
+/// Huang, McKenzie & Yau 2024) is Ramanujan.
+///
+/// # Returns
+///
+/// `None` if `trials` is zero
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{expander_monte_carlo, RandomRegular};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let graphs = RandomRegular::new(100, 3).unwrap();
+/// let stats = expander_monte_carlo(&mut rng, &graphs, 10).unwrap();
+/// assert!(stats.ramanujan_excess.mean.abs() < 0.2);
+/// assert!(stats.alon_boppana.q95 < stats.lambda_2.q05);
+/// ```
+pub fn expander_monte_carlo<R: Rng + ?Sized>(
+    rng: &mut R,
+    graphs: &RandomRegular,
+    trials: usize,
+) -> Option<ExpanderMonteCarlo> {
+    let samples: Vec<ExpanderStatistics> = (0..trials)
+        .map(|_| expander_statistics(&graphs.sample(rng)).expect("regular graph"))
+        .collect();
+    let summary = |f: fn(&ExpanderStatistics) -> f64| {
+        SampleSummary::from_samples(&samples.iter().map(f).collect::<Vec<_>>())
+    };
+    Some(ExpanderMonteCarlo {
+        trials,
+        lambda_2: summary(|s| s.lambda_2)?,
+        second_eigenvalue: summary(|s| s.second_eigenvalue)?,
+        ramanujan_excess: summary(|s| s.ramanujan_excess)?,
+        ramanujan_fraction: samples.iter().filter(|s| s.ramanujan_excess <= 0.0).count() as f64
+            / trials as f64,
+        alon_boppana: summary(|s| s.alon_boppana)?,
+    })
+}
+
+/// Largest shortest-path distance, by breadth-first search from every vertex (`None` if disconnected).
+fn diameter(adjacency: &Array2<f64>) -> Option<usize> {
+    let n = adjacency.nrows();
+    let neighbors: Vec<Vec<usize>> = adjacency
+        .rows()
+        .into_iter()
+        .map(|row| (0..n).filter(|&j| row[j] != 0.0).collect())
+        .collect();
+    let mut diameter = 0;
+    for source in 0..n {
+        let mut distance = vec![usize::MAX; n];
+        distance[source] = 0;
+        let mut queue = std::collections::VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for &v in &neighbors[u] {
+                if distance[v] == usize::MAX {
+                    distance[v] = distance[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        let eccentricity = *distance.iter().max()?;
+        if eccentricity == usize::MAX {
+            return None;
+        }
+        diameter = diameter.max(eccentricity);
+    }
+    Some(diameter)
+}
+
+/// Binomial(trials, p) represented by `count` equal-weight atoms at its quantiles.
+fn binomial_atoms(trials: usize, p: f64, count: usize) -> Vec<f64> {
+    let ln_pmf = |k: usize| {
+        let (n, k) = (trials as f64, k as f64);
+        ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+            + k * p.ln()
+            + (n - k) * (1.0 - p).ln()
+    };
+    let pmf: Vec<f64> = match p {
+        p if p <= 0.0 => (0..=trials)
+            .map(|k| if k == 0 { 1.0 } else { 0.0 })
+            .collect(),
+        p if p >= 1.0 => (0..=trials)
+            .map(|k| if k == trials { 1.0 } else { 0.0 })
+            .collect(),
+        _ => (0..=trials).map(|k| ln_pmf(k).exp()).collect(),
+    };
+    let mut atoms = Vec::with_capacity(count);
+    let (mut k, mut cumulative) = (0, pmf[0]);
+    for i in 0..count {
+        let q = (i as f64 + 0.5) / count as f64;
+        while cumulative < q && k < trials {
+            k += 1;
+            cumulative += pmf[k];
+        }
+        atoms.push(k as f64);
+    }
+    atoms
+}
+
+// Deserialization goes through the validating constructors: a model that
+// `new` rejects could otherwise make `sample` loop forever or panic.
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ErdosRenyiParams {
+    n: usize,
+    p: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ErdosRenyiParams> for ErdosRenyi {
+    type Error = Error;
+
+    fn try_from(params: ErdosRenyiParams) -> Result<Self, Error> {
+        Self::new(params.n, params.p)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RandomRegularParams {
+    n: usize,
+    degree: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RandomRegularParams> for RandomRegular {
+    type Error = Error;
+
+    fn try_from(params: RandomRegularParams) -> Result<Self, Error> {
+        Self::new(params.n, params.degree)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct StochasticBlockModelParams {
+    sizes: Vec<usize>,
+    probabilities: Array2<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StochasticBlockModelParams> for StochasticBlockModel {
+    type Error = Error;
+
+    fn try_from(params: StochasticBlockModelParams) -> Result<Self, Error> {
+        // `new` panics on a shape mismatch, which must not happen while parsing.
+        let k = params.sizes.len();
+        if params.probabilities.dim() != (k, k) {
+            return Err(Error::InvalidParameter {
+                name: "probabilities",
+                value: params.probabilities.nrows() as f64,
+                expected: "one row and column per block",
+            });
+        }
+        Self::new(params.sizes, params.probabilities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_graph_spectra_match_limiting_laws() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+
+        // Random regular: simple, d-regular, Kesten-McKay bulk.
+        let model = RandomRegular::new(200, 4).unwrap();
+        let a = model.sample(&mut rng);
+        assert!(a.diag().iter().all(|&x| x == 0.0));
+        assert_eq!(a, a.t());
+        assert!(degrees(&a).iter().all(|&d| d == 4.0));
+        let spectrum = graph_spectrum(&a, GraphMatrix::Adjacency);
+        let law = KestenMcKay::new(4.0).unwrap();
+        let fit = ks_test(&spectrum[..199], |x| law.cdf(x)).unwrap();
+        assert!(fit.p_value > 0.05, "KS p = {}", fit.p_value);
+        let l = graph_spectrum(&a, GraphMatrix::Laplacian);
+        assert!(l[0].abs() < 1e-10);
+        let normalized = graph_spectrum(&a, GraphMatrix::NormalizedLaplacian);
+        for (x, y) in normalized.iter().zip(l.iter()) {
+            assert!((x - y / 4.0).abs() < 1e-10);
+        }
+        // The reflected densities are consistent with the change of variables.
+        // x = 1 for A corresponds to 4 - 1 = 3 for L and 1 - 1/4 for I - A / 4.
+        let km = model.density(GraphMatrix::Adjacency, &[1.0])[0];
+        let lap = model.density(GraphMatrix::Laplacian, &[3.0])[0];
+        let norm = model.density(GraphMatrix::NormalizedLaplacian, &[0.75])[0];
+        assert!((km - law.pdf(1.0)).abs() < 1e-15);
+        assert!((lap - law.pdf(1.0)).abs() < 1e-15 && (norm - 4.0 * law.pdf(1.0)).abs() < 1e-15);
+
+        // Erdos-Renyi Laplacian: the mass of the bulk between its quartiles
+        // matches the free-convolution prediction.
+        let model = ErdosRenyi::new(250, 0.2).unwrap();
+        let l = graph_spectrum(&model.sample(&mut rng), GraphMatrix::Laplacian);
+        assert!(l[0].abs() < 1e-9);
+        let bulk = &l[1..];
+        let (lo, hi) = (bulk[bulk.len() / 4], bulk[3 * bulk.len() / 4]);
+        let h = (hi - lo) / 200.0;
+        let grid: Vec<f64> = (0..200).map(|i| lo + (i as f64 + 0.5) * h).collect();
+        let mass: f64 = model
+            .density(GraphMatrix::Laplacian, &grid)
+            .iter()
+            .sum::<f64>()
+            * h;
+        assert!((mass - 0.5).abs() < 0.05, "mass {mass}");
+        let total: f64 = model
+            .density(GraphMatrix::Adjacency, &[0.0])
+            .iter()
+            .chain(&model.density(GraphMatrix::NormalizedLaplacian, &[1.0]))
+            .product();
+        assert!(total > 0.0);
+
+        assert!(RandomRegular::new(5, 3).is_err());
+        assert!(ErdosRenyi::new(10, 1.5).is_err());
+        let atoms = binomial_atoms(10, 0.5, 4);
+        assert_eq!(atoms, vec![3.0, 4.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn test_kesten_mckay_test_trivial_eigenvalues() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(8);
+        let model = RandomRegular::new(150, 4).unwrap();
+        let mut a = Array2::zeros((300, 300));
+        a.slice_mut(ndarray::s![..150, ..150])
+            .assign(&model.sample(&mut rng));
+        a.slice_mut(ndarray::s![150.., 150..])
+            .assign(&model.sample(&mut rng));
+        let spectrum = graph_spectrum(&a, GraphMatrix::Adjacency);
+        let fit = kesten_mckay_test(&spectrum, 4.0).unwrap();
+        assert_eq!(fit.trivial, 2);
+        assert!(fit.ks.p_value > 0.05, "KS p = {}", fit.ks.p_value);
+        assert!(fit.second_eigenvalue < 4.0 && fit.outside_fraction < 0.05);
+
+        // K_{4,4} is bipartite: both +4 and -4 are trivial, the rest vanish.
+        let complete = Array2::from_shape_fn((8, 8), |(i, j)| ((i < 4) != (j < 4)) as u8 as f64);
+        let fit =
+            kesten_mckay_test(&graph_spectrum(&complete, GraphMatrix::Adjacency), 4.0).unwrap();
+        assert_eq!(fit.trivial, 2);
+        assert!(fit.second_eigenvalue < 1e-10);
+        assert!(kesten_mckay_test(&spectrum, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_stochastic_block_model_thresholds() {
+        // Three blocks of 100, mean degrees 8 inside and 2 to each other block.
+        let sbm = StochasticBlockModel::planted_partition(3, 100, 0.08, 0.02).unwrap();
+        assert_eq!(sbm.n(), 300);
+        assert_eq!(&sbm.labels()[99..101], &[0, 1]);
+        let theta = sbm.mean_eigenvalues();
+        for (t, expected) in theta.iter().zip([6.0, 6.0, 12.0]) {
+            assert!((t - expected).abs() < 1e-12);
+        }
+        let s = 100.0 * (0.08 * 0.92 + 2.0 * 0.02 * 0.98);
+        assert!((sbm.noise_variance() - s).abs() < 1e-12);
+        assert!((sbm.kesten_stigum_ratio() - 3.0).abs() < 1e-12);
+        let outliers = sbm.predicted_outliers();
+        assert_eq!(outliers.len(), 3);
+        assert!((outliers[0] - (6.0 + s / 6.0)).abs() < 1e-12);
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        let a = sbm.sample(&mut rng);
+        let within: f64 = a.slice(ndarray::s![..100, ..100]).sum() / 100.0;
+        let across: f64 = a.slice(ndarray::s![..100, 100..200]).sum() / 100.0;
+        assert!((within - 7.92).abs() < 0.6 && (across - 2.0).abs() < 0.4);
+
+        // Below the threshold only the Perron eigenvalue leaves the bulk.
+        let weak = StochasticBlockModel::planted_partition(2, 100, 0.03, 0.02).unwrap();
+        assert!(!weak.is_detectable());
+        assert_eq!(non_backtracking_outliers(&weak.sample(&mut rng)).len(), 1);
+        assert!(non_backtracking_outliers(&Array2::zeros((4, 4))).is_empty());
+
+        let asymmetric = ndarray::array![[0.5, 0.1], [0.2, 0.5]];
+        assert!(StochasticBlockModel::new(vec![5, 5], asymmetric).is_err());
+        assert!(StochasticBlockModel::planted_partition(2, 0, 0.5, 0.1).is_err());
+        assert!(StochasticBlockModel::planted_partition(2, 5, 1.5, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_expander_statistics_bounds() {
+        // Cycles: lambda_2 = 2 cos(2 pi / n), diameter floor(n / 2).
+        let cycle = |n: usize| {
+            Array2::from_shape_fn((n, n), |(i, j)| {
+                if (i + 1) % n == j || (j + 1) % n == i {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+        };
+        let odd = expander_statistics(&cycle(11)).unwrap();
+        let theta = 2.0 * std::f64::consts::PI / 11.0;
+        assert!((odd.lambda_2 - 2.0 * theta.cos()).abs() < 1e-10);
+        assert!((odd.second_eigenvalue - 2.0 * (theta / 2.0).cos()).abs() < 1e-10);
+        assert_eq!(odd.diameter, Some(5));
+        assert!((odd.alon_boppana - (2.0 - 1.0 / 2.0)).abs() < 1e-12);
+        assert!(odd.lambda_2 >= odd.alon_boppana);
+        // Even cycles are bipartite: lambda(G) = d.
+        assert!((expander_statistics(&cycle(10)).unwrap().second_eigenvalue - 2.0).abs() < 1e-10);
+
+        // Two disjoint triangles: disconnected, lambda_2 = d.
+        let two = Array2::from_shape_fn(
+            (6, 6),
+            |(i, j)| {
+                if i != j && i / 3 == j / 3 {
+                    1.0
+                } else {
+                    0.0
+                }
+            },
+        );
+        let stats = expander_statistics(&two).unwrap();
+        assert_eq!(stats.diameter, None);
+        assert!(stats.spectral_gap.abs() < 1e-10);
+        assert!(expander_statistics(&ndarray::array![
+            [0.0, 1.0, 0.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0]
+        ])
+        .is_none());
+
+        // Friedman: random 4-regular graphs sit near 2 sqrt(3).
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(4);
+        let graphs = RandomRegular::new(120, 4).unwrap();
+        let stats = expander_monte_carlo(&mut rng, &graphs, 8).unwrap();
+        assert_eq!(stats.trials, 8);
+        assert!(stats.ramanujan_excess.q05 > -0.25 && stats.ramanujan_excess.q95 < 0.25);
+        assert!(expander_monte_carlo(&mut rng, &graphs, 0).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates_models() {
+        let graph = RandomRegular::new(10, 3).unwrap();
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(serde_json::from_str::<RandomRegular>(&json).unwrap(), graph);
+        assert!(serde_json::from_str::<RandomRegular>(r#"{"n":5,"degree":3}"#).is_err());
+        assert!(serde_json::from_str::<RandomRegular>(r#"{"n":5,"degree":1}"#).is_err());
+        assert!(serde_json::from_str::<ErdosRenyi>(r#"{"n":5,"p":1.5}"#).is_err());
+
+        let sbm = StochasticBlockModel::planted_partition(2, 5, 0.5, 0.1).unwrap();
+        let json = serde_json::to_string(&sbm).unwrap();
+        assert_eq!(
+            serde_json::from_str::<StochasticBlockModel>(&json).unwrap(),
+            sbm
+        );
+        let bad_shape = json.replace(r#""sizes":[5,5]"#, r#""sizes":[5,5,5]"#);
+        assert!(serde_json::from_str::<StochasticBlockModel>(&bad_shape).is_err());
+        let empty_block = json.replace(r#""sizes":[5,5]"#, r#""sizes":[5,0]"#);
+        assert!(serde_json::from_str::<StochasticBlockModel>(&empty_block).is_err());
+    }
+}
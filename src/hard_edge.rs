@@ -0,0 +1,293 @@
+//! Smallest eigenvalues of Wishart matrices.
+//!
+//! For an n x p Gaussian matrix X (n >= p), the smallest eigenvalue of
+//! X^T X depends on the aspect ratio in two regimes:
+//!
+//! - hard edge, n - p = a fixed: lambda_min is O(1 / p), so the smallest
+//!   eigenvalue of the sample covariance X^T X / n is O(n^(-2)), and
+//!   p lambda_min(X^T X) has a limit law depending only on a and beta
+//!   ([`hard_edge_cdf`]). Several square-ish cases are exact at every n
+//!   ([`wishart_smallest_eigenvalue_sf`]).
+//! - soft edge, p / n -> gamma < 1: lambda_min sits at the lower
+//!   Marchenko-Pastur edge n (1 - sqrt(gamma))^2 with Tracy-Widom
+//!   fluctuations, reflected.
+//!
+//! [`smallest_eigenvalue_test`] picks the right law to decide whether an
+//! observed smallest eigenvalue is anomalously small, the signature of
+//! near-collinear variables.
+
+use ndarray::Array2;
+
+use crate::linalg::determinant;
+use crate::special::gamma_p;
+use crate::spectrum::AsEigenvalues;
+use crate::{hard_edge_gap_probability, tracy_widom_sf};
+
+/// Exact P(lambda_min > x) for the smallest eigenvalue of a white Wishart matrix X^* X.
+///
+/// X is n x p with i.i.d. N(0, 1) entries (beta = 1) or standard complex
+/// Gaussian entries with E |x|^2 = 1 (beta = 2). Exact closed forms exist
+/// in these cases:
+///
+/// - complex, any n >= p: e^(-p x) det[L_(p+j-k)^(k-j)(-x)] over
+///   j, k = 1..n - p (Forrester & Hughes 1994), with generalized Laguerre
+///   polynomials; for n = p it is e^(-p x) (Edelman 1988). The determinant
+///   is (n - p) x (n - p) and loses accuracy once n - p reaches a few tens.
+/// - real, n = p + 1: e^(-p x / 2), since the Laguerre weight then has
+///   exponent zero and shifting every eigenvalue by x factors out of the
+///   joint density.
+/// - real, p = 1: the chi-square survival function with n degrees of freedom.
+///
+/// # Arguments
+///
+/// * `x` - Threshold on the scale of X^T X (not divided by n)
+/// * `n` - Number of rows (samples), n >= p
+/// * `p` - Number of columns (variables), p >= 1
+/// * `beta` - 1 (real) or 2 (complex)
+///
+/// # Returns
+///
+/// P(lambda_min > x), or NaN outside the cases above (use [`hard_edge_cdf`]
+/// for the large-p limit of the real square case)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::wishart_smallest_eigenvalue_sf;
+///
+/// // Square complex Wishart: lambda_min is exponential with rate p.
+/// let (x, p) = (0.01, 50);
+/// let sf = wishart_smallest_eigenvalue_sf(x, p, p, 2.0);
+/// assert!((sf - (-(p as f64) * x).exp()).abs() < 1e-12);
+/// // An extra row pushes lambda_min away from zero.
+/// assert!(wishart_smallest_eigenvalue_sf(x, p + 1, p, 2.0) > sf);
+/// ```
+pub fn wishart_smallest_eigenvalue_sf(x: f64, n: usize, p: usize, beta: f64) -> f64 {
+    if p == 0 || n < p || x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let a = n - p;
+    if beta == 2.0 {
+        let entries = Array2::from_shape_fn((a, a), |(j, k)| match (p + j).checked_sub(k) {
+            Some(degree) => laguerre(degree, k as f64 - j as f64, -x),
+            None => 0.0,
+        });
+        let det = if a == 0 { 1.0 } else { determinant(&entries) };
+        (-(p as f64) * x).exp() * det
+    } else if beta == 1.0 && p == 1 {
+        1.0 - gamma_p(0.5 * n as f64, 0.5 * x)
+    } else if beta == 1.0 && a == 1 {
+        (-0.5 * p as f64 * x).exp()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Limiting CDF of p lambda_min(X^T X) at the hard edge, for n - p = a fixed.
+///
+/// With the scaling of [`wishart_smallest_eigenvalue_sf`]:
+///
+/// - beta = 2: 1 - E(4 x), with E the Bessel-kernel gap probability
+///   [`hard_edge_gap_probability`] of order a (any real a > -1), so
+///   1 - e^(-x) for the square case;
+/// - beta = 1: 1 - e^(-x/2 - sqrt(x)) for the square case a = 0
+///   (Edelman 1988) and 1 - e^(-x/2) for a = 1.
+///
+/// The finite-p error is O(1 / p). For other real cases,
+/// [`crate::StochasticBessel`] samples the limit law.
+///
+/// # Arguments
+///
+/// * `x` - Point on the p lambda_min scale
+/// * `a` - n - p
+/// * `beta` - 1 (real) or 2 (complex)
+///
+/// # Returns
+///
+/// The CDF, or NaN for an unsupported (beta, a)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::hard_edge_cdf;
+///
+/// // Square matrices: Edelman's laws for real and complex entries.
+/// assert!((hard_edge_cdf(1.0, 0.0, 1.0) - (1.0 - (-1.5_f64).exp())).abs() < 1e-15);
+/// assert!((hard_edge_cdf(1.0, 0.0, 2.0) - (1.0 - (-1.0_f64).exp())).abs() < 1e-10);
+/// // Extra rows make small eigenvalues rarer.
+/// assert!(hard_edge_cdf(1.0, 3.0, 2.0) < hard_edge_cdf(1.0, 1.0, 2.0));
+/// ```
+pub fn hard_edge_cdf(x: f64, a: f64, beta: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    match (beta, a) {
+        (2.0, _) if a > -1.0 => {
+            if x <= 0.0 {
+                0.0
+            } else {
+                1.0 - hard_edge_gap_probability(a, 4.0 * x)
+            }
+        }
+        (1.0, 0.0) => 1.0 - (-0.5 * x.max(0.0) - x.max(0.0).sqrt()).exp(),
+        (1.0, 1.0) => 1.0 - (-0.5 * x.max(0.0)).exp(),
+        _ => f64::NAN,
+    }
+}
+
+/// Result of a test for an anomalously small sample-covariance eigenvalue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmallestEigenvalueTest {
+    /// Smallest eigenvalue of the sample covariance
+    pub lambda_min: f64,
+    /// p n lambda_min at the hard edge (n - p <= 1), else the reflected TW1 score
+    pub statistic: f64,
+    /// P(lambda_min <= observed) under the pure-noise null
+    pub p_value: f64,
+}
+
+/// Test whether the smallest eigenvalue of a sample covariance is smaller than noise allows.
+///
+/// Under the null of i.i.d. N(0, 1) data (X is n x p, n >= p), with
+/// lambda_min the smallest eigenvalue of X^T X / n:
+///
+/// - n = p + 1: P(lambda_min(X^T X) <= x) = 1 - e^(-p x / 2) exactly;
+/// - n = p: Edelman's limit 1 - e^(-x/2 - sqrt(x)) at x = p n lambda_min;
+/// - n >= p + 2: (mu - n lambda_min) / sigma is approximately TW1, with
+///   the lower-edge constants mu = (sqrt(n - 1/2) - sqrt(p - 1/2))^2 and
+///   sigma = (sqrt(n - 1/2) - sqrt(p - 1/2)) (1 / sqrt(p - 1/2) - 1 / sqrt(n - 1/2))^(1/3)
+///   (Ma 2012). This is a soft-edge approximation, good once n - p is
+///   about 10 or more; nearer the hard edge it is conservative.
+///
+/// A small p-value indicates a direction of (near-)collinearity among the
+/// variables that noise alone would rarely produce.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `n` - Number of samples
+/// * `p` - Number of variables
+///
+/// # Returns
+///
+/// `None` if n < p, p < 2, or the spectrum has no finite values
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{sample_wishart_with, smallest_eigenvalue_test, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let (n, p) = (200, 50);
+/// let cov = sample_wishart_with(&mut rng, n, p) / n as f64;
+/// let eigenvalues = symmetric_eigenvalues(&cov);
+/// assert!(smallest_eigenvalue_test(&eigenvalues, n, p).unwrap().p_value > 0.01);
+/// // Shrinking one eigenvalue tenfold is flagged.
+/// let mut collinear = eigenvalues.clone();
+/// collinear[0] /= 10.0;
+/// assert!(smallest_eigenvalue_test(&collinear, n, p).unwrap().p_value < 1e-6);
+/// ```
+pub fn smallest_eigenvalue_test<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+    p: usize,
+) -> Option<SmallestEigenvalueTest> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if n < p || p < 2 {
+        return None;
+    }
+    let lambda_min = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .reduce(f64::min)?;
+    let x = n as f64 * lambda_min;
+    let (statistic, p_value) = match n - p {
+        0 => (p as f64 * x, hard_edge_cdf(p as f64 * x, 0.0, 1.0)),
+        1 => (
+            p as f64 * x,
+            1.0 - wishart_smallest_eigenvalue_sf(x, n, p, 1.0),
+        ),
+        _ => {
+            let (a, b) = ((n as f64 - 0.5).sqrt(), (p as f64 - 0.5).sqrt());
+            let mu = (a - b).powi(2);
+            let sigma = (a - b) * (1.0 / b - 1.0 / a).cbrt();
+            let statistic = (mu - x) / sigma;
+            (statistic, tracy_widom_sf(statistic, 1.0))
+        }
+    };
+    Some(SmallestEigenvalueTest {
+        lambda_min,
+        statistic,
+        p_value,
+    })
+}
+
+/// Generalized Laguerre polynomial L_k^(alpha)(x) by its three-term recurrence (any real alpha).
+fn laguerre(k: usize, alpha: f64, x: f64) -> f64 {
+    let (mut previous, mut current) = (1.0, 1.0 + alpha - x);
+    if k == 0 {
+        return previous;
+    }
+    for j in 1..k {
+        let j = j as f64;
+        let next = ((2.0 * j + 1.0 + alpha - x) * current - (j + alpha) * previous) / (j + 1.0);
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sample_wishart_with, symmetric_eigenvalues};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_smallest_eigenvalue_laws() {
+        // One complex column: lambda = |x|^2 summed over n rows ~ Gamma(n, 1).
+        let sf = wishart_smallest_eigenvalue_sf(2.5, 3, 1, 2.0);
+        assert!((sf - (1.0 - gamma_p(3.0, 2.5))).abs() < 1e-12);
+        // Two extra rows by hand: e^(-x) (1 + x + x^2 / 2) for p = 1.
+        let x: f64 = 0.7;
+        let by_hand = (-x).exp() * (1.0 + x + x * x / 2.0);
+        assert!((wishart_smallest_eigenvalue_sf(x, 3, 1, 2.0) - by_hand).abs() < 1e-12);
+        assert!((laguerre(2, 1.0, 0.3) - (0.09 - 1.8 + 6.0) / 2.0).abs() < 1e-12);
+        assert!(wishart_smallest_eigenvalue_sf(1.0, 10, 10, 1.0).is_nan());
+        assert_eq!(wishart_smallest_eigenvalue_sf(-1.0, 5, 4, 1.0), 1.0);
+
+        // The complex hard-edge limit agrees with the exact law at moderate p.
+        for a in [0usize, 2] {
+            let p = 40;
+            for x in [0.3, 1.0, 2.5] {
+                let exact = 1.0 - wishart_smallest_eigenvalue_sf(x / p as f64, p + a, p, 2.0);
+                let limit = hard_edge_cdf(x, a as f64, 2.0);
+                assert!((exact - limit).abs() < 0.02, "a = {a}, x = {x}");
+            }
+        }
+
+        // Real data: the test p-values are roughly uniform under the null.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(646);
+        for (n, p) in [(20, 20), (21, 20), (60, 20)] {
+            let trials = 200;
+            let p_values: Vec<f64> = (0..trials)
+                .map(|_| {
+                    let cov = sample_wishart_with(&mut rng, n, p) / n as f64;
+                    smallest_eigenvalue_test(&symmetric_eigenvalues(&cov), n, p)
+                        .unwrap()
+                        .p_value
+                })
+                .collect();
+            let below = |q: f64| p_values.iter().filter(|&&v| v < q).count() as f64 / trials as f64;
+            assert!((below(0.5) - 0.5).abs() < 0.12, "n = {n}: {}", below(0.5));
+            assert!(below(0.1) < 0.18, "n = {n}: {}", below(0.1));
+        }
+        assert!(smallest_eigenvalue_test(&[1.0, 2.0], 1, 2).is_none());
+    }
+}
@@ -0,0 +1,109 @@
+//! Conversions to and from other linear algebra crates.
+//!
+//! The crate computes on `ndarray`; these helpers move matrices and spectra
+//! across the boundary so callers on `nalgebra` (feature `nalgebra`) don't
+//! have to copy element by element. The orphan rule rules out
+//! `From<Array2<f64>> for DMatrix<f64>`, so matrix conversions are plain
+//! functions; [`Spectrum`] converts with `From` in both directions.
+
+use nalgebra::{DMatrix, DVector};
+use ndarray::Array2;
+use rand::Rng;
+
+use crate::{sample_goe_with, sample_wishart_with, Ensemble, Spectrum};
+
+/// Copy an `ndarray` matrix into a column-major `nalgebra` matrix.
+pub fn to_dmatrix(a: &Array2<f64>) -> DMatrix<f64> {
+    DMatrix::from_fn(a.nrows(), a.ncols(), |i, j| a[[i, j]])
+}
+
+/// Copy a `nalgebra` matrix into a row-major `ndarray` matrix.
+pub fn from_dmatrix(m: &DMatrix<f64>) -> Array2<f64> {
+    Array2::from_shape_fn(m.shape(), |(i, j)| m[(i, j)])
+}
+
+/// [`sample_wishart_with`] returning a `DMatrix`.
+pub fn sample_wishart_dmatrix_with<R: Rng>(rng: &mut R, n: usize, p: usize) -> DMatrix<f64> {
+    to_dmatrix(&sample_wishart_with(rng, n, p))
+}
+
+/// [`crate::sample_wishart`] returning a `DMatrix`.
+#[cfg(feature = "thread-rng")]
+pub fn sample_wishart_dmatrix(n: usize, p: usize) -> DMatrix<f64> {
+    sample_wishart_dmatrix_with(&mut rand::rng(), n, p)
+}
+
+/// [`sample_goe_with`] returning a `DMatrix`.
+pub fn sample_goe_dmatrix_with<R: Rng>(rng: &mut R, n: usize) -> DMatrix<f64> {
+    to_dmatrix(&sample_goe_with(rng, n))
+}
+
+/// [`crate::sample_goe`] returning a `DMatrix`.
+#[cfg(feature = "thread-rng")]
+pub fn sample_goe_dmatrix(n: usize) -> DMatrix<f64> {
+    sample_goe_dmatrix_with(&mut rand::rng(), n)
+}
+
+/// Draw one matrix from any [`Ensemble`] as a `DMatrix`.
+pub fn sample_ensemble_dmatrix<E: Ensemble, R: Rng>(ensemble: &E, rng: &mut R) -> DMatrix<f64> {
+    to_dmatrix(&ensemble.sample(rng))
+}
+
+impl From<&DVector<f64>> for Spectrum {
+    fn from(eigenvalues: &DVector<f64>) -> Self {
+        Spectrum::new(eigenvalues.as_slice())
+    }
+}
+
+impl From<DVector<f64>> for Spectrum {
+    fn from(eigenvalues: DVector<f64>) -> Self {
+        Spectrum::from(&eigenvalues)
+    }
+}
+
+impl From<&Spectrum> for DVector<f64> {
+    fn from(spectrum: &Spectrum) -> Self {
+        DVector::from_column_slice(spectrum.values())
+    }
+}
+
+impl From<Spectrum> for DVector<f64> {
+    fn from(spectrum: Spectrum) -> Self {
+        DVector::from(&spectrum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{symmetric_eigenvalues, Wishart};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_nalgebra_round_trip_and_eigenvalues() {
+        let a = Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let m = to_dmatrix(&a);
+        assert_eq!(m.shape(), (2, 3));
+        assert_eq!(m[(1, 0)], 4.0);
+        assert_eq!(from_dmatrix(&m), a);
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(620);
+        let goe = sample_goe_dmatrix_with(&mut rng, 50);
+        let ours = symmetric_eigenvalues(&from_dmatrix(&goe));
+        let mut theirs: Vec<f64> = goe.symmetric_eigenvalues().iter().copied().collect();
+        theirs.sort_by(|a, b| a.total_cmp(b));
+        for (x, y) in ours.iter().zip(&theirs) {
+            assert!((x - y).abs() < 1e-9, "{x} vs {y}");
+        }
+
+        let spectrum = Spectrum::from(DVector::from_vec(theirs.clone()));
+        assert_eq!(DVector::from(&spectrum).as_slice(), theirs.as_slice());
+
+        let w = sample_ensemble_dmatrix(&Wishart::new(40, 10), &mut rng);
+        assert_eq!(w.shape(), (10, 10));
+        assert_eq!(
+            sample_wishart_dmatrix_with(&mut rng, 40, 10).shape(),
+            (10, 10)
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! Reading and writing spectra, densities, and matrices.
+//!
+//! CSV is plain comma-separated text with a one-line header, readable by
+//! `pandas.read_csv` and R's `read.csv` as is. Parse failures surface as
+//! [`std::io::ErrorKind::InvalidData`] naming the offending line. JSON
+//! helpers need the `json` feature; Parquet writers for large Monte Carlo
+//! outputs need the `arrow` feature. Files too large to load at once can be
+//! read in chunks with [`crate::read_eigenvalues_csv_chunks`] and
+//! [`crate::read_eigenvalues_binary_chunks`].
+
+use std::io::{self, BufRead, Write};
+
+use ndarray::Array2;
+
+/// Writes one eigenvalue per line under an `eigenvalue` header.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{read_eigenvalues_csv, write_eigenvalues_csv};
+///
+/// let mut buffer = Vec::new();
+/// write_eigenvalues_csv(&mut buffer, &[0.5, 1.25]).unwrap();
+/// assert_eq!(String::from_utf8(buffer.clone()).unwrap(), "eigenvalue\n0.5\n1.25\n");
+/// assert_eq!(read_eigenvalues_csv(buffer.as_slice()).unwrap(), vec![0.5, 1.25]);
+/// ```
+pub fn write_eigenvalues_csv<W: Write>(mut writer: W, eigenvalues: &[f64]) -> io::Result<()> {
+    writeln!(writer, "eigenvalue")?;
+    for value in eigenvalues {
+        writeln!(writer, "{value}")?;
+    }
+    Ok(())
+}
+
+/// Reads the first column of a CSV file, skipping a non-numeric header line.
+pub fn read_eigenvalues_csv<R: BufRead>(reader: R) -> io::Result<Vec<f64>> {
+    Ok(read_rows(reader)?.into_iter().map(|row| row[0]).collect())
+}
+
+/// Writes a density on a grid as `x,density` rows.
+///
+/// Works for histogram output ([`crate::empirical_spectral_density`]: bin
+/// centers and densities) as well as for theoretical densities or
+/// [`crate::kernel_spectral_density`] evaluated on a grid.
+///
+/// # Errors
+///
+/// [`io::ErrorKind::InvalidInput`] if the slices differ in length.
+pub fn write_density_csv<W: Write>(mut writer: W, grid: &[f64], density: &[f64]) -> io::Result<()> {
+    if grid.len() != density.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "grid has {} points but density has {}",
+                grid.len(),
+                density.len()
+            ),
+        ));
+    }
+    writeln!(writer, "x,density")?;
+    for (x, y) in grid.iter().zip(density) {
+        writeln!(writer, "{x},{y}")?;
+    }
+    Ok(())
+}
+
+/// Reads `x,density` rows written by [`write_density_csv`].
+pub fn read_density_csv<R: BufRead>(reader: R) -> io::Result<(Vec<f64>, Vec<f64>)> {
+    let rows = read_rows(reader)?;
+    if let Some(row) = rows.iter().find(|row| row.len() != 2) {
+        return Err(invalid_data(format!(
+            "expected 2 columns, found {}",
+            row.len()
+        )));
+    }
+    Ok(rows.into_iter().map(|row| (row[0], row[1])).unzip())
+}
+
+/// Reads a dense matrix with one row per line, skipping a non-numeric header line.
+///
+/// # Errors
+///
+/// [`io::ErrorKind::InvalidData`] for unparsable fields or ragged rows.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::read_matrix_csv;
+///
+/// let csv = "a,b\n1,2\n3,4\n5,6\n";
+/// let x = read_matrix_csv(csv.as_bytes()).unwrap();
+/// assert_eq!(x.dim(), (3, 2));
+/// assert_eq!(x[[2, 1]], 6.0);
+/// ```
+pub fn read_matrix_csv<R: BufRead>(reader: R) -> io::Result<Array2<f64>> {
+    let rows = read_rows(reader)?;
+    let cols = rows.first().map_or(0, Vec::len);
+    if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+        return Err(invalid_data(format!(
+            "ragged rows: expected {cols} columns, found {}",
+            row.len()
+        )));
+    }
+    let n = rows.len();
+    Array2::from_shape_vec((n, cols), rows.concat()).map_err(|e| invalid_data(e.to_string()))
+}
+
+/// Writes a matrix as CSV with a `c0,c1,...` header.
+pub fn write_matrix_csv<W: Write>(mut writer: W, matrix: &Array2<f64>) -> io::Result<()> {
+    let header: Vec<String> = (0..matrix.ncols()).map(|j| format!("c{j}")).collect();
+    writeln!(writer, "{}", header.join(","))?;
+    for row in matrix.rows() {
+        let fields: Vec<String> = row.iter().map(f64::to_string).collect();
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Writes eigenvalues as a JSON array.
+#[cfg(feature = "json")]
+pub fn write_eigenvalues_json<W: Write>(writer: W, eigenvalues: &[f64]) -> io::Result<()> {
+    serde_json::to_writer(writer, eigenvalues).map_err(io::Error::from)
+}
+
+/// Reads eigenvalues from a JSON array.
+#[cfg(feature = "json")]
+pub fn read_eigenvalues_json<R: io::Read>(reader: R) -> io::Result<Vec<f64>> {
+    serde_json::from_reader(reader).map_err(io::Error::from)
+}
+
+/// Writes a density on a grid as `{"x": [...], "density": [...]}`.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{read_density_json, write_density_json};
+///
+/// let mut buffer = Vec::new();
+/// write_density_json(&mut buffer, &[0.0, 1.0], &[0.5, 0.25]).unwrap();
+/// assert_eq!(buffer, br#"{"x":[0.0,1.0],"density":[0.5,0.25]}"#);
+/// let (x, density) = read_density_json(buffer.as_slice()).unwrap();
+/// assert_eq!((x, density), (vec![0.0, 1.0], vec![0.5, 0.25]));
+/// ```
+#[cfg(feature = "json")]
+pub fn write_density_json<W: Write>(writer: W, grid: &[f64], density: &[f64]) -> io::Result<()> {
+    if grid.len() != density.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "grid has {} points but density has {}",
+                grid.len(),
+                density.len()
+            ),
+        ));
+    }
+    #[derive(serde::Serialize)]
+    struct Grid<'a> {
+        x: &'a [f64],
+        density: &'a [f64],
+    }
+    serde_json::to_writer(writer, &Grid { x: grid, density }).map_err(io::Error::from)
+}
+
+/// Reads a density written by [`write_density_json`].
+#[cfg(feature = "json")]
+pub fn read_density_json<R: io::Read>(reader: R) -> io::Result<(Vec<f64>, Vec<f64>)> {
+    #[derive(serde::Deserialize)]
+    struct Grid {
+        x: Vec<f64>,
+        density: Vec<f64>,
+    }
+    let grid: Grid = serde_json::from_reader(reader).map_err(io::Error::from)?;
+    if grid.x.len() != grid.density.len() {
+        return Err(invalid_data(format!(
+            "x has {} points but density has {}",
+            grid.x.len(),
+            grid.density.len()
+        )));
+    }
+    Ok((grid.x, grid.density))
+}
+
+/// Streams Monte Carlo eigenvalue samples to Parquet in long format.
+///
+/// Each eigenvalue becomes one row `(trial: u64, index: u32, eigenvalue: f64)`,
+/// so a study of 10^4 matrices of size 10^3 is 10^7 rows that never need to
+/// be held in memory at once: trials are buffered into row groups by the
+/// underlying [`parquet::arrow::ArrowWriter`] and flushed as they fill.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{sample_goe_with, symmetric_eigenvalues, EigenvalueParquetWriter};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let mut writer = EigenvalueParquetWriter::new(Vec::new()).unwrap();
+/// for _ in 0..10 {
+///     writer.write_trial(&symmetric_eigenvalues(&sample_goe_with(&mut rng, 50))).unwrap();
+/// }
+/// assert_eq!(writer.rows_written(), 500);
+/// let bytes = writer.close().unwrap();
+/// assert_eq!(&bytes[..4], b"PAR1");
+/// ```
+#[cfg(feature = "arrow")]
+pub struct EigenvalueParquetWriter<W: Write + Send> {
+    writer: parquet::arrow::ArrowWriter<W>,
+    schema: std::sync::Arc<arrow_schema::Schema>,
+    trials: u64,
+    rows: u64,
+}
+
+#[cfg(feature = "arrow")]
+impl<W: Write + Send> EigenvalueParquetWriter<W> {
+    /// Starts a Parquet file on `writer` with default (Snappy) compression.
+    pub fn new(writer: W) -> parquet::errors::Result<Self> {
+        use arrow_schema::{DataType, Field, Schema};
+
+        let schema = std::sync::Arc::new(Schema::new(vec![
+            Field::new("trial", DataType::UInt64, false),
+            Field::new("index", DataType::UInt32, false),
+            Field::new("eigenvalue", DataType::Float64, false),
+        ]));
+        let properties = parquet::file::properties::WriterProperties::builder()
+            .set_compression(parquet::basic::Compression::SNAPPY)
+            .build();
+        let writer =
+            parquet::arrow::ArrowWriter::try_new(writer, schema.clone(), Some(properties))?;
+        Ok(Self {
+            writer,
+            schema,
+            trials: 0,
+            rows: 0,
+        })
+    }
+
+    /// Appends the eigenvalues of one sampled matrix as the next trial.
+    pub fn write_trial(&mut self, eigenvalues: &[f64]) -> parquet::errors::Result<()> {
+        use arrow_array::{Float64Array, UInt32Array, UInt64Array};
+
+        let len = eigenvalues.len();
+        let batch = arrow_array::RecordBatch::try_new(
+            self.schema.clone(),
+            vec![
+                std::sync::Arc::new(UInt64Array::from(vec![self.trials; len])),
+                std::sync::Arc::new(UInt32Array::from_iter_values(0..len as u32)),
+                std::sync::Arc::new(Float64Array::from(eigenvalues.to_vec())),
+            ],
+        )?;
+        self.writer.write(&batch)?;
+        self.trials += 1;
+        self.rows += len as u64;
+        Ok(())
+    }
+
+    /// Number of trials written so far.
+    pub fn trials_written(&self) -> u64 {
+        self.trials
+    }
+
+    /// Number of rows (eigenvalues) written so far.
+    pub fn rows_written(&self) -> u64 {
+        self.rows
+    }
+
+    /// Flushes the last row group, writes the footer, and returns the inner writer.
+    pub fn close(self) -> parquet::errors::Result<W> {
+        self.writer.into_inner()
+    }
+}
+
+/// Writes one row of summary statistics per trial to Parquet.
+///
+/// Columns: `trial`, `n`, `lambda_min`, `lambda_max`, `mean`, and
+/// `mean_spacing_ratio` (see [`crate::mean_spacing_ratio`]; the eigenvalues
+/// of each trial must be sorted ascending, as [`crate::symmetric_eigenvalues`]
+/// returns them). Empty trials get NaN statistics.
+#[cfg(feature = "arrow")]
+pub fn write_spectral_statistics_parquet<W: Write + Send>(
+    writer: W,
+    trials: &[Vec<f64>],
+) -> parquet::errors::Result<W> {
+    use arrow_array::{ArrayRef, Float64Array, UInt64Array};
+    use std::sync::Arc;
+
+    let stat = |f: &dyn Fn(&[f64]) -> f64| -> ArrayRef {
+        Arc::new(Float64Array::from_iter_values(trials.iter().map(|t| {
+            if t.is_empty() {
+                f64::NAN
+            } else {
+                f(t)
+            }
+        })))
+    };
+    let batch = arrow_array::RecordBatch::try_from_iter([
+        (
+            "trial",
+            Arc::new(UInt64Array::from_iter_values(0..trials.len() as u64)) as ArrayRef,
+        ),
+        (
+            "n",
+            Arc::new(UInt64Array::from_iter_values(
+                trials.iter().map(|t| t.len() as u64),
+            )) as ArrayRef,
+        ),
+        ("lambda_min", stat(&|t| t[0])),
+        ("lambda_max", stat(&|t| t[t.len() - 1])),
+        ("mean", stat(&|t| t.iter().sum::<f64>() / t.len() as f64)),
+        ("mean_spacing_ratio", stat(&crate::mean_spacing_ratio)),
+    ])?;
+    let mut writer = parquet::arrow::ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.into_inner()
+}
+
+/// Parses numeric rows, skipping blank lines and a header in the first line.
+fn read_rows<R: BufRead>(reader: R) -> io::Result<Vec<Vec<f64>>> {
+    let mut rows = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        if let Some(row) = parse_line(index, &line?)? {
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Parses line `index` (0-based) of a CSV file; `None` for blank lines and a
+/// non-numeric header on the first line.
+pub(crate) fn parse_line(index: usize, line: &str) -> io::Result<Option<Vec<f64>>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let parsed: Result<Vec<f64>, _> = line.split(',').map(|field| field.trim().parse()).collect();
+    match parsed {
+        Ok(row) => Ok(Some(row)),
+        Err(_) if index == 0 => Ok(None),
+        Err(e) => Err(invalid_data(format!("line {}: {e}", index + 1))),
+    }
+}
+
+pub(crate) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_round_trips() {
+        let (centers, densities) =
+            crate::empirical_spectral_density(&[0.1, 0.2, 0.25, 0.9, 1.0], 4);
+        let mut buffer = Vec::new();
+        write_density_csv(&mut buffer, &centers, &densities).unwrap();
+        assert_eq!(
+            read_density_csv(buffer.as_slice()).unwrap(),
+            (centers, densities)
+        );
+
+        let matrix = Array2::from_shape_fn((3, 4), |(i, j)| i as f64 - 0.5 * j as f64);
+        let mut buffer = Vec::new();
+        write_matrix_csv(&mut buffer, &matrix).unwrap();
+        assert_eq!(read_matrix_csv(buffer.as_slice()).unwrap(), matrix);
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        let err = read_eigenvalues_csv("eigenvalue\n1.0\nabc\n".as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 3"), "{}", err);
+        assert!(read_matrix_csv("1,2\n3\n".as_bytes()).is_err());
+        assert!(write_density_csv(Vec::new(), &[1.0], &[]).is_err());
+        // Headerless input keeps its first row.
+        assert_eq!(
+            read_eigenvalues_csv("2.5\n\n3.5\n".as_bytes()).unwrap(),
+            vec![2.5, 3.5]
+        );
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_parquet_round_trip() {
+        use arrow_array::{Float64Array, UInt64Array};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let path = std::env::temp_dir().join(format!("rmt-io-{}.parquet", std::process::id()));
+        let trials = vec![vec![0.5, 1.0, 2.5], vec![], vec![-1.0, 3.0]];
+
+        let mut writer =
+            EigenvalueParquetWriter::new(std::fs::File::create(&path).unwrap()).unwrap();
+        for trial in &trials {
+            writer.write_trial(trial).unwrap();
+        }
+        assert_eq!((writer.trials_written(), writer.rows_written()), (3, 5));
+        writer.close().unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let (mut trial_ids, mut values): (Vec<u64>, Vec<f64>) = (Vec::new(), Vec::new());
+        for batch in reader {
+            let batch = batch.unwrap();
+            let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+            let ids = column("trial");
+            trial_ids.extend(ids.as_any().downcast_ref::<UInt64Array>().unwrap().values());
+            let eigs = column("eigenvalue");
+            values.extend(
+                eigs.as_any()
+                    .downcast_ref::<Float64Array>()
+                    .unwrap()
+                    .values(),
+            );
+        }
+        assert_eq!(trial_ids, vec![0, 0, 0, 2, 2]);
+        assert_eq!(values, vec![0.5, 1.0, 2.5, -1.0, 3.0]);
+
+        write_spectral_statistics_parquet(std::fs::File::create(&path).unwrap(), &trials).unwrap();
+        let batch = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let maxima = batch.column_by_name("lambda_max").unwrap().clone();
+        let maxima = maxima.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(maxima.value(0), 2.5);
+        assert!(maxima.value(1).is_nan());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
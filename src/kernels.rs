@@ -0,0 +1,454 @@
+//! Universal correlation kernels.
+//!
+//! The eigenvalues of the classical beta = 2 ensembles form determinantal
+//! point processes: the n-point correlation function is
+//! R_n(x_1, ..., x_n) = det[K(x_i, x_j)] for a kernel K. After local
+//! rescaling, K converges to one of a few universal limits, which are the
+//! reference objects for comparing measured correlations:
+//!
+//! - bulk: the sine kernel sin(pi (x - y)) / (pi (x - y)), for unfolded
+//!   levels with unit mean spacing.
+//! - soft edge: the Airy kernel, for the largest eigenvalues on the
+//!   Tracy-Widom scale.
+//! - hard edge: the Bessel kernel, for the smallest eigenvalues of
+//!   Wishart (Laguerre) matrices, which pile up against zero.
+//!
+//! Gap probabilities are Fredholm determinants det(I - K) of the same
+//! kernels restricted to an interval, which [`fredholm_determinant`]
+//! evaluates by Gauss-Legendre quadrature (Bornemann 2010); for the Airy
+//! kernel on (s, inf) this is the Tracy-Widom CDF F2(s), and for the
+//! Bessel kernel on (0, s) the law of the smallest Wishart eigenvalue.
+//! [`gap_probabilities`] extends this to the probability E(k; J) of exactly
+//! k points in J, for number-variance and k-th eigenvalue statistics.
+
+use std::f64::consts::PI;
+
+use ndarray::Array2;
+
+use crate::linalg::{determinant, symmetric_eigenvalues};
+use crate::special::{airy_ai, airy_ai_prime, bessel_j, gauss_legendre};
+
+/// Below this |x - y| the kernels switch to their diagonal limits.
+const DIAGONAL: f64 = 1e-7;
+
+/// Sine kernel K(x, y) = sin(pi (x - y)) / (pi (x - y)), with K(x, x) = 1.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::sine_kernel;
+///
+/// assert_eq!(sine_kernel(0.3, 0.3), 1.0);
+/// assert!(sine_kernel(0.0, 1.0).abs() < 1e-15);
+/// assert!((sine_kernel(0.0, 0.5) - 2.0 / std::f64::consts::PI).abs() < 1e-15);
+/// ```
+pub fn sine_kernel(x: f64, y: f64) -> f64 {
+    let d = PI * (x - y);
+    if (x - y).abs() < DIAGONAL {
+        // sin(d)/d = 1 - d^2/6 + O(d^4)
+        1.0 - d * d / 6.0
+    } else {
+        d.sin() / d
+    }
+}
+
+/// n-point correlation function det[K_sine(x_i, x_j)] of the bulk (unit density).
+///
+/// R_1 = 1, and R_2(0, r) = 1 - (sin(pi r) / (pi r))^2 is
+/// [`crate::two_point_correlation_gue`]. The determinant is computed
+/// directly, so this is meant for the small point sets (n up to a few
+/// dozen) at which correlations are compared.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{sine_correlation, two_point_correlation_gue};
+///
+/// let r2 = sine_correlation(&[0.0, 0.7]);
+/// assert!((r2 - two_point_correlation_gue(0.7)).abs() < 1e-12);
+/// // Coinciding points have zero correlation (level repulsion).
+/// assert!(sine_correlation(&[0.2, 0.2, 1.0]).abs() < 1e-12);
+/// ```
+pub fn sine_correlation(points: &[f64]) -> f64 {
+    correlation(points, sine_kernel)
+}
+
+/// Airy kernel K(x, y) = (Ai(x) Ai'(y) - Ai'(x) Ai(y)) / (x - y).
+///
+/// On the diagonal K(x, x) = Ai'(x)^2 - x Ai(x)^2, the limiting density of
+/// the largest eigenvalues of the GUE around the edge on the Tracy-Widom
+/// scale; it decays like exp(-(4/3) x^(3/2)) to the right and grows like
+/// sqrt(-x) / pi into the bulk.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::airy_kernel;
+///
+/// // Far into the bulk the edge density approaches the semicircle's sqrt(-x) / pi.
+/// let x: f64 = -30.0;
+/// assert!((airy_kernel(x, x) / ((-x).sqrt() / std::f64::consts::PI) - 1.0).abs() < 0.01);
+/// assert!((airy_kernel(0.3, -1.2) - airy_kernel(-1.2, 0.3)).abs() < 1e-15);
+/// ```
+pub fn airy_kernel(x: f64, y: f64) -> f64 {
+    if (x - y).abs() < DIAGONAL {
+        // K is symmetric, so the midpoint value is accurate to O((x - y)^2).
+        let m = 0.5 * (x + y);
+        let (ai, ai_prime) = (airy_ai(m), airy_ai_prime(m));
+        return ai_prime * ai_prime - m * ai * ai;
+    }
+    (airy_ai(x) * airy_ai_prime(y) - airy_ai_prime(x) * airy_ai(y)) / (x - y)
+}
+
+/// n-point correlation function det[K_Airy(x_i, x_j)] at the soft edge.
+///
+/// R_1(x) = K_Airy(x, x) is the mean density of eigenvalues at x on the
+/// Tracy-Widom scale; compare it to a histogram of
+/// (lambda - 2 sqrt(n)) n^(1/6) pooled over the top few GUE eigenvalues.
+pub fn airy_correlation(points: &[f64]) -> f64 {
+    correlation(points, airy_kernel)
+}
+
+/// Bessel kernel K_alpha(x, y) of the hard edge, for x, y > 0 and alpha > -1.
+///
+/// K(x, y) = (sqrt(x) J_{a+1}(sqrt(x)) J_a(sqrt(y))
+///            - sqrt(y) J_a(sqrt(x)) J_{a+1}(sqrt(y))) / (2 (x - y)),
+///
+/// the limit of the Laguerre (complex Wishart) kernel with weight
+/// x^alpha e^(-x) on the scale 4 n x. Its diagonal
+/// (J_a^2 - J_{a+1} J_{a-1}) / 4 at sqrt(x) is the density of the smallest
+/// eigenvalues, which behaves like x^alpha near zero and tends to
+/// 1 / (2 pi sqrt(x)) away from it (the Marchenko-Pastur hard edge).
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::bessel_kernel;
+///
+/// let x: f64 = 400.0;
+/// let density = 1.0 / (2.0 * std::f64::consts::PI * x.sqrt());
+/// assert!((bessel_kernel(0.0, x, x) / density - 1.0).abs() < 0.05);
+/// assert!((bessel_kernel(1.5, 0.3, 7.0) - bessel_kernel(1.5, 7.0, 0.3)).abs() < 1e-15);
+/// ```
+pub fn bessel_kernel(alpha: f64, x: f64, y: f64) -> f64 {
+    if (x - y).abs() < DIAGONAL {
+        let z = (0.5 * (x + y)).sqrt();
+        let (j, j_next) = (bessel_j(alpha, z), bessel_j(alpha + 1.0, z));
+        // J_{a-1}(z) = (2 a / z) J_a(z) - J_{a+1}(z), valid for all a > -1.
+        let j_prev = 2.0 * alpha / z * j - j_next;
+        return 0.25 * (j * j - j_next * j_prev);
+    }
+    let (sx, sy) = (x.sqrt(), y.sqrt());
+    let numerator = sx * bessel_j(alpha + 1.0, sx) * bessel_j(alpha, sy)
+        - sy * bessel_j(alpha, sx) * bessel_j(alpha + 1.0, sy);
+    numerator / (2.0 * (x - y))
+}
+
+/// n-point correlation function det[K_alpha(x_i, x_j)] at the hard edge.
+pub fn bessel_correlation(alpha: f64, points: &[f64]) -> f64 {
+    correlation(points, |x, y| bessel_kernel(alpha, x, y))
+}
+
+/// Hard-edge gap probability E(s) = det(I - K_alpha) on L^2(0, s).
+///
+/// The probability that no eigenvalue of the Bessel process lies in
+/// (0, s): for the n x n complex Wishart matrix X* X of an (n + alpha) x n
+/// Gaussian X with unit-variance entries, P(4 n lambda_min > s) -> E(s). For
+/// alpha = 0 it is exactly exp(-s / 4), Edelman's exponential law; in
+/// general 1 - E(s) is the limiting CDF of 4 n lambda_min.
+///
+/// The kernel has an x^alpha branch point at zero, which the substitution
+/// x = s u^2 turns into u^(alpha + 1/2): for alpha >= -1/2 the quadrature
+/// is accurate to ~1e-10 (relative accuracy is lost once E(s) falls below
+/// that), while for -1 < alpha < -1/2 only two or three digits are reliable.
+///
+/// # Arguments
+///
+/// * `alpha` - Bessel order, alpha > -1 (NaN otherwise)
+/// * `s` - Gap length on the 4 n lambda scale
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::hard_edge_gap_probability;
+///
+/// let s = 3.0;
+/// assert!((hard_edge_gap_probability(0.0, s) - (-s / 4.0).exp()).abs() < 1e-10);
+/// // Larger alpha pushes the smallest eigenvalue away from zero.
+/// assert!(hard_edge_gap_probability(2.0, s) > hard_edge_gap_probability(1.0, s));
+/// ```
+pub fn hard_edge_gap_probability(alpha: f64, s: f64) -> f64 {
+    if alpha.is_nan() || alpha <= -1.0 || s.is_nan() {
+        return f64::NAN;
+    }
+    if s <= 0.0 {
+        return 1.0;
+    }
+    // x = s u^2 on (0, 1): the Jacobian sqrt(2 s u) on each side makes the
+    // transformed kernel vanish like u^(alpha + 1/2) at the origin.
+    let nodes = 40 + (2.0 * s.sqrt()) as usize;
+    fredholm_determinant(
+        |u, v| {
+            let jacobian = 2.0 * s * (u * v).sqrt();
+            jacobian * bessel_kernel(alpha, s * u * u, s * v * v)
+        },
+        0.0,
+        1.0,
+        nodes,
+    )
+}
+
+/// Fredholm determinant det(I - K) of an integral operator on L^2(a, b).
+///
+/// Uses an m-point Gauss-Legendre rule, which converges exponentially in
+/// m for analytic kernels (Bornemann 2010): det[delta_ij - sqrt(w_i)
+/// K(x_i, x_j) sqrt(w_j)]. For a half-line, truncate `b` where the kernel
+/// has decayed (for the Airy kernel, a dozen units past `a` suffices).
+///
+/// # Arguments
+///
+/// * `kernel` - K(x, y), usually symmetric
+/// * `a`, `b` - Interval of integration
+/// * `nodes` - Quadrature points m (30-60 is typical)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{airy_kernel, fredholm_determinant, tracy_widom_cdf};
+///
+/// // F2(s) = det(I - K_Airy) on (s, inf): the Fredholm route to Tracy-Widom.
+/// let s = -2.0;
+/// let f2 = fredholm_determinant(airy_kernel, s, s + 16.0, 40);
+/// assert!((f2 - tracy_widom_cdf(s, 2.0)).abs() < 1e-9);
+/// ```
+pub fn fredholm_determinant<K: Fn(f64, f64) -> f64>(
+    kernel: K,
+    a: f64,
+    b: f64,
+    nodes: usize,
+) -> f64 {
+    if nodes == 0 || b <= a {
+        return 1.0;
+    }
+    let (x, w) = gauss_legendre(nodes, a, b);
+    let root_w: Vec<f64> = w.iter().map(|w| w.sqrt()).collect();
+    determinant(&Array2::from_shape_fn((nodes, nodes), |(i, j)| {
+        let identity = if i == j { 1.0 } else { 0.0 };
+        identity - root_w[i] * kernel(x[i], x[j]) * root_w[j]
+    }))
+}
+
+/// Counting probabilities E(k; (a, b)) for k = 0..=k_max.
+///
+/// E(k) is the probability that exactly k points of the determinantal
+/// process with symmetric kernel K fall in (a, b); E(0) is the gap
+/// probability [`fredholm_determinant`]. The generating function
+/// det(I - z K) factors over the eigenvalues mu_i of the discretized
+/// operator, so the count is a sum of independent Bernoulli(mu_i) variables
+/// and E(k) follows by convolution (Bornemann 2010).
+///
+/// # Arguments
+///
+/// * `kernel` - Symmetric K(x, y) whose operator has spectrum in [0, 1]
+/// * `a`, `b` - Interval
+/// * `nodes` - Gauss-Legendre points m
+/// * `k_max` - Largest count returned
+///
+/// # Returns
+///
+/// `k_max + 1` probabilities; they sum to at most 1.
+pub fn gap_probabilities<K: Fn(f64, f64) -> f64>(
+    kernel: K,
+    a: f64,
+    b: f64,
+    nodes: usize,
+    k_max: usize,
+) -> Vec<f64> {
+    let mut counts = vec![0.0; k_max + 1];
+    counts[0] = 1.0;
+    if nodes == 0 || b <= a {
+        return counts;
+    }
+    let (x, w) = gauss_legendre(nodes, a, b);
+    let root_w: Vec<f64> = w.iter().map(|w| w.sqrt()).collect();
+    let operator = Array2::from_shape_fn((nodes, nodes), |(i, j)| {
+        root_w[i] * kernel(x[i], x[j]) * root_w[j]
+    });
+    for mu in symmetric_eigenvalues(&operator) {
+        let mu = mu.clamp(0.0, 1.0);
+        for k in (0..=k_max).rev() {
+            let added = if k > 0 { mu * counts[k - 1] } else { 0.0 };
+            counts[k] = (1.0 - mu) * counts[k] + added;
+        }
+    }
+    counts
+}
+
+/// E(k; s) for an interval of length s in the bulk, for k = 0..=k_max.
+///
+/// Uses the sine kernel, so s is measured in mean spacings of unfolded
+/// levels. E(0; s) is the GUE gap probability, whose second derivative is
+/// the exact nearest-neighbor spacing density (approximated by
+/// [`crate::wigner_surmise`] with beta = 2).
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::sine_gap_probabilities;
+///
+/// let e = sine_gap_probabilities(2.0, 6);
+/// // On average the interval holds s = 2 levels...
+/// let mean: f64 = e.iter().enumerate().map(|(k, p)| k as f64 * p).sum();
+/// assert!((mean - 2.0).abs() < 1e-6);
+/// // ...and rigidity makes exactly 2 by far the most likely count.
+/// assert!(e[2] > 0.6);
+/// ```
+pub fn sine_gap_probabilities(s: f64, k_max: usize) -> Vec<f64> {
+    let nodes = 20 + (2.0 * s.max(0.0)).ceil() as usize;
+    gap_probabilities(sine_kernel, 0.0, s, nodes, k_max)
+}
+
+/// E(k; (s, inf)) at the soft edge, for k = 0..=k_max.
+///
+/// The probability that exactly k eigenvalues exceed s on the Tracy-Widom
+/// scale, so sum_{j <= k} E(j) is the CDF of the (k + 1)-th largest
+/// eigenvalue; E(0) is F2(s).
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{airy_gap_probabilities, tracy_widom_cdf};
+///
+/// let e = airy_gap_probabilities(-3.0, 1);
+/// assert!((e[0] - tracy_widom_cdf(-3.0, 2.0)).abs() < 1e-9);
+/// // The second-largest eigenvalue lies below -3 with probability e[0] + e[1].
+/// assert!(e[0] + e[1] > e[0]);
+/// ```
+pub fn airy_gap_probabilities(s: f64, k_max: usize) -> Vec<f64> {
+    // The kernel has decayed below 1e-30 past x = 8.
+    let b = (s + 16.0).max(12.0);
+    let nodes = 40 + (2.0 * (b - s)) as usize;
+    gap_probabilities(airy_kernel, s, b, nodes, k_max)
+}
+
+/// det[K(x_i, x_j)] over a point set (1 for the empty set).
+fn correlation<K: Fn(f64, f64) -> f64>(points: &[f64], kernel: K) -> f64 {
+    let n = points.len();
+    if n == 0 {
+        return 1.0;
+    }
+    determinant(&Array2::from_shape_fn((n, n), |(i, j)| {
+        kernel(points[i], points[j])
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_correlation_cluster_expansion() {
+        // R_3 = 1 - K12^2 - K13^2 - K23^2 + 2 K12 K13 K23.
+        let x = [0.1, 0.65, 1.4];
+        let k12 = sine_kernel(x[0], x[1]);
+        let k13 = sine_kernel(x[0], x[2]);
+        let k23 = sine_kernel(x[1], x[2]);
+        let expected = 1.0 - k12 * k12 - k13 * k13 - k23 * k23 + 2.0 * k12 * k13 * k23;
+        assert!((sine_correlation(&x) - expected).abs() < 1e-12);
+        // Translation invariance and symmetry.
+        assert!((sine_correlation(&[5.1, 5.65, 6.4]) - expected).abs() < 1e-12);
+        assert!((sine_correlation(&[1.4, 0.1, 0.65]) - expected).abs() < 1e-12);
+        assert_eq!(sine_correlation(&[]), 1.0);
+        assert_eq!(sine_correlation(&[3.0]), 1.0);
+        // Continuity across the removable singularity.
+        assert!((sine_kernel(0.0, 1e-9) - sine_kernel(0.0, 1e-7)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_airy_kernel_fredholm_matches_tables() {
+        for s in [-5.0, -3.0, -1.5, 0.0, 1.0, 2.5] {
+            let f2 = fredholm_determinant(airy_kernel, s, s + 16.0, 48);
+            let table = crate::tracy_widom_cdf(s, 2.0);
+            assert!((f2 - table).abs() < 1e-9, "s {s}: {f2} vs {table}");
+        }
+        // The diagonal limit joins the off-diagonal formula continuously.
+        let near = airy_kernel(-0.7 - 1e-4, -0.7 + 1e-4);
+        assert!((near - airy_kernel(-0.7, -0.7)).abs() < 1e-7);
+        // Two-point correlations vanish at coincidence and factorize far apart.
+        assert!(airy_correlation(&[-1.0, -1.0]).abs() < 1e-12);
+        let (x, y) = (-1.0, -9.0);
+        let r2 = airy_correlation(&[x, y]);
+        let product = airy_kernel(x, x) * airy_kernel(y, y);
+        assert!((r2 / product - 1.0).abs() < 0.05);
+        assert_eq!(fredholm_determinant(sine_kernel, 1.0, 0.0, 10), 1.0);
+    }
+
+    #[test]
+    fn test_hard_edge_gap_closed_forms() {
+        // alpha = 0: exp(-s/4); alpha = 1: exp(-s/4) I_0(sqrt(s)) (Forrester).
+        let i0 = |x: f64| {
+            let (mut term, mut sum) = (1.0, 1.0);
+            for k in 1..100 {
+                term *= 0.25 * x * x / (k * k) as f64;
+                sum += term;
+            }
+            sum
+        };
+        for s in [0.5, 3.0, 10.0, 30.0] {
+            let e0 = hard_edge_gap_probability(0.0, s);
+            let e1 = hard_edge_gap_probability(1.0, s);
+            assert!((e0 - (-s / 4.0).exp()).abs() < 1e-10, "s {s}: {e0}");
+            let expected = (-s / 4.0).exp() * i0(s.sqrt());
+            assert!((e1 - expected).abs() < 1e-10, "s {s}: {e1} vs {expected}");
+        }
+        assert_eq!(hard_edge_gap_probability(0.5, 0.0), 1.0);
+        assert!(hard_edge_gap_probability(-1.0, 1.0).is_nan());
+        // The one-point function is the integrand of the first-order term:
+        // E(s) = 1 - int_0^s K(x, x) dx + O(s^(2 alpha + 4)) as s -> 0.
+        let (s, alpha) = (0.05, 0.5);
+        let (x, w) = gauss_legendre(20, 0.0, s);
+        let mass: f64 = x
+            .iter()
+            .zip(&w)
+            .map(|(&x, &w)| w * bessel_kernel(alpha, x, x))
+            .sum();
+        let gap = hard_edge_gap_probability(alpha, s);
+        assert!((1.0 - gap - mass).abs() < 1e-3 * mass);
+        assert!((bessel_correlation(alpha, &[0.3, 0.3])).abs() < 1e-12);
+        let near = bessel_kernel(alpha, 2.0 - 1e-4, 2.0 + 1e-4);
+        assert!((near - bessel_kernel(alpha, 2.0, 2.0)).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_gap_probabilities_counting_laws() {
+        // Bulk: the counts form a distribution with mean s (unit density).
+        for s in [0.5, 2.0, 6.0] {
+            let e = sine_gap_probabilities(s, 20);
+            let mean: f64 = e.iter().enumerate().map(|(k, p)| k as f64 * p).sum();
+            assert!((e.iter().sum::<f64>() - 1.0).abs() < 1e-10);
+            assert!((mean - s).abs() < 1e-10, "s {s}: mean {mean}");
+            assert!((e[0] - fredholm_determinant(sine_kernel, 0.0, s, 40)).abs() < 1e-12);
+        }
+        // E(0)'' is the GUE spacing density, within 0.02 of the Wigner surmise.
+        let h = 1e-2;
+        let e0 = |s: f64| sine_gap_probabilities(s, 0)[0];
+        let density = (e0(1.0 + h) - 2.0 * e0(1.0) + e0(1.0 - h)) / (h * h);
+        assert!((density - crate::wigner_surmise(1.0, 2.0)).abs() < 0.02);
+        // Edge: E(0) = F2, and the mean count is the integrated one-point function.
+        for s in [-4.0, -1.0, 1.0] {
+            let e = airy_gap_probabilities(s, 12);
+            assert!((e[0] - crate::tracy_widom_cdf(s, 2.0)).abs() < 1e-9);
+            let mean: f64 = e.iter().enumerate().map(|(k, p)| k as f64 * p).sum();
+            let (x, w) = gauss_legendre(80, s, s + 16.0);
+            let expected: f64 = x.iter().zip(&w).map(|(&x, &w)| w * airy_kernel(x, x)).sum();
+            assert!(
+                (mean - expected).abs() < 1e-9,
+                "s {s}: {mean} vs {expected}"
+            );
+        }
+        assert_eq!(
+            gap_probabilities(sine_kernel, 1.0, 0.0, 10, 2),
+            vec![1.0, 0.0, 0.0]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Closed-form limiting laws: Marchenko-Pastur and Wigner semicircle.
+//!
+//! These functions only need `core` floating-point arithmetic, so they stay
+//! available without the `std` feature (transcendental functions then come
+//! from `libm`). That is enough to evaluate MP edges, densities, and
+//! quantile thresholds on embedded targets.
+
+use core::f64::consts::PI;
+
+use crate::math;
+
+/// Marchenko-Pastur density at point lambda.
+///
+/// For the eigenvalues of (1/n) X^T X where X is n x p with i.i.d. N(0, sigma^2) entries.
+///
+/// # Arguments
+///
+/// * `lambda` - Eigenvalue to evaluate density at
+/// * `ratio` - gamma = p/n ratio (any positive value; values > 1 are folded via min(gamma, 1/gamma))
+/// * `sigma_sq` - Variance of matrix entries (default 1.0)
+///
+/// # Returns
+///
+/// Density rho(lambda), or 0 if outside support [lambda_-, lambda_+].
+/// Parameters are not validated: a non-positive ratio gives 0, and NaN or
+/// non-positive sigma_sq gives 0 or NaN. [`crate::MarchenkoPastur::new`] is the
+/// checked alternative.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::marchenko_pastur_density;
+///
+/// let gamma = 0.5;  // p/n = 0.5
+/// let density = marchenko_pastur_density(1.5, gamma, 1.0);
+/// assert!(density > 0.0);
+/// ```
+pub fn marchenko_pastur_density(lambda: f64, ratio: f64, sigma_sq: f64) -> f64 {
+    if ratio <= 0.0 || lambda <= 0.0 {
+        return 0.0;
+    }
+
+    let gamma = ratio.min(1.0 / ratio); // Handle both p/n < 1 and p/n > 1
+    let lambda_plus = sigma_sq * math::square(1.0 + math::sqrt(gamma));
+    let lambda_minus = sigma_sq * math::square(1.0 - math::sqrt(gamma));
+
+    if lambda < lambda_minus || lambda > lambda_plus {
+        return 0.0;
+    }
+
+    let sqrt_term = math::sqrt((lambda_plus - lambda) * (lambda - lambda_minus));
+    sqrt_term / (2.0 * PI * sigma_sq * gamma * lambda)
+}
+
+/// Marchenko-Pastur support bounds [lambda_-, lambda_+].
+///
+/// # Arguments
+///
+/// * `ratio` - gamma = p/n ratio (any positive value; values > 1 are folded via min(gamma, 1/gamma))
+/// * `sigma_sq` - Variance of matrix entries
+///
+/// # Returns
+///
+/// (lambda_minus, lambda_plus)
+pub fn marchenko_pastur_support(ratio: f64, sigma_sq: f64) -> (f64, f64) {
+    let gamma = ratio.min(1.0 / ratio);
+    let lambda_plus = sigma_sq * math::square(1.0 + math::sqrt(gamma));
+    let lambda_minus = sigma_sq * math::square(1.0 - math::sqrt(gamma));
+    (lambda_minus, lambda_plus)
+}
+
+/// Support of the nonzero eigenvalues of (1/n) X^T X, without folding gamma.
+///
+/// For p > n the sample covariance has p - n null eigenvalues and the rest
+/// fill sigma^2 [(sqrt(gamma) - 1)^2, (sqrt(gamma) + 1)^2];
+/// [`marchenko_pastur_support`] describes (1/p) X X^T instead. The two agree
+/// for gamma <= 1.
+#[cfg(feature = "std")]
+pub(crate) fn sample_covariance_support(ratio: f64, sigma_sq: f64) -> (f64, f64) {
+    let root = math::sqrt(ratio);
+    (
+        sigma_sq * math::square(1.0 - root),
+        sigma_sq * math::square(1.0 + root),
+    )
+}
+
+/// Marchenko-Pastur cumulative distribution function at lambda.
+///
+/// Integrates [`marchenko_pastur_density`] in closed form, so the same
+/// ratio folding applies and the result is 0 below lambda_- and 1 above lambda_+.
+///
+/// # Arguments
+///
+/// * `lambda` - Point at which to evaluate the CDF
+/// * `ratio` - gamma = p/n ratio (any positive value; values > 1 are folded via min(gamma, 1/gamma))
+/// * `sigma_sq` - Variance of matrix entries
+///
+/// # Returns
+///
+/// P(eigenvalue <= lambda), or 0 for non-positive ratio
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{marchenko_pastur_cdf, marchenko_pastur_support};
+///
+/// let (lo, hi) = marchenko_pastur_support(0.5, 1.0);
+/// assert_eq!(marchenko_pastur_cdf(lo, 0.5, 1.0), 0.0);
+/// assert!((marchenko_pastur_cdf(hi, 0.5, 1.0) - 1.0).abs() < 1e-12);
+/// ```
+pub fn marchenko_pastur_cdf(lambda: f64, ratio: f64, sigma_sq: f64) -> f64 {
+    if ratio <= 0.0 {
+        return 0.0;
+    }
+
+    let gamma = ratio.min(1.0 / ratio);
+    let (lambda_minus, lambda_plus) = marchenko_pastur_support(ratio, sigma_sq);
+    if lambda <= lambda_minus {
+        return 0.0;
+    }
+    if lambda >= lambda_plus {
+        return 1.0;
+    }
+
+    // Substitute lambda = m + r cos(theta); the integral of the density from
+    // theta0 to pi then has an elementary antiderivative.
+    let m = 0.5 * (lambda_plus + lambda_minus);
+    let r = 0.5 * (lambda_plus - lambda_minus);
+    let theta0 = math::acos(((lambda - m) / r).clamp(-1.0, 1.0));
+    let geo = math::sqrt(lambda_plus * lambda_minus);
+    let k = math::sqrt(lambda_minus / lambda_plus);
+    let atan_term = if geo > 0.0 {
+        2.0 * geo * (PI / 2.0 - math::atan(k * math::tan(theta0 / 2.0)))
+    } else {
+        0.0
+    };
+
+    let value =
+        (m * (PI - theta0) + r * math::sin(theta0) - atan_term) / (2.0 * PI * sigma_sq * gamma);
+    value.clamp(0.0, 1.0)
+}
+
+/// Marchenko-Pastur quantile function: the inverse of [`marchenko_pastur_cdf`].
+///
+/// Solved by bisection on the closed-form CDF.
+///
+/// # Arguments
+///
+/// * `q` - Probability in [0, 1]
+/// * `ratio` - gamma = p/n ratio (folded like [`marchenko_pastur_cdf`])
+/// * `sigma_sq` - Variance of matrix entries
+///
+/// # Returns
+///
+/// lambda with P(eigenvalue <= lambda) = q, or NaN for q outside [0, 1] or non-positive ratio
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{marchenko_pastur_cdf, marchenko_pastur_quantile};
+///
+/// let median = marchenko_pastur_quantile(0.5, 0.25, 1.0);
+/// assert!((marchenko_pastur_cdf(median, 0.25, 1.0) - 0.5).abs() < 1e-12);
+/// ```
+pub fn marchenko_pastur_quantile(q: f64, ratio: f64, sigma_sq: f64) -> f64 {
+    if !(0.0..=1.0).contains(&q) || ratio <= 0.0 {
+        return f64::NAN;
+    }
+    let (mut lo, mut hi) = marchenko_pastur_support(ratio, sigma_sq);
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if marchenko_pastur_cdf(mid, ratio, sigma_sq) < q {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= 1e-15 * math::abs(hi).max(1.0) {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Wigner semicircle density at point lambda.
+///
+/// For eigenvalues of symmetric matrix with i.i.d. entries of variance sigma^2.
+///
+/// # Arguments
+///
+/// * `lambda` - Eigenvalue to evaluate density at
+/// * `sigma` - Standard deviation (radius = 2*sigma)
+///
+/// # Returns
+///
+/// Density rho(lambda), or 0 if |lambda| > 2*sigma. sigma is not validated;
+/// use [`crate::WignerSemicircle::new`] for a checked alternative.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::wigner_semicircle_density;
+///
+/// // At lambda=0 with sigma=1, R=2, density = 2/(pi*R^2) * R = 1/pi
+/// let density = wigner_semicircle_density(0.0, 1.0);
+/// assert!(density > 0.3);  // Should be ~1/pi ~ 0.318
+/// ```
+pub fn wigner_semicircle_density(lambda: f64, sigma: f64) -> f64 {
+    let r = 2.0 * sigma;
+    if math::abs(lambda) > r {
+        return 0.0;
+    }
+
+    (2.0 / (PI * r * r)) * math::sqrt(r * r - lambda * lambda)
+}
+
+/// Wigner semicircle cumulative distribution function at lambda.
+///
+/// F(lambda) = 1/2 + lambda sqrt(R^2 - lambda^2) / (pi R^2) + arcsin(lambda / R) / pi, R = 2*sigma.
+///
+/// # Arguments
+///
+/// * `lambda` - Point at which to evaluate the CDF
+/// * `sigma` - Standard deviation (radius = 2*sigma)
+///
+/// # Returns
+///
+/// P(eigenvalue <= lambda)
+pub fn wigner_semicircle_cdf(lambda: f64, sigma: f64) -> f64 {
+    let r = 2.0 * sigma;
+    if lambda <= -r {
+        return 0.0;
+    }
+    if lambda >= r {
+        return 1.0;
+    }
+
+    let value = 0.5
+        + lambda * math::sqrt(r * r - lambda * lambda) / (PI * r * r)
+        + math::asin(lambda / r) / PI;
+    value.clamp(0.0, 1.0)
+}
@@ -0,0 +1,993 @@
+//! Level statistics: unfolding and spacing-based diagnostics.
+//!
+//! Raw eigenvalues have a position-dependent density, so spacings are only
+//! comparable after *unfolding*: mapping each eigenvalue through a smooth
+//! approximation of the counting function N(lambda) = #{lambda_i <= lambda}.
+//! Unfolded levels have unit mean spacing everywhere, which is what the
+//! universal GOE/GUE/Poisson predictions assume.
+
+use std::f64::consts::PI;
+
+use crate::gof::ks_distance_sorted;
+use crate::linalg::least_squares;
+use crate::special::{gamma_p, ln_gamma, sine_cosine_integrals, EULER_GAMMA};
+use crate::spectrum::AsEigenvalues;
+use crate::{marchenko_pastur_cdf, wigner_semicircle_cdf};
+
+/// How to approximate the smooth counting function when unfolding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnfoldMethod {
+    /// Least-squares polynomial fit of the given degree to the empirical staircase.
+    Polynomial {
+        /// Polynomial degree (6-10 is typical for a single bulk).
+        degree: usize,
+    },
+    /// Least-squares cubic spline with equally spaced (in rank) interior knots.
+    Spline {
+        /// Number of interior knots.
+        knots: usize,
+    },
+    /// Analytic unfolding with the Marchenko-Pastur CDF.
+    MarchenkoPastur {
+        /// gamma = p/n ratio
+        ratio: f64,
+        /// Variance of matrix entries
+        sigma_sq: f64,
+    },
+    /// Analytic unfolding with the Wigner semicircle CDF.
+    Semicircle {
+        /// Standard deviation (radius = 2*sigma)
+        sigma: f64,
+    },
+}
+
+/// Unfold a spectrum to unit mean spacing.
+///
+/// Each eigenvalue is mapped to N_smooth(lambda_i), where N_smooth is either a
+/// fit to the empirical staircase or n times an analytic CDF.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues in any order
+/// * `method` - Smooth counting-function approximation
+///
+/// # Returns
+///
+/// Unfolded levels in ascending eigenvalue order (empty if a fit cannot be made,
+/// e.g. fewer eigenvalues than fit parameters)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{unfold, UnfoldMethod};
+///
+/// let eigenvalues: Vec<f64> = (0..100)
+///     .map(|i| {
+///         let u = i as f64 / 100.0;
+///         u + 0.5 * u * u
+///     })
+///     .collect();
+/// let unfolded = unfold(&eigenvalues, UnfoldMethod::Polynomial { degree: 4 });
+/// let mean_spacing = (unfolded[99] - unfolded[0]) / 99.0;
+/// assert!((mean_spacing - 1.0).abs() < 0.05);
+/// ```
+pub fn unfold<E: AsEigenvalues + ?Sized>(eigenvalues: &E, method: UnfoldMethod) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() {
+        return vec![];
+    }
+
+    let mut sorted = eigenvalues.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let n = sorted.len() as f64;
+
+    match method {
+        UnfoldMethod::MarchenkoPastur { ratio, sigma_sq } => sorted
+            .iter()
+            .map(|&x| n * marchenko_pastur_cdf(x, ratio, sigma_sq))
+            .collect(),
+        UnfoldMethod::Semicircle { sigma } => sorted
+            .iter()
+            .map(|&x| n * wigner_semicircle_cdf(x, sigma))
+            .collect(),
+        UnfoldMethod::Polynomial { degree } => fit_staircase(&sorted, |x| {
+            (0..=degree).map(|k| x.powi(k as i32)).collect()
+        }),
+        UnfoldMethod::Spline { knots } => {
+            // Knots at evenly spaced ranks, expressed on the rescaled [0, 1] axis.
+            let (lo, hi) = (sorted[0], sorted[sorted.len() - 1]);
+            let width = if hi > lo { hi - lo } else { 1.0 };
+            let knot_positions: Vec<f64> = (1..=knots)
+                .map(|j| {
+                    let idx = j * (sorted.len() - 1) / (knots + 1);
+                    (sorted[idx] - lo) / width
+                })
+                .collect();
+            fit_staircase(&sorted, |x| {
+                let mut row = vec![1.0, x, x * x, x * x * x];
+                row.extend(knot_positions.iter().map(|&k| (x - k).max(0.0).powi(3)));
+                row
+            })
+        }
+    }
+}
+
+/// Least-squares fit of the staircase N(lambda_i) = i + 1/2 in a given basis.
+///
+/// Eigenvalues are rescaled to [0, 1] before building the basis for conditioning.
+fn fit_staircase<F>(sorted: &[f64], basis: F) -> Vec<f64>
+where
+    F: Fn(f64) -> Vec<f64>,
+{
+    let (lo, hi) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = if hi > lo { hi - lo } else { 1.0 };
+    let scaled: Vec<f64> = sorted.iter().map(|&x| (x - lo) / width).collect();
+
+    let design: Vec<Vec<f64>> = scaled.iter().map(|&x| basis(x)).collect();
+    let staircase: Vec<f64> = (0..sorted.len()).map(|i| i as f64 + 0.5).collect();
+
+    match least_squares(&design, &staircase) {
+        Some(coef) => design
+            .iter()
+            .map(|row| row.iter().zip(&coef).map(|(b, c)| b * c).sum())
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Universality class for nearest-neighbor spacing statistics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpacingClass {
+    /// Uncorrelated levels: P(s) = exp(-s)
+    Poisson,
+    /// Gaussian Orthogonal Ensemble, beta = 1
+    Goe,
+    /// Gaussian Unitary Ensemble, beta = 2
+    Gue,
+    /// Gaussian Symplectic Ensemble, beta = 4
+    Gse,
+}
+
+impl SpacingClass {
+    /// All classes, in order of increasing level repulsion.
+    pub const ALL: [SpacingClass; 4] = [
+        SpacingClass::Poisson,
+        SpacingClass::Goe,
+        SpacingClass::Gue,
+        SpacingClass::Gse,
+    ];
+
+    /// Dyson index beta, or `None` for Poisson.
+    pub fn beta(&self) -> Option<f64> {
+        match self {
+            SpacingClass::Poisson => None,
+            SpacingClass::Goe => Some(1.0),
+            SpacingClass::Gue => Some(2.0),
+            SpacingClass::Gse => Some(4.0),
+        }
+    }
+
+    /// Spacing density P(s) for this class (Wigner surmise for the Gaussian ensembles).
+    pub fn density(&self, s: f64) -> f64 {
+        match self.beta() {
+            Some(beta) => wigner_surmise(s, beta),
+            None if s >= 0.0 => (-s).exp(),
+            None => 0.0,
+        }
+    }
+
+    /// Spacing CDF for this class.
+    pub fn cdf(&self, s: f64) -> f64 {
+        match self.beta() {
+            Some(beta) => wigner_surmise_cdf(s, beta),
+            None if s > 0.0 => 1.0 - (-s).exp(),
+            None => 0.0,
+        }
+    }
+}
+
+/// Large-n mean of the spacing ratio min(r, 1/r) for Poisson levels: 2 ln 2 - 1.
+pub const MEAN_SPACING_RATIO_POISSON: f64 = 0.386_294_361_119_890_6;
+/// Large-n mean spacing ratio for GOE (numerical value of Atas et al. 2013).
+pub const MEAN_SPACING_RATIO_GOE: f64 = 0.5307;
+/// Large-n mean spacing ratio for GUE (numerical value of Atas et al. 2013).
+pub const MEAN_SPACING_RATIO_GUE: f64 = 0.5996;
+/// Large-n mean spacing ratio for GSE (numerical value of Atas et al. 2013).
+pub const MEAN_SPACING_RATIO_GSE: f64 = 0.6744;
+
+/// Density of the spacing ratio r = min(s_i, s_{i+1}) / max(s_i, s_{i+1}) on [0, 1].
+///
+/// Uses the Atas-Bogomolny surmise for the Gaussian ensembles,
+/// P(r) = 2 (r + r^2)^beta / (Z_beta (1 + r + r^2)^(1 + 3 beta / 2)),
+/// and P(r) = 2 / (1 + r)^2 for Poisson. This is the distribution of the values
+/// returned by [`crate::level_spacing_ratios`], and unlike spacing statistics it
+/// needs no unfolding.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{spacing_ratio_density, SpacingClass};
+///
+/// // Level repulsion: GOE ratios avoid 0, Poisson ratios pile up there.
+/// assert_eq!(spacing_ratio_density(0.0, SpacingClass::Goe), 0.0);
+/// assert_eq!(spacing_ratio_density(0.0, SpacingClass::Poisson), 2.0);
+/// ```
+pub fn spacing_ratio_density(r: f64, class: SpacingClass) -> f64 {
+    if !(0.0..=1.0).contains(&r) {
+        return 0.0;
+    }
+    match class.beta() {
+        None => 2.0 / (1.0 + r).powi(2),
+        Some(beta) => {
+            let z = match class {
+                SpacingClass::Goe => 8.0 / 27.0,
+                SpacingClass::Gue => 4.0 * PI / (81.0 * 3.0_f64.sqrt()),
+                _ => 4.0 * PI / (729.0 * 3.0_f64.sqrt()),
+            };
+            2.0 * (r + r * r).powf(beta) / (z * (1.0 + r + r * r).powf(1.0 + 1.5 * beta))
+        }
+    }
+}
+
+/// CDF of the spacing ratio on [0, 1] (see [`spacing_ratio_density`]).
+pub fn spacing_ratio_cdf(r: f64, class: SpacingClass) -> f64 {
+    if r <= 0.0 {
+        return 0.0;
+    }
+    if r >= 1.0 {
+        return 1.0;
+    }
+    match class {
+        SpacingClass::Poisson => 2.0 * r / (1.0 + r),
+        _ => {
+            // Composite Simpson; the integrand is smooth on [0, 1].
+            let steps = 256;
+            let h = r / steps as f64;
+            let mut acc = spacing_ratio_density(0.0, class) + spacing_ratio_density(r, class);
+            for k in 1..steps {
+                let weight = if k % 2 == 1 { 4.0 } else { 2.0 };
+                acc += weight * spacing_ratio_density(k as f64 * h, class);
+            }
+            (acc * h / 3.0).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// Wigner surmise P_beta(s) = a_beta s^beta exp(-b_beta s^2).
+///
+/// The exact spacing distribution of 2 x 2 Gaussian matrices, normalized to unit
+/// mean spacing. It approximates the large-n bulk spacing law to within a few percent.
+///
+/// # Arguments
+///
+/// * `s` - Spacing in units of the mean spacing
+/// * `beta` - Dyson index (1 = GOE, 2 = GUE, 4 = GSE; any beta > 0 is accepted)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::wigner_surmise;
+/// use std::f64::consts::PI;
+///
+/// // GOE: P(s) = (pi/2) s exp(-pi s^2 / 4)
+/// let s = 0.8;
+/// let expected = PI / 2.0 * s * (-PI * s * s / 4.0).exp();
+/// assert!((wigner_surmise(s, 1.0) - expected).abs() < 1e-12);
+/// ```
+pub fn wigner_surmise(s: f64, beta: f64) -> f64 {
+    if s < 0.0 || beta <= 0.0 {
+        return 0.0;
+    }
+    let (a, b) = surmise_constants(beta);
+    a * s.powf(beta) * (-b * s * s).exp()
+}
+
+/// CDF of the Wigner surmise: P((beta + 1)/2, b_beta s^2) with P the regularized incomplete gamma.
+pub fn wigner_surmise_cdf(s: f64, beta: f64) -> f64 {
+    if s <= 0.0 || beta <= 0.0 {
+        return 0.0;
+    }
+    let (_, b) = surmise_constants(beta);
+    gamma_p((beta + 1.0) / 2.0, b * s * s)
+}
+
+/// Normalization constants (a_beta, b_beta) fixing unit area and unit mean.
+fn surmise_constants(beta: f64) -> (f64, f64) {
+    let ln_ratio = ln_gamma((beta + 2.0) / 2.0) - ln_gamma((beta + 1.0) / 2.0);
+    let b = (2.0 * ln_ratio).exp();
+    let ln_a = 2.0_f64.ln() + (beta + 1.0) / 2.0 * b.ln() - ln_gamma((beta + 1.0) / 2.0);
+    (ln_a.exp(), b)
+}
+
+/// Nearest-neighbor spacings s_i = e_{i+1} - e_i of an unfolded, sorted spectrum.
+pub fn nearest_neighbor_spacings<E: AsEigenvalues + ?Sized>(unfolded: &E) -> Vec<f64> {
+    let unfolded: &[f64] = &unfolded.as_eigenvalues();
+    unfolded.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// Histogram of nearest-neighbor spacings, normalized as a density on [0, s_max].
+///
+/// Spacings are rescaled to unit mean before binning, so the output can be
+/// overlaid directly on [`wigner_surmise`] or exp(-s).
+///
+/// # Arguments
+///
+/// * `unfolded` - Unfolded, sorted levels (see [`unfold`])
+/// * `bins` - Number of histogram bins
+///
+/// # Returns
+///
+/// (bin_centers, densities)
+pub fn spacing_distribution<E: AsEigenvalues + ?Sized>(
+    unfolded: &E,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let unfolded: &[f64] = &unfolded.as_eigenvalues();
+    let spacings = normalized_spacings(unfolded);
+    if spacings.is_empty() || bins == 0 {
+        return (vec![], vec![]);
+    }
+
+    let s_max = spacings.iter().cloned().fold(0.0, f64::max);
+    if s_max <= 0.0 {
+        return (vec![], vec![]);
+    }
+
+    let bin_width = s_max / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for &s in &spacings {
+        let idx = ((s / bin_width).floor() as usize).min(bins - 1);
+        counts[idx] += 1;
+    }
+
+    let n = spacings.len() as f64;
+    let centers = (0..bins).map(|i| (i as f64 + 0.5) * bin_width).collect();
+    let densities = counts.iter().map(|&c| c as f64 / (n * bin_width)).collect();
+    (centers, densities)
+}
+
+/// Result of comparing an empirical spacing distribution against the standard classes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpacingFit {
+    /// Best-matching class (smallest KS distance)
+    pub class: SpacingClass,
+    /// Kolmogorov-Smirnov distance to the best class
+    pub ks_statistic: f64,
+    /// KS distance to every candidate class, in [`SpacingClass::ALL`] order
+    pub candidates: Vec<(SpacingClass, f64)>,
+}
+
+/// Fit the nearest-neighbor spacing distribution to Poisson or a Wigner surmise.
+///
+/// Spacings are rescaled to unit mean, then compared to each class via the
+/// Kolmogorov-Smirnov distance sup |F_emp(s) - F_class(s)|.
+///
+/// # Arguments
+///
+/// * `unfolded` - Unfolded, sorted levels (see [`unfold`])
+///
+/// # Returns
+///
+/// `None` if fewer than two positive spacings are available
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{fit_spacing_distribution, SpacingClass};
+///
+/// // A picket fence is maximally rigid: closest to the strongest repulsion.
+/// let levels: Vec<f64> = (0..200).map(|i| i as f64).collect();
+/// let fit = fit_spacing_distribution(&levels).unwrap();
+/// assert_eq!(fit.class, SpacingClass::Gse);
+/// ```
+pub fn fit_spacing_distribution<E: AsEigenvalues + ?Sized>(unfolded: &E) -> Option<SpacingFit> {
+    let unfolded: &[f64] = &unfolded.as_eigenvalues();
+    let mut spacings = normalized_spacings(unfolded);
+    if spacings.len() < 2 {
+        return None;
+    }
+    spacings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let candidates: Vec<(SpacingClass, f64)> = SpacingClass::ALL
+        .iter()
+        .map(|&class| (class, ks_distance_sorted(&spacings, |s| class.cdf(s))))
+        .collect();
+
+    let &(class, ks_statistic) = candidates
+        .iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("candidate list is non-empty");
+
+    Some(SpacingFit {
+        class,
+        ks_statistic,
+        candidates,
+    })
+}
+
+/// Number variance Sigma^2(L) of an unfolded spectrum.
+///
+/// For each window length L, counts the levels in [x, x + L] for windows whose
+/// starting points x are spread evenly across the spectrum, and returns the
+/// variance of those counts. Poisson levels give Sigma^2(L) = L; GOE/GUE levels
+/// grow only logarithmically (spectral rigidity).
+///
+/// # Arguments
+///
+/// * `unfolded` - Unfolded, sorted levels (see [`unfold`])
+/// * `lengths` - Window lengths L, in units of the mean spacing
+///
+/// # Returns
+///
+/// Sigma^2(L) for each length (NaN where L does not fit inside the spectrum)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::number_variance;
+///
+/// // A picket fence has almost no fluctuation in window counts.
+/// let levels: Vec<f64> = (0..500).map(|i| i as f64).collect();
+/// let sigma2 = number_variance(&levels, &[1.0, 10.0]);
+/// assert!(sigma2.iter().all(|&v| v < 0.3));
+/// ```
+pub fn number_variance<E: AsEigenvalues + ?Sized>(unfolded: &E, lengths: &[f64]) -> Vec<f64> {
+    let unfolded: &[f64] = &unfolded.as_eigenvalues();
+    if unfolded.len() < 2 {
+        return vec![f64::NAN; lengths.len()];
+    }
+    let (lo, hi) = (unfolded[0], unfolded[unfolded.len() - 1]);
+    let windows = unfolded.len();
+
+    lengths
+        .iter()
+        .map(|&len| {
+            let room = hi - lo - len;
+            if len <= 0.0 || room <= 0.0 {
+                return f64::NAN;
+            }
+            let counts: Vec<f64> = (0..windows)
+                .map(|k| {
+                    let start = lo + room * (k as f64 + 0.5) / windows as f64;
+                    let left = unfolded.partition_point(|&e| e < start);
+                    let right = unfolded.partition_point(|&e| e <= start + len);
+                    (right - left) as f64
+                })
+                .collect();
+            let mean = counts.iter().sum::<f64>() / windows as f64;
+            counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / windows as f64
+        })
+        .collect()
+}
+
+/// Number variance of uncorrelated (Poisson) levels: Sigma^2(L) = L.
+pub fn number_variance_poisson(length: f64) -> f64 {
+    length.max(0.0)
+}
+
+/// Number variance of the GUE bulk (sine-kernel limit).
+///
+/// Sigma^2(L) = (1/pi^2)[ln(2 pi L) + gamma_E + 1 - cos(2 pi L) - Ci(2 pi L)]
+/// + L[1 - (2/pi) Si(2 pi L)], which grows like (1/pi^2) ln L.
+pub fn number_variance_gue(length: f64) -> f64 {
+    if length <= 0.0 {
+        return 0.0;
+    }
+    let x = 2.0 * PI * length;
+    let (si, ci) = sine_cosine_integrals(x);
+    (x.ln() + EULER_GAMMA + 1.0 - x.cos() - ci) / (PI * PI) + length * (1.0 - 2.0 * si / PI)
+}
+
+/// Number variance of the GOE bulk.
+///
+/// Sigma^2(L) = (2/pi^2)[ln(2 pi L) + gamma_E + 1 - cos(2 pi L) - Ci(2 pi L)]
+/// + 2L[1 - (2/pi) Si(2 pi L)] + Si(pi L)^2 / pi^2 - Si(pi L) / pi.
+pub fn number_variance_goe(length: f64) -> f64 {
+    if length <= 0.0 {
+        return 0.0;
+    }
+    let x = 2.0 * PI * length;
+    let (si, ci) = sine_cosine_integrals(x);
+    let (si_half, _) = sine_cosine_integrals(PI * length);
+    2.0 * (x.ln() + EULER_GAMMA + 1.0 - x.cos() - ci) / (PI * PI)
+        + 2.0 * length * (1.0 - 2.0 * si / PI)
+        + si_half * si_half / (PI * PI)
+        - si_half / PI
+}
+
+/// Spectral form factor K(tau) = |sum_j exp(i lambda_j tau)|^2 / n of a single spectrum.
+///
+/// For chaotic (GUE-like) unfolded spectra the ensemble-averaged K shows a dip,
+/// a linear ramp K ~ tau / (2 pi), and a plateau at 1 beyond the Heisenberg time
+/// tau_H = 2 pi. A single realization is very noisy; see
+/// [`spectral_form_factor_ensemble`] for averaging.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum (typically unfolded)
+/// * `taus` - Times at which to evaluate K
+///
+/// # Returns
+///
+/// K(tau) for each tau (zeros for an empty spectrum)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spectral_form_factor;
+///
+/// // K(0) = n for any spectrum.
+/// let k = spectral_form_factor(&[0.1, 1.3, 2.2], &[0.0]);
+/// assert!((k[0] - 3.0).abs() < 1e-12);
+/// ```
+pub fn spectral_form_factor<E: AsEigenvalues + ?Sized>(eigenvalues: &E, taus: &[f64]) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() {
+        return vec![0.0; taus.len()];
+    }
+    let n = eigenvalues.len() as f64;
+    taus.iter()
+        .map(|&tau| {
+            let (re, im) = trace_exponential(eigenvalues, tau);
+            (re * re + im * im) / n
+        })
+        .collect()
+}
+
+/// Ensemble-averaged spectral form factor over several realizations.
+///
+/// Computes <|Z(tau)|^2> / n with Z(tau) = sum_j exp(i lambda_j tau), averaging
+/// over realizations (n is the mean spectrum size). With `connected = true`
+/// the disconnected part |<Z(tau)>|^2 / n is subtracted, removing the
+/// density-driven early-time peak so the ramp is visible from small tau.
+///
+/// # Arguments
+///
+/// * `realizations` - One spectrum per sampled matrix
+/// * `taus` - Times at which to evaluate K
+/// * `connected` - Subtract the disconnected part
+///
+/// # Returns
+///
+/// Averaged K(tau) for each tau (zeros if there are no non-empty realizations)
+pub fn spectral_form_factor_ensemble(
+    realizations: &[Vec<f64>],
+    taus: &[f64],
+    connected: bool,
+) -> Vec<f64> {
+    let samples: Vec<&Vec<f64>> = realizations.iter().filter(|r| !r.is_empty()).collect();
+    if samples.is_empty() {
+        return vec![0.0; taus.len()];
+    }
+    let count = samples.len() as f64;
+    let mean_size = samples.iter().map(|r| r.len() as f64).sum::<f64>() / count;
+
+    taus.iter()
+        .map(|&tau| {
+            let (mut sum_re, mut sum_im, mut sum_sq) = (0.0, 0.0, 0.0);
+            for r in &samples {
+                let (re, im) = trace_exponential(r, tau);
+                sum_re += re;
+                sum_im += im;
+                sum_sq += re * re + im * im;
+            }
+            let mut k = sum_sq / count;
+            if connected {
+                let (mean_re, mean_im) = (sum_re / count, sum_im / count);
+                k -= mean_re * mean_re + mean_im * mean_im;
+            }
+            k / mean_size
+        })
+        .collect()
+}
+
+/// Real and imaginary parts of sum_j exp(i lambda_j tau).
+fn trace_exponential(eigenvalues: &[f64], tau: f64) -> (f64, f64) {
+    eigenvalues.iter().fold((0.0, 0.0), |(re, im), &ev| {
+        let phase = ev * tau;
+        (re + phase.cos(), im + phase.sin())
+    })
+}
+
+/// Two-level correlation function R_2(r) of one unfolded spectrum.
+///
+/// See [`two_point_correlation_ensemble`]; the cluster function is Y_2(r) = 1 - R_2(r).
+pub fn two_point_correlation<E: AsEigenvalues + ?Sized>(
+    unfolded: &E,
+    r_max: f64,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let unfolded: &[f64] = &unfolded.as_eigenvalues();
+    pair_correlation_histogram(std::iter::once(unfolded), r_max, bins)
+}
+
+/// Two-level correlation function R_2(r) estimated from one or many unfolded spectra.
+///
+/// R_2(r) dr is the probability of finding a level in [e + r, e + r + dr] given a
+/// level at e, so R_2 -> 1 at large r for unit-density levels and R_2 = 1 for
+/// Poisson. Pair distances are histogrammed on [0, r_max]; only reference levels
+/// with e + r_max inside their spectrum are used, which removes the edge bias.
+///
+/// # Arguments
+///
+/// * `realizations` - Unfolded, sorted spectra
+/// * `r_max` - Largest separation to histogram
+/// * `bins` - Number of histogram bins
+///
+/// # Returns
+///
+/// (bin_centers, R_2 estimates)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{two_point_correlation, two_point_correlation_gue};
+///
+/// let levels: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+/// let (_, r2) = two_point_correlation(&levels, 3.0, 6);
+/// // The picket fence has all its pair mass at integer separations.
+/// assert_eq!(r2[0], 0.0);
+/// assert!(two_point_correlation_gue(0.1) < 0.05);
+/// ```
+pub fn two_point_correlation_ensemble(
+    realizations: &[Vec<f64>],
+    r_max: f64,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    pair_correlation_histogram(realizations.iter().map(Vec::as_slice), r_max, bins)
+}
+
+fn pair_correlation_histogram<'a, I>(
+    realizations: I,
+    r_max: f64,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>)
+where
+    I: Iterator<Item = &'a [f64]>,
+{
+    if bins == 0 || r_max <= 0.0 {
+        return (vec![], vec![]);
+    }
+
+    let bin_width = r_max / bins as f64;
+    let mut counts = vec![0usize; bins];
+    let mut references = 0usize;
+
+    for levels in realizations {
+        let Some(&last) = levels.last() else {
+            continue;
+        };
+        for (i, &e) in levels.iter().enumerate() {
+            if e + r_max > last {
+                break;
+            }
+            references += 1;
+            for &other in &levels[i + 1..] {
+                let r = other - e;
+                if r >= r_max {
+                    break;
+                }
+                if r >= 0.0 {
+                    counts[((r / bin_width) as usize).min(bins - 1)] += 1;
+                }
+            }
+        }
+    }
+
+    let centers: Vec<f64> = (0..bins).map(|i| (i as f64 + 0.5) * bin_width).collect();
+    if references == 0 {
+        return (centers, vec![0.0; bins]);
+    }
+    let r2 = counts
+        .iter()
+        .map(|&c| c as f64 / (references as f64 * bin_width))
+        .collect();
+    (centers, r2)
+}
+
+/// GUE two-level correlation from the sine kernel: R_2(r) = 1 - (sin(pi r) / (pi r))^2.
+pub fn two_point_correlation_gue(r: f64) -> f64 {
+    let s = sinc_pi(r);
+    1.0 - s * s
+}
+
+/// GOE two-level correlation: R_2(r) = 1 - s(r)^2 - s'(r) int_r^inf s(t) dt, s(r) = sin(pi r) / (pi r).
+pub fn two_point_correlation_goe(r: f64) -> f64 {
+    let r = r.abs();
+    let s = sinc_pi(r);
+    let ds = if r < 1e-8 {
+        0.0
+    } else {
+        ((PI * r).cos() - s) / r
+    };
+    let (si, _) = sine_cosine_integrals(PI * r);
+    let tail = 0.5 - si / PI;
+    1.0 - s * s - ds * tail
+}
+
+/// sin(pi r) / (pi r), equal to 1 at r = 0.
+fn sinc_pi(r: f64) -> f64 {
+    let x = PI * r;
+    if x.abs() < 1e-8 {
+        1.0
+    } else {
+        x.sin() / x
+    }
+}
+
+/// Positive nearest-neighbor spacings rescaled to unit mean.
+fn normalized_spacings(unfolded: &[f64]) -> Vec<f64> {
+    let spacings: Vec<f64> = nearest_neighbor_spacings(unfolded)
+        .into_iter()
+        .filter(|&s| s > 0.0)
+        .collect();
+    if spacings.is_empty() {
+        return spacings;
+    }
+    let mean = spacings.iter().sum::<f64>() / spacings.len() as f64;
+    spacings.into_iter().map(|s| s / mean).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_spacing(levels: &[f64]) -> f64 {
+        (levels[levels.len() - 1] - levels[0]) / (levels.len() - 1) as f64
+    }
+
+    #[test]
+    fn test_unfold_semicircle_quantiles() {
+        // Exact semicircle quantiles unfold to (almost) equally spaced levels.
+        let n = 500;
+        let eigenvalues: Vec<f64> = (0..n)
+            .map(|i| {
+                let target = (i as f64 + 0.5) / n as f64;
+                let (mut lo, mut hi) = (-2.0, 2.0);
+                for _ in 0..60 {
+                    let mid = 0.5 * (lo + hi);
+                    if wigner_semicircle_cdf(mid, 1.0) < target {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                0.5 * (lo + hi)
+            })
+            .collect();
+        let unfolded = unfold(&eigenvalues, UnfoldMethod::Semicircle { sigma: 1.0 });
+        for (i, u) in unfolded.iter().enumerate() {
+            assert!((u - (i as f64 + 0.5)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_unfold_polynomial_and_spline() {
+        // Monotone, smoothly varying density.
+        let eigenvalues: Vec<f64> = (0..300)
+            .map(|i| {
+                let u = i as f64 / 300.0;
+                u + 0.1 * (2.0 * std::f64::consts::PI * u).sin()
+            })
+            .collect();
+        for method in [
+            UnfoldMethod::Polynomial { degree: 8 },
+            UnfoldMethod::Spline { knots: 6 },
+        ] {
+            let unfolded = unfold(&eigenvalues, method);
+            assert_eq!(unfolded.len(), 300);
+            assert!((mean_spacing(&unfolded) - 1.0).abs() < 0.05, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_unfold_sorts_input() {
+        let unfolded = unfold(
+            &[3.0, 1.0, 2.0, 0.0],
+            UnfoldMethod::Polynomial { degree: 1 },
+        );
+        assert!(unfolded.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_surmise_normalized_unit_mean() {
+        for &beta in &[1.0, 2.0, 4.0] {
+            let ds = 1e-3;
+            let (mut mass, mut mean) = (0.0, 0.0);
+            for i in 0..8000 {
+                let s = (i as f64 + 0.5) * ds;
+                let p = wigner_surmise(s, beta) * ds;
+                mass += p;
+                mean += s * p;
+            }
+            assert!((mass - 1.0).abs() < 1e-6, "beta {} mass {}", beta, mass);
+            assert!((mean - 1.0).abs() < 1e-6, "beta {} mean {}", beta, mean);
+            assert!((wigner_surmise_cdf(8.0, beta) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_gue_surmise_closed_form() {
+        let s: f64 = 1.3;
+        let expected = 32.0 / (PI * PI) * s * s * (-4.0 * s * s / PI).exp();
+        assert!((wigner_surmise(s, 2.0) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_poisson_levels() {
+        // Exponential spacings from deterministic quantiles look Poisson.
+        let n = 400;
+        let mut level = 0.0;
+        let mut levels = vec![level];
+        for i in 0..n {
+            level += -(1.0 - (i as f64 + 0.5) / n as f64).ln();
+            levels.push(level);
+        }
+        let fit = fit_spacing_distribution(&levels).unwrap();
+        assert_eq!(fit.class, SpacingClass::Poisson);
+        assert!(fit.ks_statistic < 0.05);
+        assert_eq!(fit.candidates.len(), 4);
+    }
+
+    #[test]
+    fn test_spacing_distribution_normalized() {
+        let levels: Vec<f64> = (0..100).map(|i| i as f64 + 0.3 * (i % 3) as f64).collect();
+        let (centers, densities) = spacing_distribution(&levels, 10);
+        let width = centers[1] - centers[0];
+        let mass: f64 = densities.iter().map(|d| d * width).sum();
+        assert!((mass - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_number_variance_theory_limits() {
+        // Small L: Sigma^2 ~ L for every class.
+        assert!((number_variance_gue(1e-3) - 1e-3).abs() < 1e-5);
+        assert!((number_variance_goe(1e-3) - 1e-3).abs() < 1e-5);
+        // Large L: logarithmic growth with the known constants.
+        let l: f64 = 200.0;
+        let gue = (2.0 * PI * l).ln() / (PI * PI) + (EULER_GAMMA + 1.0) / (PI * PI);
+        let goe = 2.0 / (PI * PI) * ((2.0 * PI * l).ln() + EULER_GAMMA + 1.0 - PI * PI / 8.0);
+        assert!((number_variance_gue(l + 0.25) - gue).abs() < 0.01);
+        assert!((number_variance_goe(l + 0.25) - goe).abs() < 0.02);
+        assert!(number_variance_goe(5.0) > number_variance_gue(5.0));
+    }
+
+    #[test]
+    fn test_number_variance_poisson_levels() {
+        use rand::SeedableRng;
+        use rand_distr::{Distribution, Exp1};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+        let mut level = 0.0;
+        let levels: Vec<f64> = (0..20000)
+            .map(|_| {
+                let step: f64 = Exp1.sample(&mut rng);
+                level += step;
+                level
+            })
+            .collect();
+        let sigma2 = number_variance(&levels, &[2.0]);
+        assert!(
+            (sigma2[0] - number_variance_poisson(2.0)).abs() < 0.6,
+            "{:?}",
+            sigma2
+        );
+    }
+
+    #[test]
+    fn test_number_variance_window_too_long() {
+        assert!(number_variance(&[0.0, 1.0, 2.0], &[5.0])[0].is_nan());
+    }
+
+    #[test]
+    fn test_spectral_form_factor_plateau() {
+        // Generic incommensurate levels: at late times K fluctuates around 1.
+        let levels: Vec<f64> = (0..200).map(|i| (i as f64).sqrt() * 7.3).collect();
+        let taus: Vec<f64> = (0..400).map(|k| 50.0 + k as f64 * 0.37).collect();
+        let k = spectral_form_factor(&levels, &taus);
+        let mean = k.iter().sum::<f64>() / k.len() as f64;
+        assert!((mean - 1.0).abs() < 0.25, "plateau mean {}", mean);
+    }
+
+    #[test]
+    fn test_spectral_form_factor_ensemble_connected() {
+        // Identical realizations have no fluctuations: the connected SFF vanishes.
+        let r = vec![0.3, 1.1, 2.9, 3.4];
+        let taus = [0.5, 1.0, 2.0];
+        let connected = spectral_form_factor_ensemble(&[r.clone(), r.clone()], &taus, true);
+        assert!(connected.iter().all(|&k| k.abs() < 1e-12));
+        let full = spectral_form_factor_ensemble(&[r.clone(), r.clone()], &taus, false);
+        assert_eq!(full, spectral_form_factor(&r, &taus));
+    }
+
+    #[test]
+    fn test_two_point_correlation_poisson() {
+        use rand::SeedableRng;
+        use rand_distr::{Distribution, Exp1};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(11);
+        let realizations: Vec<Vec<f64>> = (0..5)
+            .map(|_| {
+                let mut level = 0.0;
+                (0..2000)
+                    .map(|_| {
+                        let step: f64 = Exp1.sample(&mut rng);
+                        level += step;
+                        level
+                    })
+                    .collect()
+            })
+            .collect();
+        let (_, r2) = two_point_correlation_ensemble(&realizations, 4.0, 8);
+        for v in r2 {
+            assert!((v - 1.0).abs() < 0.1, "R2 {}", v);
+        }
+    }
+
+    #[test]
+    fn test_two_point_theory() {
+        assert!(two_point_correlation_gue(0.0).abs() < 1e-12);
+        assert!(two_point_correlation_goe(0.0).abs() < 1e-8);
+        // Linear repulsion for GOE: R_2 ~ (pi^2 / 6) r.
+        let r = 1e-3;
+        assert!((two_point_correlation_goe(r) / r - PI * PI / 6.0).abs() < 1e-2);
+        assert!((two_point_correlation_gue(30.5) - 1.0).abs() < 1e-3);
+        assert!((two_point_correlation_goe(30.5) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_spacing_ratio_distributions() {
+        // Surmise means (Atas et al. 2013): 4 - 2 sqrt 3 for GOE, 2 sqrt 3 / pi - 1/2 for GUE.
+        let expected = [
+            (SpacingClass::Poisson, MEAN_SPACING_RATIO_POISSON),
+            (SpacingClass::Goe, 4.0 - 2.0 * 3.0_f64.sqrt()),
+            (SpacingClass::Gue, 2.0 * 3.0_f64.sqrt() / PI - 0.5),
+            (SpacingClass::Gse, 32.0 / 15.0 * 3.0_f64.sqrt() / PI - 0.5),
+        ];
+        for (class, mean_expected) in expected {
+            let dr = 1e-4;
+            let (mut mass, mut mean) = (0.0, 0.0);
+            for i in 0..10000 {
+                let r = (i as f64 + 0.5) * dr;
+                let p = spacing_ratio_density(r, class) * dr;
+                mass += p;
+                mean += r * p;
+            }
+            assert!((mass - 1.0).abs() < 1e-6, "{:?} mass {}", class, mass);
+            assert!(
+                (mean - mean_expected).abs() < 1e-6,
+                "{:?} mean {}",
+                class,
+                mean
+            );
+            assert!((spacing_ratio_cdf(0.999_999, class) - 1.0).abs() < 1e-5);
+        }
+
+        // Interior values of the CDF, integrated in closed form.
+        let sqrt3 = 3.0_f64.sqrt();
+        let at_half = [
+            (SpacingClass::Poisson, 2.0 / 3.0),
+            (SpacingClass::Goe, 1.0 - 10.0 / (7.0 * 7.0_f64.sqrt())),
+            (
+                SpacingClass::Gue,
+                (6.0 * (2.0 / sqrt3).atan() - PI - 180.0 * sqrt3 / 343.0) / PI,
+            ),
+        ];
+        for (class, cdf_expected) in at_half {
+            let cdf = spacing_ratio_cdf(0.5, class);
+            assert!(
+                (cdf - cdf_expected).abs() < 1e-10,
+                "{:?} cdf {}",
+                class,
+                cdf
+            );
+        }
+    }
+
+    #[test]
+    fn test_unfold_too_few_points() {
+        assert!(unfold(&[1.0, 2.0], UnfoldMethod::Polynomial { degree: 5 }).is_empty());
+        assert!(unfold(&[], UnfoldMethod::Semicircle { sigma: 1.0 }).is_empty());
+    }
+}
@@ -14,7 +14,7 @@
 //! |--------------|-------------|---------|
 //! | [`marchenko_pastur_density`] | Wishart (X^T X) | Bounded support |
 //! | [`wigner_semicircle_density`] | Symmetric random | Semicircle |
-//! | Tracy--Widom (edge; not implemented here) | Largest eigenvalue | Skewed |
+//! | [`tracy_widom_cdf`] | Largest eigenvalue (edge) | Skewed |
 //!
 //! ## Quick Start
 //!
@@ -37,7 +37,7 @@
 //! ## Why RMT for ML?
 //!
 //! - **Covariance matrices**: Sample covariance eigenvalues follow Marchenko-Pastur
-//! - **Neural networks**: Weight matrix spectra reveal training dynamics
+//! - **Neural networks**: Weight matrix spectra reveal training dynamics ([`analyze_weight_matrix`])
 //! - **PCA**: Distinguish signal from noise eigenvalues
 //! - **Regularization**: Set shrinkage based on spectral distribution
 //!
@@ -68,123 +68,376 @@
 //! 1. **Finite size effects**: MP/semicircle are asymptotic. Small n deviates.
 //! 2. **Not centered**: MP assumes zero-mean data. Center your features.
 //! 3. **Correlated features**: MP assumes independence. Correlated data has different spectrum.
-//! 4. **Ratio out of range**: MP needs p/n in (0, infinity). Tracy-Widom for edge.
+//! 4. **Ratio out of range**: MP needs p/n in (0, infinity). The free functions return
+//!    0 or NaN for bad parameters; [`MarchenkoPastur::new`] reports an [`Error`].
 //! 5. **Numerical eigendecomposition**: For large matrices, use iterative methods.
 //!
+//! ## WebAssembly
+//!
+//! With `default-features = false, features = ["std"]` the crate builds for
+//! `wasm32-unknown-unknown`: the unseeded samplers that draw from
+//! `rand::rng()` are gated behind the `thread-rng` feature, every sampler has
+//! a `*_with` variant taking any [`rand::Rng`] (e.g. a seeded `SmallRng`), and
+//! nothing spawns threads unless the `parallel` feature is enabled.
+//!
+//! ## `no_std`
+//!
+//! Without the `std` feature the crate is `#![no_std]` and exposes only the
+//! closed-form Marchenko-Pastur and semicircle functions
+//! ([`marchenko_pastur_density`], [`marchenko_pastur_support`],
+//! [`marchenko_pastur_quantile`], ...), computed with `libm`. Sampling,
+//! linear algebra, and the statistical tests all need `std`.
+//!
 //! ## References
 //!
 //! - Marchenko & Pastur (1967). "Distribution of eigenvalues for some sets of random matrices"
 //! - Wigner (1955). "Characteristic vectors of bordered matrices with infinite dimensions"
 //! - Johnstone (2001). "On the distribution of the largest eigenvalue in PCA"
 
-use std::f64::consts::PI;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
 use ndarray::Array2;
+#[cfg(feature = "std")]
 use rand::Rng;
-use rand_distr::{Distribution, Normal};
+#[cfg(feature = "std")]
+use rand_distr::{ChiSquared, Distribution, Normal};
 
-/// Marchenko-Pastur density at point lambda.
-///
-/// For the eigenvalues of (1/n) X^T X where X is n x p with i.i.d. N(0, sigma^2) entries.
-///
-/// # Arguments
-///
-/// * `lambda` - Eigenvalue to evaluate density at
-/// * `ratio` - gamma = p/n ratio (any positive value; values > 1 are folded via min(gamma, 1/gamma))
-/// * `sigma_sq` - Variance of matrix entries (default 1.0)
-///
-/// # Returns
-///
-/// Density rho(lambda), or 0 if outside support [lambda_-, lambda_+]
-///
-/// # Example
-///
-/// ```rust
-/// use rmt::marchenko_pastur_density;
-///
-/// let gamma = 0.5;  // p/n = 0.5
-/// let density = marchenko_pastur_density(1.5, gamma, 1.0);
-/// assert!(density > 0.0);
-/// ```
-pub fn marchenko_pastur_density(lambda: f64, ratio: f64, sigma_sq: f64) -> f64 {
-    if ratio <= 0.0 || lambda <= 0.0 {
-        return 0.0;
-    }
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod chunked;
+#[cfg(feature = "std")]
+mod covariance;
+#[cfg(feature = "std")]
+mod denoising;
+#[cfg(feature = "std")]
+mod density;
+#[cfg(feature = "std")]
+mod distances;
+#[cfg(feature = "std")]
+mod distributions;
+#[cfg(feature = "std")]
+mod dpp;
+#[cfg(feature = "std")]
+mod dynamics;
+#[cfg(feature = "std")]
+mod eigenvectors;
+#[cfg(feature = "std")]
+mod ensembles;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
+mod factors;
+#[cfg(feature = "std")]
+mod finite;
+#[cfg(feature = "std")]
+mod free;
+#[cfg(feature = "std")]
+mod gof;
+#[cfg(feature = "std")]
+mod graphs;
+#[cfg(feature = "std")]
+mod hard_edge;
+#[cfg(feature = "nalgebra")]
+mod interop;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod kernels;
+mod laws;
+#[cfg(feature = "std")]
+mod levels;
+#[cfg(feature = "std")]
+mod linalg;
+mod math;
+#[cfg(feature = "std")]
+mod metrics;
+#[cfg(feature = "std")]
+mod multivariate;
+#[cfg(feature = "std")]
+mod nonhermitian;
+#[cfg(feature = "std")]
+mod operators;
+#[cfg(feature = "plot")]
+mod plot;
+#[cfg(feature = "std")]
+mod portfolio;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod resampling;
+#[cfg(feature = "std")]
+mod simulation;
+#[cfg(feature = "std")]
+mod special;
+#[cfg(feature = "std")]
+mod spectrum;
+#[cfg(feature = "std")]
+mod spikes;
+#[cfg(feature = "std")]
+mod streaming;
+#[cfg(feature = "std")]
+mod tails;
+#[cfg(feature = "std")]
+mod timeseries;
+#[cfg(feature = "std")]
+mod tracy_widom;
+#[cfg(feature = "std")]
+mod tracy_widom_tables;
+#[cfg(feature = "std")]
+mod transforms;
+#[cfg(feature = "std")]
+mod weights;
 
-    let gamma = ratio.min(1.0 / ratio); // Handle both p/n < 1 and p/n > 1
-    let lambda_plus = sigma_sq * (1.0 + gamma.sqrt()).powi(2);
-    let lambda_minus = sigma_sq * (1.0 - gamma.sqrt()).powi(2);
+#[cfg(feature = "std")]
+pub use finite::{
+    finite_free_convolution, gaussian_ensemble_density, wishart_eigenvalue_density,
+    ExpectedCharacteristicPolynomial,
+};
+#[cfg(feature = "std")]
+pub use free::{
+    free_compression, free_compression_cumulants, free_cumulants_to_moments,
+    free_semicircle_convolution, hutchinson_spectral_moments, moments_to_free_cumulants,
+    r_transform, spectral_moments, spectral_moments_from_matrix, MomentEstimates,
+};
+#[cfg(feature = "std")]
+pub use levels::{
+    fit_spacing_distribution, nearest_neighbor_spacings, number_variance, number_variance_goe,
+    number_variance_gue, number_variance_poisson, spacing_distribution, spacing_ratio_cdf,
+    spacing_ratio_density, spectral_form_factor, spectral_form_factor_ensemble,
+    two_point_correlation, two_point_correlation_ensemble, two_point_correlation_goe,
+    two_point_correlation_gue, unfold, wigner_surmise, wigner_surmise_cdf, SpacingClass,
+    SpacingFit, UnfoldMethod, MEAN_SPACING_RATIO_GOE, MEAN_SPACING_RATIO_GSE,
+    MEAN_SPACING_RATIO_GUE, MEAN_SPACING_RATIO_POISSON,
+};
+#[cfg(feature = "std")]
+pub use num_complex::Complex64;
+#[cfg(feature = "std")]
+pub use transforms::{
+    hilbert_transform, hilbert_transform_at, resolvent_trace, resolvent_trace_from_eigenvalues,
+    resolvent_trace_grid, resolvent_trace_grid_from_eigenvalues,
+};
+#[cfg(feature = "parallel")]
+pub use transforms::{par_resolvent_trace_grid, par_resolvent_trace_grid_from_eigenvalues};
 
-    if lambda < lambda_minus || lambda > lambda_plus {
-        return 0.0;
-    }
-
-    let sqrt_term = ((lambda_plus - lambda) * (lambda - lambda_minus)).sqrt();
-    sqrt_term / (2.0 * PI * sigma_sq * gamma * lambda)
-}
-
-/// Marchenko-Pastur support bounds [lambda_-, lambda_+].
-///
-/// # Arguments
-///
-/// * `ratio` - gamma = p/n ratio (any positive value; values > 1 are folded via min(gamma, 1/gamma))
-/// * `sigma_sq` - Variance of matrix entries
-///
-/// # Returns
-///
-/// (lambda_minus, lambda_plus)
-pub fn marchenko_pastur_support(ratio: f64, sigma_sq: f64) -> (f64, f64) {
-    let gamma = ratio.min(1.0 / ratio);
-    let lambda_plus = sigma_sq * (1.0 + gamma.sqrt()).powi(2);
-    let lambda_minus = sigma_sq * (1.0 - gamma.sqrt()).powi(2);
-    (lambda_minus, lambda_plus)
-}
+#[cfg(feature = "std")]
+pub use batch::{
+    marchenko_pastur_density_batch, tracy_widom_cdf_batch, tracy_widom_pdf_batch,
+    wigner_semicircle_density_batch,
+};
+#[cfg(feature = "parallel")]
+pub use batch::{par_tracy_widom_cdf_batch, par_tracy_widom_pdf_batch};
+#[cfg(feature = "mmap")]
+pub use chunked::MappedEigenvalues;
+#[cfg(feature = "std")]
+pub use chunked::{
+    accumulate_chunks, read_eigenvalues_binary_chunks, read_eigenvalues_csv_chunks,
+    write_eigenvalues_binary, BinaryChunks, CsvChunks,
+};
+#[cfg(feature = "std")]
+pub use covariance::{
+    clip_eigenvalues, clipped_covariance, ledoit_wolf, nonlinear_shrinkage,
+    nonlinear_shrinkage_eigenvalues, rie_covariance, rie_eigenvalues, sample_covariance,
+    CleanedCovariance, CleaningStrategy, CovarianceCleaner, LinearShrinkage,
+};
+#[cfg(feature = "std")]
+pub use denoising::{
+    hard_threshold_denoise, median_hard_threshold_coefficient, optimal_hard_threshold,
+    optimal_hard_threshold_coefficient, optimal_shrinker, shrink_singular_values,
+    shrinkage_denoise, DenoisedMatrix, ShrinkageLoss,
+};
+#[cfg(feature = "std")]
+pub use density::{
+    empirical_spectral_cdf, empirical_spectral_density_auto, histogram_bins, kde_bandwidth,
+    kernel_spectral_density, log_log_spectral_density, log_spectral_density, Bandwidth, BinRule,
+    EmpiricalCdf,
+};
+#[cfg(feature = "std")]
+pub use distances::{
+    spectral_divergences, spectral_divergences_mp, spectral_divergences_semicircle,
+    spectral_wasserstein, spectral_wasserstein_to, SpectralDivergences,
+};
+#[cfg(feature = "std")]
+pub use distributions::{
+    EdelmanConditionNumber, KestenMcKay, MarchenkoPastur, TracyWidom, WignerSemicircle,
+};
+#[cfg(feature = "std")]
+pub use dpp::DeterminantalProcess;
+#[cfg(feature = "std")]
+pub use dynamics::{
+    track_eigenpairs, track_eigenvalues, DysonBrownianMotion, DysonTrajectory, EigenvalueTracks,
+};
+#[cfg(feature = "std")]
+pub use eigenvectors::{
+    eigenvector_overlaps, haar_max_entry_ratio, inverse_participation_ratios, max_entry_ratios,
+    participation_entropies, participation_ratios, porter_thomas_density,
+};
+#[cfg(feature = "std")]
+pub use ensembles::{Ensemble, Goe, Gue, Wishart};
+#[cfg(feature = "std")]
+pub use error::{check_finite, retain_finite, Error};
+#[cfg(feature = "std")]
+pub use factors::{
+    factor_count_aic, factor_count_er, factor_count_gr, factor_count_kn, factor_count_mdl,
+    factor_count_onatski,
+};
+#[cfg(feature = "std")]
+pub use gof::{
+    ad_test, ad_test_mp, ad_test_semicircle, cvm_test, cvm_test_mp, cvm_test_semicircle, ks_test,
+    ks_test_mp, ks_test_semicircle, GoodnessOfFit,
+};
+#[cfg(feature = "std")]
+pub use graphs::{
+    alon_boppana_bound, degrees, expander_monte_carlo, expander_statistics, graph_spectrum,
+    kesten_mckay_test, laplacian, non_backtracking_outliers, non_backtracking_spectrum,
+    normalized_laplacian, ErdosRenyi, ExpanderMonteCarlo, ExpanderStatistics, GraphMatrix,
+    KestenMcKayTest, RandomRegular, StochasticBlockModel,
+};
+#[cfg(feature = "std")]
+pub use hard_edge::{
+    hard_edge_cdf, smallest_eigenvalue_test, wishart_smallest_eigenvalue_sf, SmallestEigenvalueTest,
+};
+#[cfg(feature = "nalgebra")]
+pub use interop::{
+    from_dmatrix, sample_ensemble_dmatrix, sample_goe_dmatrix_with, sample_wishart_dmatrix_with,
+    to_dmatrix,
+};
+#[cfg(all(feature = "nalgebra", feature = "thread-rng"))]
+pub use interop::{sample_goe_dmatrix, sample_wishart_dmatrix};
+#[cfg(feature = "std")]
+pub use io::{
+    read_density_csv, read_eigenvalues_csv, read_matrix_csv, write_density_csv,
+    write_eigenvalues_csv, write_matrix_csv,
+};
+#[cfg(feature = "json")]
+pub use io::{
+    read_density_json, read_eigenvalues_json, write_density_json, write_eigenvalues_json,
+};
+#[cfg(feature = "arrow")]
+pub use io::{write_spectral_statistics_parquet, EigenvalueParquetWriter};
+#[cfg(feature = "std")]
+pub use kernels::{
+    airy_correlation, airy_gap_probabilities, airy_kernel, bessel_correlation, bessel_kernel,
+    fredholm_determinant, gap_probabilities, hard_edge_gap_probability, sine_correlation,
+    sine_gap_probabilities, sine_kernel,
+};
+pub use laws::{
+    marchenko_pastur_cdf, marchenko_pastur_density, marchenko_pastur_quantile,
+    marchenko_pastur_support, wigner_semicircle_cdf, wigner_semicircle_density,
+};
+#[cfg(feature = "std")]
+pub use linalg::{general_eigenvalues, singular_values, symmetric_eigen, symmetric_eigenvalues};
+#[cfg(feature = "std")]
+pub use metrics::{
+    condition_number, effective_rank, frobenius_norm, log_frobenius_norm, log_spectral_norm,
+    participation_ratio_rank, renyi_effective_rank, renyi_spectral_entropy, spectral_entropy,
+    spectral_norm, stable_rank,
+};
+#[cfg(feature = "std")]
+pub use multivariate::{
+    canonical_correlation_test, canonical_correlations, identity_covariance_test, manova_roots,
+    roy_largest_root_sf, roy_largest_root_test, sphericity_test, wachter_support,
+    CanonicalCorrelationTest, CovarianceStatistic, CovarianceTest, RoyLargestRootTest,
+};
+#[cfg(feature = "std")]
+pub use nonhermitian::{
+    circular_law_test, complex_kernel_density, complex_spectral_density, pseudospectrum,
+    radial_spectral_density, single_ring_radii, CircularLawTest, ComplexSpectralDensity,
+    Pseudospectrum, PseudospectrumMethod, SingleRing,
+};
+#[cfg(feature = "std")]
+pub use operators::{StochasticAiry, StochasticBessel};
+#[cfg(feature = "plot")]
+pub use plot::{plot_density_overlay, plot_qq, plot_spacing_ratios};
+#[cfg(feature = "std")]
+pub use portfolio::{minimum_variance_portfolio, portfolio_variance, MinimumVariancePortfolio};
+#[cfg(feature = "std")]
+pub use resampling::{bootstrap_eigenvalues, bootstrap_rows, ConfidenceInterval};
+#[cfg(feature = "std")]
+pub use simulation::{extreme_eigenvalue_stats, ExtremeEigenvalueStats, SampleSummary};
+#[cfg(feature = "std")]
+pub use special::{airy_ai, airy_ai_prime};
+#[cfg(feature = "std")]
+pub use spectrum::{AsEigenvalues, Spectrum};
+#[cfg(feature = "std")]
+pub use spikes::{
+    bbp_threshold, bbp_threshold_wigner, corrected_spike_projection, count_spikes,
+    count_spikes_with, critical_sample_size, debias_spike, debias_spikes, estimate_aspect_ratio,
+    estimate_rank, estimate_sigma_sq, spike_eigenvector_overlap, spike_location,
+    spike_location_wigner, spike_overlap, spike_overlap_wigner, AspectRatioFit, SpikeLocation,
+    SpikeThreshold,
+};
+#[cfg(feature = "std")]
+pub use streaming::{
+    streaming_mean_spacing_ratio, streaming_moments, streaming_spacing_ratios,
+    streaming_spectral_density, SpacingRatios, StreamingSpectrum,
+};
+#[cfg(feature = "std")]
+pub use tails::{fit_power_law, hill_estimator, PowerLawFit};
+#[cfg(feature = "std")]
+pub use timeseries::{
+    correlation_eigenvalues, lagged_cross_correlations, surrogate_correlation_spectra,
+    surrogate_series, AutocorrelatedMarchenkoPastur, CrossCorrelationSingularValues,
+    SurrogateMethod, SurrogateNull,
+};
+#[cfg(feature = "std")]
+pub use tracy_widom::{
+    largest_eigenvalue_test, tracy_widom_cdf, tracy_widom_cdf_fredholm, tracy_widom_sf,
+    tw_centering_scaling, LargestEigenvalueTest, TracyWidomScaling,
+};
+#[cfg(feature = "parallel")]
+pub use weights::par_analyze_weight_matrices;
+#[cfg(feature = "std")]
+pub use weights::{
+    analyze_weight_matrices, analyze_weight_matrix, classify_esd, EsdPhase, EsdPhaseReport,
+    WeightMatrixReport,
+};
 
-/// Wigner semicircle density at point lambda.
+/// Sample a Wishart matrix W = X^T X where X is n x p Gaussian, using the
+/// provided RNG for reproducibility.
 ///
-/// For eigenvalues of symmetric matrix with i.i.d. entries of variance sigma^2.
+/// For n >= p, W is drawn through the Bartlett decomposition W = A A^T, with
+/// A lower triangular, A_ii^2 ~ chi^2(n - i) (i = 0..p) and standard normal
+/// entries below the diagonal. That takes O(p^2) random draws and one p x p
+/// product instead of forming the n x p data matrix, so the cost does not
+/// grow with n. For n < p (singular W), X is sampled directly.
 ///
 /// # Arguments
 ///
-/// * `lambda` - Eigenvalue to evaluate density at
-/// * `sigma` - Standard deviation (radius = 2*sigma)
+/// * `rng` - Random number generator
+/// * `n` - Number of samples (rows of X)
+/// * `p` - Number of features (columns of X)
 ///
 /// # Returns
 ///
-/// Density rho(lambda), or 0 if |lambda| > 2*sigma
+/// p x p Wishart matrix
 ///
 /// # Example
 ///
 /// ```rust
-/// use rmt::wigner_semicircle_density;
+/// use rand::SeedableRng;
+/// use rmt::sample_wishart_with;
 ///
-/// // At lambda=0 with sigma=1, R=2, density = 2/(pi*R^2) * R = 1/pi
-/// let density = wigner_semicircle_density(0.0, 1.0);
-/// assert!(density > 0.3);  // Should be ~1/pi ~ 0.318
+/// // A million observations cost no more than p of them.
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let w = sample_wishart_with(&mut rng, 1_000_000, 50) / 1e6;
+/// assert!((w[[3, 3]] - 1.0).abs() < 0.01 && w[[3, 4]].abs() < 0.01);
 /// ```
-pub fn wigner_semicircle_density(lambda: f64, sigma: f64) -> f64 {
-    let r = 2.0 * sigma;
-    if lambda.abs() > r {
-        return 0.0;
-    }
-
-    (2.0 / (PI * r * r)) * (r * r - lambda * lambda).sqrt()
-}
-
-/// Sample a Wishart matrix W = X^T X where X is n x p Gaussian, using the
-/// provided RNG for reproducibility.
-///
-/// # Arguments
-///
-/// * `rng` - Random number generator
-/// * `n` - Number of samples (rows of X)
-/// * `p` - Number of features (columns of X)
-///
-/// # Returns
-///
-/// p x p Wishart matrix
+#[cfg(feature = "std")]
 pub fn sample_wishart_with<R: Rng>(rng: &mut R, n: usize, p: usize) -> Array2<f64> {
     let normal = Normal::new(0.0, 1.0).expect("Normal(0, 1) should be valid");
+    if n >= p {
+        let mut a = Array2::zeros((p, p));
+        for i in 0..p {
+            let chi_sq = ChiSquared::new((n - i) as f64).expect("n - i >= 1 degrees of freedom");
+            a[[i, i]] = chi_sq.sample(rng).sqrt();
+            for j in 0..i {
+                a[[i, j]] = normal.sample(rng);
+            }
+        }
+        return a.dot(&a.t());
+    }
     let mut x = Array2::zeros((n, p));
     for i in 0..n {
         for j in 0..p {
@@ -207,6 +460,10 @@ pub fn sample_wishart_with<R: Rng>(rng: &mut R, n: usize, p: usize) -> Array2<f6
 /// # Returns
 ///
 /// p x p Wishart matrix
+///
+/// Needs the default `thread-rng` feature; use [`sample_wishart_with`] with a
+/// seeded RNG where there is no OS entropy source (e.g. wasm32-unknown-unknown).
+#[cfg(feature = "thread-rng")]
 pub fn sample_wishart(n: usize, p: usize) -> Array2<f64> {
     sample_wishart_with(&mut rand::rng(), n, p)
 }
@@ -224,6 +481,7 @@ pub fn sample_wishart(n: usize, p: usize) -> Array2<f64> {
 /// # Returns
 ///
 /// n x n symmetric random matrix
+#[cfg(feature = "std")]
 pub fn sample_goe_with<R: Rng>(rng: &mut R, n: usize) -> Array2<f64> {
     let normal = Normal::new(0.0, 1.0).expect("Normal(0, 1) should be valid");
     let mut m = Array2::zeros((n, n));
@@ -253,6 +511,9 @@ pub fn sample_goe_with<R: Rng>(rng: &mut R, n: usize) -> Array2<f64> {
 /// # Returns
 ///
 /// n x n symmetric random matrix
+///
+/// Needs the default `thread-rng` feature; see [`sample_goe_with`] otherwise.
+#[cfg(feature = "thread-rng")]
 pub fn sample_goe(n: usize) -> Array2<f64> {
     sample_goe_with(&mut rand::rng(), n)
 }
@@ -269,7 +530,9 @@ pub fn sample_goe(n: usize) -> Array2<f64> {
 /// # Returns
 ///
 /// Vector of spacing ratios
-pub fn level_spacing_ratios(eigenvalues: &[f64]) -> Vec<f64> {
+#[cfg(feature = "std")]
+pub fn level_spacing_ratios<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
     if eigenvalues.len() < 3 {
         return vec![];
     }
@@ -289,11 +552,30 @@ pub fn level_spacing_ratios(eigenvalues: &[f64]) -> Vec<f64> {
     ratios
 }
 
+/// [`level_spacing_ratios`] after checking that every eigenvalue is finite.
+///
+/// # Errors
+///
+/// [`Error::NonFinite`] for the first NaN or infinite eigenvalue.
+#[cfg(feature = "std")]
+pub fn checked_level_spacing_ratios<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+) -> Result<Vec<f64>, Error> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    check_finite(eigenvalues)?;
+    Ok(level_spacing_ratios(eigenvalues))
+}
+
 /// Mean level spacing ratio.
 ///
 /// GOE (correlated): ~0.5307
 /// Poisson (uncorrelated): ~0.3863
-pub fn mean_spacing_ratio(eigenvalues: &[f64]) -> f64 {
+///
+/// See [`MEAN_SPACING_RATIO_GOE`] and friends for the reference values, and
+/// [`spacing_ratio_density`] for the full distributions.
+#[cfg(feature = "std")]
+pub fn mean_spacing_ratio<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
     let ratios = level_spacing_ratios(eigenvalues);
     if ratios.is_empty() {
         0.0
@@ -307,12 +589,18 @@ pub fn mean_spacing_ratio(eigenvalues: &[f64]) -> f64 {
 /// # Arguments
 ///
 /// * `eigenvalues` - Eigenvalue samples
-/// * `bins` - Number of histogram bins
+/// * `bins` - Number of histogram bins (see [`empirical_spectral_density_auto`]
+///   to choose it from the data)
 ///
 /// # Returns
 ///
 /// (bin_centers, densities)
-pub fn empirical_spectral_density(eigenvalues: &[f64], bins: usize) -> (Vec<f64>, Vec<f64>) {
+#[cfg(feature = "std")]
+pub fn empirical_spectral_density<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
     if eigenvalues.is_empty() || bins == 0 {
         return (vec![], vec![]);
     }
@@ -345,15 +633,72 @@ pub fn empirical_spectral_density(eigenvalues: &[f64], bins: usize) -> (Vec<f64>
     (centers, densities)
 }
 
+/// [`empirical_spectral_density`] after checking that every eigenvalue is finite.
+///
+/// A single infinite eigenvalue would otherwise stretch the bin range to
+/// infinity and turn every density into NaN.
+///
+/// # Errors
+///
+/// [`Error::NonFinite`] for the first NaN or infinite eigenvalue.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{checked_empirical_spectral_density, retain_finite, Error};
+///
+/// let eigenvalues = [0.5, f64::NAN, 1.5, 2.0];
+/// let err = checked_empirical_spectral_density(&eigenvalues, 4).unwrap_err();
+/// assert!(matches!(err, Error::NonFinite { index: 1, .. }));
+///
+/// // Drop the bad entries instead of failing.
+/// let (centers, _) = checked_empirical_spectral_density(&retain_finite(&eigenvalues), 4).unwrap();
+/// assert_eq!(centers.len(), 4);
+/// ```
+#[cfg(feature = "std")]
+pub fn checked_empirical_spectral_density<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    bins: usize,
+) -> Result<(Vec<f64>, Vec<f64>), Error> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    check_finite(eigenvalues)?;
+    Ok(empirical_spectral_density(eigenvalues, bins))
+}
+
 /// Stieltjes transform: m(z) = (1/n) sum 1/(lambda_i - z)
 ///
 /// The Stieltjes transform encodes the spectral distribution and is
 /// central to proving limiting theorems in RMT.
-pub fn stieltjes_transform(eigenvalues: &[f64], z: f64) -> f64 {
+#[cfg(feature = "std")]
+pub fn stieltjes_transform<E: AsEigenvalues + ?Sized>(eigenvalues: &E, z: f64) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
     let n = eigenvalues.len() as f64;
     eigenvalues.iter().map(|&ev| 1.0 / (ev - z)).sum::<f64>() / n
 }
 
+/// [`stieltjes_transform`] after checking that z and every eigenvalue are finite.
+///
+/// # Errors
+///
+/// [`Error::InvalidParameter`] for a non-finite z, then [`Error::NonFinite`]
+/// for the first NaN or infinite eigenvalue.
+#[cfg(feature = "std")]
+pub fn checked_stieltjes_transform<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    z: f64,
+) -> Result<f64, Error> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if !z.is_finite() {
+        return Err(Error::InvalidParameter {
+            name: "z",
+            value: z,
+            expected: "finite",
+        });
+    }
+    check_finite(eigenvalues)?;
+    Ok(stieltjes_transform(eigenvalues, z))
+}
+
 /// Estimate effective dimensionality of an embedding matrix using the Marchenko-Pastur law.
 ///
 /// Given eigenvalues of the sample covariance matrix (X^T X / n), counts how many
@@ -385,7 +730,13 @@ pub fn stieltjes_transform(eigenvalues: &[f64], z: f64) -> f64 {
 /// let dim = effective_dimension(&eigenvalues, 200, 100);
 /// assert!(dim >= 4 && dim <= 6, "got {dim}");
 /// ```
-pub fn effective_dimension(eigenvalues: &[f64], n_samples: usize, n_features: usize) -> usize {
+#[cfg(feature = "std")]
+pub fn effective_dimension<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n_samples: usize,
+    n_features: usize,
+) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
     if eigenvalues.is_empty() || n_samples == 0 || n_features == 0 {
         return 0;
     }
@@ -407,9 +758,21 @@ pub fn effective_dimension(eigenvalues: &[f64], n_samples: usize, n_features: us
     eigenvalues.iter().filter(|&&ev| ev > lambda_plus).count()
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_marchenko_pastur_quantile_inverts_cdf() {
+        for &ratio in &[0.1, 0.5, 2.0] {
+            for &q in &[0.01, 0.25, 0.5, 0.9] {
+                let x = marchenko_pastur_quantile(q, ratio, 1.5);
+                assert!((marchenko_pastur_cdf(x, ratio, 1.5) - q).abs() < 1e-10);
+            }
+        }
+        assert!(marchenko_pastur_quantile(1.5, 0.5, 1.0).is_nan());
+    }
 
     #[test]
     fn test_marchenko_pastur_normalization() {
@@ -452,6 +815,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_marchenko_pastur_cdf_matches_density() {
+        for &ratio in &[0.25, 0.5, 3.0] {
+            let (lo, hi) = marchenko_pastur_support(ratio, 2.0);
+            let x = lo + 0.4 * (hi - lo);
+            let n_points = 20000;
+            let dx = (x - lo) / n_points as f64;
+            let integral: f64 = (0..n_points)
+                .map(|i| marchenko_pastur_density(lo + (i as f64 + 0.5) * dx, ratio, 2.0) * dx)
+                .sum();
+            let cdf = marchenko_pastur_cdf(x, ratio, 2.0);
+            assert!(
+                (cdf - integral).abs() < 1e-3,
+                "ratio {}: cdf {} vs integral {}",
+                ratio,
+                cdf,
+                integral
+            );
+        }
+    }
+
+    #[test]
+    fn test_wigner_cdf() {
+        assert!((wigner_semicircle_cdf(0.0, 1.0) - 0.5).abs() < 1e-12);
+        assert_eq!(wigner_semicircle_cdf(-3.0, 1.0), 0.0);
+        assert_eq!(wigner_semicircle_cdf(3.0, 1.0), 1.0);
+    }
+
     #[test]
     fn test_wigner_at_zero() {
         let density = wigner_semicircle_density(0.0, 1.0);
@@ -464,6 +855,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "thread-rng")]
     #[test]
     fn test_wishart_shape() {
         let wishart = sample_wishart(100, 50);
@@ -480,6 +872,38 @@ mod tests {
         assert_eq!(w1, w2);
     }
 
+    #[test]
+    fn test_wishart_bartlett_moments() {
+        use rand::SeedableRng;
+        // W ~ Wishart(n, I): E[W_ii] = n, Var(W_ii) = 2n, Var(W_ij) = n.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(625);
+        let (n, p, trials) = (40, 6, 4000);
+        let (mut diag, mut diag_sq, mut off_sq) = (0.0, 0.0, 0.0);
+        for _ in 0..trials {
+            let w = sample_wishart_with(&mut rng, n, p);
+            assert_eq!(w, w.t());
+            diag += w[[p - 1, p - 1]];
+            diag_sq += w[[p - 1, p - 1]].powi(2);
+            off_sq += w[[0, p - 1]].powi(2);
+        }
+        let t = trials as f64;
+        let mean = diag / t;
+        assert!((mean - n as f64).abs() < 0.5, "E[W_pp] = {mean}");
+        assert!(((diag_sq / t - mean * mean) / (2.0 * n as f64) - 1.0).abs() < 0.1);
+        assert!((off_sq / t / n as f64 - 1.0).abs() < 0.1);
+
+        // n < p takes the direct path: rank n.
+        let w = sample_wishart_with(&mut rng, 3, 6);
+        assert_eq!(
+            symmetric_eigenvalues(&w)
+                .iter()
+                .filter(|v| v.abs() > 1e-9)
+                .count(),
+            3
+        );
+    }
+
+    #[cfg(feature = "thread-rng")]
     #[test]
     fn test_goe_symmetric() {
         let goe = sample_goe(10);
@@ -551,9 +975,40 @@ mod tests {
             assert!(*d > 0.5 && *d < 1.5);
         }
     }
+
+    #[test]
+    fn test_checked_variants_reject_non_finite() {
+        let eigenvalues = [1.0, 2.0, f64::NEG_INFINITY, 4.0];
+        let expected = Error::NonFinite {
+            index: 2,
+            value: f64::NEG_INFINITY,
+        };
+        assert_eq!(
+            checked_level_spacing_ratios(&eigenvalues),
+            Err(expected.clone())
+        );
+        assert_eq!(
+            checked_stieltjes_transform(&eigenvalues, -1.0),
+            Err(expected)
+        );
+        assert!(matches!(
+            checked_stieltjes_transform(&[1.0], f64::NAN),
+            Err(Error::InvalidParameter { name: "z", .. })
+        ));
+
+        let finite = retain_finite(&eigenvalues);
+        assert_eq!(
+            checked_level_spacing_ratios(&finite).unwrap(),
+            level_spacing_ratios(&finite)
+        );
+        assert_eq!(
+            checked_stieltjes_transform(&finite, -1.0).unwrap(),
+            stieltjes_transform(&finite, -1.0)
+        );
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod proptests {
     use super::*;
     use proptest::prelude::*;
@@ -0,0 +1,912 @@
+//! Small dense linear-algebra helpers.
+//!
+//! The crate deliberately avoids a LAPACK dependency; these routines cover the
+//! modest sizes needed for fitting, testing, and Monte Carlo baselines. With
+//! the `faer` feature the eigensolver and singular values come from faer
+//! (pure Rust, blocked and vectorized), which pays off from a few hundred
+//! rows up; the EISPACK routines below remain the fallback.
+
+use ndarray::Array2;
+use num_complex::Complex64;
+
+/// Eigendecomposition of a real symmetric matrix.
+///
+/// Householder tridiagonalization followed by the implicit QL algorithm
+/// (the EISPACK tred2/tql2 pair), or faer's divide-and-conquer solver with
+/// the `faer` feature. Only the lower triangle is read.
+///
+/// # Arguments
+///
+/// * `a` - Symmetric n x n matrix
+///
+/// # Returns
+///
+/// (eigenvalues in ascending order, n x n matrix whose columns are the
+/// corresponding orthonormal eigenvectors); empty results if `a` is not square
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::symmetric_eigen;
+///
+/// let (values, vectors) = symmetric_eigen(&array![[2.0, 1.0], [1.0, 2.0]]);
+/// assert!((values[0] - 1.0).abs() < 1e-12 && (values[1] - 3.0).abs() < 1e-12);
+/// assert!((vectors[[0, 1]].abs() - 0.5_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn symmetric_eigen(a: &Array2<f64>) -> (Vec<f64>, Array2<f64>) {
+    let n = a.nrows();
+    if n == 0 || a.ncols() != n {
+        return (vec![], Array2::zeros((0, 0)));
+    }
+    #[cfg(feature = "faer")]
+    if let Some(result) = faer_backend::symmetric_eigen(a) {
+        return result;
+    }
+    eispack_symmetric_eigen(a)
+}
+
+/// tred2 + tql2 on a non-empty square matrix.
+fn eispack_symmetric_eigen(a: &Array2<f64>) -> (Vec<f64>, Array2<f64>) {
+    let n = a.nrows();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if j <= i { a[[i, j]] } else { a[[j, i]] })
+                .collect()
+        })
+        .collect();
+    let mut d = vec![0.0; n];
+    let mut e = vec![0.0; n];
+    tridiagonalize(&mut v, &mut d, &mut e);
+    tridiagonal_ql(&mut v, &mut d, &mut e);
+
+    let vectors = Array2::from_shape_fn((n, n), |(i, j)| v[i][j]);
+    (d, vectors)
+}
+
+/// Eigenvalues of a real symmetric matrix, in ascending order.
+///
+/// See [`symmetric_eigen`].
+pub fn symmetric_eigenvalues(a: &Array2<f64>) -> Vec<f64> {
+    #[cfg(feature = "faer")]
+    if a.nrows() > 0 && a.ncols() == a.nrows() {
+        if let Some(values) = faer_backend::symmetric_eigenvalues(a) {
+            return values;
+        }
+    }
+    symmetric_eigen(a).0
+}
+
+/// Singular values of a real matrix of any shape, in descending order.
+///
+/// Square roots of the eigenvalues of the smaller Gram matrix (A^T A or
+/// A A^T), so min(rows, cols) values are returned. Accurate relative to the
+/// largest singular value; tiny ones lose precision from squaring. With the
+/// `faer` feature a direct SVD is used instead.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::singular_values;
+///
+/// let s = singular_values(&array![[3.0, 0.0, 0.0], [0.0, -4.0, 0.0]]);
+/// assert!((s[0] - 4.0).abs() < 1e-12 && (s[1] - 3.0).abs() < 1e-12);
+/// ```
+pub fn singular_values(a: &Array2<f64>) -> Vec<f64> {
+    let (rows, cols) = a.dim();
+    if rows == 0 || cols == 0 {
+        return vec![];
+    }
+    #[cfg(feature = "faer")]
+    if let Some(values) = faer_backend::singular_values(a) {
+        return values;
+    }
+    let gram = if cols <= rows {
+        a.t().dot(a)
+    } else {
+        a.dot(&a.t())
+    };
+    symmetric_eigenvalues(&gram)
+        .into_iter()
+        .rev()
+        .map(|v| v.max(0.0).sqrt())
+        .collect()
+}
+
+/// Eigenvalues of a general real square matrix.
+///
+/// Balancing, reduction to upper Hessenberg form by stabilized elementary
+/// similarity transformations, and the Francis double-shift QR algorithm
+/// (the EISPACK balanc/elmhes/hqr sequence). Complex eigenvalues come in
+/// conjugate pairs. This is the route to the spectra of non-Hermitian
+/// random matrices such as the real Ginibre ensemble.
+///
+/// # Arguments
+///
+/// * `a` - Square n x n matrix
+///
+/// # Returns
+///
+/// The n eigenvalues in no particular order; empty if `a` is not square,
+/// and NaN for any eigenvalue on which QR failed to converge
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::general_eigenvalues;
+///
+/// // A rotation by 90 degrees has eigenvalues +-i.
+/// let values = general_eigenvalues(&array![[0.0, -1.0], [1.0, 0.0]]);
+/// assert!(values.iter().all(|z| z.re.abs() < 1e-12 && (z.im.abs() - 1.0).abs() < 1e-12));
+/// ```
+pub fn general_eigenvalues(a: &Array2<f64>) -> Vec<Complex64> {
+    let n = a.nrows();
+    if n == 0 || a.ncols() != n {
+        return vec![];
+    }
+    let mut h: Vec<Vec<f64>> = a.rows().into_iter().map(|r| r.to_vec()).collect();
+    balance(&mut h);
+    hessenberg(&mut h);
+    hessenberg_qr(&mut h)
+}
+
+/// faer-backed versions of the public routines; `None` if faer fails to
+/// converge, in which case the callers fall back to EISPACK.
+#[cfg(feature = "faer")]
+mod faer_backend {
+    use faer::{Mat, Side};
+    use ndarray::Array2;
+
+    fn to_mat(a: &Array2<f64>) -> Mat<f64> {
+        Mat::from_fn(a.nrows(), a.ncols(), |i, j| a[[i, j]])
+    }
+
+    pub(super) fn symmetric_eigen(a: &Array2<f64>) -> Option<(Vec<f64>, Array2<f64>)> {
+        let evd = to_mat(a).self_adjoint_eigen(Side::Lower).ok()?;
+        let values = evd.S().column_vector().iter().copied().collect();
+        let u = evd.U();
+        let vectors = Array2::from_shape_fn(a.dim(), |(i, j)| u[(i, j)]);
+        Some((values, vectors))
+    }
+
+    pub(super) fn symmetric_eigenvalues(a: &Array2<f64>) -> Option<Vec<f64>> {
+        to_mat(a).self_adjoint_eigenvalues(Side::Lower).ok()
+    }
+
+    pub(super) fn singular_values(a: &Array2<f64>) -> Option<Vec<f64>> {
+        to_mat(a).singular_values().ok()
+    }
+}
+
+/// Householder reduction to tridiagonal form (EISPACK tred2).
+///
+/// On exit `d` holds the diagonal, `e[1..]` the sub-diagonal, and `v` the
+/// accumulated orthogonal transformation.
+fn tridiagonalize(v: &mut [Vec<f64>], d: &mut [f64], e: &mut [f64]) {
+    let n = d.len();
+    d.copy_from_slice(&v[n - 1]);
+
+    for i in (1..n).rev() {
+        let scale: f64 = d[..i].iter().map(|x| x.abs()).sum();
+        let mut h = 0.0;
+        if scale == 0.0 {
+            e[i] = d[i - 1];
+            for j in 0..i {
+                d[j] = v[i - 1][j];
+                v[i][j] = 0.0;
+                v[j][i] = 0.0;
+            }
+        } else {
+            for dk in d[..i].iter_mut() {
+                *dk /= scale;
+                h += *dk * *dk;
+            }
+            let mut f = d[i - 1];
+            let mut g = h.sqrt();
+            if f > 0.0 {
+                g = -g;
+            }
+            e[i] = scale * g;
+            h -= f * g;
+            d[i - 1] = f - g;
+            for ej in e.iter_mut().take(i) {
+                *ej = 0.0;
+            }
+
+            for j in 0..i {
+                f = d[j];
+                v[j][i] = f;
+                g = e[j] + v[j][j] * f;
+                for k in (j + 1)..i {
+                    g += v[k][j] * d[k];
+                    e[k] += v[k][j] * f;
+                }
+                e[j] = g;
+            }
+            f = 0.0;
+            for j in 0..i {
+                e[j] /= h;
+                f += e[j] * d[j];
+            }
+            let hh = f / (h + h);
+            for j in 0..i {
+                e[j] -= hh * d[j];
+            }
+            for j in 0..i {
+                f = d[j];
+                g = e[j];
+                for k in j..i {
+                    v[k][j] -= f * e[k] + g * d[k];
+                }
+                d[j] = v[i - 1][j];
+                v[i][j] = 0.0;
+            }
+        }
+        d[i] = h;
+    }
+
+    for i in 0..n - 1 {
+        v[n - 1][i] = v[i][i];
+        v[i][i] = 1.0;
+        let h = d[i + 1];
+        if h != 0.0 {
+            for k in 0..=i {
+                d[k] = v[k][i + 1] / h;
+            }
+            for j in 0..=i {
+                let g: f64 = v[..=i].iter().map(|row| row[i + 1] * row[j]).sum();
+                for (row, dk) in v[..=i].iter_mut().zip(d.iter()) {
+                    row[j] -= g * dk;
+                }
+            }
+        }
+        for row in v[..=i].iter_mut() {
+            row[i + 1] = 0.0;
+        }
+    }
+    for j in 0..n {
+        d[j] = v[n - 1][j];
+        v[n - 1][j] = 0.0;
+    }
+    v[n - 1][n - 1] = 1.0;
+    e[0] = 0.0;
+}
+
+/// Implicit QL iteration on a symmetric tridiagonal matrix (EISPACK tql2).
+///
+/// Eigenvalues end up sorted ascending in `d` with eigenvectors in the columns of `v`.
+fn tridiagonal_ql(v: &mut [Vec<f64>], d: &mut [f64], e: &mut [f64]) {
+    let n = d.len();
+    for i in 1..n {
+        e[i - 1] = e[i];
+    }
+    e[n - 1] = 0.0;
+
+    let mut f = 0.0;
+    let mut tst1 = 0.0_f64;
+    let eps = f64::EPSILON;
+    for l in 0..n {
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+        let mut m = l;
+        while m < n {
+            if e[m].abs() <= eps * tst1 {
+                break;
+            }
+            m += 1;
+        }
+        let m = m.min(n - 1);
+
+        if m > l {
+            loop {
+                let mut g = d[l];
+                let mut p = (d[l + 1] - g) / (2.0 * e[l]);
+                let mut r = p.hypot(1.0);
+                if p < 0.0 {
+                    r = -r;
+                }
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let mut h = g - d[l];
+                for di in d.iter_mut().take(n).skip(l + 2) {
+                    *di -= h;
+                }
+                f += h;
+
+                p = d[m];
+                let mut c = 1.0;
+                let mut c2 = c;
+                let mut c3 = c;
+                let el1 = e[l + 1];
+                let mut s = 0.0;
+                let mut s2 = 0.0;
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    g = c * e[i];
+                    h = c * p;
+                    r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+                    for row in v.iter_mut() {
+                        h = row[i + 1];
+                        row[i + 1] = s * row[i] + c * h;
+                        row[i] = c * row[i] - s * h;
+                    }
+                }
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+
+                if e[l].abs() <= eps * tst1 {
+                    break;
+                }
+            }
+        }
+        d[l] += f;
+        e[l] = 0.0;
+    }
+
+    // Selection sort of eigenvalues (and vectors) into ascending order.
+    for i in 0..n.saturating_sub(1) {
+        let mut k = i;
+        let mut p = d[i];
+        for (j, &dj) in d.iter().enumerate().skip(i + 1) {
+            if dj < p {
+                k = j;
+                p = dj;
+            }
+        }
+        if k != i {
+            d[k] = d[i];
+            d[i] = p;
+            for row in v.iter_mut() {
+                row.swap(i, k);
+            }
+        }
+    }
+}
+
+/// Diagonal similarity scaling by powers of two that equalizes row and
+/// column norms (EISPACK balanc), improving the accuracy of eigenvalues of
+/// badly scaled matrices.
+fn balance(a: &mut [Vec<f64>]) {
+    const RADIX: f64 = 2.0;
+    let n = a.len();
+    let mut done = false;
+    while !done {
+        done = true;
+        for i in 0..n {
+            let (mut r, mut c) = (0.0, 0.0);
+            for j in (0..n).filter(|&j| j != i) {
+                c += a[j][i].abs();
+                r += a[i][j].abs();
+            }
+            if c == 0.0 || r == 0.0 {
+                continue;
+            }
+            let s = c + r;
+            let mut f = 1.0;
+            while c < r / RADIX {
+                f *= RADIX;
+                c *= RADIX * RADIX;
+            }
+            while c > r * RADIX {
+                f /= RADIX;
+                c /= RADIX * RADIX;
+            }
+            if (c + r) / f < 0.95 * s {
+                done = false;
+                a[i].iter_mut().for_each(|x| *x /= f);
+                a.iter_mut().for_each(|row| row[i] *= f);
+            }
+        }
+    }
+}
+
+/// Reduction to upper Hessenberg form by Gaussian elimination with
+/// pivoting (EISPACK elmhes); entries below the subdiagonal are zeroed.
+fn hessenberg(a: &mut [Vec<f64>]) {
+    let n = a.len();
+    for m in 1..n.saturating_sub(1) {
+        let pivot = (m..n)
+            .max_by(|&r, &s| a[r][m - 1].abs().total_cmp(&a[s][m - 1].abs()))
+            .unwrap_or(m);
+        let x = a[pivot][m - 1];
+        if pivot != m {
+            a.swap(pivot, m);
+            a.iter_mut().for_each(|row| row.swap(pivot, m));
+        }
+        if x == 0.0 {
+            continue;
+        }
+        for i in (m + 1)..n {
+            let y = a[i][m - 1] / x;
+            if y == 0.0 {
+                continue;
+            }
+            a[i][m - 1] = 0.0;
+            let (upper, lower) = a.split_at_mut(i);
+            for (x, p) in lower[0][m..].iter_mut().zip(&upper[m][m..]) {
+                *x -= y * p;
+            }
+            for row in a.iter_mut() {
+                row[m] += y * row[i];
+            }
+        }
+    }
+}
+
+/// Upper Hessenberg form Q^T A Q by Householder reflections (EISPACK orthes).
+///
+/// Unlike [`hessenberg`], the similarity is orthogonal, so singular values
+/// of A - z I (and hence pseudospectra) are preserved.
+pub(crate) fn orthogonal_hessenberg(a: &Array2<f64>) -> Vec<Vec<f64>> {
+    let n = a.nrows();
+    let mut h: Vec<Vec<f64>> = a.rows().into_iter().map(|r| r.to_vec()).collect();
+    for k in 0..n.saturating_sub(2) {
+        let norm = ((k + 1)..n).map(|i| h[i][k] * h[i][k]).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        let alpha = if h[k + 1][k] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = ((k + 1)..n).map(|i| h[i][k]).collect();
+        v[0] -= alpha;
+        let v_norm_sq: f64 = v.iter().map(|x| x * x).sum();
+        // H <- (I - 2 v v^T / v^T v) H (I - 2 v v^T / v^T v) on rows/columns k + 1..
+        for j in k..n {
+            let dot: f64 = h[k + 1..].iter().zip(&v).map(|(row, vi)| vi * row[j]).sum();
+            let f = 2.0 * dot / v_norm_sq;
+            for (row, vi) in h[k + 1..].iter_mut().zip(&v) {
+                row[j] -= f * vi;
+            }
+        }
+        for row in h.iter_mut() {
+            let dot: f64 = row[k + 1..].iter().zip(&v).map(|(x, vi)| x * vi).sum();
+            let f = 2.0 * dot / v_norm_sq;
+            for (x, vi) in row[k + 1..].iter_mut().zip(&v) {
+                *x -= f * vi;
+            }
+        }
+        h[k + 1][k] = alpha;
+        for row in h.iter_mut().skip(k + 2) {
+            row[k] = 0.0;
+        }
+    }
+    h
+}
+
+/// Eigenvalues of an upper Hessenberg matrix by the Francis double-shift
+/// QR algorithm with exceptional shifts (EISPACK hqr). Destroys `a`.
+fn hessenberg_qr(a: &mut [Vec<f64>]) -> Vec<Complex64> {
+    const MAX_ITERATIONS: usize = 60;
+    let n = a.len();
+    let mut values = vec![Complex64::new(f64::NAN, f64::NAN); n];
+    let norm: f64 = (0..n)
+        .flat_map(|i| (i.saturating_sub(1)..n).map(move |j| (i, j)))
+        .map(|(i, j)| a[i][j].abs())
+        .sum();
+    let mut shift = 0.0;
+    let mut nn = n as isize - 1;
+    while nn >= 0 {
+        let mut iterations = 0;
+        loop {
+            let top = nn as usize;
+            // Look for a negligible subdiagonal element a[l][l - 1].
+            let mut l = top;
+            while l > 0 {
+                let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+                if s == 0.0 {
+                    s = norm;
+                }
+                if a[l][l - 1].abs() <= f64::EPSILON * s {
+                    a[l][l - 1] = 0.0;
+                    break;
+                }
+                l -= 1;
+            }
+            let mut x = a[top][top];
+            if l == top {
+                values[top] = Complex64::new(x + shift, 0.0);
+                nn -= 1;
+                break;
+            }
+            let mut y = a[top - 1][top - 1];
+            let mut w = a[top][top - 1] * a[top - 1][top];
+            if l == top - 1 {
+                // A 2 x 2 block splits off: real pair or conjugate pair.
+                let p = 0.5 * (y - x);
+                let q = p * p + w;
+                let z = q.abs().sqrt();
+                x += shift;
+                if q >= 0.0 {
+                    let z = p + z.copysign(p);
+                    values[top - 1] = Complex64::new(x + z, 0.0);
+                    values[top] = Complex64::new(if z != 0.0 { x - w / z } else { x + z }, 0.0);
+                } else {
+                    values[top - 1] = Complex64::new(x + p, z);
+                    values[top] = Complex64::new(x + p, -z);
+                }
+                nn -= 2;
+                break;
+            }
+            if iterations == MAX_ITERATIONS {
+                return values;
+            }
+            if iterations == 10 || iterations == 20 {
+                // Exceptional shift to break cycles.
+                shift += x;
+                for (i, row) in a.iter_mut().enumerate().take(top + 1) {
+                    row[i] -= x;
+                }
+                let s = a[top][top - 1].abs() + a[top - 1][top - 2].abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            iterations += 1;
+            // Find two consecutive small subdiagonal elements.
+            let mut m = top - 2;
+            let (mut p, mut q, mut r);
+            loop {
+                let z = a[m][m];
+                let (rr, ss) = (x - z, y - z);
+                p = (rr * ss - w) / a[m + 1][m] + a[m][m + 1];
+                q = a[m + 1][m + 1] - z - rr - ss;
+                r = a[m + 2][m + 1];
+                let s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+                let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+                if u <= f64::EPSILON * v {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in (m + 2)..=top {
+                a[i][i - 2] = 0.0;
+                if i != m + 2 {
+                    a[i][i - 3] = 0.0;
+                }
+            }
+            // Double QR step on rows l..=top and columns m..=top.
+            for k in m..top {
+                if k != m {
+                    p = a[k][k - 1];
+                    q = a[k + 1][k - 1];
+                    r = if k + 1 != top { a[k + 2][k - 1] } else { 0.0 };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x != 0.0 {
+                        p /= x;
+                        q /= x;
+                        r /= x;
+                    }
+                }
+                let s = (p * p + q * q + r * r).sqrt().copysign(p);
+                if s == 0.0 {
+                    continue;
+                }
+                if k == m {
+                    if l != m {
+                        a[k][k - 1] = -a[k][k - 1];
+                    }
+                } else {
+                    a[k][k - 1] = -s * x;
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                let z = r / s;
+                q /= p;
+                r /= p;
+                let (upper, lower) = a.split_at_mut(k + 1);
+                let (row_k, (next, rest)) = (&mut upper[k], lower.split_at_mut(1));
+                for j in k..=top {
+                    let mut p = row_k[j] + q * next[0][j];
+                    if k + 1 != top {
+                        p += r * rest[0][j];
+                        rest[0][j] -= p * z;
+                    }
+                    next[0][j] -= p * y;
+                    row_k[j] -= p * x;
+                }
+                for row in a.iter_mut().take(top.min(k + 3) + 1).skip(l) {
+                    let mut p = x * row[k] + y * row[k + 1];
+                    if k + 1 != top {
+                        p += z * row[k + 2];
+                        row[k + 2] -= p * r;
+                    }
+                    row[k + 1] -= p * q;
+                    row[k] -= p;
+                }
+            }
+        }
+    }
+    values
+}
+
+/// Least-squares solution of `design * coef ~ y` via Householder QR.
+///
+/// `design` is row-major with `y.len()` rows of equal length. Returns `None`
+/// if the system is underdetermined or rank deficient.
+pub(crate) fn least_squares(design: &[Vec<f64>], y: &[f64]) -> Option<Vec<f64>> {
+    let m = design.len();
+    if m == 0 || m != y.len() {
+        return None;
+    }
+    let k = design[0].len();
+    if k == 0 || m < k || design.iter().any(|row| row.len() != k) {
+        return None;
+    }
+
+    let mut a: Vec<Vec<f64>> = design.to_vec();
+    let mut b = y.to_vec();
+
+    for j in 0..k {
+        let norm = (j..m).map(|i| a[i][j] * a[i][j]).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return None;
+        }
+        let alpha = if a[j][j] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = (j..m).map(|i| a[i][j]).collect();
+        v[0] -= alpha;
+        let v_norm_sq: f64 = v.iter().map(|x| x * x).sum();
+        if v_norm_sq == 0.0 {
+            continue;
+        }
+
+        for col in j..k {
+            let dot: f64 = a[j..].iter().zip(&v).map(|(row, vi)| vi * row[col]).sum();
+            let f = 2.0 * dot / v_norm_sq;
+            for (row, vi) in a[j..].iter_mut().zip(&v) {
+                row[col] -= f * vi;
+            }
+        }
+        let dot: f64 = b[j..].iter().zip(&v).map(|(bi, vi)| vi * bi).sum();
+        let f = 2.0 * dot / v_norm_sq;
+        for (bi, vi) in b[j..].iter_mut().zip(&v) {
+            *bi -= f * vi;
+        }
+    }
+
+    let scale = (0..k).map(|j| a[j][j].abs()).fold(0.0, f64::max);
+    let mut coef = vec![0.0; k];
+    for j in (0..k).rev() {
+        if a[j][j].abs() <= 1e-12 * scale {
+            return None;
+        }
+        let acc: f64 = ((j + 1)..k).map(|c| a[j][c] * coef[c]).sum();
+        coef[j] = (b[j] - acc) / a[j][j];
+    }
+    Some(coef)
+}
+
+/// Determinant of a square matrix via LU with partial pivoting.
+///
+/// Returns NaN for a non-square matrix.
+pub(crate) fn determinant(a: &Array2<f64>) -> f64 {
+    let n = a.nrows();
+    if n != a.ncols() {
+        return f64::NAN;
+    }
+    let mut lu: Vec<Vec<f64>> = a.rows().into_iter().map(|r| r.to_vec()).collect();
+    let mut det = 1.0;
+    for j in 0..n {
+        let pivot = (j..n)
+            .max_by(|&r, &s| lu[r][j].abs().total_cmp(&lu[s][j].abs()))
+            .unwrap_or(j);
+        if lu[pivot][j] == 0.0 {
+            return 0.0;
+        }
+        if pivot != j {
+            lu.swap(pivot, j);
+            det = -det;
+        }
+        det *= lu[j][j];
+        let (upper, lower) = lu.split_at_mut(j + 1);
+        let pivot_row = &upper[j];
+        for row in lower.iter_mut() {
+            let f = row[j] / pivot_row[j];
+            for (x, p) in row[j..].iter_mut().zip(&pivot_row[j..]) {
+                *x -= f * p;
+            }
+        }
+    }
+    det
+}
+
+/// Solve a x = b by LU with partial pivoting.
+///
+/// Returns `None` for mismatched dimensions or a (numerically) singular matrix.
+pub(crate) fn solve(a: &Array2<f64>, b: &[f64]) -> Option<Vec<f64>> {
+    let n = a.nrows();
+    if n != a.ncols() || n != b.len() {
+        return None;
+    }
+    let mut lu: Vec<Vec<f64>> = a.rows().into_iter().map(|r| r.to_vec()).collect();
+    let mut x = b.to_vec();
+    let scale = a.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+    for j in 0..n {
+        let pivot = (j..n)
+            .max_by(|&r, &s| lu[r][j].abs().total_cmp(&lu[s][j].abs()))
+            .unwrap_or(j);
+        if lu[pivot][j].abs() <= 1e-14 * scale {
+            return None;
+        }
+        lu.swap(pivot, j);
+        x.swap(pivot, j);
+        let (upper, lower) = lu.split_at_mut(j + 1);
+        let pivot_row = &upper[j];
+        let (x_upper, x_lower) = x.split_at_mut(j + 1);
+        for (row, xi) in lower.iter_mut().zip(x_lower.iter_mut()) {
+            let f = row[j] / pivot_row[j];
+            for (v, p) in row[j..].iter_mut().zip(&pivot_row[j..]) {
+                *v -= f * p;
+            }
+            *xi -= f * x_upper[j];
+        }
+    }
+    for j in (0..n).rev() {
+        let acc: f64 = ((j + 1)..n).map(|c| lu[j][c] * x[c]).sum();
+        x[j] = (x[j] - acc) / lu[j][j];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_least_squares_exact_line() {
+        let design: Vec<Vec<f64>> = (0..5).map(|i| vec![1.0, i as f64]).collect();
+        let y: Vec<f64> = (0..5).map(|i| 2.0 + 3.0 * i as f64).collect();
+        let coef = least_squares(&design, &y).unwrap();
+        assert!((coef[0] - 2.0).abs() < 1e-12);
+        assert!((coef[1] - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_general_eigenvalues_companion() {
+        // Companion matrix of (x - 1)(x - 2)(x^2 + 2x + 5): roots 1, 2, -1 +- 2i.
+        // x^4 - x^3 + x^2 - 11x + 10.
+        let coefficients = [10.0, -11.0, 1.0, -1.0];
+        let a = Array2::from_shape_fn((4, 4), |(i, j)| {
+            if j == 3 {
+                -coefficients[i]
+            } else if i == j + 1 {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        let mut values = general_eigenvalues(&a);
+        values.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+        let expected = [
+            Complex64::new(-1.0, -2.0),
+            Complex64::new(-1.0, 2.0),
+            Complex64::new(1.0, 0.0),
+            Complex64::new(2.0, 0.0),
+        ];
+        for (z, e) in values.iter().zip(&expected) {
+            assert!((z - e).norm() < 1e-10, "{z} vs {e}");
+        }
+        // Trace and determinant of a dense non-symmetric matrix.
+        let n = 30;
+        let b = Array2::from_shape_fn((n, n), |(i, j)| ((i * 17 + j * 29) % 13) as f64 - 6.0);
+        let values = general_eigenvalues(&b);
+        let trace: Complex64 = values.iter().sum();
+        let product: Complex64 = values.iter().product();
+        assert!((trace.re - b.diag().sum()).abs() < 1e-8 && trace.im.abs() < 1e-8);
+        let det = determinant(&b);
+        assert!((product.re - det).abs() < 1e-8 * det.abs().max(1.0));
+        assert!(general_eigenvalues(&Array2::zeros((2, 3))).is_empty());
+    }
+
+    #[test]
+    fn test_symmetric_eigen_reconstructs() {
+        let n = 7;
+        let a = Array2::from_shape_fn((n, n), |(i, j)| {
+            let (lo, hi) = (i.min(j) as f64, i.max(j) as f64);
+            (lo + 1.0) / (hi + 2.0) + if i == j { 0.5 } else { 0.0 }
+        });
+        let (values, vectors) = symmetric_eigen(&a);
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+
+        let reconstructed = vectors
+            .dot(&Array2::from_diag(&ndarray::Array1::from(values)))
+            .dot(&vectors.t());
+        for (x, y) in reconstructed.iter().zip(a.iter()) {
+            assert!((x - y).abs() < 1e-10);
+        }
+        let identity = vectors.t().dot(&vectors);
+        for i in 0..n {
+            for j in 0..n {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((identity[[i, j]] - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_symmetric_eigenvalues_tridiagonal() {
+        // Eigenvalues of the second-difference matrix: 2 - 2 cos(k pi / (n + 1)).
+        let n = 20;
+        let a = Array2::from_shape_fn((n, n), |(i, j)| match i.abs_diff(j) {
+            0 => 2.0,
+            1 => -1.0,
+            _ => 0.0,
+        });
+        let values = symmetric_eigenvalues(&a);
+        for (k, v) in values.iter().enumerate() {
+            let expected =
+                2.0 - 2.0 * ((k + 1) as f64 * std::f64::consts::PI / (n as f64 + 1.0)).cos();
+            assert!((v - expected).abs() < 1e-12);
+        }
+        assert_eq!(
+            symmetric_eigenvalues(&Array2::from_elem((1, 1), 4.0)),
+            vec![4.0]
+        );
+        assert!(symmetric_eigenvalues(&Array2::zeros((2, 3))).is_empty());
+    }
+
+    #[cfg(feature = "faer")]
+    #[test]
+    fn test_faer_backend_matches_eispack() {
+        let n = 40;
+        let a = Array2::from_shape_fn((n, n), |(i, j)| ((i * j + i + j) % 7) as f64 - 3.0);
+        let a = &a + &a.t();
+        let (expected, _) = eispack_symmetric_eigen(&a);
+        let (values, vectors) = faer_backend::symmetric_eigen(&a).unwrap();
+        for (x, y) in values.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9);
+        }
+        assert_eq!(symmetric_eigenvalues(&a).len(), n);
+        let residual =
+            a.dot(&vectors) - vectors.dot(&Array2::from_diag(&ndarray::Array1::from(values)));
+        assert!(residual.iter().all(|r| r.abs() < 1e-9));
+
+        let rect = a.slice(ndarray::s![..10, ..]).to_owned();
+        let s = singular_values(&rect);
+        let gram = symmetric_eigenvalues(&rect.dot(&rect.t()));
+        for (x, y) in s.iter().zip(gram.iter().rev()) {
+            assert!((x * x - y).abs() < 1e-8 * y.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_least_squares_rank_deficient() {
+        let design = vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]];
+        assert!(least_squares(&design, &[1.0, 2.0, 3.0]).is_none());
+    }
+
+    #[test]
+    fn test_determinant_with_pivoting() {
+        let a = ndarray::array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+        assert!((determinant(&a) - (-5.0)).abs() < 1e-12);
+        assert!((determinant(&Array2::eye(4)) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_solve_with_pivoting() {
+        let a = ndarray::array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+        let x = solve(&a, &[5.0, 3.0, 6.0]).unwrap();
+        let b = a.dot(&ndarray::Array1::from(x));
+        assert!(
+            (b[0] - 5.0).abs() < 1e-12 && (b[1] - 3.0).abs() < 1e-12 && (b[2] - 6.0).abs() < 1e-12
+        );
+        assert!(solve(&ndarray::array![[1.0, 2.0], [2.0, 4.0]], &[1.0, 1.0]).is_none());
+    }
+}
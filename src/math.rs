@@ -0,0 +1,57 @@
+//! Floating-point functions that live in `std` rather than `core`.
+//!
+//! With the `std` feature these forward to the inherent `f64` methods;
+//! without it they use `libm`, so [`crate::laws`] compiles under `no_std`.
+
+#[cfg(feature = "std")]
+mod imp {
+    pub fn sqrt(x: f64) -> f64 {
+        x.sqrt()
+    }
+    pub fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+    pub fn tan(x: f64) -> f64 {
+        x.tan()
+    }
+    pub fn asin(x: f64) -> f64 {
+        x.asin()
+    }
+    pub fn acos(x: f64) -> f64 {
+        x.acos()
+    }
+    pub fn atan(x: f64) -> f64 {
+        x.atan()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod imp {
+    pub use libm::{acos, asin, atan, sin, sqrt, tan};
+}
+
+pub(crate) use imp::*;
+
+pub(crate) fn abs(x: f64) -> f64 {
+    f64::from_bits(x.to_bits() & !(1 << 63))
+}
+
+pub(crate) fn square(x: f64) -> f64 {
+    x * x
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    #[test]
+    fn test_libm_matches_std() {
+        for &x in &[-0.9, -0.3, 0.0, 0.25, 0.7] {
+            assert!((libm::asin(x) - x.asin()).abs() < 1e-15);
+            assert!((libm::acos(x) - x.acos()).abs() < 1e-15);
+            assert!((libm::atan(x) - x.atan()).abs() < 1e-15);
+            assert!((libm::sin(x) - x.sin()).abs() < 1e-15);
+            assert!((libm::tan(x) - x.tan()).abs() < 1e-15);
+            assert_eq!(libm::sqrt(x.abs()), x.abs().sqrt());
+            assert_eq!(super::abs(x), x.abs());
+        }
+    }
+}
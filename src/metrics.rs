@@ -0,0 +1,231 @@
+//! Scalar spectral summaries: entropies, effective ranks, and matrix norms.
+//!
+//! The entropies and effective ranks normalize the spectrum to a probability
+//! vector p_i = |lambda_i| / sum_j |lambda_j| (eigenvalues of a covariance, or
+//! singular values of a weight matrix) and summarize how spread out it is.
+//! The norms take singular values; get them from a matrix with
+//! [`crate::singular_values`].
+
+use crate::spectrum::AsEigenvalues;
+
+/// Shannon entropy H = -sum p_i ln p_i of the normalized spectrum.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum (absolute values are used)
+///
+/// # Returns
+///
+/// Entropy in nats, between 0 (one dominant direction) and ln n (flat spectrum);
+/// 0 for an empty or all-zero spectrum
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spectral_entropy;
+///
+/// let flat = spectral_entropy(&[1.0; 8]);
+/// assert!((flat - 8.0_f64.ln()).abs() < 1e-12);
+/// ```
+pub fn spectral_entropy<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    renyi_spectral_entropy(eigenvalues, 1.0)
+}
+
+/// Effective rank exp(H) of Roy & Vetterli (2007).
+///
+/// Equals k for a spectrum with k equal non-zero values and interpolates
+/// smoothly otherwise. An empty or all-zero spectrum has rank 0, as for
+/// [`participation_ratio_rank`] and [`stable_rank`].
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::effective_rank;
+///
+/// let r = effective_rank(&[5.0, 5.0, 5.0, 0.0, 0.0]);
+/// assert!((r - 3.0).abs() < 1e-12);
+/// ```
+pub fn effective_rank<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    renyi_effective_rank(eigenvalues, 1.0)
+}
+
+/// Participation-ratio rank (sum |lambda_i|)^2 / sum lambda_i^2.
+///
+/// The order-2 Renyi effective rank; less sensitive than [`effective_rank`]
+/// to a long tail of small eigenvalues.
+pub fn participation_ratio_rank<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let sum: f64 = eigenvalues.iter().map(|x| x.abs()).sum();
+    let sum_sq: f64 = eigenvalues.iter().map(|x| x * x).sum();
+    if sum_sq == 0.0 {
+        return 0.0;
+    }
+    sum * sum / sum_sq
+}
+
+/// Renyi entropy H_alpha = ln(sum p_i^alpha) / (1 - alpha) of the normalized spectrum.
+///
+/// alpha = 1 is the Shannon limit, alpha = 2 gives ln of the participation
+/// ratio, and alpha -> infinity gives -ln max p_i.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Spectrum (absolute values are used)
+/// * `alpha` - Order (>= 0; `f64::INFINITY` is accepted)
+pub fn renyi_spectral_entropy<E: AsEigenvalues + ?Sized>(eigenvalues: &E, alpha: f64) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let total: f64 = eigenvalues.iter().map(|x| x.abs()).sum();
+    if total == 0.0 || !total.is_finite() {
+        return 0.0;
+    }
+    let probs = eigenvalues
+        .iter()
+        .map(|x| x.abs() / total)
+        .filter(|&p| p > 0.0);
+
+    if (alpha - 1.0).abs() < 1e-12 {
+        -probs.map(|p| p * p.ln()).sum::<f64>()
+    } else if alpha.is_infinite() {
+        -probs.fold(0.0, f64::max).ln()
+    } else {
+        probs.map(|p| p.powf(alpha)).sum::<f64>().ln() / (1.0 - alpha)
+    }
+}
+
+/// Renyi effective rank exp(H_alpha); 0 for an empty or all-zero spectrum.
+pub fn renyi_effective_rank<E: AsEigenvalues + ?Sized>(eigenvalues: &E, alpha: f64) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.iter().all(|&x| x == 0.0) {
+        return 0.0;
+    }
+    renyi_spectral_entropy(eigenvalues, alpha).exp()
+}
+
+/// Spectral norm ||A||_2 = max_i s_i.
+///
+/// # Arguments
+///
+/// * `singular_values` - Singular values in any order
+///
+/// # Returns
+///
+/// The largest singular value; 0 for an empty spectrum
+pub fn spectral_norm<E: AsEigenvalues + ?Sized>(singular_values: &E) -> f64 {
+    let singular_values: &[f64] = &singular_values.as_eigenvalues();
+    singular_values.iter().fold(0.0, |m, s| m.max(s.abs()))
+}
+
+/// Frobenius norm ||A||_F = sqrt(sum_i s_i^2).
+pub fn frobenius_norm<E: AsEigenvalues + ?Sized>(singular_values: &E) -> f64 {
+    let singular_values: &[f64] = &singular_values.as_eigenvalues();
+    singular_values.iter().map(|s| s * s).sum::<f64>().sqrt()
+}
+
+/// Condition number kappa = max_i s_i / min_i s_i in the 2-norm.
+///
+/// Singular values from [`crate::singular_values`] are computed through
+/// the Gram matrix unless the `faer` feature is enabled, which limits
+/// trustworthy condition numbers to about 10^7.
+///
+/// # Returns
+///
+/// kappa >= 1, infinite if the smallest singular value is 0; NaN for an empty spectrum
+pub fn condition_number<E: AsEigenvalues + ?Sized>(singular_values: &E) -> f64 {
+    let singular_values: &[f64] = &singular_values.as_eigenvalues();
+    if singular_values.is_empty() {
+        return f64::NAN;
+    }
+    let smallest = singular_values
+        .iter()
+        .fold(f64::INFINITY, |m, s| m.min(s.abs()));
+    spectral_norm(singular_values) / smallest
+}
+
+/// Stable rank ||A||_F^2 / ||A||_2^2.
+///
+/// A continuous lower bound on the rank that is insensitive to tiny singular
+/// values: equals k for k equal non-zero singular values.
+///
+/// # Returns
+///
+/// Stable rank in [1, min(rows, cols)]; 0 for an empty or all-zero spectrum
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rmt::{singular_values, stable_rank};
+///
+/// let identity = Array2::<f64>::eye(4);
+/// assert!((stable_rank(&singular_values(&identity)) - 4.0).abs() < 1e-12);
+/// ```
+pub fn stable_rank<E: AsEigenvalues + ?Sized>(singular_values: &E) -> f64 {
+    let singular_values: &[f64] = &singular_values.as_eigenvalues();
+    let top = spectral_norm(singular_values);
+    if top == 0.0 {
+        return 0.0;
+    }
+    (frobenius_norm(singular_values) / top).powi(2)
+}
+
+/// log10 ||A||_2^2, the log of the largest eigenvalue of A^T A.
+///
+/// The log-scale norms are the per-layer capacity metrics of weight-matrix
+/// diagnostics, where norms span many orders of magnitude across layers.
+///
+/// # Returns
+///
+/// log10 of the squared spectral norm; -inf for an all-zero spectrum
+pub fn log_spectral_norm<E: AsEigenvalues + ?Sized>(singular_values: &E) -> f64 {
+    let singular_values: &[f64] = &singular_values.as_eigenvalues();
+    spectral_norm(singular_values).powi(2).log10()
+}
+
+/// log10 ||A||_F^2, the log of the sum of the eigenvalues of A^T A.
+pub fn log_frobenius_norm<E: AsEigenvalues + ?Sized>(singular_values: &E) -> f64 {
+    let singular_values: &[f64] = &singular_values.as_eigenvalues();
+    frobenius_norm(singular_values).powi(2).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_rank_ordering() {
+        // Renyi ranks are non-increasing in alpha.
+        let spectrum = [10.0, 4.0, 2.0, 1.0, 0.5, 0.1];
+        let r1 = effective_rank(&spectrum);
+        let r2 = participation_ratio_rank(&spectrum);
+        let r_inf = renyi_effective_rank(&spectrum, f64::INFINITY);
+        assert!(r1 >= r2 && r2 >= r_inf, "{} {} {}", r1, r2, r_inf);
+        assert!((r2 - renyi_effective_rank(&spectrum, 2.0)).abs() < 1e-10);
+        assert!((r_inf - 17.6 / 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(spectral_entropy(&[]), 0.0);
+        assert_eq!(effective_rank(&[0.0, 0.0]), 0.0);
+        assert_eq!(effective_rank(&[]), 0.0);
+        assert_eq!(renyi_effective_rank(&[0.0], 2.0), 0.0);
+        assert_eq!(participation_ratio_rank(&[0.0]), 0.0);
+        assert_eq!(effective_rank(&[3.0]), 1.0);
+        assert_eq!(stable_rank(&[]), 0.0);
+        assert_eq!(log_spectral_norm(&[0.0]), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_norms_match_matrix() {
+        let a = ndarray::array![[1.0, 2.0, 0.0], [0.0, 1.0, -3.0]];
+        let s = crate::singular_values(&a);
+        let frobenius = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!((frobenius_norm(&s) - frobenius).abs() < 1e-12);
+        assert!(spectral_norm(&s) <= frobenius && spectral_norm(&s) >= frobenius / 2.0_f64.sqrt());
+        assert!((stable_rank(&s) - (frobenius / spectral_norm(&s)).powi(2)).abs() < 1e-12);
+        assert!((log_frobenius_norm(&s) - 15.0_f64.log10()).abs() < 1e-12);
+        assert!((log_spectral_norm(&[10.0, 1.0]) - 2.0).abs() < 1e-12);
+    }
+}
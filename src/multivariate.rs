@@ -0,0 +1,685 @@
+//! Classical multivariate tests with random-matrix null distributions.
+//!
+//! MANOVA, multivariate regression and canonical correlation analysis all
+//! reduce to the eigenvalues of (H + E)^(-1) H for two independent Wishart
+//! matrices, a hypothesis matrix H ~ W_p(I, n) and an error matrix
+//! E ~ W_p(I, m). Their joint law is the Jacobi ensemble, and Johnstone
+//! (2008) showed that the logit of the largest root is Tracy-Widom after
+//! centering and scaling, with O(p^(-2/3)) error, so the extreme-root tests
+//! stay calibrated when p is comparable to the degrees of freedom.
+//!
+//! Tests of a covariance structure (H0: Sigma = sigma^2 I or Sigma = I) face
+//! the same problem: the classical chi-square calibration of the likelihood
+//! ratio assumes p fixed, and rejects almost always once p / n is not
+//! small. The versions here are centered and scaled by central limit
+//! theorems for linear spectral statistics under Marchenko-Pastur.
+//!
+//! Squared sample canonical correlations between independent Gaussian
+//! blocks are Jacobi-distributed too, with the Wachter law as their limit,
+//! so screening two high-dimensional datasets for a shared direction is
+//! Roy's test in disguise.
+
+use ndarray::{Array1, Array2, Axis};
+
+use crate::linalg::symmetric_eigen;
+use crate::special::normal_sf;
+use crate::spectrum::AsEigenvalues;
+use crate::{singular_values, symmetric_eigenvalues, tracy_widom_sf};
+
+/// Roots theta of det(H - theta (H + E)) = 0, the eigenvalues of (H + E)^(-1) H.
+///
+/// Computed as the eigenvalues of the symmetric (H + E)^(-1/2) H (H + E)^(-1/2).
+/// They lie in [0, 1]; the eigenvalues of E^(-1) H are theta / (1 - theta).
+///
+/// # Arguments
+///
+/// * `hypothesis` - p x p hypothesis sum-of-squares-and-products matrix H
+/// * `error` - p x p error (residual) sum-of-squares-and-products matrix E
+///
+/// # Returns
+///
+/// Roots in ascending order, or an empty vector if the shapes differ or
+/// H + E is not positive definite
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::manova_roots;
+///
+/// let h = array![[3.0, 0.0], [0.0, 1.0]];
+/// let e = array![[1.0, 0.0], [0.0, 1.0]];
+/// let roots = manova_roots(&h, &e);
+/// assert!((roots[0] - 0.5).abs() < 1e-12 && (roots[1] - 0.75).abs() < 1e-12);
+/// ```
+pub fn manova_roots(hypothesis: &Array2<f64>, error: &Array2<f64>) -> Vec<f64> {
+    let p = hypothesis.nrows();
+    if hypothesis.ncols() != p || error.dim() != (p, p) {
+        return vec![];
+    }
+    let (values, vectors) = symmetric_eigen(&(hypothesis + error));
+    if values.iter().any(|&v| v.is_nan() || v <= 0.0) {
+        return vec![];
+    }
+    let inverse_sqrt = Array1::from_iter(values.iter().map(|v| 1.0 / v.sqrt()));
+    let whitening = &vectors * &inverse_sqrt;
+    let whitening = whitening.dot(&vectors.t());
+    symmetric_eigenvalues(&whitening.dot(hypothesis).dot(&whitening))
+}
+
+/// Johnstone's (2008) centering and scaling of logit(theta_max).
+fn roy_logit_constants(p: usize, m: usize, n: usize) -> (f64, f64) {
+    let total = (m + n) as f64 - 1.0;
+    let angle = |k: usize| 2.0 * ((k as f64 - 0.5) / total).sqrt().asin();
+    let (gamma, phi) = (angle(n.min(p)), angle(n.max(p)));
+    let mu = 2.0 * (0.5 * (phi + gamma)).tan().ln();
+    let sigma_cubed =
+        16.0 / (total * total) / ((phi + gamma).sin().powi(2) * phi.sin() * gamma.sin());
+    (mu, sigma_cubed.cbrt())
+}
+
+/// Tracy-Widom approximation to P(theta_max > theta) for Roy's largest root.
+///
+/// theta_max is the largest eigenvalue of (H + E)^(-1) H with H ~ W_p(I, n)
+/// and E ~ W_p(I, m) independent. Johnstone (2008) maps it to the TW1
+/// scale through W = logit(theta_max) = ln(theta / (1 - theta)):
+///
+/// - sin^2(gamma / 2) = (min(n, p) - 1/2) / (m + n - 1),
+/// - sin^2(phi / 2) = (max(n, p) - 1/2) / (m + n - 1),
+/// - mu = 2 ln tan((phi + gamma) / 2),
+/// - sigma^3 = 16 / (m + n - 1)^2 / (sin^2(phi + gamma) sin(phi) sin(gamma)),
+///
+/// and (W - mu) / sigma is approximately TW1. The approximation is already
+/// accurate to about one percentage point in the 5% tail for p = 5.
+///
+/// # Arguments
+///
+/// * `theta` - Value of the largest root, in (0, 1)
+/// * `p` - Dimension
+/// * `m` - Error degrees of freedom, m >= p
+/// * `n` - Hypothesis degrees of freedom, n >= 1
+///
+/// # Returns
+///
+/// The survival probability, or NaN if p = 0, n = 0 or m < p
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::roy_largest_root_sf;
+///
+/// // Larger roots are less likely under the null.
+/// let (p, m, n) = (5, 40, 4);
+/// assert!(roy_largest_root_sf(0.6, p, m, n) < roy_largest_root_sf(0.4, p, m, n));
+/// assert!(roy_largest_root_sf(0.9, p, m, n) < 1e-6);
+/// ```
+pub fn roy_largest_root_sf(theta: f64, p: usize, m: usize, n: usize) -> f64 {
+    if p == 0 || n == 0 || m < p || theta.is_nan() {
+        return f64::NAN;
+    }
+    if theta <= 0.0 {
+        return 1.0;
+    }
+    if theta >= 1.0 {
+        return 0.0;
+    }
+    let (mu, sigma) = roy_logit_constants(p, m, n);
+    tracy_widom_sf(((theta / (1.0 - theta)).ln() - mu) / sigma, 1.0)
+}
+
+/// Result of Roy's largest root test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoyLargestRootTest {
+    /// Largest root theta_max of (H + E)^(-1) H
+    pub theta: f64,
+    /// logit(theta_max) centered and scaled to the TW1 scale
+    pub statistic: f64,
+    /// P(TW1 >= statistic) under the null H ~ W_p(I, n)
+    pub p_value: f64,
+}
+
+/// Roy's largest root test for MANOVA and multivariate regression.
+///
+/// In the linear model Y = X B + noise with p responses, H is the
+/// hypothesis sum-of-squares-and-products matrix of a linear hypothesis
+/// with n degrees of freedom (number of groups - 1 in one-way MANOVA), and
+/// E the residual matrix with m degrees of freedom (observations - rank of
+/// X). Under the null, with Gaussian noise, H and E are independent Wishart
+/// matrices sharing the noise covariance, which cancels from the roots.
+/// Roy's test rejects for a large theta_max and is the most powerful of the
+/// classical tests against a single dominant direction of effect; the null
+/// is approximated as in [`roy_largest_root_sf`].
+///
+/// # Arguments
+///
+/// * `roots` - Eigenvalues of (H + E)^(-1) H (see [`manova_roots`]), any order
+/// * `p` - Dimension
+/// * `m` - Error degrees of freedom, m >= p
+/// * `n` - Hypothesis degrees of freedom, n >= 1
+///
+/// # Returns
+///
+/// `None` if the degrees of freedom are invalid or no root lies in (0, 1)
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rmt::{manova_roots, roy_largest_root_test, sample_wishart_with};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let (p, m, n) = (10, 60, 4);
+/// let e = sample_wishart_with(&mut rng, m, p);
+/// let h = sample_wishart_with(&mut rng, n, p);
+/// let null = roy_largest_root_test(&manova_roots(&h, &e), p, m, n).unwrap();
+/// assert!(null.p_value > 0.01);
+///
+/// // An effect along the first coordinate.
+/// let mut shifted = h.clone();
+/// shifted[[0, 0]] += 100.0;
+/// let effect = roy_largest_root_test(&manova_roots(&shifted, &e), p, m, n).unwrap();
+/// assert!(effect.p_value < 1e-4);
+/// ```
+pub fn roy_largest_root_test<E: AsEigenvalues + ?Sized>(
+    roots: &E,
+    p: usize,
+    m: usize,
+    n: usize,
+) -> Option<RoyLargestRootTest> {
+    let roots: &[f64] = &roots.as_eigenvalues();
+    if p == 0 || n == 0 || m < p {
+        return None;
+    }
+    let theta = roots
+        .iter()
+        .copied()
+        .filter(|t| *t > 0.0 && *t < 1.0)
+        .reduce(f64::max)?;
+    let (mu, sigma) = roy_logit_constants(p, m, n);
+    let statistic = ((theta / (1.0 - theta)).ln() - mu) / sigma;
+    Some(RoyLargestRootTest {
+        theta,
+        statistic,
+        p_value: tracy_widom_sf(statistic, 1.0),
+    })
+}
+
+/// Sample canonical correlations between the columns of two data matrices.
+///
+/// Both blocks are column-centered and orthonormalized (Q_x, Q_y); the
+/// canonical correlations are the singular values of Q_x^T Q_y. Directions
+/// with relative variance below 1e-12 are dropped, so collinear columns
+/// reduce the count instead of producing spurious correlations of 1.
+///
+/// # Arguments
+///
+/// * `x` - N x p data matrix, one observation per row
+/// * `y` - N x q data matrix on the same observations
+///
+/// # Returns
+///
+/// Canonical correlations in [0, 1], descending, min(rank x, rank y) of them
+///
+/// # Panics
+///
+/// If `x` and `y` have different numbers of rows.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::canonical_correlations;
+///
+/// let x = array![[1.0, 0.0], [2.0, 1.0], [3.0, 0.0], [4.0, 1.0]];
+/// // y's first column is a linear function of x's first column.
+/// let y = array![[2.0], [4.0], [6.0], [8.0]];
+/// let r = canonical_correlations(&x, &y);
+/// assert_eq!(r.len(), 1);
+/// assert!((r[0] - 1.0).abs() < 1e-12);
+/// ```
+pub fn canonical_correlations(x: &Array2<f64>, y: &Array2<f64>) -> Vec<f64> {
+    assert_eq!(x.nrows(), y.nrows(), "x and y need the same observations");
+    let (qx, qy) = (orthonormal_columns(x), orthonormal_columns(y));
+    if qx.ncols() == 0 || qy.ncols() == 0 {
+        return vec![];
+    }
+    singular_values(&qx.t().dot(&qy))
+        .into_iter()
+        .map(|r| r.min(1.0))
+        .collect()
+}
+
+/// Orthonormal basis of the column-centered data's column space.
+fn orthonormal_columns(data: &Array2<f64>) -> Array2<f64> {
+    let Some(mean) = data.mean_axis(Axis(0)) else {
+        return Array2::zeros((0, 0));
+    };
+    let centered = data - &mean;
+    let (values, vectors) = symmetric_eigen(&centered.t().dot(&centered));
+    let largest = values.last().copied().unwrap_or(0.0);
+    let kept: Vec<usize> = (0..values.len())
+        .filter(|&i| values[i] > 1e-12 * largest && largest > 0.0)
+        .collect();
+    let basis = Array2::from_shape_fn((data.ncols(), kept.len()), |(i, j)| {
+        vectors[[i, kept[j]]] / values[kept[j]].sqrt()
+    });
+    centered.dot(&basis)
+}
+
+/// Support of the Wachter law for squared canonical correlations.
+///
+/// For independent Gaussian blocks of p and q variables observed N times,
+/// with c_1 = p / N, c_2 = q / N and c_1 + c_2 < 1, the squared sample
+/// canonical correlations fill
+/// [(sqrt(c_1 (1 - c_2)) - sqrt(c_2 (1 - c_1)))^2, (sqrt(c_1 (1 - c_2)) + sqrt(c_2 (1 - c_1)))^2]
+/// (Wachter 1980), so a largest correlation near the upper edge is noise
+/// even though it may look large: with p = q = N / 4 the edge is r = 0.87.
+///
+/// # Arguments
+///
+/// * `p` - Variables in the first block
+/// * `q` - Variables in the second block
+/// * `n` - Observations N
+///
+/// # Returns
+///
+/// (lower, upper) edge for r^2, or NaN edges unless p, q >= 1 and p + q < N
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::wachter_support;
+///
+/// let (_, upper) = wachter_support(100, 100, 400);
+/// assert!((upper.sqrt() - 0.75_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn wachter_support(p: usize, q: usize, n: usize) -> (f64, f64) {
+    if p == 0 || q == 0 || p + q >= n {
+        return (f64::NAN, f64::NAN);
+    }
+    let (c1, c2) = (p as f64 / n as f64, q as f64 / n as f64);
+    let (a, b) = ((c1 * (1.0 - c2)).sqrt(), (c2 * (1.0 - c1)).sqrt());
+    ((a - b).powi(2), (a + b).powi(2))
+}
+
+/// Result of the largest canonical correlation test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CanonicalCorrelationTest {
+    /// Largest sample canonical correlation
+    pub r_max: f64,
+    /// logit(r_max^2) centered and scaled to the TW1 scale
+    pub statistic: f64,
+    /// P(TW1 >= statistic) under independence of the two blocks
+    pub p_value: f64,
+}
+
+/// Test whether the largest canonical correlation exceeds what independent blocks produce.
+///
+/// For independent Gaussian X (N x p) and Y (N x q), after centering, the
+/// squared canonical correlations are the roots of (H + E)^(-1) H with
+/// H ~ W_p(I, q) and E ~ W_p(I, N - 1 - q), so r_max^2 follows Roy's
+/// largest root law with (p, m, n) = (p, N - 1 - q, q) and the Tracy-Widom
+/// approximation of [`roy_largest_root_sf`] applies (Johnstone 2008). The
+/// roles of p and q are interchangeable.
+///
+/// # Arguments
+///
+/// * `correlations` - Canonical correlations (see [`canonical_correlations`]), any order
+/// * `n` - Observations N (the data are assumed centered, using one degree of freedom)
+/// * `p` - Variables in the first block
+/// * `q` - Variables in the second block
+///
+/// # Returns
+///
+/// `None` unless p, q >= 1 and p + q < N, or if no correlation lies in (0, 1)
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{canonical_correlation_test, canonical_correlations};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+/// let (n, p, q) = (200, 20, 30);
+/// let x = Array2::from_shape_fn((n, p), |_| StandardNormal.sample(&mut rng));
+/// let mut y: Array2<f64> = Array2::from_shape_fn((n, q), |_| StandardNormal.sample(&mut rng));
+/// let r = canonical_correlations(&x, &y);
+/// // Independent blocks: r_max is large (near the Wachter edge) but not significant.
+/// assert!(r[0] > 0.6);
+/// assert!(canonical_correlation_test(&r, n, p, q).unwrap().p_value > 0.01);
+///
+/// // A shared signal in one column of each block.
+/// for i in 0..n {
+///     y[[i, 0]] += x[[i, 0]];
+/// }
+/// let r = canonical_correlations(&x, &y);
+/// assert!(canonical_correlation_test(&r, n, p, q).unwrap().p_value < 1e-6);
+/// ```
+pub fn canonical_correlation_test(
+    correlations: &[f64],
+    n: usize,
+    p: usize,
+    q: usize,
+) -> Option<CanonicalCorrelationTest> {
+    if p == 0 || q == 0 || p + q >= n {
+        return None;
+    }
+    let r_max = correlations
+        .iter()
+        .copied()
+        .filter(|r| *r > 0.0 && *r < 1.0)
+        .reduce(f64::max)?;
+    let theta = r_max * r_max;
+    let (mu, sigma) = roy_logit_constants(p, n - 1 - q, q);
+    let statistic = ((theta / (1.0 - theta)).ln() - mu) / sigma;
+    Some(CanonicalCorrelationTest {
+        r_max,
+        statistic,
+        p_value: tracy_widom_sf(statistic, 1.0),
+    })
+}
+
+/// Statistic used by [`sphericity_test`] and [`identity_covariance_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CovarianceStatistic {
+    /// Likelihood ratio with the Bai, Jiang, Yao & Zheng (2009) correction; needs p < n
+    LikelihoodRatio,
+    /// Frobenius distance from the identity: John's U for sphericity and
+    /// Ledoit & Wolf's W for the identity (2002); any p / n
+    Frobenius,
+}
+
+/// Result of a high-dimensional covariance structure test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CovarianceTest {
+    /// Uncorrected statistic (likelihood ratio, U or W)
+    pub statistic: f64,
+    /// Statistic centered and scaled to be asymptotically N(0, 1) under the null
+    pub z: f64,
+    /// P(N(0, 1) >= z); both statistics grow under the alternative
+    pub p_value: f64,
+}
+
+/// Test H0: Sigma = sigma^2 I (sphericity, sigma^2 unknown) from a sample covariance spectrum.
+///
+/// With a_1 and a_2 the means of lambda_i and lambda_i^2, and y = p / n:
+///
+/// - [`CovarianceStatistic::LikelihoodRatio`]: L = p ln a_1 - sum ln lambda_i.
+///   Under H0, L - p F(y) -> N(-ln(1 - y) / 2, -2 ln(1 - y) - 2y) with
+///   F(y) = 1 + (1 - y) ln(1 - y) / y. The ratio a_1 is consistent to O(1 / p),
+///   so this is the same limit as the identity test (Bai et al. 2009; Wang &
+///   Yao 2013); the classical -2 ln Lambda ~ chi^2 calibration is badly
+///   oversized unless y is tiny.
+/// - [`CovarianceStatistic::Frobenius`]: John's U = a_2 / a_1^2 - 1, with
+///   n U - p -> N(1, 4) for any limiting y (Ledoit & Wolf 2002), so it also
+///   works when p > n.
+///
+/// Both are scale invariant.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues of S = E / n, with E the p x p scatter matrix
+/// * `n` - Degrees of freedom of E (observations, minus one if the data were centered)
+/// * `statistic` - Which statistic to use
+///
+/// # Returns
+///
+/// `None` for fewer than two eigenvalues, non-finite values, a zero
+/// spectrum, or (likelihood ratio) p >= n or a singular S
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{sample_wishart_with, sphericity_test, symmetric_eigenvalues, CovarianceStatistic};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let (n, p) = (200, 100);
+/// // Scale does not matter under sphericity.
+/// let s = sample_wishart_with(&mut rng, n, p) * (3.0 / n as f64);
+/// let eigenvalues = symmetric_eigenvalues(&s);
+/// for statistic in [CovarianceStatistic::LikelihoodRatio, CovarianceStatistic::Frobenius] {
+///     assert!(sphericity_test(&eigenvalues, n, statistic).unwrap().p_value > 0.01);
+/// }
+/// ```
+pub fn sphericity_test<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+    statistic: CovarianceStatistic,
+) -> Option<CovarianceTest> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let (p, a1, a2) = spectrum_means(eigenvalues, n)?;
+    match statistic {
+        CovarianceStatistic::LikelihoodRatio => {
+            let log_det = log_determinant(eigenvalues, n)?;
+            corrected_likelihood_ratio(p * a1.ln() - log_det, p, n as f64)
+        }
+        CovarianceStatistic::Frobenius => Some(frobenius_test(a2 / (a1 * a1) - 1.0, p, n as f64)),
+    }
+}
+
+/// Test H0: Sigma = I from a sample covariance spectrum.
+///
+/// With a_1 and a_2 the means of lambda_i and lambda_i^2, and y = p / n:
+///
+/// - [`CovarianceStatistic::LikelihoodRatio`]: L = sum (lambda_i - ln lambda_i - 1),
+///   with L - p F(y) -> N(-ln(1 - y) / 2, -2 ln(1 - y) - 2y) and
+///   F(y) = 1 + (1 - y) ln(1 - y) / y (Bai, Jiang, Yao & Zheng 2009).
+/// - [`CovarianceStatistic::Frobenius`]: Ledoit & Wolf's (2002)
+///   W = a_2 - 2 a_1 + 1 - y a_1^2 + y, with n W - p -> N(1, 4) for any
+///   limiting y.
+///
+/// Test a known covariance Sigma_0 by applying it to the spectrum of
+/// Sigma_0^(-1/2) S Sigma_0^(-1/2).
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Eigenvalues of S = E / n, with E the p x p scatter matrix
+/// * `n` - Degrees of freedom of E (observations, minus one if the data were centered)
+/// * `statistic` - Which statistic to use
+///
+/// # Returns
+///
+/// `None` for fewer than two eigenvalues, non-finite values, a zero
+/// spectrum, or (likelihood ratio) p >= n or a singular S
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{identity_covariance_test, sample_wishart_with, symmetric_eigenvalues, CovarianceStatistic};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+/// let (n, p) = (200, 100);
+/// let s = sample_wishart_with(&mut rng, n, p) / n as f64;
+/// let null = symmetric_eigenvalues(&s);
+/// let test = identity_covariance_test(&null, n, CovarianceStatistic::LikelihoodRatio).unwrap();
+/// assert!(test.p_value > 0.01);
+/// // Variance 1.5 is spherical but not the identity.
+/// let inflated: Vec<f64> = null.iter().map(|l| 1.5 * l).collect();
+/// let test = identity_covariance_test(&inflated, n, CovarianceStatistic::Frobenius).unwrap();
+/// assert!(test.p_value < 1e-6);
+/// ```
+pub fn identity_covariance_test<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+    statistic: CovarianceStatistic,
+) -> Option<CovarianceTest> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let (p, a1, a2) = spectrum_means(eigenvalues, n)?;
+    match statistic {
+        CovarianceStatistic::LikelihoodRatio => {
+            let log_det = log_determinant(eigenvalues, n)?;
+            corrected_likelihood_ratio(p * (a1 - 1.0) - log_det, p, n as f64)
+        }
+        CovarianceStatistic::Frobenius => {
+            let y = p / n as f64;
+            let w = a2 - 2.0 * a1 + 1.0 - y * a1 * a1 + y;
+            Some(frobenius_test(w, p, n as f64))
+        }
+    }
+}
+
+/// (p, mean lambda, mean lambda^2) of a finite spectrum; rounding-level
+/// negative eigenvalues of a singular S count as zero.
+fn spectrum_means(eigenvalues: &[f64], n: usize) -> Option<(f64, f64, f64)> {
+    if eigenvalues.len() < 2 || n == 0 || !eigenvalues.iter().all(|l| l.is_finite()) {
+        return None;
+    }
+    let p = eigenvalues.len() as f64;
+    let a1 = eigenvalues.iter().map(|l| l.max(0.0)).sum::<f64>() / p;
+    let a2 = eigenvalues.iter().map(|l| l.max(0.0).powi(2)).sum::<f64>() / p;
+    (a1 > 0.0).then_some((p, a1, a2))
+}
+
+/// sum ln lambda_i, if p < n and every eigenvalue is positive.
+fn log_determinant(eigenvalues: &[f64], n: usize) -> Option<f64> {
+    if eigenvalues.len() >= n || eigenvalues.iter().any(|&l| l <= 0.0) {
+        return None;
+    }
+    Some(eigenvalues.iter().map(|l| l.ln()).sum())
+}
+
+/// Bai et al. (2009) centering and scaling of a likelihood-ratio statistic.
+fn corrected_likelihood_ratio(statistic: f64, p: f64, n: f64) -> Option<CovarianceTest> {
+    let y = p / n;
+    let log_complement = (-y).ln_1p();
+    let centering = p * (1.0 + (1.0 - y) * log_complement / y) - 0.5 * log_complement;
+    let variance = -2.0 * log_complement - 2.0 * y;
+    let z = (statistic - centering) / variance.sqrt();
+    Some(CovarianceTest {
+        statistic,
+        z,
+        p_value: normal_sf(z),
+    })
+}
+
+/// Ledoit & Wolf (2002): n U - p -> N(1, 4).
+fn frobenius_test(statistic: f64, p: f64, n: f64) -> CovarianceTest {
+    let z = (n * statistic - p - 1.0) / 2.0;
+    CovarianceTest {
+        statistic,
+        z,
+        p_value: normal_sf(z),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_roy_largest_root_null_size() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(4);
+        let (p, m, n) = (5, 20, 5);
+        let trials = 1000;
+        let mut rejections = 0;
+        for _ in 0..trials {
+            let e = crate::sample_wishart_with(&mut rng, m, p);
+            let h = crate::sample_wishart_with(&mut rng, n, p);
+            let roots = manova_roots(&h, &e);
+            assert_eq!(roots.len(), p);
+            let test = roy_largest_root_test(&roots, p, m, n).unwrap();
+            assert!((roy_largest_root_sf(test.theta, p, m, n) - test.p_value).abs() < 1e-12);
+            if test.p_value < 0.05 {
+                rejections += 1;
+            }
+        }
+        let size = rejections as f64 / trials as f64;
+        assert!((0.025..0.08).contains(&size), "size {size}");
+
+        assert!(roy_largest_root_test(&[0.5], 5, 4, 2).is_none());
+        assert!(roy_largest_root_test(&[0.0, 1.0], 5, 20, 2).is_none());
+        assert!(manova_roots(&Array2::zeros((2, 2)), &Array2::zeros((2, 2))).is_empty());
+    }
+
+    #[test]
+    fn test_covariance_structure_tests() {
+        use CovarianceStatistic::{Frobenius, LikelihoodRatio};
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(6);
+        let (n, p) = (80, 40);
+        let trials = 300;
+        let mut rejections = [0; 4];
+        for _ in 0..trials {
+            let s = crate::sample_wishart_with(&mut rng, n, p) / n as f64;
+            let eigenvalues = symmetric_eigenvalues(&s);
+            let tests = [
+                sphericity_test(&eigenvalues, n, LikelihoodRatio),
+                sphericity_test(&eigenvalues, n, Frobenius),
+                identity_covariance_test(&eigenvalues, n, LikelihoodRatio),
+                identity_covariance_test(&eigenvalues, n, Frobenius),
+            ];
+            for (count, test) in rejections.iter_mut().zip(tests) {
+                if test.unwrap().p_value < 0.05 {
+                    *count += 1;
+                }
+            }
+        }
+        for count in rejections {
+            let size = count as f64 / trials as f64;
+            assert!((0.02..0.1).contains(&size), "size {size}");
+        }
+
+        // Variances spread over [0.5, 1.5] are neither spherical nor the identity.
+        let s = crate::sample_wishart_with(&mut rng, n, p) / n as f64;
+        let scales = Array1::from_iter((0..p).map(|i| (0.5 + i as f64 / p as f64).sqrt()));
+        let s = Array2::from_shape_fn((p, p), |(i, j)| s[[i, j]] * scales[i] * scales[j]);
+        let eigenvalues = symmetric_eigenvalues(&s);
+        for statistic in [LikelihoodRatio, Frobenius] {
+            assert!(sphericity_test(&eigenvalues, n, statistic).unwrap().p_value < 0.01);
+            assert!(
+                identity_covariance_test(&eigenvalues, n, statistic)
+                    .unwrap()
+                    .p_value
+                    < 0.01
+            );
+        }
+
+        // The likelihood ratio needs p < n; the Frobenius statistics do not.
+        let wide = symmetric_eigenvalues(&crate::sample_wishart_with(&mut rng, 20, 40));
+        assert!(sphericity_test(&wide, 20, LikelihoodRatio).is_none());
+        assert!(sphericity_test(&wide, 20, Frobenius).is_some());
+        assert!(identity_covariance_test(&[1.0], 20, Frobenius).is_none());
+        assert!(identity_covariance_test(&[0.0, 0.0], 20, Frobenius).is_none());
+    }
+
+    #[test]
+    fn test_canonical_correlation_null() {
+        use rand_distr::{Distribution, StandardNormal};
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(8);
+        let (n, p, q) = (60, 5, 8);
+        let trials = 600;
+        let mut rejections = 0;
+        let mut mean_r2 = 0.0;
+        for _ in 0..trials {
+            let x = Array2::from_shape_fn((n, p), |_| StandardNormal.sample(&mut rng));
+            let y = Array2::from_shape_fn((n, q), |_| StandardNormal.sample(&mut rng));
+            let r = canonical_correlations(&x, &y);
+            assert_eq!(r.len(), p);
+            mean_r2 += r.iter().map(|r| r * r).sum::<f64>() / trials as f64;
+            if canonical_correlation_test(&r, n, p, q).unwrap().p_value < 0.05 {
+                rejections += 1;
+            }
+        }
+        let size = rejections as f64 / trials as f64;
+        assert!((0.025..0.085).contains(&size), "size {size}");
+        // E sum r_i^2 = p q / (N - 1) under independence.
+        let expected = (p * q) as f64 / (n - 1) as f64;
+        assert!((mean_r2 / expected - 1.0).abs() < 0.05, "{mean_r2}");
+
+        let (lower, upper) = wachter_support(p, q, n);
+        assert!(lower > 0.0 && upper < 1.0);
+        assert!(wachter_support(30, 30, 60).0.is_nan());
+        assert!(canonical_correlation_test(&[0.5], 10, 5, 5).is_none());
+    }
+}
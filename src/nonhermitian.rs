@@ -0,0 +1,932 @@
+//! Spectra of non-Hermitian random matrices.
+//!
+//! Eigenvalues of non-symmetric matrices fill regions of the complex plane
+//! rather than intervals: the real Ginibre matrix G / sqrt(n) with i.i.d.
+//! N(0, 1) entries has its eigenvalues uniform on the unit disk (the
+//! circular law), apart from an excess of O(sqrt(n)) exactly real ones.
+//! The estimators here are the planar counterparts of
+//! [`crate::empirical_spectral_density`] and [`crate::kernel_spectral_density`],
+//! all normalized as densities per unit area, and [`circular_law_test`]
+//! checks a spectrum against the uniform disk. Use
+//! [`crate::general_eigenvalues`] to obtain the spectra.
+//!
+//! Eigenvalues of strongly non-normal matrices are fragile: perturbations of
+//! size epsilon can move them by far more than epsilon. The
+//! epsilon-pseudospectrum {z : sigma_min(A - z I) < epsilon}, computed by
+//! [`pseudospectrum`], shows where they can go (Trefethen & Embree 2005).
+//!
+//! [`SingleRing`] predicts the eigenvalue law of U D V with Haar U and V
+//! from the singular values D alone: an annulus, whose radii and radial
+//! profile follow from the S-transform of D^2 (Haagerup & Larsen 2000;
+//! Guionnet, Krishnapur & Zeitouni 2011).
+
+use std::f64::consts::PI;
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::error::Error;
+use crate::gof::{ks_test, GoodnessOfFit};
+use crate::linalg::{orthogonal_hessenberg, singular_values, symmetric_eigenvalues};
+
+/// Relative change in the Ritz value at which inverse Lanczos stops.
+const LANCZOS_TOLERANCE: f64 = 1e-10;
+
+/// Two-dimensional histogram of complex eigenvalues.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexSpectralDensity {
+    /// Bin centers along the real axis
+    pub re: Vec<f64>,
+    /// Bin centers along the imaginary axis
+    pub im: Vec<f64>,
+    /// `density[[i, j]]` is the density per unit area at (`re[i]`, `im[j]`)
+    pub density: Array2<f64>,
+}
+
+/// Empirical spectral density of complex eigenvalues via a 2D histogram.
+///
+/// The grid spans the bounding box of the eigenvalues with `bins` bins per
+/// axis. An axis of zero extent (for instance an all-real spectrum) is
+/// widened to unit length around its value, so densities stay finite.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Complex eigenvalue samples
+/// * `bins` - Number of bins along each axis
+///
+/// # Returns
+///
+/// Bin centers and densities; empty if there are no eigenvalues or bins
+///
+/// # Example
+///
+/// ```rust
+/// use num_complex::Complex64;
+/// use rmt::complex_spectral_density;
+///
+/// let eigenvalues: Vec<Complex64> = (0..400)
+///     .map(|k| Complex64::new((k % 20) as f64 + 0.5, (k / 20) as f64 + 0.5))
+///     .collect();
+/// let esd = complex_spectral_density(&eigenvalues, 4);
+/// // A uniform grid of points over a 19 x 19 box.
+/// assert!(esd.density.iter().all(|&d| (d - 1.0 / 361.0).abs() < 0.2 / 361.0));
+/// ```
+pub fn complex_spectral_density(eigenvalues: &[Complex64], bins: usize) -> ComplexSpectralDensity {
+    if eigenvalues.is_empty() || bins == 0 {
+        return ComplexSpectralDensity {
+            re: vec![],
+            im: vec![],
+            density: Array2::zeros((0, 0)),
+        };
+    }
+    let axis = |part: fn(&Complex64) -> f64| {
+        let min = eigenvalues.iter().map(part).fold(f64::INFINITY, f64::min);
+        let max = eigenvalues
+            .iter()
+            .map(part)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if (max - min).abs() < 1e-10 {
+            (min - 0.5, 1.0 / bins as f64)
+        } else {
+            (min, (max - min) / bins as f64)
+        }
+    };
+    let (re_min, re_width) = axis(|z| z.re);
+    let (im_min, im_width) = axis(|z| z.im);
+    let index = |x: f64, min: f64, width: f64| (((x - min) / width).floor() as usize).min(bins - 1);
+
+    let mut density = Array2::zeros((bins, bins));
+    for z in eigenvalues {
+        density[[index(z.re, re_min, re_width), index(z.im, im_min, im_width)]] += 1.0;
+    }
+    density /= eigenvalues.len() as f64 * re_width * im_width;
+    let centers =
+        |min: f64, width: f64| (0..bins).map(|i| min + (i as f64 + 0.5) * width).collect();
+    ComplexSpectralDensity {
+        re: centers(re_min, re_width),
+        im: centers(im_min, im_width),
+        density,
+    }
+}
+
+/// Gaussian kernel density estimate of complex eigenvalues at given points.
+///
+/// rho(z) = (1 / n) sum_k exp(-|z - lambda_k|^2 / (2 h^2)) / (2 pi h^2).
+/// Without an explicit bandwidth, Scott's rule for two dimensions
+/// h = sigma n^(-1/6) is used, with sigma^2 the mean of the variances of the
+/// real and imaginary parts.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Complex eigenvalue samples
+/// * `points` - Evaluation points
+/// * `bandwidth` - Kernel width h, or `None` for Scott's rule
+///
+/// # Returns
+///
+/// Density per unit area at each point (zeros without eigenvalues)
+pub fn complex_kernel_density(
+    eigenvalues: &[Complex64],
+    points: &[Complex64],
+    bandwidth: Option<f64>,
+) -> Vec<f64> {
+    let n = eigenvalues.len();
+    if n == 0 {
+        return vec![0.0; points.len()];
+    }
+    let h = bandwidth.unwrap_or_else(|| {
+        let mean = eigenvalues.iter().sum::<Complex64>() / n as f64;
+        let variance = eigenvalues
+            .iter()
+            .map(|z| (z - mean).norm_sqr())
+            .sum::<f64>()
+            / n as f64;
+        (0.5 * variance).sqrt().max(f64::MIN_POSITIVE) * (n as f64).powf(-1.0 / 6.0)
+    });
+    let norm = 1.0 / (2.0 * PI * h * h * n as f64);
+    points
+        .iter()
+        .map(|z| {
+            norm * eigenvalues
+                .iter()
+                .map(|l| (-(z - l).norm_sqr() / (2.0 * h * h)).exp())
+                .sum::<f64>()
+        })
+        .collect()
+}
+
+/// Radial profile of complex eigenvalues: density per unit area in annuli about 0.
+///
+/// Annuli of equal width span radii 0 to max |lambda|; each density is
+/// count / (n pi (r_outer^2 - r_inner^2)). For a rotation-invariant law this
+/// is the planar density as a function of |z|: 1 / pi inside the unit disk
+/// for the circular law.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Complex eigenvalue samples
+/// * `bins` - Number of annuli
+///
+/// # Returns
+///
+/// (annulus mid-radii, densities)
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{general_eigenvalues, radial_spectral_density};
+///
+/// // Real Ginibre: the circular law predicts density 1 / pi on the unit disk.
+/// let n = 150;
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let g = Array2::from_shape_fn((n, n), |_| {
+///     let x: f64 = StandardNormal.sample(&mut rng);
+///     x / (n as f64).sqrt()
+/// });
+/// let (radii, density) = radial_spectral_density(&general_eigenvalues(&g), 5);
+/// for (r, d) in radii.iter().zip(&density).take(4) {
+///     assert!((d * std::f64::consts::PI - 1.0).abs() < 0.35, "r = {r}: {d}");
+/// }
+/// ```
+pub fn radial_spectral_density(eigenvalues: &[Complex64], bins: usize) -> (Vec<f64>, Vec<f64>) {
+    if eigenvalues.is_empty() || bins == 0 {
+        return (vec![], vec![]);
+    }
+    let max = eigenvalues.iter().map(|z| z.norm()).fold(0.0, f64::max);
+    let width = if max > 0.0 { max / bins as f64 } else { 1.0 };
+    let mut counts = vec![0usize; bins];
+    for z in eigenvalues {
+        counts[((z.norm() / width).floor() as usize).min(bins - 1)] += 1;
+    }
+    let n = eigenvalues.len() as f64;
+    let radii = (0..bins).map(|i| (i as f64 + 0.5) * width).collect();
+    let densities = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let area = PI * width * width * ((i + 1).pow(2) - i.pow(2)) as f64;
+            c as f64 / (n * area)
+        })
+        .collect();
+    (radii, densities)
+}
+
+/// Comparison of a complex spectrum with the circular law.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircularLawTest {
+    /// KS test of the moduli |z| against the radial CDF r^2 / R^2
+    pub radial: GoodnessOfFit,
+    /// Largest modulus max |z|
+    pub spectral_radius: f64,
+    /// Fraction of eigenvalues with |z| > R
+    pub outside_fraction: f64,
+    /// Mean distance R - |z| to the boundary, in units of its circular-law mean R / 3
+    pub boundary_distance: f64,
+    /// z-score of the mean boundary distance under i.i.d. uniform points
+    pub boundary_z: f64,
+}
+
+/// Goodness-of-fit test of complex eigenvalues against the uniform disk of radius R.
+///
+/// Under the circular law |z|^2 / R^2 is uniform on [0, 1], which the
+/// radial KS test checks; it is blind to the angular distribution, so it
+/// also accepts any rotation-invariant law with the same radial profile.
+/// The boundary statistics target the edge, where non-normal perturbations
+/// and heavy-tailed entries show first: the mean distance to the circle
+/// (R / 3 for the disk, variance R^2 / 18 per point), the spectral radius
+/// (1 + O(sqrt(ln n / n)) for Ginibre with R = 1), and the fraction of
+/// outliers beyond R. As with [`crate::ks_test`], eigenvalues repel, so the i.i.d.
+/// p-value and z-score are conservative.
+///
+/// For an n x n matrix with i.i.d. entries of variance sigma^2, R = sigma sqrt(n).
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Complex eigenvalues
+/// * `radius` - Radius R of the predicted disk
+///
+/// # Returns
+///
+/// `None` without eigenvalues or for a non-positive radius
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{circular_law_test, general_eigenvalues};
+///
+/// let n = 150;
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+/// let g = Array2::from_shape_fn((n, n), |_| StandardNormal.sample(&mut rng));
+/// let eigenvalues = general_eigenvalues(&g);
+/// let fit = circular_law_test(&eigenvalues, (n as f64).sqrt()).unwrap();
+/// assert!(fit.radial.p_value > 0.05);
+/// assert!((fit.boundary_distance - 1.0).abs() < 0.1);
+/// // The wrong radius is rejected.
+/// assert!(circular_law_test(&eigenvalues, 1.2 * (n as f64).sqrt()).unwrap().radial.p_value < 1e-3);
+/// ```
+pub fn circular_law_test(eigenvalues: &[Complex64], radius: f64) -> Option<CircularLawTest> {
+    if !(radius > 0.0 && radius.is_finite()) {
+        return None;
+    }
+    let moduli: Vec<f64> = eigenvalues.iter().map(|z| z.norm()).collect();
+    let radial = ks_test(&moduli, |r| (r / radius).powi(2).clamp(0.0, 1.0))?;
+    let n = moduli.len() as f64;
+    let mean_distance = moduli.iter().map(|r| radius - r).sum::<f64>() / n;
+    Some(CircularLawTest {
+        radial,
+        spectral_radius: moduli.iter().fold(0.0, |m, &r| m.max(r)),
+        outside_fraction: moduli.iter().filter(|&&r| r > radius).count() as f64 / n,
+        boundary_distance: mean_distance / (radius / 3.0),
+        boundary_z: (mean_distance - radius / 3.0) / (radius / (18.0 * n).sqrt()),
+    })
+}
+
+/// How [`pseudospectrum`] computes sigma_min(A - z I).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PseudospectrumMethod {
+    /// All singular values of A - z I (as a real 2n x 2n matrix) at every
+    /// point: O(n^3) each. Without the `faer` feature the singular values
+    /// come from the Gram matrix, so values below ~1e-8 ||A|| are noise.
+    Dense,
+    /// One orthogonal reduction to Hessenberg form, then inverse Lanczos on
+    /// ((H - z I)^* (H - z I))^(-1) with O(n^2) Hessenberg solves per step
+    /// (Trefethen 1999; the EigTool algorithm).
+    InverseLanczos {
+        /// Maximum Lanczos steps per grid point (15-30 is typical)
+        iterations: usize,
+    },
+}
+
+/// sigma_min(A - z I) over a grid, with membership in epsilon-pseudospectra.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pseudospectrum {
+    /// Smallest singular value of A - z I at each grid point
+    pub sigma_min: Vec<f64>,
+    /// Levels epsilon, in the order given
+    pub epsilons: Vec<f64>,
+    /// `inside[[k, i]]` is true if grid point i lies in the `epsilons[k]`-pseudospectrum
+    pub inside: Array2<bool>,
+}
+
+/// epsilon-pseudospectra of a real square matrix on a grid of complex points.
+///
+/// The epsilon-pseudospectrum is the set of z with sigma_min(A - z I) <
+/// epsilon: the union of the spectra of all A + E with ||E||_2 < epsilon.
+/// For a normal matrix it is the union of epsilon-disks around the
+/// eigenvalues; for non-normal ones it can be far larger, which is when
+/// eigenvalues alone mislead. Contour `sigma_min` on a lattice for the
+/// usual picture. With the `parallel` feature the grid is evaluated on the
+/// rayon pool.
+///
+/// # Arguments
+///
+/// * `a` - Square n x n matrix
+/// * `grid` - Points z at which to evaluate
+/// * `epsilons` - Levels epsilon
+/// * `method` - Dense SVD or inverse Lanczos
+///
+/// # Returns
+///
+/// sigma_min per grid point and the membership table; NaN values if `a`
+/// is not square
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use num_complex::Complex64;
+/// use rmt::{pseudospectrum, PseudospectrumMethod};
+///
+/// // A Jordan block: the only eigenvalue is 0, yet a perturbation of size
+/// // 1e-8 moves it by ~1e-1, so z = 0.05 is in the 1e-6-pseudospectrum.
+/// let n = 8;
+/// let jordan = Array2::from_shape_fn((n, n), |(i, j)| if j == i + 1 { 1.0 } else { 0.0 });
+/// let grid = [Complex64::new(0.05, 0.0), Complex64::new(2.0, 0.0)];
+/// let method = PseudospectrumMethod::InverseLanczos { iterations: 20 };
+/// let ps = pseudospectrum(&jordan, &grid, &[1e-6], method);
+/// assert!(ps.inside[[0, 0]] && !ps.inside[[0, 1]]);
+/// // Far away, sigma_min(J - z I) >= |z| - ||J|| = 1.
+/// assert!(ps.sigma_min[1] >= 1.0);
+/// ```
+pub fn pseudospectrum(
+    a: &Array2<f64>,
+    grid: &[Complex64],
+    epsilons: &[f64],
+    method: PseudospectrumMethod,
+) -> Pseudospectrum {
+    let square = a.nrows() == a.ncols();
+    let hessenberg = match method {
+        PseudospectrumMethod::InverseLanczos { .. } if square => orthogonal_hessenberg(a),
+        _ => vec![],
+    };
+    let sigma = |z: &Complex64| {
+        if !square {
+            return f64::NAN;
+        }
+        match method {
+            PseudospectrumMethod::Dense => dense_sigma_min(a, *z),
+            PseudospectrumMethod::InverseLanczos { iterations } => {
+                inverse_lanczos_sigma_min(&hessenberg, *z, iterations)
+            }
+        }
+    };
+    #[cfg(feature = "parallel")]
+    let sigma_min: Vec<f64> = {
+        use rayon::prelude::*;
+        grid.par_iter().map(sigma).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let sigma_min: Vec<f64> = grid.iter().map(sigma).collect();
+    let inside = Array2::from_shape_fn((epsilons.len(), grid.len()), |(k, i)| {
+        sigma_min[i] < epsilons[k]
+    });
+    Pseudospectrum {
+        sigma_min,
+        epsilons: epsilons.to_vec(),
+        inside,
+    }
+}
+
+/// sigma_min(A - z I) from the real embedding [[Re, -Im], [Im, Re]], whose
+/// singular values are those of A - z I, each twice.
+fn dense_sigma_min(a: &Array2<f64>, z: Complex64) -> f64 {
+    let n = a.nrows();
+    if n == 0 {
+        return f64::INFINITY;
+    }
+    let embedding = Array2::from_shape_fn((2 * n, 2 * n), |(i, j)| {
+        let (block_i, block_j) = (i / n, j / n);
+        let (r, c) = (i % n, j % n);
+        let re = a[[r, c]] - if r == c { z.re } else { 0.0 };
+        let im = if r == c { -z.im } else { 0.0 };
+        match (block_i, block_j) {
+            (0, 0) | (1, 1) => re,
+            (0, 1) => -im,
+            _ => im,
+        }
+    });
+    singular_values(&embedding).last().copied().unwrap_or(0.0)
+}
+
+/// LU factors of an upper Hessenberg matrix with adjacent-row pivoting.
+struct HessenbergLu {
+    /// Upper triangular factor, row-major
+    u: Vec<Vec<Complex64>>,
+    /// Multiplier eliminating the subdiagonal entry of column k
+    multipliers: Vec<Complex64>,
+    /// Whether rows k and k + 1 were swapped before elimination
+    swaps: Vec<bool>,
+}
+
+impl HessenbergLu {
+    /// Factors H - z I; `None` if it is exactly singular.
+    fn new(h: &[Vec<f64>], z: Complex64) -> Option<Self> {
+        let n = h.len();
+        let mut u: Vec<Vec<Complex64>> = h
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &x)| Complex64::new(x, 0.0) - if i == j { z } else { 0.0.into() })
+                    .collect()
+            })
+            .collect();
+        let mut multipliers = vec![Complex64::new(0.0, 0.0); n.saturating_sub(1)];
+        let mut swaps = vec![false; n.saturating_sub(1)];
+        for k in 0..n.saturating_sub(1) {
+            if u[k + 1][k].norm() > u[k][k].norm() {
+                u.swap(k, k + 1);
+                swaps[k] = true;
+            }
+            if u[k][k].norm() == 0.0 {
+                return None;
+            }
+            let l = u[k + 1][k] / u[k][k];
+            multipliers[k] = l;
+            let (upper, lower) = u.split_at_mut(k + 1);
+            for (x, p) in lower[0][k..].iter_mut().zip(&upper[k][k..]) {
+                *x -= l * p;
+            }
+        }
+        if n > 0 && u[n - 1][n - 1].norm() == 0.0 {
+            return None;
+        }
+        Some(Self {
+            u,
+            multipliers,
+            swaps,
+        })
+    }
+
+    /// x = M^(-1) b.
+    fn solve(&self, b: &mut [Complex64]) {
+        for (k, (&l, &swap)) in self.multipliers.iter().zip(&self.swaps).enumerate() {
+            if swap {
+                b.swap(k, k + 1);
+            }
+            let pivot = b[k];
+            b[k + 1] -= l * pivot;
+        }
+        for i in (0..b.len()).rev() {
+            let acc: Complex64 = ((i + 1)..b.len()).map(|j| self.u[i][j] * b[j]).sum();
+            b[i] = (b[i] - acc) / self.u[i][i];
+        }
+    }
+
+    /// y = M^(-*) c.
+    fn solve_adjoint(&self, c: &mut [Complex64]) {
+        for i in 0..c.len() {
+            let acc: Complex64 = (0..i).map(|j| self.u[j][i].conj() * c[j]).sum();
+            c[i] = (c[i] - acc) / self.u[i][i].conj();
+        }
+        for (k, (&l, &swap)) in self.multipliers.iter().zip(&self.swaps).enumerate().rev() {
+            let next = c[k + 1];
+            c[k] -= l.conj() * next;
+            if swap {
+                c.swap(k, k + 1);
+            }
+        }
+    }
+}
+
+/// sigma_min(H - z I) as 1 / sqrt(lambda_max) of (M^* M)^(-1), by Lanczos.
+fn inverse_lanczos_sigma_min(h: &[Vec<f64>], z: Complex64, iterations: usize) -> f64 {
+    let n = h.len();
+    if n == 0 {
+        return f64::INFINITY;
+    }
+    let Some(lu) = HessenbergLu::new(h, z) else {
+        return 0.0;
+    };
+    // A fixed, generic start vector (no special structure relative to H).
+    let mut q: Vec<Complex64> = (0..n)
+        .map(|i| Complex64::from_polar(1.0, 2.399_963 * i as f64) * (1.0 + (i % 7) as f64))
+        .collect();
+    let norm = q.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+    q.iter_mut().for_each(|x| *x /= norm);
+    let mut q_prev = vec![Complex64::new(0.0, 0.0); n];
+    let (mut alphas, mut betas) = (Vec::new(), Vec::new());
+    let mut theta = 0.0;
+    for _ in 0..iterations.max(1).min(n) {
+        let mut v = q.clone();
+        lu.solve_adjoint(&mut v);
+        lu.solve(&mut v);
+        let beta_prev = betas.last().copied().unwrap_or(0.0);
+        v.iter_mut()
+            .zip(&q_prev)
+            .for_each(|(x, p)| *x -= p * beta_prev);
+        let alpha: f64 = q.iter().zip(&v).map(|(a, b)| (a.conj() * b).re).sum();
+        v.iter_mut().zip(&q).for_each(|(x, p)| *x -= p * alpha);
+        alphas.push(alpha);
+        let m = alphas.len();
+        let t = Array2::from_shape_fn((m, m), |(i, j)| {
+            if i == j {
+                alphas[i]
+            } else if i.abs_diff(j) == 1 {
+                betas[i.min(j)]
+            } else {
+                0.0
+            }
+        });
+        let ritz = symmetric_eigenvalues(&t).last().copied().unwrap_or(0.0);
+        let converged = (ritz - theta).abs() <= LANCZOS_TOLERANCE * ritz;
+        theta = ritz;
+        let beta = v.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        if converged || beta <= f64::EPSILON * ritz {
+            break;
+        }
+        betas.push(beta);
+        q_prev = std::mem::replace(&mut q, v.iter().map(|x| x / beta).collect());
+    }
+    1.0 / theta.sqrt()
+}
+
+/// Inner and outer radii of the single ring from two moments of the singular values.
+///
+/// a = E[s^-2]^(-1/2) and b = E[s^2]^(1/2): only these two moments enter
+/// the support, while the radial profile in between needs the whole
+/// distribution (see [`SingleRing`]).
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::single_ring_radii;
+///
+/// // Singular values 1 and 3 with equal weight.
+/// let (inner, outer) = single_ring_radii(0.5 * (1.0 + 9.0), 0.5 * (1.0 + 1.0 / 9.0));
+/// assert!((outer - 5.0_f64.sqrt()).abs() < 1e-12);
+/// assert!((inner - (9.0_f64 / 5.0).sqrt()).abs() < 1e-12);
+/// ```
+pub fn single_ring_radii(mean_square: f64, mean_inverse_square: f64) -> (f64, f64) {
+    (mean_inverse_square.recip().sqrt(), mean_square.sqrt())
+}
+
+/// Single-ring law of U D V for Haar orthogonal U, V and fixed singular values D.
+///
+/// As n grows, the eigenvalues fill the annulus a <= |z| <= b of
+/// [`single_ring_radii`] with a rotation-invariant density. With nu the
+/// law of s^2, the fraction inside radius r is the t in (0, 1) with
+/// r^2 = 1 / S_nu(t - 1), S the S-transform (Haagerup & Larsen). This is
+/// evaluated by inverting psi(z) = E[z s^2 / (1 - z s^2)] on z < 0, so each
+/// call costs O(n) per bisection step. Sampling draws U D V with
+/// independent Haar orthogonal factors, for validating the prediction
+/// against [`crate::general_eigenvalues`].
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::SingleRing;
+///
+/// // Quarter-circle singular values: the Ginibre case, uniform on the unit disk.
+/// let n = 2000;
+/// let singular_values: Vec<f64> = (0..n)
+///     .map(|i| rmt::marchenko_pastur_quantile((i as f64 + 0.5) / n as f64, 1.0, 1.0).sqrt())
+///     .collect();
+/// let ring = SingleRing::new(singular_values).unwrap();
+/// assert!((ring.outer_radius() - 1.0).abs() < 0.01);
+/// assert!((ring.radial_cdf(0.6) - 0.36).abs() < 0.01);
+/// assert!((ring.radial_density(0.6) * std::f64::consts::PI - 1.0).abs() < 0.02);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SingleRing {
+    squared: Vec<f64>,
+}
+
+impl SingleRing {
+    /// Single-ring law for the given singular values (one per matrix dimension).
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless there is at least one singular
+    /// value and every one is positive and finite.
+    pub fn new(singular_values: Vec<f64>) -> Result<Self, Error> {
+        if singular_values.is_empty() {
+            return Err(Error::InvalidParameter {
+                name: "singular_values",
+                value: 0.0,
+                expected: "non-empty",
+            });
+        }
+        let squared = singular_values
+            .into_iter()
+            .map(|s| Error::check_positive("singular_values", s).map(|s| s * s))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { squared })
+    }
+
+    /// Matrix dimension n.
+    pub fn dimension(&self) -> usize {
+        self.squared.len()
+    }
+
+    /// Inner radius a = E[s^-2]^(-1/2).
+    pub fn inner_radius(&self) -> f64 {
+        let n = self.squared.len() as f64;
+        single_ring_radii(0.0, self.squared.iter().map(|t| 1.0 / t).sum::<f64>() / n).0
+    }
+
+    /// Outer radius b = E[s^2]^(1/2).
+    pub fn outer_radius(&self) -> f64 {
+        let n = self.squared.len() as f64;
+        single_ring_radii(self.squared.iter().sum::<f64>() / n, 0.0).1
+    }
+
+    /// Fraction of eigenvalues with |z| <= r.
+    pub fn radial_cdf(&self, r: f64) -> f64 {
+        let (a, b) = (self.inner_radius(), self.outer_radius());
+        if r.is_nan() {
+            return f64::NAN;
+        }
+        if r <= a {
+            return 0.0;
+        }
+        if r >= b {
+            return 1.0;
+        }
+        // r(t) increases from a to b on (0, 1).
+        let (mut lo, mut hi) = (0.0_f64, 1.0_f64);
+        for _ in 0..60 {
+            let mid = 0.5 * (lo + hi);
+            if self.radius_squared(mid) < r * r {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    /// Eigenvalue density per unit area at modulus r (0 outside the annulus).
+    ///
+    /// The planar density, comparable to [`radial_spectral_density`]:
+    /// F'(r) / (2 pi r) for the radial CDF F.
+    pub fn radial_density(&self, r: f64) -> f64 {
+        let t = self.radial_cdf(r);
+        if t <= 0.0 || t >= 1.0 {
+            return 0.0;
+        }
+        // r^2 = w / (z (1 + w)) with w = t - 1 and psi(z) = w, so
+        // d(r^2)/dt = (z - w (1 + w) / psi'(z)) / (z (1 + w))^2.
+        let w = t - 1.0;
+        let z = self.psi_inverse(w);
+        let n = self.squared.len() as f64;
+        let psi_prime = self
+            .squared
+            .iter()
+            .map(|t| t / (1.0 - z * t).powi(2))
+            .sum::<f64>()
+            / n;
+        let dr2_dt = (z - w * (1.0 + w) / psi_prime) / (z * (1.0 + w)).powi(2);
+        1.0 / (PI * dr2_dt)
+    }
+
+    /// r(t)^2 = 1 / S(t - 1) for t in (0, 1).
+    fn radius_squared(&self, t: f64) -> f64 {
+        let w = t - 1.0;
+        w / (self.psi_inverse(w) * (1.0 + w))
+    }
+
+    /// The z < 0 with psi(z) = w, for w in (-1, 0).
+    fn psi_inverse(&self, w: f64) -> f64 {
+        let n = self.squared.len() as f64;
+        let psi = |z: f64| {
+            self.squared
+                .iter()
+                .map(|t| z * t / (1.0 - z * t))
+                .sum::<f64>()
+                / n
+        };
+        // psi increases from -1 to 0 on (-inf, 0); bisect on ln(-z).
+        let (mut lo, mut hi) = (-1.0_f64, 1.0_f64);
+        while psi(-hi.exp()) > w && hi < 700.0 {
+            hi *= 2.0;
+        }
+        while psi(-lo.exp()) < w && lo > -700.0 {
+            lo *= 2.0;
+        }
+        for _ in 0..100 {
+            let mid = 0.5 * (lo + hi);
+            if psi(-mid.exp()) > w {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        -(0.5 * (lo + hi)).exp()
+    }
+}
+
+impl Distribution<Array2<f64>> for SingleRing {
+    /// One matrix U D V with independent Haar orthogonal U and V.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Array2<f64> {
+        let n = self.squared.len();
+        let u = haar_orthogonal(rng, n);
+        let v = haar_orthogonal(rng, n);
+        let mut ud = u;
+        for (mut column, t) in ud.columns_mut().into_iter().zip(&self.squared) {
+            column *= t.sqrt();
+        }
+        ud.dot(&v)
+    }
+}
+
+/// Haar orthogonal matrix: Gram-Schmidt on Gaussian columns, which gives
+/// the QR factor with positive diagonal R and hence the Haar measure.
+fn haar_orthogonal<R: Rng + ?Sized>(rng: &mut R, n: usize) -> Array2<f64> {
+    let mut q: Array2<f64> = Array2::from_shape_simple_fn((n, n), || StandardNormal.sample(rng));
+    for j in 0..n {
+        for k in 0..j {
+            let dot = q.column(j).dot(&q.column(k));
+            let previous = q.column(k).to_owned();
+            q.column_mut(j).scaled_add(-dot, &previous);
+        }
+        let norm = q.column(j).dot(&q.column(j)).sqrt();
+        q.column_mut(j).mapv_inplace(|x| x / norm);
+    }
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_complex_densities_integrate_to_one() {
+        // Deterministic points spread over the unit disk (sunflower spiral).
+        let n = 2000;
+        let golden = PI * (3.0 - 5.0_f64.sqrt());
+        let points: Vec<Complex64> = (0..n)
+            .map(|k| Complex64::from_polar(((k as f64 + 0.5) / n as f64).sqrt(), golden * k as f64))
+            .collect();
+
+        let esd = complex_spectral_density(&points, 10);
+        let cell = (esd.re[1] - esd.re[0]) * (esd.im[1] - esd.im[0]);
+        assert!((esd.density.sum() * cell - 1.0).abs() < 1e-12);
+        let center = esd.density[[4, 4]] + esd.density[[5, 5]];
+        assert!((0.5 * center * PI - 1.0).abs() < 0.1);
+
+        let (radii, density) = radial_spectral_density(&points, 8);
+        let mass: f64 = radii
+            .iter()
+            .zip(&density)
+            .map(|(r, d)| d * 2.0 * PI * r * (radii[1] - radii[0]))
+            .sum();
+        assert!((mass - 1.0).abs() < 1e-12);
+        assert!(density.iter().all(|d| (d * PI - 1.0).abs() < 0.05));
+
+        let kde = complex_kernel_density(
+            &points,
+            &[Complex64::new(0.0, 0.0), Complex64::new(3.0, 0.0)],
+            Some(0.1),
+        );
+        assert!((kde[0] * PI - 1.0).abs() < 0.05);
+        assert!(kde[1] < 1e-80);
+        // Scott's rule blurs the disk edge but keeps the center near 1 / pi.
+        let kde = complex_kernel_density(&points, &[Complex64::new(0.0, 0.0)], None);
+        assert!((kde[0] * PI - 1.0).abs() < 0.1);
+
+        // All-real spectra get a unit-height imaginary axis.
+        let real = [Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)];
+        let esd = complex_spectral_density(&real, 2);
+        assert_eq!(esd.im, vec![-0.25, 0.25]);
+        assert!(complex_spectral_density(&[], 3).re.is_empty());
+    }
+
+    #[test]
+    fn test_circular_law_test_edge_statistics() {
+        let n = 1000;
+        let golden = PI * (3.0 - 5.0_f64.sqrt());
+        let disk: Vec<Complex64> = (0..n)
+            .map(|k| {
+                Complex64::from_polar(
+                    2.0 * ((k as f64 + 0.5) / n as f64).sqrt(),
+                    golden * k as f64,
+                )
+            })
+            .collect();
+        let fit = circular_law_test(&disk, 2.0).unwrap();
+        assert!(fit.radial.statistic < 1e-3 && fit.radial.p_value > 0.99);
+        assert!((fit.boundary_distance - 1.0).abs() < 1e-3 && fit.boundary_z.abs() < 0.1);
+        assert_eq!(fit.outside_fraction, 0.0);
+        assert!(fit.spectral_radius < 2.0);
+
+        // Points piled on the circle: the same support, the wrong profile.
+        let ring: Vec<Complex64> = (0..n)
+            .map(|k| Complex64::from_polar(2.0 * (1.0 - 0.05 * (k % 2) as f64), golden * k as f64))
+            .collect();
+        let fit = circular_law_test(&ring, 2.0).unwrap();
+        assert!(fit.radial.p_value < 1e-10);
+        assert!(fit.boundary_distance < 0.1 && fit.boundary_z < -10.0);
+
+        // An outlier shows in the radius and the outside fraction.
+        let mut spiked = disk.clone();
+        spiked[0] = Complex64::new(5.0, 0.0);
+        let fit = circular_law_test(&spiked, 2.0).unwrap();
+        assert_eq!(fit.spectral_radius, 5.0);
+        assert_eq!(fit.outside_fraction, 1.0 / n as f64);
+        assert!(circular_law_test(&[], 1.0).is_none());
+        assert!(circular_law_test(&disk, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_pseudospectrum_methods_agree() {
+        // Non-normal upper triangular matrix with known eigenvalues 1..n.
+        let n = 12;
+        let a = Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                (i + 1) as f64
+            } else if j > i {
+                1.0 / (j - i) as f64
+            } else {
+                0.0
+            }
+        });
+        let grid: Vec<Complex64> = (0..6)
+            .map(|k| Complex64::new(0.7 * k as f64 + 0.3, 0.4 * (k % 3) as f64))
+            .collect();
+        let epsilons = [1e-2, 1e-1];
+        let dense = pseudospectrum(&a, &grid, &epsilons, PseudospectrumMethod::Dense);
+        let method = PseudospectrumMethod::InverseLanczos { iterations: 30 };
+        let lanczos = pseudospectrum(&a, &grid, &epsilons, method);
+        for (d, l) in dense.sigma_min.iter().zip(&lanczos.sigma_min) {
+            assert!((d - l).abs() < 1e-8 * d.max(1.0), "{d} vs {l}");
+        }
+        assert_eq!(dense.inside, lanczos.inside);
+        // sigma_min(A - z I) <= dist(z, spectrum), with equality for normal A.
+        for (z, s) in grid.iter().zip(&lanczos.sigma_min) {
+            let distance = (1..=n)
+                .map(|k| (z - k as f64).norm())
+                .fold(f64::INFINITY, f64::min);
+            assert!(*s <= distance + 1e-12);
+        }
+        let diagonal = Array2::from_diag(&ndarray::Array1::from_iter((1..=n).map(|k| k as f64)));
+        let normal = pseudospectrum(&diagonal, &grid, &[], method);
+        for (z, s) in grid.iter().zip(&normal.sigma_min) {
+            let distance = (1..=n)
+                .map(|k| (z - k as f64).norm())
+                .fold(f64::INFINITY, f64::min);
+            assert!((s - distance).abs() < 1e-8);
+        }
+        // Exactly at an eigenvalue of a Hessenberg matrix the solver reports 0.
+        let at_eigenvalue = pseudospectrum(&diagonal, &[Complex64::new(3.0, 0.0)], &[1e-3], method);
+        assert_eq!(at_eigenvalue.sigma_min, vec![0.0]);
+        assert!(at_eigenvalue.inside[[0, 0]]);
+    }
+
+    #[test]
+    fn test_single_ring_matches_sampled_spectra() {
+        // Singular values 1 and 3 in equal proportion.
+        let n = 120;
+        let singular_values: Vec<f64> =
+            (0..n).map(|i| if i % 2 == 0 { 1.0 } else { 3.0 }).collect();
+        let ring = SingleRing::new(singular_values).unwrap();
+        let (a, b) = (ring.inner_radius(), ring.outer_radius());
+        assert!((a - 1.8_f64.sqrt()).abs() < 1e-12 && (b - 5.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(
+            (ring.radial_cdf(0.9 * a), ring.radial_cdf(1.1 * b)),
+            (0.0, 1.0)
+        );
+
+        // The density integrates to the CDF.
+        let (x, w) = crate::special::gauss_legendre(40, a, 1.8);
+        let mass: f64 = x
+            .iter()
+            .zip(&w)
+            .map(|(&r, &w)| w * 2.0 * PI * r * ring.radial_density(r))
+            .sum();
+        assert!((mass - ring.radial_cdf(1.8)).abs() < 1e-4);
+
+        // Pooled eigenvalues of U D V against the predicted radial law.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(11);
+        let mut eigenvalues = Vec::new();
+        for _ in 0..4 {
+            let m: Array2<f64> = ring.sample(&mut rng);
+            let singular = crate::singular_values(&m);
+            assert!((singular[0] - 3.0).abs() < 1e-9 && (singular[n - 1] - 1.0).abs() < 1e-9);
+            eigenvalues.extend(crate::general_eigenvalues(&m));
+        }
+        // Away from the edges, where finite-n spreading is O(n^(-1/2)).
+        for r in [1.5, 1.7, 1.9] {
+            let inside = eigenvalues.iter().filter(|z| z.norm() <= r).count();
+            let fraction = inside as f64 / eigenvalues.len() as f64;
+            assert!(
+                (fraction - ring.radial_cdf(r)).abs() < 0.03,
+                "r {r}: {fraction}"
+            );
+        }
+        assert!(SingleRing::new(vec![]).is_err());
+        assert!(SingleRing::new(vec![1.0, 0.0]).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//! One-call SVG figures for spectral diagnostics (`plot` feature).
+//!
+//! Each function renders a complete figure to an SVG file: an empirical
+//! density against a limiting law, spacing ratios against the Poisson and
+//! Gaussian-ensemble curves, or a Q-Q plot. They are meant for notebooks and
+//! reports; for custom layouts, compute the series with the underlying
+//! functions and draw them with plotters directly.
+
+use std::error::Error;
+use std::path::Path;
+
+use plotters::prelude::*;
+
+use crate::density::{
+    empirical_spectral_cdf, empirical_spectral_density_auto, kernel_spectral_density, Bandwidth,
+    BinRule,
+};
+use crate::level_spacing_ratios;
+use crate::levels::{spacing_ratio_density, SpacingClass};
+
+const SIZE: (u32, u32) = (800, 600);
+/// Points on which smooth curves are evaluated.
+const CURVE_POINTS: usize = 400;
+
+/// Histogram and KDE of the eigenvalues with a limiting density overlaid.
+///
+/// Bins follow the Freedman-Diaconis rule and the KDE uses Silverman's
+/// bandwidth. `law` is any density, e.g. `|x| marchenko_pastur_density(x, 0.25, 1.0)`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rmt::{marchenko_pastur_density, plot_density_overlay, sample_wishart, symmetric_eigenvalues};
+///
+/// let (n, p) = (2000, 500);
+/// let eigenvalues: Vec<f64> = symmetric_eigenvalues(&sample_wishart(n, p))
+///     .into_iter()
+///     .map(|x| x / n as f64)
+///     .collect();
+/// plot_density_overlay("mp.svg", &eigenvalues, |x| marchenko_pastur_density(x, 0.25, 1.0), "Marchenko-Pastur")
+///     .unwrap();
+/// ```
+pub fn plot_density_overlay<P, F>(
+    path: P,
+    eigenvalues: &[f64],
+    law: F,
+    law_label: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    P: AsRef<Path>,
+    F: Fn(f64) -> f64,
+{
+    let (centers, densities) =
+        empirical_spectral_density_auto(eigenvalues, BinRule::FreedmanDiaconis);
+    if centers.is_empty() {
+        return Err("no finite eigenvalues to plot".into());
+    }
+    let width = if centers.len() > 1 {
+        centers[1] - centers[0]
+    } else {
+        1.0
+    };
+    let lo = centers[0] - width;
+    let hi = centers[centers.len() - 1] + width;
+    let grid = linspace(lo, hi, CURVE_POINTS);
+    let kde = kernel_spectral_density(eigenvalues, &grid, Bandwidth::Silverman, None);
+    let curve: Vec<f64> = grid.iter().map(|&x| law(x)).collect();
+    let top = densities
+        .iter()
+        .chain(&kde)
+        .chain(&curve)
+        .copied()
+        .filter(|y| y.is_finite())
+        .fold(0.0, f64::max);
+
+    let root = SVGBackend::new(path.as_ref(), SIZE).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Empirical spectral density", ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(55)
+        .build_cartesian_2d(lo..hi, 0.0..1.1 * top.max(f64::MIN_POSITIVE))?;
+    chart
+        .configure_mesh()
+        .x_desc("eigenvalue")
+        .y_desc("density")
+        .draw()?;
+
+    chart
+        .draw_series(centers.iter().zip(&densities).map(|(&x, &y)| {
+            Rectangle::new(
+                [(x - 0.5 * width, 0.0), (x + 0.5 * width, y)],
+                BLUE.mix(0.3).filled(),
+            )
+        }))?
+        .label("histogram")
+        .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 15, y + 5)], BLUE.mix(0.3).filled()));
+    chart
+        .draw_series(LineSeries::new(
+            grid.iter().copied().zip(kde.iter().copied()),
+            BLUE.stroke_width(2),
+        ))?
+        .label("KDE")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 15, y)], BLUE.stroke_width(2)));
+    chart
+        .draw_series(LineSeries::new(
+            grid.iter().copied().zip(curve.iter().copied()),
+            RED.stroke_width(2),
+        ))?
+        .label(law_label)
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 15, y)], RED.stroke_width(2)));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+/// Histogram of the level spacing ratios against P(r) for Poisson, GOE, and GUE.
+///
+/// `eigenvalues` must be sorted ascending, as for [`crate::level_spacing_ratios`].
+pub fn plot_spacing_ratios<P: AsRef<Path>>(
+    path: P,
+    eigenvalues: &[f64],
+) -> Result<(), Box<dyn Error>> {
+    let ratios = level_spacing_ratios(eigenvalues);
+    if ratios.is_empty() {
+        return Err("need at least three distinct eigenvalues".into());
+    }
+    let bins = 25;
+    let width = 1.0 / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for &r in &ratios {
+        counts[((r / width) as usize).min(bins - 1)] += 1;
+    }
+    let densities: Vec<f64> = counts
+        .iter()
+        .map(|&c| c as f64 / (ratios.len() as f64 * width))
+        .collect();
+    let grid = linspace(0.0, 1.0, CURVE_POINTS);
+    let classes = [
+        (SpacingClass::Poisson, "Poisson", GREEN),
+        (SpacingClass::Goe, "GOE", RED),
+        (SpacingClass::Gue, "GUE", MAGENTA),
+    ];
+    let top = densities.iter().copied().fold(2.0, f64::max);
+
+    let root = SVGBackend::new(path.as_ref(), SIZE).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Level spacing ratios", ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(55)
+        .build_cartesian_2d(0.0..1.0, 0.0..1.1 * top)?;
+    chart.configure_mesh().x_desc("r").y_desc("P(r)").draw()?;
+
+    chart
+        .draw_series(densities.iter().enumerate().map(|(i, &y)| {
+            let x = i as f64 * width;
+            Rectangle::new([(x, 0.0), (x + width, y)], BLUE.mix(0.3).filled())
+        }))?
+        .label(format!("empirical (n = {})", ratios.len()))
+        .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 15, y + 5)], BLUE.mix(0.3).filled()));
+    for (class, label, color) in classes {
+        chart
+            .draw_series(LineSeries::new(
+                grid.iter().map(|&r| (r, spacing_ratio_density(r, class))),
+                color.stroke_width(2),
+            ))?
+            .label(label)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 15, y)], color.stroke_width(2)));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+/// Q-Q plot of the eigenvalues against a reference quantile function.
+///
+/// Points on the diagonal mean the eigenvalues follow the law; edge
+/// deviations show up as the first and last points leaving the line.
+/// `quantile` is e.g. `|q| MarchenkoPastur::new(0.25, 1.0).unwrap().quantile(q)`.
+pub fn plot_qq<P, Q>(
+    path: P,
+    eigenvalues: &[f64],
+    quantile: Q,
+    law_label: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    P: AsRef<Path>,
+    Q: Fn(f64) -> f64,
+{
+    let points: Vec<(f64, f64)> = empirical_spectral_cdf(eigenvalues)
+        .qq_points(quantile)
+        .into_iter()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+    if points.is_empty() {
+        return Err("no finite quantile pairs to plot".into());
+    }
+    let (lo, hi) = points
+        .iter()
+        .flat_map(|&(x, y)| [x, y])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+    let pad = 0.05 * (hi - lo).max(f64::MIN_POSITIVE);
+    let range = (lo - pad)..(hi + pad);
+
+    let root = SVGBackend::new(path.as_ref(), SIZE).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(format!("Q-Q plot against {law_label}"), ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(55)
+        .build_cartesian_2d(range.clone(), range)?;
+    chart
+        .configure_mesh()
+        .x_desc(format!("{law_label} quantile"))
+        .y_desc("eigenvalue")
+        .draw()?;
+    chart.draw_series(LineSeries::new([(lo, lo), (hi, hi)], BLACK.mix(0.5)))?;
+    chart.draw_series(
+        points
+            .iter()
+            .map(|&(x, y)| Circle::new((x, y), 2, BLUE.filled())),
+    )?;
+    root.present()?;
+    Ok(())
+}
+
+fn linspace(lo: f64, hi: f64, n: usize) -> Vec<f64> {
+    let step = (hi - lo) / (n - 1) as f64;
+    (0..n).map(|i| lo + i as f64 * step).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        marchenko_pastur_density, sample_goe_with, symmetric_eigenvalues, WignerSemicircle,
+    };
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_plots_render_svg() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(191);
+        let n = 200;
+        let eigenvalues: Vec<f64> = symmetric_eigenvalues(&sample_goe_with(&mut rng, n));
+        let dir = std::env::temp_dir();
+        let id = std::process::id();
+        let semicircle = WignerSemicircle::new(1.0).unwrap();
+
+        let path = dir.join(format!("rmt-density-{id}.svg"));
+        plot_density_overlay(&path, &eigenvalues, |x| semicircle.pdf(x), "semicircle").unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        assert!(svg.starts_with("<svg") && svg.contains("semicircle"));
+
+        plot_spacing_ratios(&path, &eigenvalues).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("GUE"));
+
+        plot_qq(
+            &path,
+            &eigenvalues,
+            |q| semicircle.quantile(q),
+            "semicircle",
+        )
+        .unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<circle"));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(
+            plot_density_overlay(&path, &[], |x| marchenko_pastur_density(x, 0.5, 1.0), "MP")
+                .is_err()
+        );
+        assert!(plot_spacing_ratios(&path, &[1.0, 2.0]).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! Python bindings (`python` feature).
+//!
+//! Build the extension module with maturin (see `pyproject.toml`):
+//!
+//! ```text
+//! maturin develop --release
+//! ```
+//!
+//! Arrays cross the boundary as NumPy arrays; densities and CDFs are
+//! vectorized over their first argument. Invalid parameters raise
+//! `ValueError` instead of returning the sentinel values of the Rust API.
+
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rand::SeedableRng;
+
+use crate::{
+    count_spikes_with, ks_test_mp, ks_test_semicircle, largest_eigenvalue_test,
+    marchenko_pastur_density, sample_goe_with, sample_wishart_with, symmetric_eigenvalues,
+    tracy_widom_cdf, wigner_semicircle_density, AsEigenvalues, CleaningStrategy, CovarianceCleaner,
+    Error, MarchenkoPastur, SpikeThreshold, TracyWidom, WignerSemicircle,
+};
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+fn rng(seed: Option<u64>) -> rand::rngs::StdRng {
+    match seed {
+        Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+        None => rand::rngs::StdRng::from_os_rng(),
+    }
+}
+
+/// Marchenko-Pastur density of (1/n) X^T X, evaluated elementwise.
+#[pyfunction]
+#[pyo3(signature = (x, ratio, sigma_sq = 1.0))]
+fn mp_density<'py>(
+    py: Python<'py>,
+    x: PyReadonlyArray1<'py, f64>,
+    ratio: f64,
+    sigma_sq: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    MarchenkoPastur::new(ratio, sigma_sq)?;
+    let x = x.as_array();
+    Ok(x.mapv(|v| marchenko_pastur_density(v, ratio, sigma_sq))
+        .into_pyarray(py))
+}
+
+/// Wigner semicircle density on [-2 sigma, 2 sigma], evaluated elementwise.
+#[pyfunction]
+#[pyo3(signature = (x, sigma = 1.0))]
+fn semicircle_density<'py>(
+    py: Python<'py>,
+    x: PyReadonlyArray1<'py, f64>,
+    sigma: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    WignerSemicircle::new(sigma)?;
+    let x = x.as_array();
+    Ok(x.mapv(|v| wigner_semicircle_density(v, sigma))
+        .into_pyarray(py))
+}
+
+/// Tracy-Widom CDF F_beta for beta in {1, 2, 4}, evaluated elementwise.
+#[pyfunction]
+#[pyo3(signature = (s, beta = 1.0))]
+fn tw_cdf<'py>(
+    py: Python<'py>,
+    s: PyReadonlyArray1<'py, f64>,
+    beta: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    TracyWidom::new(beta)?;
+    let s = s.as_array();
+    Ok(s.mapv(|v| tracy_widom_cdf(v, beta)).into_pyarray(py))
+}
+
+/// Unnormalized n x p Wishart sample X^T X (p x p).
+#[pyfunction]
+#[pyo3(signature = (n, p, seed = None))]
+fn sample_wishart(
+    py: Python<'_>,
+    n: usize,
+    p: usize,
+    seed: Option<u64>,
+) -> Bound<'_, PyArray2<f64>> {
+    sample_wishart_with(&mut rng(seed), n, p).into_pyarray(py)
+}
+
+/// n x n GOE matrix with semicircle radius 2.
+#[pyfunction]
+#[pyo3(signature = (n, seed = None))]
+fn sample_goe(py: Python<'_>, n: usize, seed: Option<u64>) -> Bound<'_, PyArray2<f64>> {
+    sample_goe_with(&mut rng(seed), n).into_pyarray(py)
+}
+
+/// Ascending eigenvalues of a symmetric matrix.
+#[pyfunction]
+fn eigvalsh<'py>(
+    py: Python<'py>,
+    a: PyReadonlyArray2<'py, f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let a = a.as_array();
+    if a.nrows() != a.ncols() {
+        return Err(PyValueError::new_err("matrix must be square"));
+    }
+    Ok(symmetric_eigenvalues(&a.to_owned()).into_pyarray(py))
+}
+
+/// KS test of eigenvalues against MP or the semicircle; returns (statistic, p_value).
+///
+/// `law` is "mp" (uses `ratio` and `sigma_sq`) or "semicircle" (uses `sigma`).
+#[pyfunction]
+#[pyo3(signature = (eigenvalues, law = "mp", ratio = None, sigma_sq = 1.0, sigma = 1.0))]
+fn ks_test(
+    eigenvalues: PyReadonlyArray1<'_, f64>,
+    law: &str,
+    ratio: Option<f64>,
+    sigma_sq: f64,
+    sigma: f64,
+) -> PyResult<(f64, f64)> {
+    ks_fit(&eigenvalues.as_array(), law, ratio, sigma_sq, sigma)
+}
+
+/// Body of [`ks_test`] on a borrowed array.
+fn ks_fit<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    law: &str,
+    ratio: Option<f64>,
+    sigma_sq: f64,
+    sigma: f64,
+) -> PyResult<(f64, f64)> {
+    let fit = match law {
+        "mp" => {
+            let ratio = ratio.ok_or_else(|| PyValueError::new_err("law='mp' needs ratio"))?;
+            MarchenkoPastur::new(ratio, sigma_sq)?;
+            ks_test_mp(eigenvalues, ratio, sigma_sq)
+        }
+        "semicircle" => {
+            WignerSemicircle::new(sigma)?;
+            ks_test_semicircle(eigenvalues, sigma)
+        }
+        other => return Err(PyValueError::new_err(format!("unknown law {other:?}"))),
+    };
+    let fit = fit.ok_or_else(|| PyValueError::new_err("eigenvalues must be non-empty"))?;
+    Ok((fit.statistic, fit.p_value))
+}
+
+/// Tracy-Widom test of the top sample-covariance eigenvalue.
+///
+/// Returns a dict with `lambda_max`, `statistic`, and `p_value`.
+#[pyfunction]
+fn top_eigenvalue_test<'py>(
+    py: Python<'py>,
+    eigenvalues: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    p: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let test = largest_eigenvalue_test(&eigenvalues.as_array(), n, p)
+        .ok_or_else(|| PyValueError::new_err("need a finite eigenvalue and n, p >= 2"))?;
+    let dict = PyDict::new(py);
+    dict.set_item("lambda_max", test.lambda_max)?;
+    dict.set_item("statistic", test.statistic)?;
+    dict.set_item("p_value", test.p_value)?;
+    Ok(dict)
+}
+
+/// Number of eigenvalues outside the MP bulk (sequential TW tests when `alpha` is given).
+#[pyfunction]
+#[pyo3(signature = (eigenvalues, ratio, sigma_sq = 1.0, alpha = None))]
+fn count_spikes(
+    eigenvalues: PyReadonlyArray1<'_, f64>,
+    ratio: f64,
+    sigma_sq: f64,
+    alpha: Option<f64>,
+) -> PyResult<usize> {
+    MarchenkoPastur::new(ratio, sigma_sq)?;
+    let threshold = match alpha {
+        Some(alpha) => SpikeThreshold::TracyWidom { alpha },
+        None => SpikeThreshold::MpEdge,
+    };
+    Ok(count_spikes_with(
+        &eigenvalues.as_array(),
+        ratio,
+        sigma_sq,
+        threshold,
+    ))
+}
+
+/// Cleaned covariance of an n x p data matrix (rows are observations).
+///
+/// `method` is one of "sample", "clip", "rie", "linear", or "nonlinear".
+#[pyfunction]
+#[pyo3(signature = (data, method = "nonlinear", standardize = false))]
+fn clean_covariance<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray2<'py, f64>,
+    method: &str,
+    standardize: bool,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let cleaned = CovarianceCleaner::new()
+        .standardize(standardize)
+        .strategy(cleaning_strategy(method)?)
+        .clean(&data.as_array().to_owned())
+        .ok_or_else(|| PyValueError::new_err("cannot clean this data set"))?;
+    Ok(cleaned.covariance.into_pyarray(py))
+}
+
+/// The [`CleaningStrategy`] named by the `method` argument of [`clean_covariance`].
+fn cleaning_strategy(method: &str) -> PyResult<CleaningStrategy> {
+    Ok(match method {
+        "sample" => CleaningStrategy::Sample,
+        "clip" => CleaningStrategy::Clipping,
+        "rie" => CleaningStrategy::Rie,
+        "linear" => CleaningStrategy::LinearShrinkage,
+        "nonlinear" => CleaningStrategy::NonlinearShrinkage,
+        other => return Err(PyValueError::new_err(format!("unknown method {other:?}"))),
+    })
+}
+
+/// The `rmt` Python module.
+#[pymodule]
+fn rmt(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(mp_density, m)?)?;
+    m.add_function(wrap_pyfunction!(semicircle_density, m)?)?;
+    m.add_function(wrap_pyfunction!(tw_cdf, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_wishart, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_goe, m)?)?;
+    m.add_function(wrap_pyfunction!(eigvalsh, m)?)?;
+    m.add_function(wrap_pyfunction!(ks_test, m)?)?;
+    m.add_function(wrap_pyfunction!(top_eigenvalue_test, m)?)?;
+    m.add_function(wrap_pyfunction!(count_spikes, m)?)?;
+    m.add_function(wrap_pyfunction!(clean_covariance, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `f` with an embedded interpreter; the helpers under test need no NumPy.
+    fn with_python<R>(f: impl for<'py> FnOnce(Python<'py>) -> R) -> R {
+        Python::initialize();
+        Python::attach(f)
+    }
+
+    fn value_error_message(py: Python<'_>, err: PyErr) -> String {
+        assert!(err.is_instance_of::<PyValueError>(py));
+        err.value(py).to_string()
+    }
+
+    #[test]
+    fn test_error_maps_to_value_error() {
+        with_python(|py| {
+            let err = PyErr::from(MarchenkoPastur::new(-1.0, 1.0).unwrap_err());
+            assert!(value_error_message(py, err).contains("ratio"));
+        });
+    }
+
+    #[test]
+    fn test_ks_fit_laws() {
+        with_python(|py| {
+            let eigenvalues = [-1.5, -0.7, 0.0, 0.4, 1.2, 1.8];
+            let (statistic, p_value) = ks_fit(&eigenvalues, "semicircle", None, 1.0, 1.0).unwrap();
+            assert!((0.0..=1.0).contains(&statistic) && (0.0..=1.0).contains(&p_value));
+            let positive = [0.2, 0.5, 0.9, 1.4, 2.0];
+            assert!(ks_fit(&positive, "mp", Some(0.5), 1.0, 1.0).is_ok());
+
+            let missing = ks_fit(&positive, "mp", None, 1.0, 1.0).unwrap_err();
+            assert!(value_error_message(py, missing).contains("needs ratio"));
+            let invalid = ks_fit(&positive, "mp", Some(0.0), 1.0, 1.0).unwrap_err();
+            assert!(value_error_message(py, invalid).contains("ratio"));
+            let sigma = ks_fit(&eigenvalues, "semicircle", None, 1.0, -1.0).unwrap_err();
+            assert!(value_error_message(py, sigma).contains("sigma"));
+            let unknown = ks_fit(&eigenvalues, "gue", None, 1.0, 1.0).unwrap_err();
+            assert!(value_error_message(py, unknown).contains("unknown law"));
+            let empty = ks_fit(&[] as &[f64], "semicircle", None, 1.0, 1.0).unwrap_err();
+            assert!(value_error_message(py, empty).contains("non-empty"));
+        });
+    }
+
+    #[test]
+    fn test_cleaning_strategy_names() {
+        with_python(|py| {
+            for (name, strategy) in [
+                ("sample", CleaningStrategy::Sample),
+                ("clip", CleaningStrategy::Clipping),
+                ("rie", CleaningStrategy::Rie),
+                ("linear", CleaningStrategy::LinearShrinkage),
+                ("nonlinear", CleaningStrategy::NonlinearShrinkage),
+            ] {
+                assert_eq!(cleaning_strategy(name).unwrap(), strategy);
+            }
+            let err = cleaning_strategy("ledoit").unwrap_err();
+            assert!(value_error_message(py, err).contains("unknown method"));
+        });
+    }
+}
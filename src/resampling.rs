@@ -0,0 +1,210 @@
+//! Bootstrap confidence intervals for spectral statistics.
+//!
+//! Spectral summaries of a single data set (lambda_max, effective rank, a
+//! shrinkage intensity) come without error bars. Resampling data rows with
+//! replacement and recomputing the statistic gives percentile intervals that
+//! reflect sampling variability at the actual n and p.
+
+use ndarray::{Array2, Axis};
+use rand::Rng;
+
+use crate::simulation::quantile_sorted;
+
+/// Bootstrap percentile confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfidenceInterval {
+    /// Statistic on the original data
+    pub estimate: f64,
+    /// Lower percentile bound
+    pub lower: f64,
+    /// Upper percentile bound
+    pub upper: f64,
+    /// Standard deviation of the bootstrap replicates
+    pub std_error: f64,
+    /// Nominal coverage, e.g. 0.95
+    pub level: f64,
+}
+
+/// Bootstrap a statistic of a data matrix by resampling its rows (observations).
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `data` - n x p data matrix, one observation per row
+/// * `replicates` - Number of bootstrap resamples (1000+ for 95% intervals)
+/// * `level` - Coverage of the interval in (0, 1)
+/// * `statistic` - Maps a data matrix to a scalar
+///
+/// # Returns
+///
+/// `None` if the data has no rows, `replicates` is zero, `level` is outside
+/// (0, 1), or every replicate is non-finite
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{bootstrap_rows, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let data = ndarray::Array2::from_shape_fn((200, 20), |_| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     z
+/// });
+/// let lambda_max = |x: &ndarray::Array2<f64>| {
+///     let cov = x.t().dot(x) / x.nrows() as f64;
+///     *symmetric_eigenvalues(&cov).last().unwrap()
+/// };
+/// let ci = bootstrap_rows(&mut rng, &data, 200, 0.9, lambda_max).unwrap();
+/// assert!(ci.lower <= ci.upper);
+/// ```
+pub fn bootstrap_rows<R, F>(
+    rng: &mut R,
+    data: &Array2<f64>,
+    replicates: usize,
+    level: f64,
+    statistic: F,
+) -> Option<ConfidenceInterval>
+where
+    R: Rng,
+    F: Fn(&Array2<f64>) -> f64,
+{
+    let n = data.nrows();
+    if n == 0 {
+        return None;
+    }
+    let estimate = statistic(data);
+    let values = (0..replicates).map(|_| {
+        let rows: Vec<usize> = (0..n).map(|_| rng.random_range(0..n)).collect();
+        statistic(&data.select(Axis(0), &rows))
+    });
+    percentile_interval(estimate, values, level)
+}
+
+/// Bootstrap a statistic of a sample of scalars by resampling with replacement.
+///
+/// Suited to statistics that are averages of exchangeable pieces, such as
+/// the mean of [`level_spacing_ratios`](crate::level_spacing_ratios) (resample
+/// the ratios, not the eigenvalues: duplicated eigenvalues create zero
+/// spacings) or effective ranks of eigenvalue sets. Eigenvalues of one matrix
+/// are strongly correlated, so prefer [`bootstrap_rows`] when the data matrix
+/// is available.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `samples` - Values to resample
+/// * `replicates` - Number of bootstrap resamples
+/// * `level` - Coverage of the interval in (0, 1)
+/// * `statistic` - Maps a sample to a scalar
+///
+/// # Returns
+///
+/// `None` under the same conditions as [`bootstrap_rows`]
+pub fn bootstrap_eigenvalues<R, F>(
+    rng: &mut R,
+    samples: &[f64],
+    replicates: usize,
+    level: f64,
+    statistic: F,
+) -> Option<ConfidenceInterval>
+where
+    R: Rng,
+    F: Fn(&[f64]) -> f64,
+{
+    let n = samples.len();
+    if n == 0 {
+        return None;
+    }
+    let estimate = statistic(samples);
+    let mut resample = vec![0.0; n];
+    let values = (0..replicates).map(|_| {
+        for slot in resample.iter_mut() {
+            *slot = samples[rng.random_range(0..n)];
+        }
+        statistic(&resample)
+    });
+    percentile_interval(estimate, values, level)
+}
+
+fn percentile_interval<I: Iterator<Item = f64>>(
+    estimate: f64,
+    values: I,
+    level: f64,
+) -> Option<ConfidenceInterval> {
+    if !(level > 0.0 && level < 1.0) {
+        return None;
+    }
+    let mut values: Vec<f64> = values.filter(|v| v.is_finite()).collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let m = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / m;
+    let std_error = if values.len() > 1 {
+        (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (m - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+    let tail = (1.0 - level) / 2.0;
+
+    Some(ConfidenceInterval {
+        estimate,
+        lower: quantile_sorted(&values, tail),
+        upper: quantile_sorted(&values, 1.0 - tail),
+        std_error,
+        level,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{effective_rank, symmetric_eigenvalues};
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_bootstrap_mean_matches_standard_error() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(8);
+        let samples: Vec<f64> = (0..400).map(|_| StandardNormal.sample(&mut rng)).collect();
+        let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+        let ci = bootstrap_eigenvalues(&mut rng, &samples, 2000, 0.95, mean).unwrap();
+        // Standard error of the mean is 1/sqrt(400) = 0.05.
+        assert!((ci.std_error - 0.05).abs() < 0.01, "{:?}", ci);
+        assert!(ci.lower < ci.estimate && ci.estimate < ci.upper);
+        assert!((ci.upper - ci.lower - 2.0 * 1.96 * 0.05).abs() < 0.04);
+    }
+
+    #[test]
+    fn test_bootstrap_rows_effective_rank() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(9);
+        let data = Array2::from_shape_fn((300, 10), |(_, j)| {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            z * (j + 1) as f64
+        });
+        let rank = |x: &Array2<f64>| {
+            effective_rank(&symmetric_eigenvalues(&(x.t().dot(x) / x.nrows() as f64)))
+        };
+        let ci = bootstrap_rows(&mut rng, &data, 300, 0.95, rank).unwrap();
+        assert!(
+            ci.lower <= ci.estimate && ci.estimate <= ci.upper,
+            "{:?}",
+            ci
+        );
+        assert!(ci.upper - ci.lower < 1.0);
+    }
+
+    #[test]
+    fn test_bootstrap_invalid() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        let sum = |x: &[f64]| x.iter().sum::<f64>();
+        assert!(bootstrap_eigenvalues(&mut rng, &[], 10, 0.95, sum).is_none());
+        assert!(bootstrap_eigenvalues(&mut rng, &[1.0], 0, 0.95, sum).is_none());
+        assert!(bootstrap_eigenvalues(&mut rng, &[1.0], 10, 1.5, sum).is_none());
+    }
+}
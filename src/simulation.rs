@@ -0,0 +1,217 @@
+//! Monte Carlo baselines for finite-n spectral statistics.
+//!
+//! Asymptotic laws (MP edges, semicircle radius, Tracy-Widom) are only
+//! approached at rate n^{-2/3} at the edges. These helpers sample an ensemble
+//! repeatedly and summarize the empirical distribution of extreme eigenvalues
+//! so finite-n behavior can be compared against the limits.
+
+use ndarray::Array2;
+use rand::Rng;
+
+use crate::linalg::symmetric_eigenvalues;
+
+/// Mean, spread, and quantiles of a Monte Carlo sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SampleSummary {
+    /// Sample mean
+    pub mean: f64,
+    /// Sample standard deviation (n - 1 denominator)
+    pub std_dev: f64,
+    /// 5% quantile
+    pub q05: f64,
+    /// 25% quantile
+    pub q25: f64,
+    /// Median
+    pub median: f64,
+    /// 75% quantile
+    pub q75: f64,
+    /// 95% quantile
+    pub q95: f64,
+}
+
+impl SampleSummary {
+    /// Summarize a sample (quantiles by linear interpolation between order statistics).
+    ///
+    /// Returns `None` for an empty sample.
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let std_dev = if sorted.len() > 1 {
+            (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean,
+            std_dev,
+            q05: quantile_sorted(&sorted, 0.05),
+            q25: quantile_sorted(&sorted, 0.25),
+            median: quantile_sorted(&sorted, 0.5),
+            q75: quantile_sorted(&sorted, 0.75),
+            q95: quantile_sorted(&sorted, 0.95),
+        })
+    }
+}
+
+/// Monte Carlo distribution of extreme eigenvalues for a symmetric ensemble.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtremeEigenvalueStats {
+    /// Number of sampled matrices
+    pub trials: usize,
+    /// Largest eigenvalue
+    pub lambda_max: SampleSummary,
+    /// Smallest eigenvalue
+    pub lambda_min: SampleSummary,
+    /// Condition number max |lambda| / min |lambda|
+    pub condition_number: SampleSummary,
+}
+
+/// Monte Carlo estimate of the distribution of lambda_max, lambda_min, and the condition number.
+///
+/// Draws `trials` symmetric matrices from `sampler`, diagonalizes each, and
+/// summarizes the extremes. The sampler fixes the ensemble and size, e.g.
+/// `|rng| sample_goe_with(rng, 200)`.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator passed to the sampler
+/// * `trials` - Number of matrices to draw
+/// * `sampler` - Produces one symmetric matrix per call
+///
+/// # Returns
+///
+/// `None` if `trials` is zero or the sampler produces empty matrices
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{extreme_eigenvalue_stats, sample_goe_with};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let stats = extreme_eigenvalue_stats(&mut rng, 20, |r| sample_goe_with(r, 50)).unwrap();
+/// // The GOE edge sits near 2 (semicircle radius) with O(n^{-2/3}) fluctuations.
+/// assert!((stats.lambda_max.mean - 2.0).abs() < 0.3);
+/// ```
+pub fn extreme_eigenvalue_stats<R, F>(
+    rng: &mut R,
+    trials: usize,
+    mut sampler: F,
+) -> Option<ExtremeEigenvalueStats>
+where
+    R: Rng,
+    F: FnMut(&mut R) -> Array2<f64>,
+{
+    let mut maxima = Vec::with_capacity(trials);
+    let mut minima = Vec::with_capacity(trials);
+    let mut conditions = Vec::with_capacity(trials);
+
+    for _ in 0..trials {
+        let eigenvalues = symmetric_eigenvalues(&sampler(rng));
+        let (Some(&lo), Some(&hi)) = (eigenvalues.first(), eigenvalues.last()) else {
+            return None;
+        };
+        let abs_max = lo.abs().max(hi.abs());
+        let abs_min = eigenvalues
+            .iter()
+            .fold(f64::INFINITY, |m, v| m.min(v.abs()));
+        maxima.push(hi);
+        minima.push(lo);
+        conditions.push(abs_max / abs_min);
+    }
+
+    Some(ExtremeEigenvalueStats {
+        trials,
+        lambda_max: SampleSummary::from_samples(&maxima)?,
+        lambda_min: SampleSummary::from_samples(&minima)?,
+        condition_number: SampleSummary::from_samples(&conditions)?,
+    })
+}
+
+/// Quantile of sorted data with linear interpolation between order statistics.
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    let frac = pos - lo as f64;
+    sorted[lo] + frac * (sorted[hi] - sorted[lo])
+}
+
+/// Sample covariance eigenvalues of n Gaussian observations of dimension p,
+/// with population covariance I + diag(spikes, 0, ..., 0); the spiked model
+/// shared by the tests of the detection modules.
+#[cfg(test)]
+pub(crate) fn spiked_covariance_eigenvalues(
+    seed: u64,
+    n: usize,
+    p: usize,
+    spikes: &[f64],
+) -> Vec<f64> {
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
+    let mut x: Array2<f64> = Array2::from_shape_fn((n, p), |_| StandardNormal.sample(&mut rng));
+    for (k, theta) in spikes.iter().enumerate() {
+        x.column_mut(k).mapv_inplace(|v| v * (1.0 + theta).sqrt());
+    }
+    symmetric_eigenvalues(&(x.t().dot(&x) / n as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{marchenko_pastur_support, sample_wishart_with};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_sample_summary() {
+        let s = SampleSummary::from_samples(&[4.0, 1.0, 3.0, 2.0, 5.0]).unwrap();
+        assert_eq!(s.mean, 3.0);
+        assert_eq!(s.median, 3.0);
+        assert_eq!(s.q25, 2.0);
+        assert!((s.q05 - 1.2).abs() < 1e-12);
+        assert!((s.std_dev - 2.5_f64.sqrt()).abs() < 1e-12);
+        assert!(SampleSummary::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_wishart_extremes_near_mp_edges() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(21);
+        let (n, p) = (400, 100);
+        let stats =
+            extreme_eigenvalue_stats(&mut rng, 10, |r| sample_wishart_with(r, n, p) / n as f64)
+                .unwrap();
+        let (lo, hi) = marchenko_pastur_support(p as f64 / n as f64, 1.0);
+        assert!(
+            (stats.lambda_max.mean - hi).abs() < 0.15,
+            "{:?}",
+            stats.lambda_max
+        );
+        assert!(
+            (stats.lambda_min.mean - lo).abs() < 0.1,
+            "{:?}",
+            stats.lambda_min
+        );
+        assert!(stats.condition_number.median > 1.0);
+        assert_eq!(stats.trials, 10);
+    }
+
+    #[test]
+    fn test_zero_trials() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        assert!(extreme_eigenvalue_stats(&mut rng, 0, |_| Array2::eye(2)).is_none());
+    }
+}
@@ -0,0 +1,527 @@
+//! Special functions used by the distribution and test code.
+//!
+//! Most are crate-internal; the Airy function and its derivative are public
+//! because the soft-edge kernels are built from them.
+
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+
+/// Euler-Mascheroni constant.
+pub(crate) const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+/// Natural log of the gamma function for x > 0 (Lanczos approximation, g = 7).
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const COEF: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection: Gamma(x) Gamma(1 - x) = pi / sin(pi x).
+        return (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let mut acc = COEF[0];
+    for (i, &c) in COEF.iter().enumerate().skip(1) {
+        acc += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + acc.ln()
+}
+
+/// Regularized lower incomplete gamma function P(a, x) = gamma(a, x) / Gamma(a).
+pub(crate) fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x < a + 1.0 {
+        gamma_p_series(a, x)
+    } else {
+        1.0 - gamma_q_continued_fraction(a, x)
+    }
+}
+
+/// Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x), accurate far into the tail.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        1.0 - gamma_p_series(a, x)
+    } else {
+        gamma_q_continued_fraction(a, x)
+    }
+}
+
+/// Standard normal survival function P(Z > z).
+pub(crate) fn normal_sf(z: f64) -> f64 {
+    let tail = 0.5 * gamma_q(0.5, 0.5 * z * z);
+    if z >= 0.0 {
+        tail
+    } else {
+        1.0 - tail
+    }
+}
+
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    let mut ap = a;
+    for _ in 0..1000 {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * 1e-16 {
+            break;
+        }
+    }
+    (sum.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn gamma_q_continued_fraction(a: f64, x: f64) -> f64 {
+    // Modified Lentz evaluation of the continued fraction for Q(a, x).
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..1000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-16 {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+/// Sine and cosine integrals (Si(x), Ci(x)) for x > 0.
+///
+/// Power series for small x, continued fraction for E_1(ix) otherwise.
+pub(crate) fn sine_cosine_integrals(x: f64) -> (f64, f64) {
+    if x <= 0.0 {
+        return (0.0, f64::NEG_INFINITY);
+    }
+
+    if x > 2.0 {
+        let tiny = 1e-300;
+        let mut b = Complex64::new(1.0, x);
+        let mut c = Complex64::new(1.0 / tiny, 0.0);
+        let mut d = b.inv();
+        let mut h = d;
+        for i in 2..1000 {
+            let a = -((i - 1) as f64).powi(2);
+            b += 2.0;
+            d = (d * a + b).inv();
+            c = b + c.inv() * a;
+            let delta = c * d;
+            h *= delta;
+            if (delta.re - 1.0).abs() + delta.im.abs() < 1e-15 {
+                break;
+            }
+        }
+        h *= Complex64::new(x.cos(), -x.sin());
+        return (PI / 2.0 + h.im, -h.re);
+    }
+
+    // Series: Si = sum (-1)^k x^(2k+1) / ((2k+1)(2k+1)!), Ci = gamma + ln x + sum (-1)^k x^(2k) / (2k (2k)!)
+    let mut si = 0.0;
+    let mut ci = 0.0;
+    let mut term = 1.0; // x^k / k!
+    for k in 1..100 {
+        term *= x / k as f64;
+        let contribution = term / k as f64;
+        match k % 4 {
+            1 => si += contribution,
+            2 => ci -= contribution,
+            3 => si -= contribution,
+            _ => ci += contribution,
+        }
+        if contribution < 1e-17 * (si.abs() + ci.abs()).max(1e-300) {
+            break;
+        }
+    }
+    (si, EULER_GAMMA + x.ln() + ci)
+}
+
+/// Modified Bessel function K_nu(z) = int_0^inf exp(-z cosh t) cosh(nu t) dt for z > 0.
+///
+/// The integrand decays double-exponentially, so the trapezoid rule is
+/// spectrally accurate here.
+pub(crate) fn bessel_k(nu: f64, z: f64) -> f64 {
+    let h: f64 = 0.02;
+    let mut sum = 0.5 * (-z).exp();
+    let mut t = h;
+    loop {
+        let term = (-z * t.cosh()).exp() * (nu * t).cosh();
+        sum += term;
+        if term < 1e-18 * sum || t > 50.0 {
+            break;
+        }
+        t += h;
+    }
+    sum * h
+}
+
+/// Bessel function of the first kind J_nu(x) for real nu > -1 and x >= 0.
+///
+/// Power series for x <= 12 (at most ~4 digits lost to cancellation), and
+/// Schlafli's integral
+/// J_nu(x) = (1/pi) int_0^pi cos(nu t - x sin t) dt
+///           - (sin(nu pi) / pi) int_0^inf exp(-x sinh t - nu t) dt
+/// by Gauss-Legendre quadrature beyond.
+pub(crate) fn bessel_j(nu: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return if x < 0.0 || nu > 0.0 {
+            if x < 0.0 {
+                f64::NAN
+            } else {
+                0.0
+            }
+        } else if nu == 0.0 {
+            1.0
+        } else {
+            f64::INFINITY
+        };
+    }
+    if x <= 12.0 {
+        let q = -0.25 * x * x;
+        let mut term = (nu * (0.5 * x).ln() - ln_gamma(nu + 1.0)).exp();
+        let mut sum = term;
+        for k in 1..200 {
+            let kf = k as f64;
+            term *= q / (kf * (kf + nu));
+            sum += term;
+            if term.abs() < 1e-17 * sum.abs() {
+                break;
+            }
+        }
+        return sum;
+    }
+    let (t, w) = gauss_legendre(40 + x as usize, 0.0, PI);
+    let oscillatory: f64 = t
+        .iter()
+        .zip(&w)
+        .map(|(&t, &w)| w * (nu * t - x * t.sin()).cos())
+        .sum();
+    let sin_nu_pi = (nu * PI).sin();
+    let tail = if sin_nu_pi.abs() < 1e-15 {
+        0.0
+    } else {
+        let (t, w) = gauss_legendre(40, 0.0, (50.0 / x).asinh() + 1.0);
+        t.iter()
+            .zip(&w)
+            .map(|(&t, &w)| w * (-x * t.sinh() - nu * t).exp())
+            .sum()
+    };
+    (oscillatory - sin_nu_pi * tail) / PI
+}
+
+/// Airy function Ai(x) for real x.
+///
+/// Maclaurin series on [-8, 2] (cancellation stays below ~1e-8 relative there),
+/// Ai(x) = sqrt(x / 3) K_{1/3}(zeta) / pi with zeta = (2/3) x^(3/2) for x > 2,
+/// and the oscillatory Poincare expansion below -8.
+pub fn airy_ai(x: f64) -> f64 {
+    // Ai(0) and -Ai'(0)
+    const C1: f64 = 0.355_028_053_887_817_2;
+    const C2: f64 = 0.258_819_403_792_806_8;
+
+    if x > 2.0 {
+        let zeta = 2.0 / 3.0 * x.powf(1.5);
+        if zeta > 700.0 {
+            return 0.0;
+        }
+        return (x / 3.0).sqrt() * bessel_k(1.0 / 3.0, zeta) / PI;
+    }
+
+    if x < -8.0 {
+        let y = -x;
+        let zeta = 2.0 / 3.0 * y.powf(1.5);
+        // u_k = (6k-5)(6k-3)(6k-1) / ((2k-1) 216 k) u_{k-1}
+        let mut even = 0.0;
+        let mut odd = 0.0;
+        let mut u = 1.0;
+        let mut zeta_pow = 1.0;
+        for k in 0..12 {
+            if k > 0 {
+                let kf = k as f64;
+                u *= (6.0 * kf - 5.0) * (6.0 * kf - 3.0) * (6.0 * kf - 1.0)
+                    / ((2.0 * kf - 1.0) * 216.0 * kf);
+                zeta_pow *= zeta;
+            }
+            let term = u / zeta_pow;
+            match k % 4 {
+                0 => even += term,
+                1 => odd += term,
+                2 => even -= term,
+                _ => odd -= term,
+            }
+        }
+        let phase = zeta + PI / 4.0;
+        return (phase.sin() * even - phase.cos() * odd) / (PI.sqrt() * y.powf(0.25));
+    }
+
+    // Ai(x) = C1 f(x) - C2 g(x) with
+    // f = sum 3^k (1/3)_k x^(3k) / (3k)!, g = sum 3^k (2/3)_k x^(3k+1) / (3k+1)!
+    let x3 = x * x * x;
+    let mut f_term = 1.0;
+    let mut g_term = x;
+    let mut f = f_term;
+    let mut g = g_term;
+    for k in 1..200 {
+        let kf = k as f64;
+        f_term *= x3 / ((3.0 * kf - 1.0) * 3.0 * kf);
+        g_term *= x3 / (3.0 * kf * (3.0 * kf + 1.0));
+        f += f_term;
+        g += g_term;
+        if f_term.abs() + g_term.abs() < 1e-17 * (f.abs() + g.abs()) {
+            break;
+        }
+    }
+    C1 * f - C2 * g
+}
+
+/// Derivative Ai'(x) of the Airy function, on the same three regimes as [`airy_ai`].
+///
+/// Ai'(x) = -x K_{2/3}(zeta) / (pi sqrt 3) for x > 2, the Poincare
+/// expansion with coefficients v_k = -(6k + 1) / (6k - 1) u_k below -8, and
+/// the differentiated Maclaurin series in between.
+pub fn airy_ai_prime(x: f64) -> f64 {
+    const C1: f64 = 0.355_028_053_887_817_2;
+    const C2: f64 = 0.258_819_403_792_806_8;
+
+    if x > 2.0 {
+        let zeta = 2.0 / 3.0 * x.powf(1.5);
+        if zeta > 700.0 {
+            return 0.0;
+        }
+        return -x * bessel_k(2.0 / 3.0, zeta) / (PI * 3f64.sqrt());
+    }
+
+    if x < -8.0 {
+        let y = -x;
+        let zeta = 2.0 / 3.0 * y.powf(1.5);
+        let mut even = 0.0;
+        let mut odd = 0.0;
+        let mut u = 1.0;
+        let mut zeta_pow = 1.0;
+        for k in 0..12 {
+            let kf = k as f64;
+            if k > 0 {
+                u *= (6.0 * kf - 5.0) * (6.0 * kf - 3.0) * (6.0 * kf - 1.0)
+                    / ((2.0 * kf - 1.0) * 216.0 * kf);
+                zeta_pow *= zeta;
+            }
+            let v = -(6.0 * kf + 1.0) / (6.0 * kf - 1.0) * u;
+            let term = v / zeta_pow;
+            match k % 4 {
+                0 => even += term,
+                1 => odd += term,
+                2 => even -= term,
+                _ => odd -= term,
+            }
+        }
+        let phase = zeta - PI / 4.0;
+        return y.powf(0.25) * (phase.sin() * even - phase.cos() * odd) / PI.sqrt();
+    }
+
+    // Ai'(x) = C1 f'(x) - C2 g'(x) with f' = sum 3k a_k x^(3k-1), g' = sum (3k+1) b_k x^(3k).
+    let x3 = x * x * x;
+    let mut f_term = x * x / 6.0;
+    let mut g_term = 1.0;
+    let mut f = 3.0 * f_term;
+    let mut g = g_term;
+    for k in 1..200 {
+        let kf = k as f64;
+        f_term *= x3 / ((3.0 * kf + 2.0) * (3.0 * kf + 3.0));
+        g_term *= x3 / (3.0 * kf * (3.0 * kf + 1.0));
+        f += (3.0 * kf + 3.0) * f_term;
+        g += (3.0 * kf + 1.0) * g_term;
+        if f_term.abs() + g_term.abs() < 1e-17 * (f.abs() + g.abs()) {
+            break;
+        }
+    }
+    C1 * f - C2 * g
+}
+
+/// Gauss-Legendre nodes and weights on [a, b] with m points.
+pub(crate) fn gauss_legendre(m: usize, a: f64, b: f64) -> (Vec<f64>, Vec<f64>) {
+    let mut nodes = vec![0.0; m];
+    let mut weights = vec![0.0; m];
+    let half = 0.5 * (b - a);
+    let mid = 0.5 * (b + a);
+    for i in 0..m.div_ceil(2) {
+        // Newton iteration on P_m from the Tricomi initial guess.
+        let mut z = (PI * (i as f64 + 0.75) / (m as f64 + 0.5)).cos();
+        let mut dp = 1.0;
+        for _ in 0..100 {
+            let (mut p0, mut p1) = (1.0, 0.0);
+            for j in 0..m {
+                let p2 = p1;
+                p1 = p0;
+                p0 = ((2 * j + 1) as f64 * z * p1 - j as f64 * p2) / (j + 1) as f64;
+            }
+            dp = m as f64 * (z * p0 - p1) / (z * z - 1.0);
+            let step = p0 / dp;
+            z -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        let w = 2.0 / ((1.0 - z * z) * dp * dp);
+        nodes[i] = mid - half * z;
+        nodes[m - 1 - i] = mid + half * z;
+        weights[i] = half * w;
+        weights[m - 1 - i] = half * w;
+    }
+    (nodes, weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_gamma_factorials() {
+        assert!(ln_gamma(1.0).abs() < 1e-12);
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sine_cosine_integrals() {
+        // Reference values from Abramowitz & Stegun table 5.1.
+        let (si1, ci1) = sine_cosine_integrals(1.0);
+        assert!((si1 - 0.946_083_070_367_183).abs() < 1e-12);
+        assert!((ci1 - 0.337_403_922_900_968).abs() < 1e-12);
+        let (si5, ci5) = sine_cosine_integrals(5.0);
+        assert!((si5 - 1.549_931_244_944_674).abs() < 1e-12);
+        assert!((ci5 - (-0.190_029_749_656_644)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gamma_p_exponential() {
+        // P(1, x) = 1 - e^{-x}; x = 5 exercises the continued-fraction branch.
+        for &x in &[0.1, 1.0, 5.0] {
+            assert!((gamma_p(1.0, x) - (1.0 - (-x).exp())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_normal_sf() {
+        assert!((normal_sf(0.0) - 0.5).abs() < 1e-15);
+        assert!((normal_sf(1.959_963_984_540_054) - 0.025).abs() < 1e-12);
+        assert!((normal_sf(-1.0) - 0.841_344_746_068_542_9).abs() < 1e-12);
+        // Relative accuracy deep in the tail, where 1 - P(a, x) would round to 0.
+        assert!((normal_sf(10.0) / 7.619_853_024_160_527e-24 - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_bessel_k_half() {
+        // K_{1/2}(z) = sqrt(pi / (2z)) e^{-z}
+        for &z in &[0.1, 1.0, 7.5] {
+            let expected = (PI / (2.0 * z)).sqrt() * (-z).exp();
+            assert!((bessel_k(0.5, z) - expected).abs() < 1e-10 * expected.max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_airy_ai_reference() {
+        // Reference values from Abramowitz & Stegun table 10.11 and DLMF.
+        let cases = [
+            (0.0, 0.355_028_053_887_817),
+            (1.0, 0.135_292_416_312_881),
+            (2.5, 0.015_725_923_380_470_5),
+            (5.0, 1.083_444_281_360_744e-4),
+            (-2.0, 0.227_407_428_201_686),
+            (-5.0, 0.350_761_009_024_114),
+            (-10.0, 0.040_241_238_486_443),
+        ];
+        for &(x, expected) in &cases {
+            let got = airy_ai(x);
+            assert!(
+                (got - expected).abs() < 1e-8 * expected.abs().max(1e-3),
+                "Ai({}) = {} vs {}",
+                x,
+                got,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_bessel_j_reference() {
+        assert!((bessel_j(0.0, 1.0) - 0.765_197_686_557_966_6).abs() < 1e-13);
+        assert!((bessel_j(0.0, 20.0) - 0.167_024_664_340_583_1).abs() < 1e-12);
+        assert!((bessel_j(1.0, 15.0) - 0.205_104_038_613_522_8).abs() < 1e-12);
+        // Half-integer orders are elementary; check both branches.
+        for x in [0.5, 5.0, 11.0, 13.0, 40.0] {
+            let c = (2.0 / (PI * x)).sqrt();
+            assert!((bessel_j(0.5, x) - c * x.sin()).abs() < 1e-12, "J_1/2({x})");
+            assert!(
+                (bessel_j(-0.5, x) - c * x.cos()).abs() < 1e-12,
+                "J_-1/2({x})"
+            );
+            let j32 = c * (x.sin() / x - x.cos());
+            assert!((bessel_j(1.5, x) - j32).abs() < 1e-12, "J_3/2({x})");
+        }
+        assert_eq!(bessel_j(0.0, 0.0), 1.0);
+        assert_eq!(bessel_j(2.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_airy_ai_prime_reference() {
+        let cases = [
+            (0.0, -0.258_819_403_792_807),
+            (1.0, -0.159_147_441_296_793),
+            (2.0, -0.053_090_384_433_180),
+            (-1.0, -0.010_160_567_116_645),
+        ];
+        for &(x, expected) in &cases {
+            let got = airy_ai_prime(x);
+            assert!(
+                (got - expected).abs() < 1e-10,
+                "Ai'({x}) = {got} vs {expected}"
+            );
+        }
+        // Agreement with central differences of Ai in every regime.
+        for x in [-20.0, -10.0, -8.5, -7.5, -3.0, 1.9, 2.1, 3.5, 6.0] {
+            let h = 1e-5;
+            let fd = (airy_ai(x + h) - airy_ai(x - h)) / (2.0 * h);
+            let got = airy_ai_prime(x);
+            assert!(
+                (got - fd).abs() < 1e-6 * fd.abs().max(1e-3),
+                "Ai'({x}) = {got} vs {fd}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_gauss_legendre_polynomials() {
+        let (x, w) = gauss_legendre(10, 0.0, 2.0);
+        let integral: f64 = x.iter().zip(&w).map(|(x, w)| w * x.powi(19)).sum();
+        assert!((integral - 2.0_f64.powi(20) / 20.0).abs() < 1e-8 * 2.0_f64.powi(20));
+        assert!((w.iter().sum::<f64>() - 2.0).abs() < 1e-14);
+    }
+}
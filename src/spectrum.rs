@@ -0,0 +1,278 @@
+//! A sorted eigenvalue set with the crate's statistics as methods.
+//!
+//! Most functions in this crate take `&[f64]` and sort internally. A multi-step
+//! analysis (density, CDF, spacing ratios, moments, ...) on one spectrum
+//! sorts once by wrapping it in a [`Spectrum`].
+
+use std::borrow::Cow;
+
+use ndarray::{Array2, ArrayBase, Data, Ix1};
+use num_complex::Complex64;
+
+use crate::density::{kernel_spectral_density, step_quantile, Bandwidth, BinRule, EmpiricalCdf};
+use crate::free::spectral_moments;
+use crate::levels::{unfold, UnfoldMethod};
+use crate::linalg::symmetric_eigenvalues;
+use crate::metrics::{effective_rank, spectral_entropy};
+use crate::transforms::resolvent_trace_from_eigenvalues;
+use crate::{
+    empirical_spectral_density, empirical_spectral_density_auto, level_spacing_ratios,
+    mean_spacing_ratio, stieltjes_transform,
+};
+
+/// Eigenvalues sorted ascending, NaNs removed.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{sample_goe_with, Spectrum, MEAN_SPACING_RATIO_GOE};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let spectrum = Spectrum::from_symmetric(&sample_goe_with(&mut rng, 400));
+/// assert!((spectrum.mean_spacing_ratio() - MEAN_SPACING_RATIO_GOE).abs() < 0.05);
+/// assert!(spectrum.moments(2)[1] > 0.9); // semicircle second moment is 1
+/// assert!(spectrum.quantile(0.5).abs() < 0.2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SpectrumValues"))]
+pub struct Spectrum {
+    values: Vec<f64>,
+}
+
+/// Wire form of [`Spectrum`]; deserializing goes through [`Spectrum::new`]
+/// so the values come out sorted and NaN-free.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SpectrumValues {
+    values: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SpectrumValues> for Spectrum {
+    fn from(wire: SpectrumValues) -> Self {
+        Self::new(&wire.values)
+    }
+}
+
+impl Spectrum {
+    /// Wrap eigenvalues in any order; NaNs are dropped.
+    pub fn new<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> Self {
+        let mut values: Vec<f64> = eigenvalues
+            .as_eigenvalues()
+            .iter()
+            .copied()
+            .filter(|v| !v.is_nan())
+            .collect();
+        values.sort_by(|a, b| a.total_cmp(b));
+        Self { values }
+    }
+
+    /// Spectrum of a real symmetric matrix (see [`crate::symmetric_eigenvalues`]).
+    pub fn from_symmetric(a: &Array2<f64>) -> Self {
+        Self::new(&symmetric_eigenvalues(a))
+    }
+
+    /// Sorted eigenvalues.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Number of eigenvalues.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the spectrum is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Smallest eigenvalue (NaN if empty).
+    pub fn min(&self) -> f64 {
+        self.values.first().copied().unwrap_or(f64::NAN)
+    }
+
+    /// Largest eigenvalue (NaN if empty).
+    pub fn max(&self) -> f64 {
+        self.values.last().copied().unwrap_or(f64::NAN)
+    }
+
+    /// Histogram density; see [`crate::empirical_spectral_density`].
+    pub fn density(&self, bins: usize) -> (Vec<f64>, Vec<f64>) {
+        empirical_spectral_density(&self.values, bins)
+    }
+
+    /// Histogram density with a data-driven bin count; see [`crate::empirical_spectral_density_auto`].
+    pub fn density_auto(&self, rule: BinRule) -> (Vec<f64>, Vec<f64>) {
+        empirical_spectral_density_auto(&self.values, rule)
+    }
+
+    /// Kernel density estimate on a grid; see [`crate::kernel_spectral_density`].
+    pub fn kde(&self, grid: &[f64], bandwidth: Bandwidth, support: Option<(f64, f64)>) -> Vec<f64> {
+        kernel_spectral_density(&self.values, grid, bandwidth, support)
+    }
+
+    /// Empirical CDF, sharing the sorted values without re-sorting.
+    pub fn cdf(&self) -> EmpiricalCdf {
+        EmpiricalCdf::from_sorted(self.values.clone())
+    }
+
+    /// Empirical quantile: the smallest eigenvalue with F_n >= q (see [`EmpiricalCdf::quantile`]).
+    pub fn quantile(&self, q: f64) -> f64 {
+        step_quantile(&self.values, q)
+    }
+
+    /// Raw moments m_1..=m_k_max; see [`crate::spectral_moments`].
+    pub fn moments(&self, k_max: usize) -> Vec<f64> {
+        spectral_moments(&self.values, k_max)
+    }
+
+    /// Consecutive level spacing ratios; see [`crate::level_spacing_ratios`].
+    pub fn spacing_ratios(&self) -> Vec<f64> {
+        level_spacing_ratios(&self.values)
+    }
+
+    /// Mean level spacing ratio; see [`crate::mean_spacing_ratio`].
+    pub fn mean_spacing_ratio(&self) -> f64 {
+        mean_spacing_ratio(&self.values)
+    }
+
+    /// Unfolded spectrum; see [`crate::unfold`].
+    pub fn unfold(&self, method: UnfoldMethod) -> Vec<f64> {
+        unfold(&self.values, method)
+    }
+
+    /// Real-axis Stieltjes transform; see [`crate::stieltjes_transform`].
+    pub fn stieltjes(&self, z: f64) -> f64 {
+        stieltjes_transform(&self.values, z)
+    }
+
+    /// Normalized resolvent trace at a complex point; see [`crate::resolvent_trace_from_eigenvalues`].
+    pub fn resolvent(&self, z: Complex64) -> Complex64 {
+        resolvent_trace_from_eigenvalues(&self.values, z)
+    }
+
+    /// Shannon entropy of the normalized spectrum; see [`crate::spectral_entropy`].
+    pub fn entropy(&self) -> f64 {
+        spectral_entropy(&self.values)
+    }
+
+    /// Effective rank exp(H); see [`crate::effective_rank`].
+    pub fn effective_rank(&self) -> f64 {
+        effective_rank(&self.values)
+    }
+}
+
+impl From<Vec<f64>> for Spectrum {
+    fn from(mut values: Vec<f64>) -> Self {
+        values.retain(|v| !v.is_nan());
+        values.sort_by(|a, b| a.total_cmp(b));
+        Self { values }
+    }
+}
+
+impl FromIterator<f64> for Spectrum {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<f64>>())
+    }
+}
+
+/// Inputs accepted wherever the crate takes a set of eigenvalues.
+///
+/// Implemented for slices, arrays, `Vec`s, one-dimensional ndarray arrays and
+/// views, and [`Spectrum`]. Contiguous data is read in place; a strided view
+/// (e.g. a matrix column) is copied once. Iterators can be collected into a
+/// [`Spectrum`] first.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::{mean_spacing_ratio, Spectrum};
+///
+/// let m = array![[0.0, 1.0], [1.3, 2.0], [2.1, 3.0], [3.5, 4.0]];
+/// let r = mean_spacing_ratio(&[0.0, 1.3, 2.1, 3.5]);
+/// assert_eq!(mean_spacing_ratio(&m.column(0)), r);
+/// assert_eq!(mean_spacing_ratio(&vec![0.0, 1.3, 2.1, 3.5]), r);
+/// let spectrum: Spectrum = m.column(0).iter().map(|x| -x).collect();
+/// assert_eq!(mean_spacing_ratio(&spectrum), r);
+/// ```
+pub trait AsEigenvalues {
+    /// The values as a slice, borrowed when they are contiguous.
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]>;
+}
+
+impl AsEigenvalues for [f64] {
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<const N: usize> AsEigenvalues for [f64; N] {
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl AsEigenvalues for Vec<f64> {
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<S: Data<Elem = f64>> AsEigenvalues for ArrayBase<S, Ix1> {
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]> {
+        match self.as_slice() {
+            Some(values) => Cow::Borrowed(values),
+            None => Cow::Owned(self.to_vec()),
+        }
+    }
+}
+
+impl AsEigenvalues for Spectrum {
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]> {
+        Cow::Borrowed(&self.values)
+    }
+}
+
+impl<T: AsEigenvalues + ?Sized> AsEigenvalues for &T {
+    fn as_eigenvalues(&self) -> Cow<'_, [f64]> {
+        (**self).as_eigenvalues()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spectrum_matches_free_functions() {
+        let raw = vec![3.0, f64::NAN, 1.0, 4.0, 1.5, 9.0, 2.6];
+        let spectrum = Spectrum::from(raw.clone());
+        let clean: Vec<f64> = raw.iter().copied().filter(|v| !v.is_nan()).collect();
+        assert_eq!(spectrum.values(), &[1.0, 1.5, 2.6, 3.0, 4.0, 9.0]);
+        assert_eq!(spectrum, Spectrum::new(&raw));
+        assert_eq!((spectrum.min(), spectrum.max()), (1.0, 9.0));
+        assert_eq!(spectrum.moments(3), spectral_moments(&clean, 3));
+        assert_eq!(spectrum.cdf(), EmpiricalCdf::new(&clean));
+        assert_eq!(spectrum.quantile(0.5), spectrum.cdf().quantile(0.5));
+        assert_eq!(spectrum.density(4), empirical_spectral_density(&clean, 4));
+        assert_eq!(spectrum.spacing_ratios().len(), 4);
+        assert!((spectrum.stieltjes(0.0) - stieltjes_transform(&clean, 0.0)).abs() < 1e-15);
+        assert!(Spectrum::new(&[]).max().is_nan());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let spectrum = Spectrum::new(&[3.0, 1.0, 2.0]);
+        let json = serde_json::to_string(&spectrum).unwrap();
+        assert_eq!(serde_json::from_str::<Spectrum>(&json).unwrap(), spectrum);
+        // Unsorted input is sorted on the way in, as by `Spectrum::new`.
+        let unsorted = serde_json::from_str::<Spectrum>(r#"{"values":[3.0,1.0,2.0]}"#).unwrap();
+        assert_eq!(unsorted.values(), &[1.0, 2.0, 3.0]);
+        assert_eq!(unsorted, spectrum);
+    }
+}
@@ -0,0 +1,956 @@
+//! Spiked covariance models: eigenvalues that separate from the MP bulk.
+//!
+//! In the spiked model Sigma = sigma^2 (I + sum_k theta_k u_k u_k^T), a population
+//! spike theta_k > sqrt(gamma) pushes a sample eigenvalue beyond the
+//! Marchenko-Pastur edge sigma^2 (1 + sqrt(gamma))^2; weaker spikes are
+//! swallowed by the bulk (the BBP transition).
+//!
+//! The additive spiked Wigner model W + sum_k theta_k v_k v_k^T, with W a GOE
+//! matrix whose semicircle has radius 2 sigma, has the same phase transition
+//! at theta = sigma.
+
+use ndarray::Array2;
+
+use crate::gof::ks_distance_sorted;
+use crate::laws::sample_covariance_support;
+use crate::simulation::quantile_sorted;
+use crate::spectrum::AsEigenvalues;
+use crate::tracy_widom::{tracy_widom_sf, tw_centering_scaling};
+use crate::{marchenko_pastur_cdf, marchenko_pastur_quantile, marchenko_pastur_support};
+
+/// Threshold rule used by [`count_spikes_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpikeThreshold {
+    /// Count eigenvalues above the asymptotic MP upper edge.
+    MpEdge,
+    /// Sequential Tracy-Widom tests at level `alpha`.
+    ///
+    /// The k-th largest eigenvalue (0-based) is tested against the TW1 law for
+    /// a p - k dimensional noise bulk, stopping at the first non-significant
+    /// one. Accounts for the n^{-2/3} edge fluctuations that make the plain
+    /// MP edge over-count at finite n.
+    TracyWidom {
+        /// Per-test significance level
+        alpha: f64,
+    },
+}
+
+/// Number of eigenvalues above the Marchenko-Pastur upper edge.
+///
+/// The edge is sigma^2 (1 + sqrt(gamma))^2 for every gamma, including
+/// gamma > 1 where the sample covariance also has p - n null eigenvalues.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::count_spikes;
+///
+/// // Edge for gamma = 0.25, sigma^2 = 1 is 2.25.
+/// assert_eq!(count_spikes(&[0.3, 1.0, 2.0, 4.0, 9.0], 0.25, 1.0), 2);
+/// ```
+pub fn count_spikes<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    count_spikes_with(eigenvalues, ratio, sigma_sq, SpikeThreshold::MpEdge)
+}
+
+/// Number of eigenvalues statistically outside the bulk under the given threshold rule.
+///
+/// The Tracy-Widom rule needs the full spectrum of the p x p sample
+/// covariance: p is taken as `eigenvalues.len()` and n as p / ratio.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+/// * `threshold` - Threshold rule
+///
+/// # Returns
+///
+/// Number of spikes (0 for an empty spectrum or invalid parameters)
+pub fn count_spikes_with<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+    threshold: SpikeThreshold,
+) -> usize {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() || ratio <= 0.0 || sigma_sq <= 0.0 {
+        return 0;
+    }
+
+    match threshold {
+        SpikeThreshold::MpEdge => {
+            let (_, upper) = sample_covariance_support(ratio, sigma_sq);
+            eigenvalues.iter().filter(|&&v| v > upper).count()
+        }
+        SpikeThreshold::TracyWidom { alpha } => {
+            let p = eigenvalues.len();
+            let n = (p as f64 / ratio).round() as usize;
+            if n < 2 {
+                return 0;
+            }
+            let mut sorted = eigenvalues.to_vec();
+            sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+            sorted
+                .iter()
+                .enumerate()
+                .take_while(|&(k, &lambda)| {
+                    if p - k < 2 {
+                        return false;
+                    }
+                    let scaling = tw_centering_scaling(n, p - k);
+                    let (mu, sigma) = (scaling.mu, scaling.sigma);
+                    let statistic = (n as f64 * lambda / sigma_sq - mu) / sigma;
+                    tracy_widom_sf(statistic, 1.0) < alpha
+                })
+                .count()
+        }
+    }
+}
+
+/// Number of singular values of an n x p data matrix above the noise edge.
+///
+/// For Y = X + sigma Z with Z an n x p matrix of i.i.d. N(0, 1) entries and
+/// X of low rank, the noise singular values fill [0, sigma (sqrt(n) + sqrt(p))]
+/// and a signal component is visible only if its singular value lands
+/// beyond that edge. This counts them, i.e. [`count_spikes`] on the
+/// eigenvalues s^2 / max(n, p) at ratio min(n, p) / max(n, p). Without a known sigma it comes from
+/// [`estimate_sigma_sq`] on the same eigenvalues, which is robust to the
+/// signal components themselves.
+///
+/// The largest pure-noise singular value exceeds the asymptotic edge with
+/// probability about 0.17 at any n, so this slightly over-counts on pure
+/// noise; [`count_spikes_with`] with [`SpikeThreshold::TracyWidom`] controls
+/// that error rate instead.
+///
+/// # Arguments
+///
+/// * `singular_values` - All min(n, p) singular values of Y, in any order
+/// * `n` - Rows of Y
+/// * `p` - Columns of Y
+/// * `sigma` - Noise standard deviation per entry, or `None` to estimate it
+///
+/// # Returns
+///
+/// Estimated rank (0 if n or p is zero or sigma is not positive)
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{estimate_rank, singular_values};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(4);
+/// let (n, p) = (300, 100);
+/// // Rank-2 signal plus noise with sigma = 0.5.
+/// let u: Vec<f64> = (0..n).map(|i| ((i % 7) as f64 - 3.0) / 2.0).collect();
+/// let v: Vec<f64> = (0..n).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+/// let y = ndarray::Array2::from_shape_fn((n, p), |(i, j)| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     u[i] * (j % 3) as f64 + v[i] * 0.5 + 0.5 * z
+/// });
+/// let s = singular_values(&y);
+/// assert_eq!(estimate_rank(&s, n, p, Some(0.5)), 2);
+/// assert_eq!(estimate_rank(&s, n, p, None), 2);
+/// ```
+pub fn estimate_rank(singular_values: &[f64], n: usize, p: usize, sigma: Option<f64>) -> usize {
+    if n == 0 || p == 0 {
+        return 0;
+    }
+    let (small, large) = (n.min(p) as f64, n.max(p) as f64);
+    let ratio = small / large;
+    let eigenvalues: Vec<f64> = singular_values.iter().map(|s| s * s / large).collect();
+    let sigma_sq = match sigma {
+        Some(sigma) if sigma > 0.0 => sigma * sigma,
+        Some(_) => return 0,
+        None => estimate_sigma_sq(&eigenvalues, ratio),
+    };
+    count_spikes(&eigenvalues, ratio, sigma_sq)
+}
+
+/// BBP detection threshold sqrt(gamma) on the spike strength theta in the spiked covariance model.
+///
+/// A population spike sigma^2 (1 + theta) produces a sample eigenvalue outside
+/// the MP bulk, and a sample eigenvector correlated with the truth, if and
+/// only if theta > sqrt(gamma). Below the threshold no spectral method can
+/// detect the spike asymptotically.
+///
+/// # Arguments
+///
+/// * `ratio` - gamma = p/n ratio
+///
+/// # Returns
+///
+/// sqrt(gamma), or NaN for a negative ratio
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::bbp_threshold;
+///
+/// assert_eq!(bbp_threshold(0.25), 0.5);
+/// ```
+pub fn bbp_threshold(ratio: f64) -> f64 {
+    if ratio < 0.0 {
+        return f64::NAN;
+    }
+    ratio.sqrt()
+}
+
+/// BBP detection threshold on theta in the additive spiked Wigner model W + theta v v^T.
+///
+/// With W normalized like [`crate::sample_goe`] (semicircle on [-2 sigma, 2 sigma])
+/// the threshold is theta = sigma, i.e. 1 for the standard GOE.
+///
+/// # Returns
+///
+/// sigma, or NaN for a negative sigma
+pub fn bbp_threshold_wigner(sigma: f64) -> f64 {
+    if sigma < 0.0 {
+        return f64::NAN;
+    }
+    sigma
+}
+
+/// Smallest sample size n at which a spike of strength theta is above the BBP threshold.
+///
+/// Detection requires theta > sqrt(p / n), i.e. n > p / theta^2: the critical
+/// SNR expressed as a sample budget.
+///
+/// # Arguments
+///
+/// * `theta` - Spike strength (population eigenvalue sigma^2 (1 + theta))
+/// * `p` - Dimension
+///
+/// # Returns
+///
+/// The smallest n with theta > sqrt(p / n), or `None` for a non-positive theta
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::critical_sample_size;
+///
+/// // theta = 0.5 in p = 100 dimensions needs n > 400.
+/// assert_eq!(critical_sample_size(0.5, 100), Some(401));
+/// ```
+pub fn critical_sample_size(theta: f64, p: usize) -> Option<usize> {
+    if theta.is_nan() || theta <= 0.0 {
+        return None;
+    }
+    Some((p as f64 / (theta * theta)).floor() as usize + 1)
+}
+
+/// Asymptotic location of the sample eigenvalue produced by a population spike.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpikeLocation {
+    /// Limiting sample eigenvalue: the outlier, or the bulk edge it sticks to
+    pub eigenvalue: f64,
+    /// Whether the eigenvalue separates from the bulk (spike beyond the BBP threshold)
+    pub separated: bool,
+}
+
+/// Forward BBP map for the spiked covariance model: where a spike theta ends up in the sample spectrum.
+///
+/// For |theta| > sqrt(gamma) the sample eigenvalue converges to
+/// sigma^2 (1 + theta)(1 + gamma / theta), above the upper MP edge for a
+/// positive spike and below the lower edge for a negative one (theta > -1,
+/// gamma < 1). Weaker spikes stick to the nearest edge. The inverse map is
+/// [`debias_spike`].
+///
+/// # Arguments
+///
+/// * `theta` - Spike strength; the population eigenvalue is sigma^2 (1 + theta)
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+///
+/// # Returns
+///
+/// Limiting location; NaN eigenvalue for invalid parameters or theta <= -1
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spike_location;
+///
+/// // theta = 3, gamma = 0.5: 4 * (1 + 0.5 / 3).
+/// let location = spike_location(3.0, 0.5, 1.0);
+/// assert!(location.separated);
+/// assert!((location.eigenvalue - 4.0 * (1.0 + 0.5 / 3.0)).abs() < 1e-12);
+/// ```
+pub fn spike_location(theta: f64, ratio: f64, sigma_sq: f64) -> SpikeLocation {
+    if ratio <= 0.0 || sigma_sq <= 0.0 || theta.is_nan() || theta <= -1.0 {
+        return SpikeLocation {
+            eigenvalue: f64::NAN,
+            separated: false,
+        };
+    }
+    let (lower, upper) = sample_covariance_support(ratio, sigma_sq);
+    // A negative spike needs a lower edge away from zero to escape below it.
+    if theta.abs() > bbp_threshold(ratio) && (theta > 0.0 || ratio < 1.0) {
+        SpikeLocation {
+            eigenvalue: sigma_sq * (1.0 + theta) * (1.0 + ratio / theta),
+            separated: true,
+        }
+    } else {
+        SpikeLocation {
+            eigenvalue: if theta >= 0.0 { upper } else { lower },
+            separated: false,
+        }
+    }
+}
+
+/// Forward BBP map for the additive spiked Wigner model W + theta v v^T.
+///
+/// With W normalized like [`crate::sample_goe`] (semicircle on [-2 sigma, 2 sigma]),
+/// a spike |theta| > sigma produces an outlier at theta + sigma^2 / theta;
+/// weaker spikes stick to the edge +/- 2 sigma.
+///
+/// # Arguments
+///
+/// * `theta` - Spike strength (either sign)
+/// * `sigma` - Semicircle scale of the noise
+///
+/// # Returns
+///
+/// Limiting location; NaN eigenvalue for a non-positive sigma
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spike_location_wigner;
+///
+/// assert_eq!(spike_location_wigner(2.0, 1.0).eigenvalue, 2.5);
+/// assert!(!spike_location_wigner(0.5, 1.0).separated);
+/// ```
+pub fn spike_location_wigner(theta: f64, sigma: f64) -> SpikeLocation {
+    if sigma <= 0.0 || theta.is_nan() {
+        return SpikeLocation {
+            eigenvalue: f64::NAN,
+            separated: false,
+        };
+    }
+    if theta.abs() > bbp_threshold_wigner(sigma) {
+        SpikeLocation {
+            eigenvalue: theta + sigma * sigma / theta,
+            separated: true,
+        }
+    } else {
+        SpikeLocation {
+            eigenvalue: 2.0 * sigma * theta.signum(),
+            separated: false,
+        }
+    }
+}
+
+/// Asymptotic squared overlap |<u_hat, u>|^2 for a spike of strength theta in the spiked covariance model.
+///
+/// Paul (2007), Benaych-Georges & Nadakuditi (2011):
+///
+/// |<u_hat, u>|^2 -> (1 - gamma / theta^2) / (1 + gamma / theta)  for theta > sqrt(gamma),
+///
+/// and 0 below the BBP threshold. The overlap does not depend on sigma^2.
+/// [`spike_eigenvector_overlap`] evaluates the same curve at the theta
+/// inferred from an observed eigenvalue.
+///
+/// # Arguments
+///
+/// * `theta` - Spike strength; the population eigenvalue is sigma^2 (1 + theta)
+/// * `ratio` - gamma = p/n ratio
+///
+/// # Returns
+///
+/// Squared overlap in [0, 1); NaN for a non-positive ratio
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spike_overlap;
+///
+/// assert!((spike_overlap(1.0, 0.5) - 1.0 / 3.0).abs() < 1e-12);
+/// assert_eq!(spike_overlap(0.5, 0.5), 0.0);
+/// ```
+pub fn spike_overlap(theta: f64, ratio: f64) -> f64 {
+    if ratio <= 0.0 || theta.is_nan() {
+        return f64::NAN;
+    }
+    if theta <= bbp_threshold(ratio) {
+        return 0.0;
+    }
+    ((1.0 - ratio / (theta * theta)) / (1.0 + ratio / theta)).max(0.0)
+}
+
+/// Asymptotic squared overlap |<v_hat, v>|^2 for the additive spiked Wigner model W + theta v v^T.
+///
+/// With W normalized like [`crate::sample_goe`] the overlap is
+/// 1 - sigma^2 / theta^2 for |theta| > sigma and 0 otherwise.
+///
+/// # Arguments
+///
+/// * `theta` - Spike strength (either sign)
+/// * `sigma` - Semicircle scale of the noise
+///
+/// # Returns
+///
+/// Squared overlap in [0, 1); NaN for a non-positive sigma
+pub fn spike_overlap_wigner(theta: f64, sigma: f64) -> f64 {
+    if sigma <= 0.0 || theta.is_nan() {
+        return f64::NAN;
+    }
+    if theta.abs() <= bbp_threshold_wigner(sigma) {
+        return 0.0;
+    }
+    1.0 - (sigma / theta).powi(2)
+}
+
+/// Population eigenvalue sigma^2 (1 + theta) behind an outlier sample eigenvalue (inverse BBP map).
+///
+/// A spike theta > sqrt(gamma) produces a sample eigenvalue at
+/// lambda = sigma^2 (1 + theta)(1 + gamma / theta), biased upward by the noise.
+/// Inverting with x = lambda / sigma^2 gives
+///
+/// theta = ((x - 1 - gamma) + sqrt((x - 1 - gamma)^2 - 4 gamma)) / 2.
+///
+/// # Arguments
+///
+/// * `lambda` - Observed sample eigenvalue
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+///
+/// # Returns
+///
+/// The debiased population eigenvalue, or `None` if `lambda` is inside the
+/// bulk (at or below the MP edge), where no spike can be identified
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::debias_spike;
+///
+/// // theta = 3, gamma = 0.5: observed at 4 * (1 + 0.5 / 3) = 4.667.
+/// let population = debias_spike(4.0 * (1.0 + 0.5 / 3.0), 0.5, 1.0).unwrap();
+/// assert!((population - 4.0).abs() < 1e-12);
+/// ```
+pub fn debias_spike(lambda: f64, ratio: f64, sigma_sq: f64) -> Option<f64> {
+    if ratio <= 0.0 || sigma_sq <= 0.0 {
+        return None;
+    }
+    spike_strength(lambda, ratio, sigma_sq).map(|theta| sigma_sq * (1.0 + theta))
+}
+
+/// Debiased population eigenvalues of all outliers, largest first.
+///
+/// Applies [`debias_spike`] to every eigenvalue above the MP edge; eigenvalues
+/// inside the bulk are dropped.
+pub fn debias_spikes<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+    sigma_sq: f64,
+) -> Vec<f64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mut debiased: Vec<f64> = eigenvalues
+        .iter()
+        .filter_map(|&v| debias_spike(v, ratio, sigma_sq))
+        .collect();
+    debiased.sort_by(|a, b| b.total_cmp(a));
+    debiased
+}
+
+/// Expected squared overlap |<u_hat, u>|^2 between an outlier's sample eigenvector and its population eigenvector.
+///
+/// Benaych-Georges & Nadakuditi (2011): for a spike theta above the BBP
+/// threshold sqrt(gamma),
+///
+/// |<u_hat, u>|^2 -> (1 - gamma / theta^2) / (1 + gamma / theta),
+///
+/// with theta recovered from the observed eigenvalue by the inverse BBP map.
+/// Sample eigenvectors of weak spikes are far from the truth even when the
+/// eigenvalue clearly separates from the bulk.
+///
+/// # Arguments
+///
+/// * `lambda` - Observed sample eigenvalue
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+///
+/// # Returns
+///
+/// Squared overlap in [0, 1); 0 for an eigenvalue inside the bulk, whose
+/// eigenvector carries no information about the spike
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::spike_eigenvector_overlap;
+///
+/// // theta = 1, gamma = 0.5: overlap (1 - 0.5) / 1.5 = 1/3.
+/// let overlap = spike_eigenvector_overlap(2.0 * 1.5, 0.5, 1.0);
+/// assert!((overlap - 1.0 / 3.0).abs() < 1e-12);
+/// ```
+pub fn spike_eigenvector_overlap(lambda: f64, ratio: f64, sigma_sq: f64) -> f64 {
+    match spike_strength(lambda, ratio, sigma_sq) {
+        Some(theta) => spike_overlap(theta, ratio),
+        None => 0.0,
+    }
+}
+
+/// Overlap-corrected projector sum_k u_hat_k u_hat_k^T / |<u_hat_k, u_k>|^2 onto the outlier eigenvectors.
+///
+/// The plain projector on the sample eigenvectors shrinks every bilinear form
+/// a^T u_k u_k^T b by the squared overlap. Dividing by it gives an estimator
+/// whose bilinear forms with fixed vectors a, b are asymptotically unbiased
+/// for a^T (sum_k u_k u_k^T) b, e.g. for the true loading of a variable on a
+/// factor. Eigenvectors whose eigenvalue is inside the bulk are skipped.
+///
+/// # Arguments
+///
+/// * `eigenvectors` - p x k matrix of sample eigenvectors (unit columns)
+/// * `eigenvalues` - The k matching sample eigenvalues
+/// * `ratio` - gamma = p/n ratio
+/// * `sigma_sq` - Noise variance
+///
+/// # Returns
+///
+/// p x p corrected projector (all zeros if no eigenvalue is an outlier;
+/// empty if the dimensions do not match)
+pub fn corrected_spike_projection(
+    eigenvectors: &Array2<f64>,
+    eigenvalues: &[f64],
+    ratio: f64,
+    sigma_sq: f64,
+) -> Array2<f64> {
+    if eigenvectors.ncols() != eigenvalues.len() {
+        return Array2::zeros((0, 0));
+    }
+    let p = eigenvectors.nrows();
+    let mut projection = Array2::zeros((p, p));
+    for (col, &lambda) in eigenvectors.columns().into_iter().zip(eigenvalues) {
+        let overlap = spike_eigenvector_overlap(lambda, ratio, sigma_sq);
+        if overlap <= 0.0 {
+            continue;
+        }
+        for ((i, j), v) in projection.indexed_iter_mut() {
+            *v += col[i] * col[j] / overlap;
+        }
+    }
+    projection
+}
+
+/// Robust estimate of the noise variance sigma^2 from a sample covariance spectrum.
+///
+/// Matches the median of the bulk to the median of the Marchenko-Pastur law,
+/// then repeatedly drops the eigenvalues above the implied MP edge and
+/// re-matches, so a handful of spikes does not inflate the estimate the way
+/// the plain mean tr(S)/p does. For gamma > 1 only the n nonzero eigenvalues
+/// are used; they follow gamma times the MP law with ratio 1/gamma.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - All p sample covariance eigenvalues in any order
+/// * `ratio` - gamma = p/n ratio
+///
+/// # Returns
+///
+/// Estimated sigma^2, or NaN if `ratio` is not positive or no eigenvalue is positive
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{estimate_sigma_sq, sample_wishart_with, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let cov = sample_wishart_with(&mut rng, 400, 100) * (4.0 / 400.0);
+/// let mut eigenvalues = symmetric_eigenvalues(&cov);
+/// eigenvalues[99] = 100.0; // a spike does not move the estimate
+/// assert!((estimate_sigma_sq(&eigenvalues, 0.25) - 4.0).abs() < 0.2);
+/// ```
+pub fn estimate_sigma_sq<E: AsEigenvalues + ?Sized>(eigenvalues: &E, ratio: f64) -> f64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if ratio <= 0.0 {
+        return f64::NAN;
+    }
+    let max = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .fold(0.0_f64, f64::max);
+    let mut values: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|&v| v.is_finite() && v > 1e-12 * max)
+        .collect();
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    // Nonzero eigenvalues follow scale * MP(law_ratio, sigma^2).
+    let (scale, law_ratio) = if ratio > 1.0 {
+        (ratio, 1.0 / ratio)
+    } else {
+        (1.0, ratio)
+    };
+    let law_median = scale * marchenko_pastur_quantile(0.5, law_ratio, 1.0);
+    let edge_factor = scale * (1.0 + law_ratio.sqrt()).powi(2);
+
+    let mut bulk_len = values.len();
+    let mut sigma_sq = quantile_sorted(&values, 0.5) / law_median;
+    for _ in 0..50 {
+        let edge = sigma_sq * edge_factor;
+        let next_len = values.partition_point(|&v| v <= edge).max(1);
+        if next_len == bulk_len {
+            break;
+        }
+        bulk_len = next_len;
+        sigma_sq = quantile_sorted(&values[..bulk_len], 0.5) / law_median;
+    }
+    sigma_sq
+}
+
+/// Marchenko-Pastur bulk fitted to an observed spectrum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AspectRatioFit {
+    /// Effective gamma = p / n_eff
+    pub ratio: f64,
+    /// Noise variance at that ratio (see [`estimate_sigma_sq`])
+    pub sigma_sq: f64,
+    /// KS distance between the bulk eigenvalues and the fitted law
+    pub ks_statistic: f64,
+}
+
+/// Estimate the effective aspect ratio gamma = p / n_eff by fitting the MP bulk shape.
+///
+/// With dependent, weighted, or duplicated samples the nominal n overstates
+/// the information in the data and the bulk is wider than MP at p/n
+/// predicts. For each candidate gamma the noise variance comes from
+/// [`estimate_sigma_sq`], eigenvalues above the implied edge are set aside as
+/// spikes, and the KS distance of the remaining bulk to MP(gamma, sigma^2) is
+/// minimized: a log-spaced grid search refined by golden-section search.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Positive sample covariance eigenvalues (p <= n_eff, so no zero block)
+///
+/// # Returns
+///
+/// `None` for fewer than 10 positive eigenvalues
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{estimate_aspect_ratio, sample_wishart_with, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+/// let cov = sample_wishart_with(&mut rng, 400, 100) / 400.0;
+/// let fit = estimate_aspect_ratio(&symmetric_eigenvalues(&cov)).unwrap();
+/// assert!((fit.ratio - 0.25).abs() < 0.05);
+/// ```
+pub fn estimate_aspect_ratio<E: AsEigenvalues + ?Sized>(eigenvalues: &E) -> Option<AspectRatioFit> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let mut values: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|&v| v.is_finite() && v > 0.0)
+        .collect();
+    if values.len() < 10 {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let fit = |ratio: f64| {
+        let sigma_sq = estimate_sigma_sq(&values, ratio);
+        let (_, edge) = marchenko_pastur_support(ratio, sigma_sq);
+        let bulk = &values[..values.partition_point(|&v| v <= edge).max(1)];
+        let ks_statistic = ks_distance_sorted(bulk, |x| marchenko_pastur_cdf(x, ratio, sigma_sq));
+        AspectRatioFit {
+            ratio,
+            sigma_sq,
+            ks_statistic,
+        }
+    };
+
+    // Coarse search over log(gamma) in [1e-3, 1).
+    let (lo_log, hi_log, steps) = (1e-3_f64.ln(), 0.999_f64.ln(), 60);
+    let step = (hi_log - lo_log) / steps as f64;
+    let best = (0..=steps)
+        .map(|k| lo_log + k as f64 * step)
+        .min_by(|&a, &b| {
+            fit(a.exp())
+                .ks_statistic
+                .total_cmp(&fit(b.exp()).ks_statistic)
+        })?;
+
+    // Golden-section refinement within one grid step on either side.
+    let golden = 0.5 * (5.0_f64.sqrt() - 1.0);
+    let (mut a, mut b) = ((best - step).max(lo_log), (best + step).min(hi_log));
+    for _ in 0..40 {
+        let c = b - golden * (b - a);
+        let d = a + golden * (b - a);
+        if fit(c.exp()).ks_statistic <= fit(d.exp()).ks_statistic {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+    Some(fit((0.5 * (a + b)).exp()))
+}
+
+/// Spike strength theta behind an outlier eigenvalue, or `None` inside the bulk.
+fn spike_strength(lambda: f64, ratio: f64, sigma_sq: f64) -> Option<f64> {
+    if ratio <= 0.0 || sigma_sq <= 0.0 {
+        return None;
+    }
+    let (_, edge) = sample_covariance_support(ratio, sigma_sq);
+    if lambda.is_nan() || lambda <= edge {
+        return None;
+    }
+    let b = lambda / sigma_sq - 1.0 - ratio;
+    Some(0.5 * (b + (b * b - 4.0 * ratio).max(0.0).sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::spiked_covariance_eigenvalues;
+    use crate::{sample_goe_with, sample_wishart_with, symmetric_eigen, symmetric_eigenvalues};
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_count_spikes_detects_strong_spikes() {
+        // gamma = 0.25: spikes above sqrt(gamma) = 0.5 separate from the bulk.
+        let eigenvalues = spiked_covariance_eigenvalues(7, 800, 200, &[10.0, 4.0, 2.0]);
+        assert_eq!(count_spikes(&eigenvalues, 0.25, 1.0), 3);
+        let tw = SpikeThreshold::TracyWidom { alpha: 0.01 };
+        assert_eq!(count_spikes_with(&eigenvalues, 0.25, 1.0, tw), 3);
+    }
+
+    #[test]
+    fn test_count_spikes_more_variables_than_samples() {
+        // gamma = 2: the edge is (1 + sqrt(2))^2 ~ 5.83, not the folded ~ 2.91.
+        let tw = SpikeThreshold::TracyWidom { alpha: 0.01 };
+        let noise = spiked_covariance_eigenvalues(23, 200, 400, &[]);
+        assert_eq!(count_spikes(&noise, 2.0, 1.0), 0);
+        assert_eq!(count_spikes_with(&noise, 2.0, 1.0, tw), 0);
+        let spiked = spiked_covariance_eigenvalues(29, 200, 400, &[10.0, 5.0]);
+        assert_eq!(count_spikes(&spiked, 2.0, 1.0), 2);
+        assert_eq!(count_spikes_with(&spiked, 2.0, 1.0, tw), 2);
+    }
+
+    #[test]
+    fn test_tracy_widom_threshold_on_pure_noise() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(19);
+        let (n, p) = (200, 100);
+        let cov = sample_wishart_with(&mut rng, n, p) / n as f64;
+        let eigenvalues = symmetric_eigenvalues(&cov);
+        let tw = SpikeThreshold::TracyWidom { alpha: 0.01 };
+        assert_eq!(count_spikes_with(&eigenvalues, 0.5, 1.0, tw), 0);
+    }
+
+    #[test]
+    fn test_debias_spikes_recovers_population_eigenvalues() {
+        let eigenvalues = spiked_covariance_eigenvalues(73, 600, 300, &[9.0, 3.0]);
+        let debiased = debias_spikes(&eigenvalues, 0.5, 1.0);
+        assert_eq!(debiased.len(), 2);
+        // Sample eigenvalues are biased up by gamma (1 + theta) / theta; the debiased ones are not.
+        assert!((debiased[0] - 10.0).abs() < 1.0, "{:?}", debiased);
+        assert!((debiased[1] - 4.0).abs() < 0.5, "{:?}", debiased);
+        assert!(debias_spike(1.0, 0.5, 1.0).is_none());
+
+        // gamma = 2: 4 is inside the bulk, whose edge is (1 + sqrt(2))^2 ~ 5.83.
+        assert!(debias_spike(4.0, 2.0, 1.0).is_none());
+        assert_eq!(spike_eigenvector_overlap(4.0, 2.0, 1.0), 0.0);
+        // theta = 5 is observed at 6 (1 + 2 / 5) = 8.4.
+        assert!((debias_spike(8.4, 2.0, 1.0).unwrap() - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_eigenvector_overlap_matches_simulation() {
+        // Population eigenvectors are the coordinate axes e_0 (theta = 4) and e_1 (theta = 1.5).
+        let (n, p) = (600, 300);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(79);
+        let mut x = Array2::from_shape_fn((n, p), |_| StandardNormal.sample(&mut rng));
+        x.column_mut(0).mapv_inplace(|v: f64| v * 5.0_f64.sqrt());
+        x.column_mut(1).mapv_inplace(|v: f64| v * 2.5_f64.sqrt());
+        let (values, vectors) = symmetric_eigen(&(x.t().dot(&x) / n as f64));
+
+        for (k, axis) in [(p - 1, 0), (p - 2, 1)] {
+            let predicted = spike_eigenvector_overlap(values[k], 0.5, 1.0);
+            let observed = vectors[[axis, k]].powi(2);
+            assert!(
+                (predicted - observed).abs() < 0.08,
+                "{} vs {}",
+                predicted,
+                observed
+            );
+        }
+
+        // The corrected projector restores the diagonal loading of the strong spike.
+        let top = vectors.slice(ndarray::s![.., p - 1..]).to_owned();
+        let projection = corrected_spike_projection(&top, &values[p - 1..], 0.5, 1.0);
+        assert!(
+            (projection[[0, 0]] - 1.0).abs() < 0.1,
+            "{}",
+            projection[[0, 0]]
+        );
+        assert_eq!(spike_eigenvector_overlap(1.0, 0.5, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_sigma_sq_with_spikes_and_wide_data() {
+        let eigenvalues = spiked_covariance_eigenvalues(83, 400, 200, &[20.0, 10.0, 5.0]);
+        let plain_mean = eigenvalues.iter().sum::<f64>() / 200.0;
+        let robust = estimate_sigma_sq(&eigenvalues, 0.5);
+        assert!((robust - 1.0).abs() < 0.05, "{}", robust);
+        assert!(plain_mean > 1.15);
+
+        // p > n: half of the spectrum is zero.
+        let wide = spiked_covariance_eigenvalues(89, 100, 200, &[]);
+        let wide_scaled: Vec<f64> = wide.iter().map(|v| v * 2.5).collect();
+        let estimate = estimate_sigma_sq(&wide_scaled, 2.0);
+        assert!((estimate - 2.5).abs() < 0.15, "{}", estimate);
+        assert!(estimate_sigma_sq(&[0.0, 0.0], 0.5).is_nan());
+    }
+
+    #[test]
+    fn test_estimate_aspect_ratio_detects_duplicated_samples() {
+        // Each of 300 independent rows appears twice: nominal gamma = 150 / 600,
+        // effective gamma = 150 / 300.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(97);
+        let x: Array2<f64> = Array2::from_shape_fn((300, 150), |_| StandardNormal.sample(&mut rng));
+        let doubled = ndarray::concatenate![ndarray::Axis(0), x, x];
+        let eigenvalues = symmetric_eigenvalues(&(doubled.t().dot(&doubled) / 600.0));
+        let fit = estimate_aspect_ratio(&eigenvalues).unwrap();
+        assert!((fit.ratio - 0.5).abs() < 0.06, "{:?}", fit);
+        assert!((fit.sigma_sq - 1.0).abs() < 0.1, "{:?}", fit);
+        assert!(estimate_aspect_ratio(&[1.0; 5]).is_none());
+    }
+
+    #[test]
+    fn test_bbp_threshold_separates_spikes() {
+        // gamma = 0.5: threshold 0.707. theta = 1.5 separates, theta = 0.3 does not.
+        assert!((bbp_threshold(0.5) - 0.5_f64.sqrt()).abs() < 1e-15);
+        let eigenvalues = spiked_covariance_eigenvalues(101, 800, 400, &[1.5, 0.3]);
+        assert_eq!(count_spikes(&eigenvalues, 0.5, 1.0), 1);
+        assert_eq!(bbp_threshold_wigner(2.0), 2.0);
+        assert!(bbp_threshold(-1.0).is_nan());
+        assert_eq!(critical_sample_size(0.0, 100), None);
+        assert_eq!(critical_sample_size(2.0, 100), Some(26));
+    }
+
+    #[test]
+    fn test_spike_location_matches_simulation() {
+        let eigenvalues = spiked_covariance_eigenvalues(103, 800, 400, &[4.0]);
+        let predicted = spike_location(4.0, 0.5, 1.0);
+        let observed = eigenvalues.iter().copied().fold(f64::MIN, f64::max);
+        assert!(
+            (predicted.eigenvalue - observed).abs() < 0.3,
+            "{:?} vs {}",
+            predicted,
+            observed
+        );
+        assert!((debias_spike(predicted.eigenvalue, 0.5, 1.0).unwrap() - 5.0).abs() < 1e-12);
+
+        // Spiked Wigner: GOE + 3 e_0 e_0^T has an outlier near 3 + 1/3.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(107);
+        let mut w = sample_goe_with(&mut rng, 400);
+        w[[0, 0]] += 3.0;
+        let top = symmetric_eigenvalues(&w)
+            .into_iter()
+            .fold(f64::MIN, f64::max);
+        let predicted = spike_location_wigner(3.0, 1.0);
+        assert!(
+            (predicted.eigenvalue - top).abs() < 0.2,
+            "{:?} vs {}",
+            predicted,
+            top
+        );
+
+        let sub = spike_location(0.3, 0.5, 1.0);
+        assert!(!sub.separated);
+        assert_eq!(sub.eigenvalue, marchenko_pastur_support(0.5, 1.0).1);
+        let negative = spike_location(-0.9, 0.25, 1.0);
+        assert!(negative.separated && negative.eigenvalue < 0.25);
+        assert_eq!(spike_location_wigner(-0.5, 1.0).eigenvalue, -2.0);
+        assert!(spike_location(-1.0, 0.5, 1.0).eigenvalue.is_nan());
+        // gamma = 2: the nonzero bulk is [(sqrt(2) - 1)^2, (sqrt(2) + 1)^2].
+        let edge = (1.0 + 2.0_f64.sqrt()).powi(2);
+        let stuck = spike_location(1.0, 2.0, 1.0);
+        assert!(!stuck.separated && (stuck.eigenvalue - edge).abs() < 1e-12);
+        let outlier = spike_location(5.0, 2.0, 1.0);
+        assert!(outlier.separated && outlier.eigenvalue > edge);
+        let negative = spike_location(-0.5, 2.0, 1.0);
+        assert!((negative.eigenvalue - (2.0_f64.sqrt() - 1.0).powi(2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spike_overlap_wigner_matches_simulation() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(109);
+        let n = 500;
+        let mut w = sample_goe_with(&mut rng, n);
+        w[[0, 0]] += 2.0;
+        let (_, vectors) = symmetric_eigen(&w);
+        let observed = vectors[[0, n - 1]].powi(2);
+        let predicted = spike_overlap_wigner(2.0, 1.0);
+        assert!((predicted - 0.75).abs() < 1e-12);
+        assert!(
+            (predicted - observed).abs() < 0.08,
+            "{} vs {}",
+            predicted,
+            observed
+        );
+        assert_eq!(spike_overlap_wigner(-0.5, 1.0), 0.0);
+        assert!(spike_overlap(1.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_estimate_rank_matches_planted_rank() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(12);
+        let (n, p, sigma) = (200, 400, 2.0);
+        // Three orthogonal components with singular values well above the
+        // edge sigma (sqrt(n) + sqrt(p)) = 68.3 and one well below it.
+        let strengths = [300.0, 200.0, 120.0, 20.0];
+        let y = Array2::from_shape_fn((n, p), |(i, j)| {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            let signal: f64 = strengths
+                .iter()
+                .enumerate()
+                .map(|(k, s)| {
+                    let u = if (i >> k) % 2 == 0 { 1.0 } else { -1.0 };
+                    let v = if (j >> (k + 1)) % 2 == 0 { 1.0 } else { -1.0 };
+                    s * u * v / ((n * p) as f64).sqrt()
+                })
+                .sum();
+            signal + sigma * z
+        });
+        let s = crate::singular_values(&y);
+        assert_eq!(estimate_rank(&s, n, p, Some(sigma)), 3);
+        assert_eq!(estimate_rank(&s, n, p, None), 3);
+        assert_eq!(estimate_rank(&s, n, p, Some(100.0)), 0);
+        assert_eq!(estimate_rank(&s, n, p, Some(-1.0)), 0);
+    }
+
+    #[test]
+    fn test_count_spikes_invalid() {
+        assert_eq!(count_spikes(&[], 0.5, 1.0), 0);
+        assert_eq!(count_spikes(&[10.0], 0.5, 0.0), 0);
+    }
+}
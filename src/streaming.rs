@@ -0,0 +1,390 @@
+//! One-pass statistics over eigenvalue iterators.
+//!
+//! The slice-based functions keep the whole spectrum in memory (and most
+//! sort it). For spectra read from disk or pooled over many Monte Carlo
+//! trials, [`StreamingSpectrum`] accumulates moments, a fixed-range
+//! histogram, and an approximate CDF in O(k_max + bins) memory, and
+//! [`streaming_spacing_ratios`] turns an already-sorted stream into spacing
+//! ratios with a three-value window.
+
+/// One-pass accumulator for moments, a histogram density, and a CDF.
+///
+/// The histogram range must be fixed up front (e.g. the MP support with some
+/// margin); values outside it are counted but not binned. NaNs are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{spectral_moments, StreamingSpectrum};
+///
+/// let eigenvalues: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.37).sin() + 1.0).collect();
+/// let mut acc = StreamingSpectrum::new(0.0, 2.0, 20).moments(3);
+/// acc.extend(eigenvalues.iter().copied());
+/// assert_eq!(acc.count(), 1000);
+/// for (a, b) in acc.spectral_moments().iter().zip(spectral_moments(&eigenvalues, 3)) {
+///     assert!((a - b).abs() < 1e-12);
+/// }
+/// assert!((acc.cdf(1.0) - 0.5).abs() < 0.05);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingSpectrum {
+    lo: f64,
+    hi: f64,
+    counts: Vec<u64>,
+    below: u64,
+    above: u64,
+    count: u64,
+    min: f64,
+    max: f64,
+    power_sums: Vec<f64>,
+}
+
+impl StreamingSpectrum {
+    /// Accumulator with `bins` equal-width bins on [lo, hi] and no moments.
+    ///
+    /// An empty or inverted range, or zero bins, leaves only the counts,
+    /// extremes, and moments.
+    pub fn new(lo: f64, hi: f64, bins: usize) -> Self {
+        let bins = if lo < hi { bins } else { 0 };
+        Self {
+            lo,
+            hi,
+            counts: vec![0; bins],
+            below: 0,
+            above: 0,
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            power_sums: vec![],
+        }
+    }
+
+    /// Also accumulate raw moments of orders 1..=k_max.
+    pub fn moments(mut self, k_max: usize) -> Self {
+        self.power_sums = vec![0.0; k_max];
+        self
+    }
+
+    /// Empty accumulator with the same range, bins, and moment order.
+    #[cfg(feature = "mmap")]
+    pub(crate) fn cleared(&self) -> Self {
+        Self::new(self.lo, self.hi, self.counts.len()).moments(self.power_sums.len())
+    }
+
+    /// Add one eigenvalue.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        self.count += 1;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        let mut power = 1.0;
+        for s in self.power_sums.iter_mut() {
+            power *= x;
+            *s += power;
+        }
+        let bins = self.counts.len();
+        if bins == 0 {
+            return;
+        }
+        if x < self.lo {
+            self.below += 1;
+        } else if x > self.hi {
+            self.above += 1;
+        } else {
+            let idx = ((x - self.lo) / self.bin_width()) as usize;
+            self.counts[idx.min(bins - 1)] += 1;
+        }
+    }
+
+    /// Combine with an accumulator built on the same range, bins, and
+    /// moment order (e.g. from another thread or file chunk).
+    ///
+    /// # Panics
+    ///
+    /// If the layouts differ.
+    pub fn merge(&mut self, other: &Self) {
+        assert!(
+            self.lo == other.lo
+                && self.hi == other.hi
+                && self.counts.len() == other.counts.len()
+                && self.power_sums.len() == other.power_sums.len(),
+            "merging StreamingSpectrum accumulators with different layouts"
+        );
+        for (a, b) in self.counts.iter_mut().zip(&other.counts) {
+            *a += b;
+        }
+        for (a, b) in self.power_sums.iter_mut().zip(&other.power_sums) {
+            *a += b;
+        }
+        self.below += other.below;
+        self.above += other.above;
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Number of (non-NaN) eigenvalues seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Smallest eigenvalue seen (NaN if none).
+    pub fn min(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.min
+        }
+    }
+
+    /// Largest eigenvalue seen (NaN if none).
+    pub fn max(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.max
+        }
+    }
+
+    /// Counts that fell below and above the histogram range.
+    pub fn outside(&self) -> (u64, u64) {
+        (self.below, self.above)
+    }
+
+    /// Raw moments m_k = (1/n) sum lambda_i^k, as [`crate::spectral_moments`].
+    pub fn spectral_moments(&self) -> Vec<f64> {
+        if self.count == 0 {
+            return vec![];
+        }
+        let n = self.count as f64;
+        self.power_sums.iter().map(|s| s / n).collect()
+    }
+
+    /// (bin_centers, densities), normalized by the total count so that the
+    /// histogram integrates to the fraction of eigenvalues inside the range.
+    pub fn density(&self) -> (Vec<f64>, Vec<f64>) {
+        if self.count == 0 || self.counts.is_empty() {
+            return (vec![], vec![]);
+        }
+        let width = self.bin_width();
+        let scale = 1.0 / (self.count as f64 * width);
+        (0..self.counts.len())
+            .map(|i| {
+                (
+                    self.lo + (i as f64 + 0.5) * width,
+                    self.counts[i] as f64 * scale,
+                )
+            })
+            .unzip()
+    }
+
+    /// Empirical CDF at x, linear within bins (exact at bin edges).
+    ///
+    /// Returns NaN before any eigenvalue is pushed or without a histogram.
+    pub fn cdf(&self, x: f64) -> f64 {
+        if self.count == 0 || self.counts.is_empty() || x.is_nan() {
+            return f64::NAN;
+        }
+        let n = self.count as f64;
+        if x < self.lo {
+            return if x < self.min { 0.0 } else { f64::NAN };
+        }
+        if x >= self.hi {
+            return if x >= self.max {
+                1.0
+            } else {
+                (n - self.above as f64) / n
+            };
+        }
+        let t = (x - self.lo) / self.bin_width();
+        let idx = (t as usize).min(self.counts.len() - 1);
+        let before: u64 = self.below + self.counts[..idx].iter().sum::<u64>();
+        (before as f64 + (t - idx as f64) * self.counts[idx] as f64) / n
+    }
+
+    /// Inverse of [`cdf`](Self::cdf): linear within bins.
+    ///
+    /// NaN for q outside [0, 1] or when q falls in the part of the
+    /// distribution that lies outside the histogram range.
+    pub fn quantile(&self, q: f64) -> f64 {
+        if self.count == 0 || self.counts.is_empty() || !(0.0..=1.0).contains(&q) {
+            return f64::NAN;
+        }
+        let target = q * self.count as f64;
+        let mut acc = self.below as f64;
+        if target < acc {
+            return f64::NAN;
+        }
+        let width = self.bin_width();
+        for (i, &c) in self.counts.iter().enumerate() {
+            let c = c as f64;
+            if c > 0.0 && target <= acc + c {
+                return self.lo + (i as f64 + (target - acc) / c) * width;
+            }
+            acc += c;
+        }
+        if self.above == 0 {
+            self.hi.min(self.max)
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn bin_width(&self) -> f64 {
+        (self.hi - self.lo) / self.counts.len() as f64
+    }
+}
+
+impl Extend<f64> for StreamingSpectrum {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Raw moments of orders 1..=k_max in one pass; see [`crate::spectral_moments`].
+pub fn streaming_moments<I: IntoIterator<Item = f64>>(eigenvalues: I, k_max: usize) -> Vec<f64> {
+    let mut acc = StreamingSpectrum::new(0.0, 0.0, 0).moments(k_max);
+    acc.extend(eigenvalues);
+    acc.spectral_moments()
+}
+
+/// Histogram density on a fixed range in one pass; see [`StreamingSpectrum::density`].
+pub fn streaming_spectral_density<I: IntoIterator<Item = f64>>(
+    eigenvalues: I,
+    lo: f64,
+    hi: f64,
+    bins: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let mut acc = StreamingSpectrum::new(lo, hi, bins);
+    acc.extend(eigenvalues);
+    acc.density()
+}
+
+/// Level spacing ratios of an ascending stream, as [`crate::level_spacing_ratios`].
+///
+/// Lazily yields one ratio per consecutive triple with two positive gaps.
+/// The input must already be sorted (e.g. written in order by
+/// [`crate::write_eigenvalues_csv`]); this is not checked.
+pub fn streaming_spacing_ratios<I: IntoIterator<Item = f64>>(
+    sorted_eigenvalues: I,
+) -> SpacingRatios<I::IntoIter> {
+    SpacingRatios {
+        inner: sorted_eigenvalues.into_iter(),
+        window: [f64::NAN; 2],
+        seen: 0,
+    }
+}
+
+/// Mean of [`streaming_spacing_ratios`] (0.0 if there are none), as [`crate::mean_spacing_ratio`].
+pub fn streaming_mean_spacing_ratio<I: IntoIterator<Item = f64>>(sorted_eigenvalues: I) -> f64 {
+    let (sum, n) = streaming_spacing_ratios(sorted_eigenvalues)
+        .fold((0.0, 0usize), |(sum, n), r| (sum + r, n + 1));
+    if n == 0 {
+        0.0
+    } else {
+        sum / n as f64
+    }
+}
+
+/// Iterator returned by [`streaming_spacing_ratios`].
+#[derive(Debug, Clone)]
+pub struct SpacingRatios<I> {
+    inner: I,
+    window: [f64; 2],
+    seen: usize,
+}
+
+impl<I: Iterator<Item = f64>> Iterator for SpacingRatios<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        for x in self.inner.by_ref() {
+            let [a, b] = self.window;
+            self.window = [b, x];
+            self.seen += 1;
+            if self.seen < 3 {
+                continue;
+            }
+            let (s1, s2) = (b - a, x - b);
+            if s1 > 0.0 && s2 > 0.0 {
+                return Some(s1.min(s2) / s1.max(s2));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        empirical_spectral_cdf, level_spacing_ratios, sample_goe_with, symmetric_eigenvalues,
+    };
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_streaming_matches_in_memory() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(623);
+        let eigenvalues = symmetric_eigenvalues(&sample_goe_with(&mut rng, 300));
+
+        let ratios: Vec<f64> = streaming_spacing_ratios(eigenvalues.iter().copied()).collect();
+        assert_eq!(ratios, level_spacing_ratios(&eigenvalues));
+        assert_eq!(
+            streaming_mean_spacing_ratio(eigenvalues.iter().copied()),
+            crate::mean_spacing_ratio(&eigenvalues)
+        );
+
+        let mut acc = StreamingSpectrum::new(-2.5, 2.5, 50).moments(4);
+        let (first, second) = eigenvalues.split_at(100);
+        acc.extend(first.iter().copied());
+        let mut rest = StreamingSpectrum::new(-2.5, 2.5, 50).moments(4);
+        rest.extend(second.iter().copied().chain([f64::NAN]));
+        acc.merge(&rest);
+        assert_eq!(acc.count(), 300);
+        assert_eq!(acc.outside(), (0, 0));
+        let expected = crate::spectral_moments(&eigenvalues, 4);
+        for (a, b) in acc.spectral_moments().iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert_eq!(streaming_moments(eigenvalues.iter().copied(), 4).len(), 4);
+
+        let (centers, densities) = acc.density();
+        let mass: f64 = densities.iter().sum::<f64>() * (centers[1] - centers[0]);
+        assert!((mass - 1.0).abs() < 1e-12);
+
+        let exact = empirical_spectral_cdf(&eigenvalues);
+        for x in [-1.5, -0.3, 0.0, 0.8, 1.9] {
+            assert!((acc.cdf(x) - exact.eval(x)).abs() < 0.02, "cdf at {x}");
+            let q = acc.quantile(exact.eval(x));
+            assert!((q - x).abs() < 0.2, "quantile near {x}: {q}");
+        }
+        assert_eq!((acc.cdf(-3.0), acc.cdf(3.0)), (0.0, 1.0));
+        assert!(acc.quantile(1.5).is_nan());
+    }
+
+    #[test]
+    fn test_streaming_edge_cases() {
+        let acc = StreamingSpectrum::new(0.0, 1.0, 10);
+        assert!(acc.min().is_nan() && acc.cdf(0.5).is_nan());
+        assert!(acc.density().0.is_empty());
+        assert!(streaming_moments([], 3).is_empty());
+        assert_eq!(streaming_spacing_ratios([1.0, 2.0]).count(), 0);
+        assert_eq!(streaming_mean_spacing_ratio([1.0, 1.0, 1.0]), 0.0);
+
+        let mut acc = StreamingSpectrum::new(0.0, 1.0, 4);
+        acc.extend([-1.0, 0.1, 0.6, 2.0]);
+        assert_eq!(acc.outside(), (1, 1));
+        assert!(acc.cdf(-0.5).is_nan());
+        assert_eq!(acc.cdf(0.5), 0.5);
+        assert!(acc.quantile(0.1).is_nan());
+        assert_eq!(
+            streaming_spectral_density([0.1, 0.6], 0.0, 1.0, 2).1,
+            vec![1.0, 1.0]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Power-law tail fits of eigenvalue distributions.
+//!
+//! Heavy-tailed self-regularization (Martin & Mahoney 2021) reads the training
+//! quality of a neural-network layer off the tail of its empirical spectral
+//! density: well-trained layers have rho(lambda) ~ lambda^(-alpha) with alpha
+//! roughly in [2, 6], while random-like layers follow Marchenko-Pastur.
+
+use crate::gof::ks_distance_sorted;
+use crate::spectrum::AsEigenvalues;
+
+/// Fewest tail points [`fit_power_law`] accepts for a candidate x_min.
+const MIN_TAIL: usize = 10;
+
+/// Power-law tail rho(x) ~ x^(-alpha) for x >= x_min fitted by maximum likelihood.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerLawFit {
+    /// Density exponent alpha (> 1)
+    pub alpha: f64,
+    /// Lower cutoff of the power-law regime
+    pub x_min: f64,
+    /// Number of values at or above `x_min`
+    pub tail_len: usize,
+    /// KS distance between the tail and the fitted Pareto law
+    pub ks_statistic: f64,
+    /// Asymptotic standard error (alpha - 1) / sqrt(tail_len)
+    pub std_error: f64,
+}
+
+/// Hill estimator of the tail index from the k largest values.
+///
+/// xi_hat = k / sum_{i < k} ln(x_(i) / x_(k)), with x_(0) >= x_(1) >= ... the
+/// values in descending order. The tail index is the survival exponent,
+/// P(X > x) ~ x^(-xi); the density exponent of [`fit_power_law`] is 1 + xi.
+///
+/// # Arguments
+///
+/// * `values` - Positive samples in any order (non-positive values are ignored)
+/// * `k` - Number of upper order statistics used
+///
+/// # Returns
+///
+/// The tail index, or NaN if k is 0 or not smaller than the number of positive values
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::hill_estimator;
+///
+/// // Exact Pareto quantiles with tail index 2: x_i = (m / i)^(1/2).
+/// let values: Vec<f64> = (1..=10_000).map(|i| (10_000.0 / i as f64).sqrt()).collect();
+/// assert!((hill_estimator(&values, 1000) - 2.0).abs() < 0.05);
+/// ```
+pub fn hill_estimator<E: AsEigenvalues + ?Sized>(values: &E, k: usize) -> f64 {
+    let values: &[f64] = &values.as_eigenvalues();
+    let sorted = positive_descending(values);
+    if k == 0 || k >= sorted.len() {
+        return f64::NAN;
+    }
+    let threshold = sorted[k].ln();
+    let sum: f64 = sorted[..k].iter().map(|x| x.ln() - threshold).sum();
+    k as f64 / sum
+}
+
+/// Clauset, Shalizi & Newman (2009) power-law fit with KS-optimal x_min.
+///
+/// For every candidate x_min among the observed values (keeping at least 10
+/// points in the tail) the continuous MLE
+///
+/// alpha = 1 + m / sum_{x_i >= x_min} ln(x_i / x_min)
+///
+/// is computed, and the x_min whose tail has the smallest KS distance to the
+/// fitted Pareto law is kept. The KS distance doubles as a fit-quality
+/// measure: values well above ~0.1 mean the tail is not a power law.
+///
+/// # Arguments
+///
+/// * `values` - Positive samples in any order, e.g. the eigenvalues of W^T W
+///
+/// # Returns
+///
+/// `None` for fewer than 10 positive values
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::fit_power_law;
+///
+/// // Exact Pareto quantiles with alpha = 3 above x_min = 1.
+/// let values: Vec<f64> = (1..=2000).map(|i| (2000.0 / i as f64).sqrt()).collect();
+/// let fit = fit_power_law(&values).unwrap();
+/// assert!((fit.alpha - 3.0).abs() < 0.1);
+/// ```
+pub fn fit_power_law<E: AsEigenvalues + ?Sized>(values: &E) -> Option<PowerLawFit> {
+    let values: &[f64] = &values.as_eigenvalues();
+    let mut sorted = positive_descending(values);
+    if sorted.len() < MIN_TAIL {
+        return None;
+    }
+    sorted.reverse();
+    let len = sorted.len();
+
+    // Suffix sums of ln x for O(1) MLEs per candidate.
+    let mut log_suffix = vec![0.0; len + 1];
+    for i in (0..len).rev() {
+        log_suffix[i] = log_suffix[i + 1] + sorted[i].ln();
+    }
+
+    (0..=len - MIN_TAIL)
+        .filter(|&i| i == 0 || sorted[i] > sorted[i - 1])
+        .filter_map(|i| {
+            let x_min = sorted[i];
+            let tail = &sorted[i..];
+            let m = tail.len() as f64;
+            let log_sum = log_suffix[i] - m * x_min.ln();
+            if log_sum <= 0.0 {
+                return None;
+            }
+            let alpha = 1.0 + m / log_sum;
+            let ks_statistic = ks_distance_sorted(tail, |x| 1.0 - (x / x_min).powf(1.0 - alpha));
+            Some(PowerLawFit {
+                alpha,
+                x_min,
+                tail_len: tail.len(),
+                ks_statistic,
+                std_error: (alpha - 1.0) / m.sqrt(),
+            })
+        })
+        .min_by(|a, b| a.ks_statistic.total_cmp(&b.ks_statistic))
+}
+
+fn positive_descending(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .collect();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_fit_power_law_finds_tail_above_body() {
+        // Uniform body on [0, 1) plus a Pareto tail with alpha = 2.5 above x_min = 2.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(113);
+        let mut values: Vec<f64> = (0..2000).map(|_| rng.random::<f64>()).collect();
+        values.extend((0..3000).map(|_| 2.0 * (1.0 - rng.random::<f64>()).powf(-1.0 / 1.5)));
+        let fit = fit_power_law(&values).unwrap();
+        assert!((fit.alpha - 2.5).abs() < 0.1, "{:?}", fit);
+        assert!(fit.x_min >= 1.0 && fit.x_min < 3.0, "{:?}", fit);
+        assert!(fit.ks_statistic < 0.05, "{:?}", fit);
+        assert!((hill_estimator(&values, 500) - 1.5).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_power_law_degenerate() {
+        assert!(fit_power_law(&[1.0; 5]).is_none());
+        assert!(fit_power_law(&[2.0; 20]).is_none());
+        assert!(hill_estimator(&[1.0, 2.0], 2).is_nan());
+        assert!(hill_estimator(&[1.0, 2.0], 0).is_nan());
+    }
+}
@@ -0,0 +1,954 @@
+//! Random-matrix nulls for correlation matrices of time series.
+//!
+//! Marchenko-Pastur assumes the T samples behind an N x N correlation matrix
+//! are independent. Financial returns, neural recordings and climate series
+//! are autocorrelated in time, which widens the noise bulk: with an
+//! exponential autocorrelation of one day, say, eigenvalues well above the
+//! plain MP edge are still pure noise. Burda, Jurkiewicz and Waclaw (2005)
+//! showed that the limit law then follows from the spectrum of the temporal
+//! correlation matrix alone, which for exponential decay is explicit.
+//!
+//! Lead-lag analyses look at the rectangular cross-correlation between two
+//! sets of series, or one set and its own lagged copy. After each set is
+//! whitened its singular values have a universal null law (Bouchaud, Miceli
+//! and Potters 2007) that depends only on the two aspect ratios.
+//!
+//! When no analytic null fits (heavy tails, unknown or non-exponential
+//! autocorrelation) surrogate data give an empirical one: rotating,
+//! shuffling or phase-randomizing each series independently destroys the
+//! cross-sectional correlation and keeps what the method preserves.
+
+use num_complex::Complex64;
+use rand::Rng;
+use rand_distr::Distribution;
+use std::f64::consts::PI;
+
+use ndarray::{s, Array2, Axis};
+
+use crate::distributions::invert_cdf;
+use crate::error::Error;
+use crate::simulation::quantile_sorted;
+use crate::special::gauss_legendre;
+use crate::spectrum::AsEigenvalues;
+use crate::{canonical_correlations, general_eigenvalues, symmetric_eigenvalues};
+
+/// Marchenko-Pastur law for samples with exponential temporal autocorrelation.
+///
+/// Limit of the eigenvalues of C = (1/T) X X^T where X is N x T, the rows
+/// are independent, and each row is a stationary series with unit variance
+/// and autocorrelation Corr(x_t, x_s) = exp(-|t - s| / tau), as for an AR(1)
+/// process x_t = a x_(t-1) + sqrt(1 - a^2) e_t with a = exp(-1 / tau). The
+/// ratio is r = N / T and tau = 0 recovers [`crate::MarchenkoPastur`] with
+/// unit variance.
+///
+/// Writing kappa = (1 + a^2) / (1 - a^2) and b = 2 a / (1 - a^2), the
+/// Stieltjes transform m of C / r solves (Burda, Jurkiewicz and Waclaw 2005)
+///
+/// z = -1/m + 1 / (r sqrt((m + kappa)^2 - b^2)),
+///
+/// which is a quartic in m. The density is Im m(x / r) / (pi r) and the edges
+/// are the critical values of the right-hand side on the real axis. The
+/// mean eigenvalue stays 1 while the bulk spreads out as tau grows, so the
+/// plain MP edge flags noise eigenvalues as signal.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{marchenko_pastur_support, AutocorrelatedMarchenkoPastur};
+///
+/// let law = AutocorrelatedMarchenkoPastur::new(0.25, 2.0).unwrap();
+/// let (lo, hi) = law.support();
+/// let (mp_lo, mp_hi) = marchenko_pastur_support(0.25, 1.0);
+/// // Autocorrelation widens the noise bulk on both sides.
+/// assert!(lo < mp_lo && hi > mp_hi);
+/// assert!((law.cdf(law.quantile(0.3)) - 0.3).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "AutocorrelatedMarchenkoPasturParams")
+)]
+pub struct AutocorrelatedMarchenkoPastur {
+    ratio: f64,
+    tau: f64,
+}
+
+impl AutocorrelatedMarchenkoPastur {
+    /// Law for ratio r = N / T and autocorrelation time tau (in samples).
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless 0 < ratio <= 1 and tau is finite
+    /// and non-negative. For N > T the nonzero eigenvalues are those of the
+    /// T x T matrix (1/T) X^T X, whose law this type does not describe.
+    pub fn new(ratio: f64, tau: f64) -> Result<Self, Error> {
+        let ratio = Error::check_positive("ratio", ratio)?;
+        if ratio > 1.0 {
+            return Err(Error::InvalidParameter {
+                name: "ratio",
+                value: ratio,
+                expected: "in (0, 1]",
+            });
+        }
+        if !tau.is_finite() || tau < 0.0 {
+            return Err(Error::InvalidParameter {
+                name: "tau",
+                value: tau,
+                expected: "finite and non-negative",
+            });
+        }
+        Ok(Self { ratio, tau })
+    }
+
+    /// Ratio r = N / T.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Autocorrelation time tau.
+    pub fn tau(&self) -> f64 {
+        self.tau
+    }
+
+    /// Lag-one autocorrelation a = exp(-1 / tau), 0 for tau = 0.
+    pub fn autocorrelation(&self) -> f64 {
+        if self.tau == 0.0 {
+            0.0
+        } else {
+            (-1.0 / self.tau).exp()
+        }
+    }
+
+    /// Support [lambda_-, lambda_+]; lambda_- is 0 for ratio 1.
+    pub fn support(&self) -> (f64, f64) {
+        let a = self.autocorrelation();
+        let r = self.ratio;
+        // Critical points of x(m) to the right of the pole of the temporal
+        // spectrum give the upper edge, those in (0, inf) the lower edge.
+        let upper = bisect(|m| self.dx_dm(m), -(1.0 - a) / (1.0 + a), 0.0);
+        let lower = if r < 1.0 {
+            let mut hi = 1.0;
+            while self.dx_dm(hi) > 0.0 {
+                hi *= 2.0;
+            }
+            r * self.x_of_m(bisect(|m| -self.dx_dm(m), 0.0, hi))
+        } else {
+            0.0
+        };
+        (lower.max(0.0), r * self.x_of_m(upper))
+    }
+
+    /// Density at x.
+    pub fn pdf(&self, x: f64) -> f64 {
+        let (lo, hi) = self.support();
+        if x <= lo || x >= hi {
+            return 0.0;
+        }
+        self.density(x)
+    }
+
+    /// CDF at x, by Gauss-Legendre quadrature in the angle x = c - h cos(t),
+    /// which absorbs the square-root vanishing of the density at the edges.
+    pub fn cdf(&self, x: f64) -> f64 {
+        let (lo, hi) = self.support();
+        if x <= lo {
+            return 0.0;
+        }
+        if x >= hi {
+            return 1.0;
+        }
+        let (center, half) = (0.5 * (hi + lo), 0.5 * (hi - lo));
+        let upper = ((center - x) / half).clamp(-1.0, 1.0).acos();
+        let (nodes, weights) = gauss_legendre(64, 0.0, upper);
+        let value: f64 = nodes
+            .iter()
+            .zip(&weights)
+            .map(|(&t, &w)| {
+                let (s, c) = t.sin_cos();
+                w * half * s * self.density(center - half * c)
+            })
+            .sum();
+        value.clamp(0.0, 1.0)
+    }
+
+    /// Quantile function (NaN for q outside [0, 1]).
+    pub fn quantile(&self, q: f64) -> f64 {
+        let (lo, hi) = self.support();
+        invert_cdf(|x| self.cdf(x), q, lo, hi)
+    }
+
+    /// (kappa, b) of the temporal spectrum kappa - b cos(theta), in units of 1 / t.
+    fn temporal(&self) -> (f64, f64) {
+        let a = self.autocorrelation();
+        let d = 1.0 - a * a;
+        ((1.0 + a * a) / d, 2.0 * a / d)
+    }
+
+    /// Inverse Stieltjes transform of C / r on the real axis.
+    fn x_of_m(&self, m: f64) -> f64 {
+        let (kappa, b) = self.temporal();
+        let s = m + kappa;
+        -1.0 / m + s.signum() / ((s * s - b * b).sqrt() * self.ratio)
+    }
+
+    fn dx_dm(&self, m: f64) -> f64 {
+        let (kappa, b) = self.temporal();
+        let s = m + kappa;
+        1.0 / (m * m) - s.abs() / ((s * s - b * b).powf(1.5) * self.ratio)
+    }
+
+    /// Density inside the support from the roots of the quartic
+    /// (z m + 1)^2 ((m + kappa)^2 - b^2) = m^2 / r^2, with z = x / r.
+    fn density(&self, x: f64) -> f64 {
+        let (kappa, b) = self.temporal();
+        let c = 1.0 / self.ratio;
+        let z = x * c;
+        let z2 = z * z;
+        // Monic coefficients of m^3, m^2, m, 1; note kappa^2 - b^2 = 1.
+        let coefficients = [
+            (2.0 * kappa * z2 + 2.0 * z) / z2,
+            (z2 + 4.0 * kappa * z + 1.0 - c * c) / z2,
+            (2.0 * z + 2.0 * kappa) / z2,
+            1.0 / z2,
+        ];
+        let companion = Array2::from_shape_fn((4, 4), |(i, j)| {
+            if i == 0 {
+                -coefficients[j]
+            } else if i == j + 1 {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        // Squaring admits spurious roots; keep the upper half-plane root
+        // that solves the unsquared equation on the principal branch.
+        let residual = |m: Complex64| {
+            let s = m + kappa;
+            let g = 1.0 / (s * (1.0 - b * b / (s * s)).sqrt());
+            (z + 1.0 / m - c * g).norm()
+        };
+        general_eigenvalues(&companion)
+            .into_iter()
+            .filter(|m| m.im > 0.0)
+            .min_by(|p, q| residual(*p).total_cmp(&residual(*q)))
+            .map_or(0.0, |m| m.im * c / PI)
+    }
+}
+
+impl Distribution<f64> for AutocorrelatedMarchenkoPastur {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Null law of the singular values of a whitened cross-correlation matrix.
+///
+/// X (T x N) and Y (T x M) hold T observations of two independent sets of
+/// i.i.d. Gaussian series. Whitening each set (rotating it so its own sample
+/// correlation matrix is the identity) leaves the N x M cross-correlation
+/// X^T Y / T with singular values s in [0, 1], the sample canonical
+/// correlations (see [`crate::canonical_correlations`]). With n = N / T,
+/// m = M / T and c = min(n, m), the law of the min(N, M) singular values is
+/// (Bouchaud, Miceli and Potters 2007)
+///
+/// rho(s) = sqrt((s^2 - g_-) (g_+ - s^2)) / (pi c s (1 - s^2))
+///
+/// on [sqrt(g_-), sqrt(g_+)], g_+- = n + m - 2 n m +- 2 sqrt(n m (1 - n) (1 - m)),
+/// plus an atom of mass (n + m - 1) / c at s = 1 when n + m > 1, where the
+/// two column spaces must intersect. The squared edges are the
+/// [`crate::wachter_support`] of canonical correlation analysis.
+///
+/// The same law describes a single set against its own lagged copy, since
+/// for serially uncorrelated data x_t and x_(t + lag) are uncorrelated; see
+/// [`lagged_cross_correlations`].
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::CrossCorrelationSingularValues;
+///
+/// // 50 and 100 series over 500 observations.
+/// let law = CrossCorrelationSingularValues::new(0.1, 0.2).unwrap();
+/// let (_, upper) = law.support();
+/// // Canonical correlations up to about 0.71 are noise.
+/// assert!((upper - 0.5_f64.sqrt()).abs() < 1e-12);
+/// assert!((law.cdf(law.quantile(0.5)) - 0.5).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "CrossCorrelationSingularValuesParams")
+)]
+pub struct CrossCorrelationSingularValues {
+    n_ratio: f64,
+    m_ratio: f64,
+}
+
+impl CrossCorrelationSingularValues {
+    /// Law for aspect ratios n = N / T and m = M / T.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidParameter`] unless both ratios are in (0, 1); each
+    /// set needs more observations than series to be whitened.
+    pub fn new(n_ratio: f64, m_ratio: f64) -> Result<Self, Error> {
+        for (name, value) in [("n_ratio", n_ratio), ("m_ratio", m_ratio)] {
+            Error::check_positive(name, value)?;
+            if value >= 1.0 {
+                return Err(Error::InvalidParameter {
+                    name,
+                    value,
+                    expected: "in (0, 1)",
+                });
+            }
+        }
+        Ok(Self { n_ratio, m_ratio })
+    }
+
+    /// Ratio n = N / T.
+    pub fn n_ratio(&self) -> f64 {
+        self.n_ratio
+    }
+
+    /// Ratio m = M / T.
+    pub fn m_ratio(&self) -> f64 {
+        self.m_ratio
+    }
+
+    /// Support [s_-, s_+] of the continuous part.
+    pub fn support(&self) -> (f64, f64) {
+        let (n, m) = (self.n_ratio, self.m_ratio);
+        let (a, b) = ((n * (1.0 - m)).sqrt(), (m * (1.0 - n)).sqrt());
+        ((a - b).abs(), (a + b).min(1.0))
+    }
+
+    /// Mass of the atom at s = 1, nonzero when n + m > 1.
+    pub fn atom(&self) -> f64 {
+        let (n, m) = (self.n_ratio, self.m_ratio);
+        ((n + m - 1.0) / n.min(m)).max(0.0)
+    }
+
+    /// Density of the continuous part at s.
+    pub fn pdf(&self, s: f64) -> f64 {
+        let (lo, hi) = self.support();
+        if s <= lo || s >= hi {
+            return 0.0;
+        }
+        let (n, m) = (self.n_ratio, self.m_ratio);
+        let s2 = s * s;
+        let (lo2, hi2) = (lo * lo, hi * hi);
+        ((s2 - lo2) * (hi2 - s2)).sqrt() / (PI * n.min(m) * s * (1.0 - s2))
+    }
+
+    /// CDF at s, including the atom at 1.
+    pub fn cdf(&self, s: f64) -> f64 {
+        let (lo, hi) = self.support();
+        if s >= 1.0 {
+            return 1.0;
+        }
+        if s <= lo {
+            return 0.0;
+        }
+        if s >= hi {
+            return 1.0 - self.atom();
+        }
+        let (center, half) = (0.5 * (hi + lo), 0.5 * (hi - lo));
+        let upper = ((center - s) / half).clamp(-1.0, 1.0).acos();
+        let (nodes, weights) = gauss_legendre(64, 0.0, upper);
+        let value: f64 = nodes
+            .iter()
+            .zip(&weights)
+            .map(|(&t, &w)| {
+                let (sin, cos) = t.sin_cos();
+                w * half * sin * self.pdf(center - half * cos)
+            })
+            .sum();
+        value.clamp(0.0, 1.0 - self.atom())
+    }
+
+    /// Quantile function (NaN for q outside [0, 1]); 1 within the atom.
+    pub fn quantile(&self, q: f64) -> f64 {
+        if q > 1.0 - self.atom() && q <= 1.0 {
+            return 1.0;
+        }
+        let (lo, hi) = self.support();
+        invert_cdf(|s| self.cdf(s), q, lo, hi)
+    }
+}
+
+impl Distribution<f64> for CrossCorrelationSingularValues {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.quantile(rng.random())
+    }
+}
+
+/// Singular values of the whitened lagged cross-correlation between two sets of series.
+///
+/// Pairs row t of `x` with row t + lag of `y` and returns the canonical
+/// correlations of the T - lag overlapping observations. Passing the same
+/// matrix twice gives the lagged auto-cross-correlations used to look for
+/// lead-lag structure within one set. Under the null of no lead-lag
+/// structure they follow [`CrossCorrelationSingularValues`] with ratios
+/// N / (T - lag) and M / (T - lag).
+///
+/// # Arguments
+///
+/// * `x` - T x N data matrix, one observation per row
+/// * `y` - T x M data matrix on the same time grid
+/// * `lag` - How many steps `y` lags behind `x`
+///
+/// # Returns
+///
+/// Singular values in [0, 1], descending
+///
+/// # Panics
+///
+/// If `x` and `y` have different numbers of rows or lag >= T.
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rmt::lagged_cross_correlations;
+///
+/// // y_t = x_(t - 1): the lag-one correlation is perfect.
+/// let x = Array2::from_shape_fn((20, 1), |(t, _)| ((t * t) % 7) as f64);
+/// let y = Array2::from_shape_fn((20, 1), |(t, _)| (((t + 6) * (t + 6)) % 7) as f64);
+/// let s = lagged_cross_correlations(&x, &y, 1);
+/// assert!((s[0] - 1.0).abs() < 1e-12);
+/// ```
+pub fn lagged_cross_correlations(x: &Array2<f64>, y: &Array2<f64>, lag: usize) -> Vec<f64> {
+    let t = x.nrows();
+    assert_eq!(t, y.nrows(), "x and y need the same time grid");
+    assert!(lag < t, "lag must be shorter than the series");
+    canonical_correlations(
+        &x.slice(s![..t - lag, ..]).to_owned(),
+        &y.slice(s![lag.., ..]).to_owned(),
+    )
+}
+
+/// How [`surrogate_series`] scrambles each series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SurrogateMethod {
+    /// Rotate each series by an independent uniform circular shift
+    /// (rotational random shuffling). Keeps the marginal distribution and,
+    /// up to the wrap-around point, the autocorrelation.
+    CircularShift,
+    /// Permute each series independently. Keeps the marginal distribution
+    /// but destroys the autocorrelation, so the null is plain MP.
+    Permutation,
+    /// Give each Fourier mode of each series an independent uniform phase.
+    /// Keeps the periodogram, hence the autocorrelation, but makes the
+    /// series Gaussian.
+    PhaseRandomization,
+}
+
+/// Surrogate data: every column of `data` scrambled independently.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `data` - T x N data matrix, one observation per row, one series per column
+/// * `method` - How each series is scrambled
+///
+/// # Returns
+///
+/// T x N surrogate matrix with the same shape as `data`
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rand::SeedableRng;
+/// use rmt::{surrogate_series, SurrogateMethod};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let data = array![[1.0, 10.0], [2.0, 20.0], [3.0, 30.0], [4.0, 40.0]];
+/// let shifted = surrogate_series(&mut rng, &data, SurrogateMethod::CircularShift);
+/// // Each column is a rotation of the original one.
+/// let start = shifted[[0, 0]] as usize - 1;
+/// assert!((0..4).all(|t| shifted[[t, 0]] == data[[(start + t) % 4, 0]]));
+/// ```
+pub fn surrogate_series<R: Rng + ?Sized>(
+    rng: &mut R,
+    data: &Array2<f64>,
+    method: SurrogateMethod,
+) -> Array2<f64> {
+    let t = data.nrows();
+    let mut out = data.clone();
+    if t < 2 {
+        return out;
+    }
+    for mut column in out.columns_mut() {
+        let original = column.to_vec();
+        match method {
+            SurrogateMethod::CircularShift => {
+                let shift = rng.random_range(0..t);
+                for (i, value) in column.iter_mut().enumerate() {
+                    *value = original[(i + shift) % t];
+                }
+            }
+            SurrogateMethod::Permutation => {
+                for i in (1..t).rev() {
+                    column.swap(i, rng.random_range(0..=i));
+                }
+            }
+            SurrogateMethod::PhaseRandomization => {
+                let mut spectrum: Vec<Complex64> =
+                    original.iter().map(|&v| Complex64::new(v, 0.0)).collect();
+                fft(&mut spectrum, false);
+                // Modes k and t - k are conjugate for real data; the mean
+                // and the Nyquist mode stay real.
+                for k in 1..t.div_ceil(2) {
+                    let phase = 2.0 * PI * rng.random::<f64>();
+                    let value = Complex64::from_polar(spectrum[k].norm(), phase);
+                    spectrum[k] = value;
+                    spectrum[t - k] = value.conj();
+                }
+                fft(&mut spectrum, true);
+                for (value, z) in column.iter_mut().zip(&spectrum) {
+                    *value = z.re / t as f64;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Empirical null spectra of the correlation matrix from surrogate data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurrogateNull {
+    /// Correlation eigenvalues of each surrogate, ascending
+    pub spectra: Vec<Vec<f64>>,
+}
+
+impl SurrogateNull {
+    /// Quantile `level` of the largest surrogate eigenvalue, an empirical
+    /// noise edge: observed eigenvalues above `edge(0.95)` are signal at
+    /// the 5% level.
+    pub fn edge(&self, level: f64) -> f64 {
+        let mut maxima: Vec<f64> = self
+            .spectra
+            .iter()
+            .filter_map(|s| s.last().copied())
+            .collect();
+        maxima.sort_by(f64::total_cmp);
+        quantile_sorted(&maxima, level)
+    }
+
+    /// Monte Carlo p-values of the observed eigenvalues, largest first.
+    ///
+    /// The k-th largest observed eigenvalue is compared with the k-th
+    /// largest of each surrogate spectrum, giving (1 + #exceedances) /
+    /// (1 + replicates).
+    pub fn p_values<E: AsEigenvalues + ?Sized>(&self, eigenvalues: &E) -> Vec<f64> {
+        let mut eigenvalues: Vec<f64> = eigenvalues.as_eigenvalues().to_vec();
+        eigenvalues.sort_by(|a, b| b.total_cmp(a));
+        let replicates = self.spectra.len() as f64;
+        eigenvalues
+            .iter()
+            .enumerate()
+            .map(|(k, &observed)| {
+                let exceed = self
+                    .spectra
+                    .iter()
+                    .filter(|s| s.len() > k && s[s.len() - 1 - k] >= observed)
+                    .count();
+                (1.0 + exceed as f64) / (1.0 + replicates)
+            })
+            .collect()
+    }
+}
+
+/// Correlation eigenvalues of surrogate data sets, an empirical null for [`correlation_eigenvalues`].
+///
+/// Use when the analytic nulls ([`crate::MarchenkoPastur`],
+/// [`AutocorrelatedMarchenkoPastur`]) do not fit the data: circular shifts
+/// keep heavy tails and any autocorrelation, so eigenvalues above the
+/// surrogate edge reflect genuine cross-sectional structure.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `data` - T x N data matrix, one observation per row, one series per column
+/// * `method` - How each series is scrambled
+/// * `replicates` - Number of surrogate data sets (100+ for a 95% edge)
+///
+/// # Returns
+///
+/// `None` if [`correlation_eigenvalues`] fails on `data` or `replicates` is zero
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{correlation_eigenvalues, surrogate_correlation_spectra, SurrogateMethod};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+/// // 20 series sharing one common factor.
+/// let factor: Vec<f64> = (0..200).map(|_| StandardNormal.sample(&mut rng)).collect();
+/// let data = ndarray::Array2::from_shape_fn((200, 20), |(t, _)| {
+///     let z: f64 = StandardNormal.sample(&mut rng);
+///     0.5 * factor[t] + z
+/// });
+/// let null = surrogate_correlation_spectra(&mut rng, &data, SurrogateMethod::CircularShift, 50)
+///     .unwrap();
+/// let observed = correlation_eigenvalues(&data).unwrap();
+/// assert!(*observed.last().unwrap() > null.edge(0.95));
+/// assert!(null.p_values(&observed)[0] < 0.05);
+/// ```
+pub fn surrogate_correlation_spectra<R: Rng + ?Sized>(
+    rng: &mut R,
+    data: &Array2<f64>,
+    method: SurrogateMethod,
+    replicates: usize,
+) -> Option<SurrogateNull> {
+    correlation_eigenvalues(data)?;
+    if replicates == 0 {
+        return None;
+    }
+    let spectra = (0..replicates)
+        .map(|_| correlation_eigenvalues(&surrogate_series(rng, data, method)))
+        .collect::<Option<Vec<_>>>()?;
+    Some(SurrogateNull { spectra })
+}
+
+/// Eigenvalues of the sample correlation matrix of the columns of `data`, ascending.
+///
+/// # Arguments
+///
+/// * `data` - T x N data matrix, one observation per row
+///
+/// # Returns
+///
+/// `None` if T < 2, N = 0, an entry is non-finite, or a column is constant
+pub fn correlation_eigenvalues(data: &Array2<f64>) -> Option<Vec<f64>> {
+    let (t, n) = data.dim();
+    if t < 2 || n == 0 || data.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let mut z = data - &data.mean_axis(Axis(0))?;
+    for mut column in z.columns_mut() {
+        let norm = column.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return None;
+        }
+        column /= norm;
+    }
+    Some(symmetric_eigenvalues(&z.t().dot(&z)))
+}
+
+/// In-place discrete Fourier transform, unnormalized; radix 2 for powers of
+/// two and Bluestein's chirp-z reduction to radix 2 otherwise.
+fn fft(values: &mut [Complex64], inverse: bool) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    if n.is_power_of_two() {
+        fft_radix2(values, inverse);
+        return;
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    // chirp_k = exp(sign i pi k^2 / n), with k^2 reduced mod 2n for accuracy.
+    let chirp: Vec<Complex64> = (0..n)
+        .map(|k| {
+            let k2 = (k as u128 * k as u128 % (2 * n as u128)) as f64;
+            Complex64::from_polar(1.0, sign * PI * k2 / n as f64)
+        })
+        .collect();
+    let m = (2 * n - 1).next_power_of_two();
+    let mut a = vec![Complex64::new(0.0, 0.0); m];
+    let mut b = vec![Complex64::new(0.0, 0.0); m];
+    for k in 0..n {
+        a[k] = values[k] * chirp[k];
+        b[k] = chirp[k].conj();
+        if k > 0 {
+            b[m - k] = chirp[k].conj();
+        }
+    }
+    fft_radix2(&mut a, false);
+    fft_radix2(&mut b, false);
+    for (x, y) in a.iter_mut().zip(&b) {
+        *x *= y;
+    }
+    fft_radix2(&mut a, true);
+    for k in 0..n {
+        values[k] = a[k] * chirp[k] / m as f64;
+    }
+}
+
+fn fft_radix2(values: &mut [Complex64], inverse: bool) {
+    let n = values.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let step = Complex64::from_polar(1.0, sign * 2.0 * PI / len as f64);
+        for chunk in values.chunks_mut(len) {
+            let mut w = Complex64::new(1.0, 0.0);
+            let (lower, upper) = chunk.split_at_mut(len / 2);
+            for (u, v) in lower.iter_mut().zip(upper.iter_mut()) {
+                let t = *v * w;
+                *v = *u - t;
+                *u += t;
+                w *= step;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Bisection for a sign change of f from negative near lo to positive near hi.
+fn bisect<F: Fn(f64) -> f64>(f: F, mut lo: f64, mut hi: f64) -> f64 {
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if f(mid) < 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+// Deserialization goes through the validating constructors.
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AutocorrelatedMarchenkoPasturParams {
+    ratio: f64,
+    tau: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AutocorrelatedMarchenkoPasturParams> for AutocorrelatedMarchenkoPastur {
+    type Error = Error;
+
+    fn try_from(params: AutocorrelatedMarchenkoPasturParams) -> Result<Self, Error> {
+        Self::new(params.ratio, params.tau)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CrossCorrelationSingularValuesParams {
+    n_ratio: f64,
+    m_ratio: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CrossCorrelationSingularValuesParams> for CrossCorrelationSingularValues {
+    type Error = Error;
+
+    fn try_from(params: CrossCorrelationSingularValuesParams) -> Result<Self, Error> {
+        Self::new(params.n_ratio, params.m_ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{marchenko_pastur_density, marchenko_pastur_support, symmetric_eigenvalues};
+    use rand::SeedableRng;
+    use rand_distr::StandardNormal;
+
+    #[test]
+    fn test_autocorrelated_marchenko_pastur() {
+        // tau = 0 is plain MP.
+        let white = AutocorrelatedMarchenkoPastur::new(0.5, 0.0).unwrap();
+        let (lo, hi) = white.support();
+        let (mp_lo, mp_hi) = marchenko_pastur_support(0.5, 1.0);
+        assert!((lo - mp_lo).abs() < 1e-10 && (hi - mp_hi).abs() < 1e-10);
+        for x in [0.2, 1.0, 2.5] {
+            assert!((white.pdf(x) - marchenko_pastur_density(x, 0.5, 1.0)).abs() < 1e-10);
+        }
+
+        // Unit mass and unit mean for a correlated law.
+        let law = AutocorrelatedMarchenkoPastur::new(0.25, 2.0).unwrap();
+        let (lo, hi) = law.support();
+        let (nodes, weights) = gauss_legendre(200, 0.0, PI);
+        let (center, half) = (0.5 * (hi + lo), 0.5 * (hi - lo));
+        let (mut mass, mut mean) = (0.0, 0.0);
+        for (&t, &w) in nodes.iter().zip(&weights) {
+            let x = center - half * t.cos();
+            let d = w * half * t.sin() * law.pdf(x);
+            mass += d;
+            mean += x * d;
+        }
+        assert!((mass - 1.0).abs() < 1e-6 && (mean - 1.0).abs() < 1e-6);
+
+        // Eigenvalues of AR(1) samples follow the law, not MP.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        let (n, t) = (100, 400);
+        let a = law.autocorrelation();
+        let mut x = Array2::zeros((n, t));
+        for mut row in x.rows_mut() {
+            let mut value: f64 = rng.sample(StandardNormal);
+            for entry in row.iter_mut() {
+                *entry = value;
+                let e: f64 = rng.sample(StandardNormal);
+                value = a * value + (1.0 - a * a).sqrt() * e;
+            }
+        }
+        let mut eigenvalues = symmetric_eigenvalues(&(x.dot(&x.t()) / t as f64));
+        eigenvalues.sort_by(f64::total_cmp);
+        assert!(eigenvalues[n - 1] > marchenko_pastur_support(0.25, 1.0).1 + 0.5);
+        assert!(eigenvalues[n - 1] < hi + 0.3);
+        for q in [0.25, 0.5, 0.75] {
+            let empirical = eigenvalues[(q * n as f64) as usize];
+            assert!((empirical - law.quantile(q)).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn test_cross_correlation_singular_values() {
+        // The continuous part and the atom at 1 share the unit mass.
+        for (n, m) in [(0.1, 0.2), (0.6, 0.6), (0.3, 0.9)] {
+            let law = CrossCorrelationSingularValues::new(n, m).unwrap();
+            let (_, hi) = law.support();
+            assert!((law.cdf(hi) + law.atom() - 1.0).abs() < 1e-6);
+        }
+        assert!(
+            (CrossCorrelationSingularValues::new(0.6, 0.6)
+                .unwrap()
+                .atom()
+                - 1.0 / 3.0)
+                .abs()
+                < 1e-12
+        );
+        assert!(CrossCorrelationSingularValues::new(0.5, 1.0).is_err());
+
+        // Lag-one auto-cross-correlations of white noise follow the law.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        let (t, n) = (401, 100);
+        let x = Array2::from_shape_fn((t, n), |_| rng.sample::<f64, _>(StandardNormal));
+        let mut values = lagged_cross_correlations(&x, &x, 1);
+        values.reverse();
+        let ratio = n as f64 / (t - 1) as f64;
+        let law = CrossCorrelationSingularValues::new(ratio, ratio).unwrap();
+        assert!(values[n - 1] < law.support().1 + 0.03);
+        for q in [0.25, 0.5, 0.75] {
+            let empirical = values[(q * n as f64) as usize];
+            assert!((empirical - law.quantile(q)).abs() < 0.03);
+        }
+    }
+
+    #[test]
+    fn test_fft_matches_direct_transform() {
+        for n in [12, 16] {
+            let x: Vec<Complex64> = (0..n)
+                .map(|k| Complex64::new((k * k % 5) as f64, k as f64 - 3.0))
+                .collect();
+            let mut y = x.clone();
+            fft(&mut y, false);
+            for (k, yk) in y.iter().enumerate() {
+                let direct: Complex64 = x
+                    .iter()
+                    .enumerate()
+                    .map(|(j, xj)| {
+                        xj * Complex64::from_polar(1.0, -2.0 * PI * (j * k) as f64 / n as f64)
+                    })
+                    .sum();
+                assert!((yk - direct).norm() < 1e-10);
+            }
+            fft(&mut y, true);
+            assert!(x
+                .iter()
+                .zip(&y)
+                .all(|(a, b)| (a - b / n as f64).norm() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_surrogate_nulls() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        let (t, n) = (200, 40);
+        let law = AutocorrelatedMarchenkoPastur::new(n as f64 / t as f64, 3.0).unwrap();
+        let a = law.autocorrelation();
+        let mut x = Array2::zeros((t, n));
+        for mut column in x.columns_mut() {
+            let mut value: f64 = rng.sample(StandardNormal);
+            for entry in column.iter_mut() {
+                *entry = value;
+                let e: f64 = rng.sample(StandardNormal);
+                value = a * value + (1.0 - a * a).sqrt() * e;
+            }
+        }
+
+        // Phase randomization keeps the periodogram, hence the mean, the
+        // variance and the circular autocorrelation of each series.
+        let surrogate = surrogate_series(&mut rng, &x, SurrogateMethod::PhaseRandomization);
+        let circular = |v: &[f64], lag: usize| (0..t).map(|i| v[i] * v[(i + lag) % t]).sum::<f64>();
+        for j in 0..3 {
+            let (u, v) = (x.column(j).to_vec(), surrogate.column(j).to_vec());
+            for lag in 0..3 {
+                assert!((circular(&u, lag) - circular(&v, lag)).abs() < 1e-8);
+            }
+        }
+
+        // Shifts keep the autocorrelation and reproduce its wider edge;
+        // permutations fall back to the plain MP edge.
+        let shifted =
+            surrogate_correlation_spectra(&mut rng, &x, SurrogateMethod::CircularShift, 20)
+                .unwrap();
+        let observed = correlation_eigenvalues(&x).unwrap();
+        let largest = observed[n - 1];
+        let permuted =
+            surrogate_correlation_spectra(&mut rng, &x, SurrogateMethod::Permutation, 20).unwrap();
+        let mp_edge = marchenko_pastur_support(n as f64 / t as f64, 1.0).1;
+        assert!((permuted.edge(0.5) - mp_edge).abs() < 0.2);
+        assert!(largest > mp_edge + 0.5 && largest < law.support().1);
+        assert!((shifted.edge(0.5) - largest).abs() < 0.3);
+        assert!(permuted.p_values(&observed)[0] < 0.1);
+        assert!(shifted.p_values(&observed)[0] > 0.1);
+        assert!(
+            surrogate_correlation_spectra(&mut rng, &x, SurrogateMethod::Permutation, 0).is_none()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_autocorrelated_serde_validates() {
+        let law = AutocorrelatedMarchenkoPastur::new(0.5, 2.0).unwrap();
+        let json = serde_json::to_string(&law).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AutocorrelatedMarchenkoPastur>(&json).unwrap(),
+            law
+        );
+        let bad = r#"{"ratio":2.0,"tau":1.0}"#;
+        assert!(serde_json::from_str::<AutocorrelatedMarchenkoPastur>(bad).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_cross_correlation_serde_validates() {
+        let law = CrossCorrelationSingularValues::new(0.2, 0.3).unwrap();
+        let json = serde_json::to_string(&law).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CrossCorrelationSingularValues>(&json).unwrap(),
+            law
+        );
+        let bad = r#"{"n_ratio":0.2,"m_ratio":1.0}"#;
+        assert!(serde_json::from_str::<CrossCorrelationSingularValues>(bad).is_err());
+    }
+}
@@ -0,0 +1,537 @@
+//! Tracy-Widom laws for the largest eigenvalue.
+//!
+//! The CDFs are evaluated as Fredholm determinants on Gauss-Legendre grids
+//! (Bornemann 2010), which converge exponentially in the number of nodes and
+//! need only the Airy function:
+//!
+//! - F1(s) = det(I - A_s), A_s(x, y) = Ai((x + y)/2 + s) / 2 on L^2(0, inf)
+//! - F2(s) = det(I - K_Ai) on L^2(s, inf), K_Ai(x, y) = int_0^inf Ai(x + t) Ai(y + t) dt
+//! - F4(s) = (det(I - A_{s sqrt 2}) + det(I + A_{s sqrt 2})) / 2
+//!
+//! A determinant costs milliseconds, so [`tracy_widom_cdf`] instead
+//! interpolates tables of logit F_beta precomputed this way (cubic Hermite
+//! on a grid of step 1/32) and continues them into the tails with the
+//! leading asymptotic forms: log F_beta ~ -c |s|^3 on the left and
+//! log(1 - F_beta) ~ -c s^(3/2) on the right.
+
+use ndarray::Array2;
+
+use crate::linalg::{determinant, solve};
+use crate::special::{airy_ai, gauss_legendre};
+use crate::spectrum::AsEigenvalues;
+use crate::tracy_widom_tables::{STEP, TW1_LO, TW1_LOGIT, TW2_LO, TW2_LOGIT, TW4_LO, TW4_LOGIT};
+
+/// Quadrature nodes per Fredholm determinant.
+const NODES: usize = 48;
+/// Length of the truncated half-line; Ai(8) ~ 1e-8 and the kernels decay as Ai^2.
+const HALF_LINE: f64 = 16.0;
+
+/// Tracy-Widom CDF F_beta(s) for beta = 1 (GOE), 2 (GUE), or 4 (GSE).
+///
+/// Interpolated from precomputed tables, so a call takes well under a
+/// microsecond; see [`tracy_widom_cdf_fredholm`] for the direct evaluation.
+///
+/// # Arguments
+///
+/// * `s` - Point on the Tracy-Widom scale
+/// * `beta` - Dyson index (1, 2, or 4)
+///
+/// # Returns
+///
+/// F_beta(s), accurate to about 1e-9 (and to a few digits relative to F in
+/// the far left tail); NaN for any other beta
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::tracy_widom_cdf;
+///
+/// // The 95% quantile of TW1 is about 0.9793.
+/// assert!((tracy_widom_cdf(0.9793, 1.0) - 0.95).abs() < 1e-3);
+/// ```
+pub fn tracy_widom_cdf(s: f64, beta: f64) -> f64 {
+    match logit(s, beta) {
+        Some(y) => 1.0 / (1.0 + (-y).exp()),
+        None => f64::NAN,
+    }
+}
+
+/// Tracy-Widom survival function 1 - F_beta(s).
+///
+/// Computed without cancellation, so right-tail p-values keep their
+/// relative accuracy where `1.0 - tracy_widom_cdf(s, beta)` rounds to 0.
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{tracy_widom_cdf, tracy_widom_sf};
+///
+/// assert!((tracy_widom_sf(0.9793, 1.0) - 0.05).abs() < 1e-3);
+/// assert!(tracy_widom_sf(12.0, 2.0) > 0.0);
+/// assert_eq!(1.0 - tracy_widom_cdf(12.0, 2.0), 0.0);
+/// ```
+pub fn tracy_widom_sf(s: f64, beta: f64) -> f64 {
+    match logit(s, beta) {
+        Some(y) => 1.0 / (1.0 + y.exp()),
+        None => f64::NAN,
+    }
+}
+
+/// Tracy-Widom CDF evaluated directly as a Fredholm determinant.
+///
+/// Accurate to about 1e-10 in absolute terms but costs a dense 48 x 48
+/// determinant per call; this is the reference the tables behind
+/// [`tracy_widom_cdf`] were generated from.
+pub fn tracy_widom_cdf_fredholm(s: f64, beta: f64) -> f64 {
+    if s.is_nan() {
+        return f64::NAN;
+    }
+    let value = if beta == 1.0 {
+        goe_determinant(s, -1.0)
+    } else if beta == 2.0 {
+        gue_determinant(s)
+    } else if beta == 4.0 {
+        let s = s * std::f64::consts::SQRT_2;
+        0.5 * (goe_determinant(s, -1.0) + goe_determinant(s, 1.0))
+    } else {
+        return f64::NAN;
+    };
+    value.clamp(0.0, 1.0)
+}
+
+/// One term of an asymptotic tail expansion.
+type Term = fn(f64) -> f64;
+
+/// logit F_beta(s) = ln F - ln(1 - F) from the tables; `None` for an unsupported beta.
+fn logit(s: f64, beta: f64) -> Option<f64> {
+    // Right tail: y ~ -log(1 - F) ~ a s^(3/2) + d log s + const. The
+    // exponents a (and d, where known) come from theory; the rest is fitted.
+    let (lo, table, leading, right): (f64, &[f64], Term, &[Term]) = if beta == 1.0 {
+        // 1 - F1 ~ e^(-2 s^(3/2) / 3) / (4 sqrt(pi) s^(3/4))
+        (
+            TW1_LO,
+            &TW1_LOGIT,
+            |s| 2.0 / 3.0 * s.powf(1.5) + 0.75 * s.ln(),
+            &[|s| s.powf(-1.5)],
+        )
+    } else if beta == 2.0 {
+        // 1 - F2 ~ e^(-4 s^(3/2) / 3) / (16 pi s^(3/2))
+        (
+            TW2_LO,
+            &TW2_LOGIT,
+            |s| 4.0 / 3.0 * s.powf(1.5) + 1.5 * s.ln(),
+            &[|s| s.powf(-1.5)],
+        )
+    } else if beta == 4.0 {
+        // The first-order terms of det(I -+ A) cancel, leaving e^(-4 (s sqrt 2)^(3/2) / 3).
+        (
+            TW4_LO,
+            &TW4_LOGIT,
+            |s| 4.0 / 3.0 * std::f64::consts::SQRT_2.powf(1.5) * s.powf(1.5),
+            &[|s| s.ln(), |s| s.powf(-1.5)],
+        )
+    } else {
+        return None;
+    };
+    if s.is_nan() {
+        return Some(f64::NAN);
+    }
+    let last = table.len() - 1;
+    let hi = lo + last as f64 * STEP;
+    if s < lo {
+        // log F ~ -a |s|^3 + b |s|^(3/2) + c log|s| + const.
+        let basis: [Term; 3] = [|s| s.abs().powi(3), |s| s.abs().powf(1.5), |s| s.abs().ln()];
+        return Some(tail(table, lo, 0, s, |_| 0.0, &basis));
+    }
+    if s > hi {
+        return Some(tail(table, lo, last, s, leading, right));
+    }
+
+    let t = (s - lo) / STEP;
+    let k = (t as usize).min(last - 1);
+    let u = t - k as f64;
+    let (y0, y1) = (table[k], table[k + 1]);
+    let (d0, d1) = (
+        table_slope(table, k) * STEP,
+        table_slope(table, k + 1) * STEP,
+    );
+    // Cubic Hermite basis on [0, 1].
+    let u2 = u * u;
+    let u3 = u2 * u;
+    Some(
+        (2.0 * u3 - 3.0 * u2 + 1.0) * y0
+            + (u3 - 2.0 * u2 + u) * d0
+            + (-2.0 * u3 + 3.0 * u2) * y1
+            + (u3 - u2) * d1,
+    )
+}
+
+/// Continue the table past node `edge` as
+/// y(s) = y_edge + (f(s) - f(s_edge)) + sum_j c_j (phi_j(s) - phi_j(s_edge)).
+///
+/// `f` holds the known terms of the asymptotic expansion; the coefficients
+/// c_j are fitted exactly to the nodes 1, 2, ... units of s inside the edge.
+fn tail(table: &[f64], lo: f64, edge: usize, s: f64, f: Term, basis: &[Term]) -> f64 {
+    let per_unit = (1.0 / STEP) as usize;
+    let node = |k: usize| {
+        let idx = if edge == 0 {
+            k * per_unit
+        } else {
+            edge - k * per_unit
+        };
+        (lo + idx as f64 * STEP, table[idx])
+    };
+    let (s_edge, y_edge) = node(0);
+    let m = basis.len();
+    let mut a = Array2::zeros((m, m));
+    let mut b = vec![0.0; m];
+    for k in 0..m {
+        let (s_k, y_k) = node(k + 1);
+        for (j, phi) in basis.iter().enumerate() {
+            a[[k, j]] = phi(s_k) - phi(s_edge);
+        }
+        b[k] = y_k - y_edge - (f(s_k) - f(s_edge));
+    }
+    match solve(&a, &b) {
+        Some(c) => {
+            y_edge
+                + (f(s) - f(s_edge))
+                + basis
+                    .iter()
+                    .zip(&c)
+                    .map(|(phi, c)| c * (phi(s) - phi(s_edge)))
+                    .sum::<f64>()
+        }
+        None => f64::NAN,
+    }
+}
+
+/// dy/ds at node k from fourth-order finite differences (one-sided at the ends).
+fn table_slope(y: &[f64], k: usize) -> f64 {
+    let last = y.len() - 1;
+    let d = if k == 0 {
+        -25.0 * y[0] + 48.0 * y[1] - 36.0 * y[2] + 16.0 * y[3] - 3.0 * y[4]
+    } else if k == 1 {
+        -3.0 * y[0] - 10.0 * y[1] + 18.0 * y[2] - 6.0 * y[3] + y[4]
+    } else if k == last {
+        25.0 * y[k] - 48.0 * y[k - 1] + 36.0 * y[k - 2] - 16.0 * y[k - 3] + 3.0 * y[k - 4]
+    } else if k == last - 1 {
+        3.0 * y[k + 1] + 10.0 * y[k] - 18.0 * y[k - 1] + 6.0 * y[k - 2] - y[k - 3]
+    } else {
+        y[k - 2] - 8.0 * y[k - 1] + 8.0 * y[k + 1] - y[k + 2]
+    };
+    d / (12.0 * STEP)
+}
+
+/// Result of a Tracy-Widom test on the largest sample-covariance eigenvalue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LargestEigenvalueTest {
+    /// Largest eigenvalue of the sample covariance
+    pub lambda_max: f64,
+    /// lambda_max centered and scaled to the TW1 scale
+    pub statistic: f64,
+    /// P(TW1 >= statistic) under the pure-noise null
+    pub p_value: f64,
+}
+
+/// Test whether the top eigenvalue of a sample covariance exceeds the pure-noise edge.
+///
+/// Under the null of i.i.d. N(0, 1) data (X is n x p), n lambda_max of the
+/// sample covariance X^T X / n is approximately mu + sigma TW1 with Johnstone's
+/// (2001) constants
+///
+/// mu = (sqrt(n - 1) + sqrt(p))^2,
+/// sigma = (sqrt(n - 1) + sqrt(p)) (1/sqrt(n - 1) + 1/sqrt(p))^(1/3).
+///
+/// The approximation is already good for n, p around 10. Data with unknown
+/// noise variance should be standardized (or the eigenvalues divided by an
+/// estimate of sigma^2) first.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - Sample covariance eigenvalues in any order
+/// * `n` - Number of samples
+/// * `p` - Number of variables
+///
+/// # Returns
+///
+/// `None` if the spectrum has no finite values or n, p < 2
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{largest_eigenvalue_test, sample_wishart_with, symmetric_eigenvalues};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let (n, p) = (200, 50);
+/// let cov = sample_wishart_with(&mut rng, n, p) / n as f64;
+/// let result = largest_eigenvalue_test(&symmetric_eigenvalues(&cov), n, p).unwrap();
+/// assert!(result.p_value > 0.01);
+/// ```
+pub fn largest_eigenvalue_test<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    n: usize,
+    p: usize,
+) -> Option<LargestEigenvalueTest> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if n < 2 || p < 2 {
+        return None;
+    }
+    let lambda_max = eigenvalues
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .reduce(f64::max)?;
+
+    let TracyWidomScaling { mu, sigma, .. } = tw_centering_scaling(n, p);
+    let statistic = (n as f64 * lambda_max - mu) / sigma;
+    Some(LargestEigenvalueTest {
+        lambda_max,
+        statistic,
+        p_value: tracy_widom_sf(statistic, 1.0),
+    })
+}
+
+/// Constants mapping n lambda_max of a real white Wishart matrix to the TW1 scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TracyWidomScaling {
+    /// Johnstone (2001) centering (sqrt(n - 1) + sqrt(p))^2
+    pub mu: f64,
+    /// Johnstone (2001) scale (sqrt(n - 1) + sqrt(p)) (1 / sqrt(n - 1) + 1 / sqrt(p))^(1/3)
+    pub sigma: f64,
+    /// Second-order centering (sqrt(n - 1/2) + sqrt(p - 1/2))^2
+    pub mu_improved: f64,
+    /// Second-order scale with the same half-integer shifts
+    pub sigma_improved: f64,
+}
+
+/// Centering mu_np and scaling sigma_np for the largest eigenvalue of a real white Wishart matrix.
+///
+/// For an n x p matrix X of i.i.d. N(0, 1) entries, (lambda_max(X^T X) -
+/// mu) / sigma converges to TW1 as n, p grow together; equivalently
+/// (n lambda_max(S) - mu) / sigma for the sample covariance S = X^T X / n.
+/// Johnstone's (2001) constants, used by [`largest_eigenvalue_test`], shift
+/// n to n - 1. Replacing both n and p by n - 1/2 and p - 1/2 also cancels
+/// the next term of the expansion, giving an O(p^(-2/3)) error in the TW
+/// approximation (Johnstone 2009; Ma 2012); the improvement is most visible
+/// in the tail probabilities that tests use, for p in the tens.
+///
+/// Both dimensions play symmetric roles in the improved constants, so they
+/// apply equally when p > n.
+///
+/// # Arguments
+///
+/// * `n` - Number of samples (at least 2)
+/// * `p` - Number of variables (at least 1)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{tracy_widom_sf, tw_centering_scaling};
+///
+/// let scaling = tw_centering_scaling(100, 20);
+/// // Is a sample-covariance lambda_max of 2.6 unusual for n = 100, p = 20?
+/// let s = (100.0 * 2.6 - scaling.mu_improved) / scaling.sigma_improved;
+/// assert!(tracy_widom_sf(s, 1.0) < 0.01);
+/// ```
+pub fn tw_centering_scaling(n: usize, p: usize) -> TracyWidomScaling {
+    let constants = |a: f64, b: f64| {
+        let (a, b) = (a.sqrt(), b.sqrt());
+        ((a + b).powi(2), (a + b) * (1.0 / a + 1.0 / b).cbrt())
+    };
+    let (n, p) = (n as f64, p as f64);
+    let (mu, sigma) = constants(n - 1.0, p);
+    let (mu_improved, sigma_improved) = constants(n - 0.5, p - 0.5);
+    TracyWidomScaling {
+        mu,
+        sigma,
+        mu_improved,
+        sigma_improved,
+    }
+}
+
+/// det(I + sign A_s) for the GOE kernel A_s(x, y) = Ai((x + y)/2 + s) / 2 on L^2(0, inf).
+fn goe_determinant(s: f64, sign: f64) -> f64 {
+    // Shift the window so the kernel has decayed by the end of it.
+    let length = HALF_LINE.max(2.0 * (HALF_LINE / 2.0 - s));
+    let (x, w) = gauss_legendre(NODES, 0.0, length);
+    let root_w: Vec<f64> = w.iter().map(|w| w.sqrt()).collect();
+    let m = Array2::from_shape_fn((NODES, NODES), |(i, j)| {
+        let kernel = 0.5 * airy_ai(0.5 * (x[i] + x[j]) + s);
+        let identity = if i == j { 1.0 } else { 0.0 };
+        identity + sign * root_w[i] * kernel * root_w[j]
+    });
+    determinant(&m)
+}
+
+/// det(I - K_Ai) on L^2(s, inf), with K_Ai = B B^T factored through its integral representation.
+fn gue_determinant(s: f64) -> f64 {
+    let upper = s.max(0.0) + HALF_LINE / 2.0;
+    let (x, w) = gauss_legendre(NODES, s, upper);
+    let (t, v) = gauss_legendre(NODES, 0.0, upper - s);
+    let b = Array2::from_shape_fn((NODES, NODES), |(i, k)| {
+        (w[i] * v[k]).sqrt() * airy_ai(x[i] + t[k])
+    });
+    let m = Array2::eye(NODES) - b.dot(&b.t());
+    determinant(&m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sample_wishart_with, symmetric_eigenvalues};
+    use rand::SeedableRng;
+    use std::f64::consts::PI;
+
+    /// Mean of F_beta by integrating s dF over a grid.
+    fn tw_mean(beta: f64) -> f64 {
+        let (lo, hi, steps) = (-10.0, 6.0, 320);
+        let h = (hi - lo) / steps as f64;
+        // E[S] = hi - int_lo^hi F(s) ds when F(lo) ~ 0 and F(hi) ~ 1 (Simpson's rule).
+        let integral: f64 = (0..=steps)
+            .map(|k| {
+                let weight = if k == 0 || k == steps {
+                    1.0
+                } else if k % 2 == 1 {
+                    4.0
+                } else {
+                    2.0
+                };
+                weight * tracy_widom_cdf(lo + k as f64 * h, beta)
+            })
+            .sum::<f64>()
+            * h
+            / 3.0;
+        hi - integral
+    }
+
+    #[test]
+    fn test_tracy_widom_means() {
+        // Reference means from Bornemann (2010), Table 4.
+        for &(beta, mean) in &[
+            (1.0, -1.206_533_574_582),
+            (2.0, -1.771_086_807_411),
+            (4.0, -2.306_884_893_241),
+        ] {
+            let got = tw_mean(beta);
+            assert!(
+                (got - mean).abs() < 1e-6,
+                "beta {}: {} vs {}",
+                beta,
+                got,
+                mean
+            );
+        }
+    }
+
+    #[test]
+    fn test_tables_match_fredholm() {
+        for &beta in &[1.0, 2.0, 4.0] {
+            // Off-grid points across the tabulated range and into the tails.
+            for k in 0..70 {
+                let s = -11.0 + 0.2371 * k as f64;
+                let exact = tracy_widom_cdf_fredholm(s, beta);
+                let got = tracy_widom_cdf(s, beta);
+                assert!(
+                    (got - exact).abs() < 1e-9,
+                    "beta {beta}, s {s}: {got} vs {exact}"
+                );
+                let tail = 1.0 - exact;
+                if tail > 1e-7 {
+                    let sf = tracy_widom_sf(s, beta);
+                    assert!(
+                        (sf / tail - 1.0).abs() < 1e-5,
+                        "beta {beta}, s {s}: sf {sf} vs {tail}"
+                    );
+                }
+                if exact > 1e-12 {
+                    assert!(
+                        (got / exact - 1.0).abs() < 1e-3,
+                        "beta {beta}, s {s}: relative"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tracy_widom_tail_asymptotics() {
+        // F2(s) ~ tau2 |s|^(-1/8) exp(-|s|^3 / 12), tau2 = 2^(1/24) e^(zeta'(-1)).
+        let tau2 = 2f64.powf(1.0 / 24.0) * (-0.165_421_143_559_922_4f64).exp();
+        for s in [-9.5f64, -12.0, -15.0] {
+            let expected = tau2 * (-s).powf(-0.125) * (s.powi(3) / 12.0).exp();
+            let got = tracy_widom_cdf(s, 2.0);
+            assert!(
+                (got / expected - 1.0).abs() < 0.02,
+                "s {s}: {got} vs {expected}"
+            );
+        }
+        // 1 - F2(s) ~ exp(-4 s^(3/2) / 3) / (16 pi s^(3/2)) (1 + O(s^(-3/2))).
+        for s in [20.0f64, 40.0] {
+            let expected = (-4.0 / 3.0 * s.powf(1.5)).exp() / (16.0 * PI * s.powf(1.5));
+            let got = tracy_widom_sf(s, 2.0);
+            assert!(
+                (got / expected - 1.0).abs() < 0.04,
+                "s {s}: {got} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tracy_widom_tails() {
+        assert!(tracy_widom_cdf(-9.0, 1.0) < 1e-6);
+        assert!(tracy_widom_cdf(6.0, 2.0) > 1.0 - 1e-8);
+        assert!((tracy_widom_cdf(2.0234, 1.0) - 0.99).abs() < 1e-3);
+        assert!(tracy_widom_cdf(0.0, 3.0).is_nan());
+    }
+
+    #[test]
+    fn test_largest_eigenvalue_detects_spike() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(41);
+        let (n, p) = (300, 60);
+        let cov = sample_wishart_with(&mut rng, n, p) / n as f64;
+        let mut eigenvalues = symmetric_eigenvalues(&cov);
+        let null = largest_eigenvalue_test(&eigenvalues, n, p).unwrap();
+        assert!(null.p_value > 0.01, "{:?}", null);
+
+        // A top eigenvalue of 3 sits far beyond the MP edge (1 + sqrt(0.2))^2 ~ 2.09.
+        *eigenvalues.last_mut().unwrap() = 3.0;
+        let spiked = largest_eigenvalue_test(&eigenvalues, n, p).unwrap();
+        assert!(spiked.p_value < 1e-6, "{:?}", spiked);
+        assert!(largest_eigenvalue_test(&[], n, p).is_none());
+    }
+
+    #[test]
+    fn test_tw_centering_scaling() {
+        let scaling = tw_centering_scaling(10, 5);
+        let root = 3.0 + 5f64.sqrt();
+        assert!((scaling.mu - root * root).abs() < 1e-12);
+        assert!((scaling.sigma - root * (1.0 / 3.0 + 1.0 / 5f64.sqrt()).cbrt()).abs() < 1e-12);
+        let root = 9.5f64.sqrt() + 4.5f64.sqrt();
+        assert!((scaling.mu_improved - root * root).abs() < 1e-12);
+        // The two versions differ at relative order 1 / p.
+        let large = tw_centering_scaling(4000, 1000);
+        assert!((large.mu_improved / large.mu - 1.0).abs() < 1e-3);
+        assert!((large.sigma_improved / large.sigma - 1.0).abs() < 1e-3);
+
+        // The improved constants hold the 95% TW1 quantile near its nominal level at small p.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(648);
+        let (n, p, trials) = (40, 10, 2000);
+        let scaling = tw_centering_scaling(n, p);
+        let exceed = |mu: f64, sigma: f64, top: &[f64]| {
+            top.iter().filter(|&&x| (x - mu) / sigma > 0.9793).count() as f64 / trials as f64
+        };
+        let top: Vec<f64> = (0..trials)
+            .map(|_| {
+                *symmetric_eigenvalues(&sample_wishart_with(&mut rng, n, p))
+                    .last()
+                    .unwrap()
+            })
+            .collect();
+        let improved = exceed(scaling.mu_improved, scaling.sigma_improved, &top);
+        assert!((improved - 0.05).abs() < 0.015, "{improved}");
+    }
+}
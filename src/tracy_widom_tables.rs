@@ -0,0 +1,370 @@
+//! Tabulated logit F_beta(s) = ln F - ln(1 - F) for the Tracy-Widom laws.
+//!
+//! Generated with [`crate::tracy_widom_cdf_fredholm`] on a grid of step 1/32
+//! by `examples/tracy_widom_tables.rs`. The ranges stop once F is below 1e-18
+//! or 1 - F below about 1e-9 (where 1 - det loses relative accuracy); the tail
+//! expansions take over beyond.
+
+/// Grid spacing shared by all tables.
+pub(crate) const STEP: f64 = 1.0 / 32.0;
+
+/// First grid point of [`TW1_LOGIT`].
+pub(crate) const TW1_LO: f64 = -10.0;
+/// First grid point of [`TW2_LOGIT`].
+pub(crate) const TW2_LO: f64 = -9.0;
+/// First grid point of [`TW4_LOGIT`].
+pub(crate) const TW4_LO: f64 = -8.0;
+
+/// logit F_1 at s = TW1_LO + k STEP.
+#[rustfmt::skip]
+pub(crate) const TW1_LOGIT: [f64; 577] = [
+    -49.50640933870193, -49.08196113720305, -48.66002234836506, -48.240429014088214,
+    -47.82342572775058, -47.40876063526946, -46.996619052536424, -46.586997055851874,
+    -46.17969912599248, -45.77490653611644, -45.372521564752645, -44.97256207794514,
+    -44.57501569023539, -44.17985372455008, -43.787090592026686, -43.3967561957347,
+    -43.008784819928614, -42.62317692824798, -42.239924975405714, -41.859052805797226,
+    -41.480521626710804, -41.104346781305296, -40.730496371049945, -40.3589726788241,
+    -39.98978375078817, -39.62290427069559, -39.25832660398406, -38.89605364274443,
+    -38.53606966210481, -38.17837049298143, -37.82294862345186, -37.46979771691653,
+    -37.118903539194534, -36.77026624891215, -36.423875020028596, -36.07972377299659,
+    -35.73780333225275, -35.398105123946415, -35.06062624097206, -34.72535496438413,
+    -34.39228522453432, -34.06140909917651, -33.73271972090592, -33.406208466531446,
+    -33.081869036982106, -32.75969310525382, -32.43967294112717, -32.1218021444832,
+    -31.806072147885946, -31.49247585134676, -31.181005550109706, -30.87165377381231,
+    -30.564413092800088, -30.259276076390567, -29.956234905185873, -29.655282178843283,
+    -29.356410497005047, -29.059612158443496, -28.764879811391474, -28.472205754053814,
+    -28.18158263263928, -27.89300282689563, -27.606458840836176, -27.321943180507926,
+    -27.039448312766517, -26.758966731124843, -26.480490861609184, -26.204013268316977,
+    -25.929526387665163, -25.6570227187647, -25.386494763397952, -25.11793497704952,
+    -24.851335866908375, -24.58668990515046, -24.323989608049168, -24.06322744820859,
+    -23.804395914355464, -23.54748749640897, -23.292494695240894, -23.039409993330903,
+    -22.788225891380062, -22.538934878613002, -22.29152945114266, -22.046002101870396,
+    -21.80234532538718, -21.560551626212757, -21.320613493545746, -21.082523429412724,
+    -20.846273932859564, -20.611857502854406, -20.37926663898177, -20.148493843782198,
+    -19.91953161936645, -19.69237246799411, -19.46700889404047, -19.2434334014814,
+    -19.021638496005988, -18.80161668382949, -18.583360471125314, -18.366862368056637,
+    -18.152114881356265, -17.939110521307125, -17.72784179856762, -17.51830122465841,
+    -17.31048131147604, -17.104374572389858, -16.89997352138079, -16.697270673532753,
+    -16.496258544535422, -16.29692965128627, -16.09927651131791, -15.90329164313236,
+    -15.708967566156373, -15.516296800655368, -15.325271867695678, -15.135885289239987,
+    -14.948129588158247, -14.761997288052623, -14.577480913163928, -14.394572988734156,
+    -14.213266040547046, -14.0335525950695, -13.855425179380287, -13.678876321093759,
+    -13.50389854826567, -13.330484389361546, -13.158626373100313, -12.988317028378164,
+    -12.819548884154859, -12.652314469265367, -12.48660631225232, -12.322416941297499,
+    -12.1597388838229, -11.99856466644677, -11.838886814618968, -11.68069785234413,
+    -11.523990301917175, -11.368756683498875, -11.21498951478609, -11.06268131053671,
+    -10.91182458211883, -10.762411836984468, -10.614435578094115, -10.467888303313652,
+    -10.322762504687555, -10.179050667749163, -10.036745270696882, -9.895838783514769,
+    -9.756323667054128, -9.618192371994304, -9.481437337779356, -9.34605099142613,
+    -9.212025746285123, -9.079354000697775, -8.948028136570171, -8.818040517863144,
+    -8.68938348897536, -8.562049373053641, -8.4360304701862, -8.311319055523184,
+    -8.187907377285697, -8.065787654689982, -7.944952075774442, -7.8253927951363895,
+    -7.707101931583362, -7.590071565691816, -7.474293737295511, -7.359760442887099,
+    -7.246463632954887, -7.134395209257485, -7.0235470220366, -6.913910867186558,
+    -6.805478483382749, -6.698241549183033, -6.59219168010784, -6.487320425719012,
+    -6.383619266699568, -6.2810796119570815, -6.179692795757544, -6.079450074906485,
+    -5.980342625993853, -5.882361542714758, -5.785497833283818, -5.689742417958228,
+    -5.595086126684486, -5.501519696886852, -5.409033771410862, -5.317618896639093,
+    -5.227265520793135, -5.137963992437653, -5.049704559198817, -4.962477366712211,
+    -4.87627245781101, -4.791079771967133, -4.7068891449945855, -4.623690309024641,
+    -4.541472892760878, -4.460226422019996, -4.37994032056387, -4.300603911226232,
+    -4.22220641733596, -4.1447369644373495, -4.06818458230624, -3.9925382072593134,
+    -3.9177866847520564, -3.843918772259506, -3.7709231424324288, -3.69878838651985,
+    -3.627503018047775, -3.5570554767424256, -3.487434132684855, -3.418627290683335,
+    -3.350623194848004, -3.28341003335208, -3.216975943362612, -3.151309016123383,
+    -3.0863973021719207, -3.022228816672068, -2.958791544843417, -2.8960734474686056,
+    -2.834062466459525, -2.772746530463552, -2.7121135604911055, -2.6521514755461224,
+    -2.592848198241553, -2.5341916603823673, -2.4761698084992783, -2.418770609317103,
+    -2.3619820551422817, -2.3057921691551466, -2.2501890105932247, -2.195160679812903,
+    -2.140695323217685, -2.0867811380423946, -2.033406376983482, -1.9805593526668854,
+    -1.9282284419456754, -1.8764020900209644, -1.825068814380372, -1.7742172085495262,
+    -1.7238359456528822, -1.6739137817812177, -1.6244395591639738, -1.5754022091455446,
+    -1.5267907549654098, -1.4785943143428049, -1.4308021018673704, -1.383403431197844,
+    -1.3363877170716147, -1.2897444771284352, -1.243463333552175, -1.1975340145349815,
+    -1.1519463555686622, -1.1066903005684479, -1.061755902834688, -1.0171333258582855,
+    -0.9728128439759496, -0.9287848428815435, -0.8850398199999511, -0.8415683847300486,
+    -0.7983612585634507, -0.755409275085677, -0.7127033798665767, -0.6702346302466813,
+    -0.6279941950261488, -0.5859733540630214, -0.5441634977872953, -0.5025561266372617,
+    -0.46114285042449843, -0.4199153876335958, -0.3788655646627992, -0.3379853150113513,
+    -0.2972666784192243, -0.2567017999648217, -0.2162829291259103, -0.17600241880890544,
+    -0.13585272435139295, -0.09582640250266872, -0.05591611038665123, -0.016114604451530568,
+    0.02358526058990862, 0.06319053282530418, 0.10270816420967521, 0.14214501163072002,
+    0.18150783796950498, 0.2208033131533489, 0.26003801519834446, 0.2992184312388646,
+    0.33835095854179453, 0.3774419055032354, 0.41649749262586544, 0.4555238534751461,
+    0.49452703561277095, 0.5335130015060394, 0.5724876294119456, 0.6114567142348781,
+    0.6504259683571415, 0.6894010224415353, 0.7283874262055043, 0.7673906491664096,
+    0.8064160813576216, 0.8454690340154587, 0.8845547402368559, 0.9236783556080133,
+    0.9628449588041537, 1.002059552161028, 1.041327062218318, 1.0806523402359214,
+    1.1200401626835184, 1.159495231704444, 1.1990221755545365, 1.2386255490172444,
+    1.278309833795697, 1.318079438883119, 1.3579387009126884, 1.3978918844880819,
+    1.4379431824960143, 1.4780967164021794, 1.518356536531953, 1.5587266223372285,
+    1.5992108826510234, 1.639813155931141, 1.6805372104946508, 1.7213867447443256,
+    1.7623653873891545, 1.8034766976598486, 1.8447241655212707, 1.8861112118832928,
+    1.9276411888113552, 1.9693173797385195, 2.0111429996803363, 2.053121195453936,
+    2.0952550459029036, 2.1375475621291518, 2.1800016877333577, 2.2226202990648085,
+    2.2654062054825603, 2.3083621496283255, 2.351490807713007, 2.3947947898171495,
+    2.4382766402070075, 2.4819388376666924, 2.525783795847402, 2.56981386363478,
+    2.614031325534552, 2.658438402077612, 2.703037250244963, 2.747829963912939,
+    2.792818574318869, 2.838005050548473, 2.8833913000436406, 2.92897916913248,
+    2.9747704435805913, 3.0207668491637967, 3.066970052262791, 3.1133816604786895,
+    3.160003223270219, 3.206836232611694, 3.2538821236712123, 3.301142275509555,
+    3.3486180117985405, 3.3963106015581013, 3.444221259912625, 3.4923511488646923,
+    3.5407013780860206, 3.589273005725161, 3.6380670392305405, 3.6870844361881763,
+    3.7363261051738395, 3.785792906617736, 3.8354856536813373, 3.8854051131457963,
+    3.9355520063095417, 3.9859270098962125, 4.036530756969702, 4.087363837856417,
+    4.138426801074408, 4.189720154266456, 4.2412443651374705, 4.292999862395365,
+    4.344987036693081, 4.397206241571388, 4.449657794402784, 4.502341977332516,
+    4.555259038219108, 4.608409191571194, 4.66179261947936, 4.715409472545243,
+    4.769259870802804, 4.823343904635159, 4.8776616356806475, 4.932213097735223,
+    4.986998297641824, 5.042017216171833, 5.097269808896883, 5.152756007048033,
+    5.208475718364936, 5.264428827931093, 5.320615198998149, 5.377034673795894,
+    5.433687074329366, 5.490572203160461, 5.547689844177104, 5.605039763346001,
+    5.6626217094521225, 5.720435414818619, 5.778480596017834, 5.836756954559971,
+    5.89526417757026, 5.954001938446917, 6.01296989750549, 6.072167702605834,
+    6.131594989760305, 6.191251383730602, 6.251136498605103, 6.311249938359904,
+    6.371591297404534, 6.432160161108242, 6.492956106320094, 6.553978701856893,
+    6.615227508993434, 6.676702081923372, 6.73840196820934, 6.8003267092213955,
+    6.862475840554045, 6.92484889243499, 6.98744539011367, 7.050264854238806,
+    7.1133068012217215, 7.176570743583788, 7.24005619029544, 7.3037626470892585,
+    7.367689616786268, 7.431836599579756, 7.496203093317418, 7.560788593792632,
+    7.625592594977426, 7.6906145893035, 7.755854067871916, 7.82131052071138,
+    7.886983436955951, 7.9528723051013, 8.01897661315857, 8.085295848869748,
+    8.151829499879307, 8.21857705389402, 8.285537998868843, 8.352711823125865,
+    8.420098015548744, 8.487696065655147, 8.555505463788771, 8.623525701207614,
+    8.691756270205667, 8.760196664226378, 8.828846377965599, 8.89770490749486,
+    8.966771750278115, 9.036046405325331, 9.105528373254238, 9.175217156374169,
+    9.245112258736967, 9.315213186165426, 9.38551944644332, 9.456030549236266,
+    9.526746006187803, 9.597665331006697, 9.668788039431842, 9.740113649361485,
+    9.811641680845453, 9.88337165607853, 9.955303099592493, 10.027435538034315,
+    10.099768500331402, 10.172301517872281, 10.24503412415161, 10.317965855133842,
+    10.391096249082405, 10.464424846680858, 10.537951190884447, 10.611674827170988,
+    10.685595303282975, 10.759712169400245, 10.83402497813158, 10.908533284600038,
+    10.983236645976005, 11.058134622337983, 11.133226775738464, 11.208512670943797,
+    11.28399187483539, 11.359663957057629, 11.435528489151578, 11.511585045539627,
+    11.5878332027424, 11.664272539667765, 11.740902637858891, 11.81772308060859,
+    11.89473345430588, 11.971933347139098, 12.049322349585248, 12.12690005496709,
+    12.204666058349344, 12.282619957368215, 12.360761351877883, 12.43908984375707,
+    12.517605037584856, 12.596306540024186, 12.675193959556355, 12.754266907208237,
+    12.83352499673386, 12.912967842707006, 12.992595062813763, 13.072406276699837,
+    13.152401107515075, 13.232579176999115, 13.312940112512283, 13.393483541570513,
+    13.474209095016167, 13.555116404489715, 13.636205103850383, 13.717474830436753,
+    13.79892522189815, 13.880555919691068, 13.962366564626807, 14.044356800274423,
+    14.126526275640783, 14.20887463604373, 14.291401533177895, 14.374106618280724,
+    14.456989545104102, 14.54004996934627, 14.623287549950458, 14.706701944229303,
+    14.790292814563742, 14.874059825314735, 14.95800263788507, 15.042120922173586,
+    15.126414342145832, 15.21088257380572, 15.295525285811678, 15.3803421513329,
+    15.465332851544526, 15.55049705430381, 15.63583444303169, 15.721344699300099,
+    15.807027501740354, 15.892882534995886, 15.978909489532569, 16.06510804301217,
+    16.15147789175256, 16.238018729710866, 16.324730233722736, 16.41161210586268,
+    16.498664047654728, 16.58588574108769, 16.67327689456538, 16.760837204747617,
+    16.848566372646033, 16.936464105375588, 17.024530101616552, 17.11276405676418,
+    17.20116570735272, 17.28973472743273, 17.37847084484105, 17.467373762794605,
+    17.55644319440178, 17.645678875295612, 17.735080508903913, 17.82464778845164,
+    17.9143804396444, 18.00427821148514, 18.09434083115479, 18.184567968450004,
+    18.274959396907175, 18.365514796692164, 18.456233889940197, 18.54711648589455,
+    18.638162250491657,
+];
+
+/// logit F_2 at s = TW2_LO + k STEP.
+#[rustfmt::skip]
+pub(crate) const TW2_LOGIT: [f64; 433] = [
+    -61.161126767333904, -60.53007345425286, -59.90339841205442, -59.28108568916371,
+    -58.663119878985995, -58.04948501417262, -57.44016875326918, -56.835153007481,
+    -56.234423556061316, -55.637964775761176, -55.04576176241451, -54.45779859104421,
+    -53.87406090953392, -53.2945329326489, -52.71919902878181, -52.14804489451176,
+    -51.5810543084974, -51.01821272142766, -50.45950441358743, -49.904914222945955,
+    -49.35442687414554, -48.80802723915636, -48.26569987426848, -47.72742957189306,
+    -47.19320108352711, -46.66299902037407, -46.136808263340114, -45.61461341667158,
+    -45.09639934245113, -44.58215063295819, -44.07185207136292, -43.56548838530888,
+    -43.06304431257256, -42.56450456668717, -42.069853848846755, -41.57907691605043,
+    -41.09215849252545, -40.609083287154576, -40.12983604423573, -39.654401475591385,
+    -39.18276430292388, -38.71490925866794, -38.25082106901016, -37.79048445185599,
+    -37.33388413396455, -36.88100483589266, -36.43183128875311, -35.986348208968145,
+    -35.54454032092669, -35.106392348623, -34.67188901340467, -34.24101503741702,
+    -33.81375514243424, -33.39009405252589, -32.97001648576846, -32.55350716483566,
+    -32.14055081210794, -31.731132146851163, -31.325235889439707, -30.922846760343216,
+    -30.52394947960369, -30.12852876663895, -29.73656934031164, -29.348055919500304,
+    -28.962973223611566, -28.58130597016473, -28.203038877323312, -27.828156663337307,
+    -27.456644044898066, -27.08848573923553, -26.723666463010773, -26.362170932762915,
+    -26.00398386411234, -25.649089972394822, -25.297473973273064, -24.949120581503166,
+    -24.604014511231373, -24.262140476750147, -23.92348319155507, -23.58802736890735,
+    -23.25575772151268, -22.926658961776095, -22.600715801550972, -22.27791295233145,
+    -21.958235125127214, -21.641667030358047, -21.32819337817392, -21.017798878047945,
+    -20.7104682389887, -20.406186169584572, -20.104937377769705, -19.806706571017273,
+    -19.511478456187355, -19.21923773957897, -18.929969126897458, -18.64365732325099,
+    -18.360287033106207, -18.07984296026632, -17.802309807832813, -17.527672278262756,
+    -17.255915073198842, -16.987022893531215, -16.720980439339176, -16.457772409806733,
+    -16.197383503186465, -15.939798416742914, -15.685001846659691, -15.432978487932457,
+    -15.183713034285864, -14.93719017804376, -14.69339460995239, -14.452311019031404,
+    -14.213924092344717, -13.978218514766809, -13.745178968696719, -13.51479013370095,
+    -13.287036686151298, -13.061903298731865, -12.839374639932547, -12.619435373404185,
+    -12.402070157256894, -12.187263643216324, -11.975000475671424, -11.765265290578323,
+    -11.558042714192354, -11.353317361640052, -11.151073835275625, -10.95129672283023,
+    -10.75397059531239, -10.559080004643542, -10.366609481000605, -10.17654352984209,
+    -9.988866628591953, -9.803563222948808, -9.620617722801036, -9.440014497712845,
+    -9.261737871962712, -9.085772119105831, -8.912101456035053, -8.740710036522954,
+    -8.571581944225953, -8.404701185132382, -8.24005167944598, -8.077617252900456,
+    -7.917381627497139, -7.759328411679136, -7.603441089946339, -7.449703011933658,
+    -7.298097380977775, -7.148607242206799, -7.0012154701955325, -6.8559047562387105,
+    -6.712657595301633, -6.5714562727196215, -6.432282850723312, -6.295119154877223,
+    -6.159946760526582, -6.026746979355186, -5.895500846162753, -5.7661891059773005,
+    -5.638792201620717, -5.513290261851388, -5.389663090206726, -5.267890154670732,
+    -5.1479505782898345, -5.029823130856464, -4.913486221775616, -4.798917894223335,
+    -4.686095820696828, -4.574997300047036, -4.465599256072477, -4.357878237740718,
+    -4.251810421089665, -4.147371612845976, -4.044537255782238, -3.943282435817949,
+    -3.843581890853402, -3.74541002130816, -3.6487409023201542, -3.55354829754549,
+    -3.4598056744835888, -3.3674862212388246, -3.2765628646164213, -3.187008289439164,
+    -3.098794958961441, -3.011895136248792, -2.9262809063845427, -2.8419241993604043,
+    -2.7587968135047314, -2.6768704393011697, -2.596116683450656, -2.516507093032222,
+    -2.438013179621438, -2.3606064432307656, -2.2842583959423015, -2.208940585111134,
+    -2.1346246160258735, -2.0612821739222844, -1.9888850452559235, -1.917405138149891,
+    -1.8468145019445936, -1.777085345787074, -1.7081900562082066, -1.640101213646722,
+    -1.5727916078892519, -1.5062342524055297, -1.440402397567384, -1.3752695427490906,
+    -1.3108094473149374, -1.246996140507539, -1.1838039302575654, -1.1212074109416534,
+    -1.0591814701211426, -0.9977012942989751, -0.9367423737365066, -0.8762805063754453,
+    -0.8162918009131712, -0.7567526790818744, -0.6976398771839627, -0.6389304469370937,
+    -0.5806017556832149, -0.5226314860161325, -0.4649976348819704, -0.4076785122065535,
+    -0.35065273910265915, -0.2938992457091967, -0.2373972687128859, -0.18112634860133603,
+    -0.12506632669474715, -0.0691973420014439, -0.013499827940278486, 0.04204549102913879,
+    0.09745760282982296, 0.15275521120818247, 0.20795673915838309, 0.26308033229900607,
+    0.3181438621730365, 0.3731649294449696, 0.42816086697108346, 0.48314874272145786,
+    0.5381453625349488, 0.5931672726905424, 0.6482307622810758, 0.7033518653776474,
+    0.7585463629753708, 0.8138297847134379, 0.8692174103646214, 0.9247242710916792,
+    0.9803651504701077, 1.0361545852787588, 1.0921068660619706, 1.1482360374685754,
+    1.204555898375105, 1.2610800018022603, 1.3178216546352832, 1.3747939171606334,
+    1.4320096024324447, 1.489481275484112, 1.5472212524010485, 1.6052415992722309,
+    1.6635541310388404, 1.7221704102593367, 1.7811017458107548, 1.8403591915471371,
+    1.899953544935785, 1.959895345692718, 2.0201948744389604, 2.0808621513988004,
+    2.141906935161426, 2.203338721526972, 2.2651667424571693, 2.327399965150996,
+    2.390047091263841, 2.4531165562889923, 2.5166165291183558, 2.5805549117985573,
+    2.6449393394976246, 2.709777180695242, 2.775075537609109, 2.8408412468677784,
+    2.907080880439142, 2.9738007468216603, 3.041006892504459, 3.1087051036998354,
+    3.176900908350852, 3.245599578414773, 3.31480613242033, 3.3845253382979608,
+    3.4547617164764812, 3.52551954324211, 3.596802854352094, 3.6686154488933225,
+    3.740960893377603, 3.8138425260609763, 3.887263461475353, 3.9612265951597543,
+    4.035734608575583, 4.110789974193166, 4.186394960732133, 4.262551638540894,
+    4.339261885098543, 4.416527390622338, 4.494349663765171, 4.572730037384662,
+    4.651669674368745, 4.731169573500721, 4.811230575347868, 4.891853368157403,
+    4.973038493746587, 5.0547863533689394, 5.1370972135454975, 5.2199712118461195,
+    5.303408362610336, 5.3874085625933334, 5.471971596526305, 5.557097142584095,
+    5.642784777745008, 5.729033983043714, 5.815844148696132, 5.903214579104904,
+    5.991144497723556, 6.079633051792378, 6.168679316921534, 6.2582823015340825,
+    6.348440951160422, 6.439154152581965, 6.5304207378174475, 6.622239487961805,
+    6.7146091368731255, 6.807528374703158, 6.900995851278905, 6.995010179330399,
+    7.089569937580229, 7.1846736736731325, 7.280319906973765, 7.376507131226058,
+    7.47323381706443, 7.570498414398853, 7.668299354674731, 7.7666350529946815,
+    7.865503910149539, 7.9649043144732685, 8.064834643649741, 8.16529326637123,
+    8.266278543905404, 8.367788831577338, 8.469822480102986, 8.572377836898445,
+    8.675453247253136, 8.77904705545107, 8.883157605819788, 8.9877832436051,
+    9.092922315952938, 9.198573172686828, 9.304734166998086, 9.411403656276168,
+    9.51858000261078, 9.626261573452405, 9.734446742127805, 9.843133888327259,
+    9.952321398494039, 10.062007666367492, 10.172191093186614, 10.282870088104122,
+    10.394043068408296, 10.505708460024131, 10.617864697398758, 10.730510223842316,
+    10.843643491886692, 10.957262963177158, 11.071367108876789, 11.185954409536546,
+    11.301023355290063, 11.416572445909507, 11.53260019105862, 11.649105110102626,
+    11.766085732345212, 11.883540596534765, 12.001468252094867, 12.1198672574233,
+    12.238736181639672, 12.358073603182765, 12.47787811027698, 12.598148301343668,
+    12.718882785152394, 12.840080178643317, 12.961739109664368, 13.083858215750725,
+    13.206436143603089, 13.32947154920786, 13.45296309936787, 13.576909469032682,
+    13.701309343141897, 13.826161415851452, 13.951464389981542, 14.07721697898603,
+    14.203417904733357, 14.330065895448149, 14.457159695239268, 14.584698049356149,
+    14.712679714893284, 14.84110346144416, 14.969968057660214, 15.099272296025129,
+    15.229014960805973, 15.359194852589862, 15.489810790203416, 15.620861576221557,
+    15.752346044222664, 15.884263024637848, 16.016611365373663, 16.149389908268766,
+    16.28259751589916, 16.416233057989395, 16.55029538236052, 16.684783405459463,
+    16.819695993696232, 16.95503205085079, 17.090790464212215, 17.22697017087756,
+    17.36357005651419, 17.50058906748248, 17.638026136006125, 17.77588021639204,
+    17.91415024331696, 18.052835155116853, 18.19193390636378, 18.331445515694334,
+    18.47136891742352, 18.61170307556803, 18.752446967844982, 18.89359972993961,
+    19.035160194875356,
+];
+
+/// logit F_4 at s = TW4_LO + k STEP.
+#[rustfmt::skip]
+pub(crate) const TW4_LOGIT: [f64; 337] = [
+    -52.106011406937384, -51.45812580630805, -50.80805760831457, -50.169069376389,
+    -49.533173718138144, -48.901810283420986, -48.27790703348708, -47.65741822684143,
+    -47.04196465843001, -46.43318735815655, -45.82789823489317, -45.22883227034667,
+    -44.63567838984699, -44.046739062471815, -43.46326644919965, -42.884812894584385,
+    -42.311308802624055, -41.74308484716536, -41.17985012362239, -40.62153277943586,
+    -40.06822180284233, -39.52004300816295, -38.97675436727485, -38.438337912342355,
+    -37.90491395859337, -37.37637896535216, -36.85270605114709, -36.33391181567465,
+    -35.81993522241612, -35.310760475455766, -34.80637237415176, -34.30676896331065,
+    -33.811903864284076, -33.3217597173889, -32.8363118018251, -32.355546187205384,
+    -31.87943703716073, -31.407965735466078, -30.941106331959798, -30.478840154763873,
+    -30.021141925665276, -29.567991875995165, -29.119367566511954, -28.675248848220566,
+    -28.235611084775492, -27.8004344146419, -27.369696177630523, -26.94337481435926,
+    -26.521448280365163, -26.103894884137798, -25.69069265469064, -25.281819609826243,
+    -24.877253992032294, -24.476974055944037, -24.080957610910815, -23.68918297178348,
+    -23.30162824801545, -22.918271541240077, -22.53909090002042, -22.16406450955278,
+    -21.79317040080179, -21.426386737823314, -21.06369158149887, -20.705063044878777,
+    -20.350479207294793, -19.999918164577235, -19.653358008322087, -19.31077680835419,
+    -18.97215265305056, -18.637463614191013, -18.306687770687265, -17.979803184822554,
+    -17.656787927453905, -17.337620054587976, -17.022277628641667, -16.710738701233733,
+    -16.40298132362056, -16.098983537982836, -15.79872338707778, -15.502178905160687,
+    -15.209328122834357, -14.920149064204013, -14.634619748241537, -14.352718186817405,
+    -14.074422384595225, -13.799710338869357, -13.528560037970731, -13.260949460684738,
+    -12.996856575364387, -12.736259337739387, -12.479135690061351, -12.225463558941515,
+    -11.975220853073253, -11.728385460620622, -11.48493524624826, -11.24484804739454,
+    -11.008101670274467, -10.77467388498966, -10.54454241998189, -10.31768495573936,
+    -10.094079117494188, -9.873702466959262, -9.656532492963771, -9.442546600863022,
+    -9.231722100662878, -9.024036193758691, -8.819465958150603, -8.617988332097683,
+    -8.419580096124632, -8.224217853291663, -8.03187800771443, -7.842536741275191,
+    -7.656169988542917, -7.472753409901381, -7.29226236294283, -7.114671872211861,
+    -6.939956597397663, -6.768090800150476, -6.5990483096796515, -6.432802487390682,
+    -6.26932619080955, -6.108591737106385, -5.9505708665536705, -5.7952347062981575,
+    -5.642553734842692, -5.492497747664319, -5.3450358244136105, -5.200136298144412,
+    -5.0577667270310425, -4.917893869022324, -4.780483659865304, -4.645501194910685,
+    -4.51291071507769, -4.382675597314153, -4.254758349840317, -4.129120612407159,
+    -4.005723161738334, -3.884525922257666, -3.765487982133886, -3.6485676146030346,
+    -3.5337223044556656, -3.4209087795072257, -3.3100830468014504, -3.2012004332350004,
+    -3.0942156302344754, -2.989082742067903, -2.8857553373314677, -2.784186503119618,
+    -2.684328901363487, -2.5861348268086704, -2.48955626609851, -2.3945449574336117,
+    -2.3010524502907885, -2.2090301647051493, -2.1184294496464076, -2.0292016400535067,
+    -1.9412981121299149, -1.8546703365436084, -1.769269929220625, -1.6850486994670706,
+    -1.6019586952017544, -1.5199522451284138, -1.438981997722376, -1.3590009569506856,
+    -1.2799625146868407, -1.2018204798202818, -1.1245291040970953, -1.0480431047609922,
+    -0.972317684092918, -0.897308545973249, -0.8229719096125178, -0.7492645206151272,
+    -0.6761436595555796, -0.6035671482585265, -0.5314933539828484, -0.4598811917157259,
+    -0.38869012478628573, -0.3178801640091333, -0.24741186556722283, -0.17724632784027183,
+    -0.10734518738047205, -0.037670614230966404, 0.031814693224850754, 0.10114751370348718,
+    0.17036410858094786, 0.2395002274117678, 0.3085911132586475, 0.37767150769509283,
+    0.44677565535577696, 0.5159373079214766, 0.5851897274383506, 0.6545656888841307,
+    0.724097481907354, 0.7938169116785638, 0.8637552988066726, 0.9339434782862122,
+    1.004411797455742, 1.0751901129601857, 1.146307786723979, 1.2177936809542884,
+    1.2896761522069151, 1.3619830445597714, 1.4347416819506058, 1.507978859747507,
+    1.5817208356310533, 1.6559933198773287, 1.7308214651400207, 1.8062298558381242,
+    1.8822424972626126, 1.9588828045219566, 2.036173591450225, 2.1141370596059024,
+    2.1927947874901648, 2.2721677201153625, 2.3522761590522965, 2.4331397530825187,
+    2.5147774895787967, 2.597207686730046, 2.6804479867214486, 2.7645153499712123,
+    2.8494260505169646, 2.9351956726331214, 3.021839108750512, 3.1093705587356326,
+    3.197803530575259, 3.287150842497971, 3.377424626550771, 3.4686363336353336,
+    3.56079673999465, 3.6539159551277502, 3.7480034310969113, 3.8430679731809145,
+    3.9391177518146767, 4.036160315747727, 4.134202606342704, 4.233250972927282,
+    4.3333111891085485, 4.434388469949302, 4.536487489905328, 4.639612401417721,
+    4.743766854052791, 4.848954014083676, 4.955176584405301, 5.062436824679567,
+    5.170736571610101, 5.280077259244753, 5.390459939220005, 5.501885300853011,
+    5.614353691001358, 5.727865133622096, 5.842419348946183, 5.95801577222624,
+    6.074653571986836, 6.192331667737628, 6.311048747109711, 6.430803282377498,
+    6.551593546340703, 6.6734176275482175, 6.796273444842574, 6.92015876122357,
+    7.045071197028739, 7.1710082424144765, 7.297967269174199, 7.425945541849461,
+    7.5549402282191895, 7.684948409126071, 7.815967087675913, 7.947993197824107,
+    8.081023612382271, 8.215055150493756, 8.350084584476704, 8.486108646283574,
+    8.623124033391665, 8.761127414217613, 8.900115433170214, 9.040084715247648,
+    9.18103187015342, 9.322953496341, 9.465846184302418, 9.609706519980564,
+    9.75453108755317, 9.900316472031834, 10.04705926175483, 10.194756050542951,
+    10.343403439507913, 10.492998038953933, 10.64353646972858, 10.79501536499391,
+    10.947431370883026, 11.100781148388437, 11.255061373494945, 11.410268738729412,
+    11.566399953588542, 11.7234517451474, 11.881420859036817, 12.040304059412081,
+    12.200098129571055, 12.360799873231969, 12.522406112435887, 12.684913691101155,
+    12.84831947269614, 13.012620341100451, 13.177813201469759, 13.343894978362602,
+    13.510862619825618, 13.67871309143301, 13.847443384147166, 14.017050505214796,
+    14.187531485906522, 14.358883373296216, 14.531103248148533, 14.704188199229815,
+    14.878135336364917, 15.052941795696837, 15.228604742788074, 15.405121327123323,
+    15.58248877514329, 15.760704280906904, 15.939765085463646, 16.119668464143793,
+    16.300411640680174, 16.48199200060583, 16.66440676927131, 16.847653319017873,
+    17.031729013883723, 17.21663122776268, 17.402357263364046, 17.58890469069038,
+    17.776270914332397, 17.964453138490835, 18.1534491426767, 18.34325628078839,
+    18.53387197780775, 18.72529401663713, 18.91751970539389, 19.110546477927716,
+    19.30437218450515,
+];
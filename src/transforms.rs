@@ -0,0 +1,344 @@
+//! Integral transforms of spectral densities.
+//!
+//! The Cauchy transform G(z) = int rho(y) / (z - y) dy of a density has, on the
+//! real axis, the boundary value
+//!
+//! ```text
+//! G(x + i0) = H[rho](x) - i pi rho(x),    H[rho](x) = p.v. int rho(y) / (x - y) dy
+//! ```
+//!
+//! so the Hilbert transform H supplies the real part that shrinkage formulas
+//! (Ledoit-Peche, rotationally invariant estimators) need.
+//!
+//! The resolvent helpers are the empirical counterpart: for an explicit matrix
+//! they compute m(z) = (1/n) tr (A - z)^{-1}, the Stieltjes transform of its
+//! eigenvalue distribution, at complex z off the real axis.
+
+use ndarray::Array2;
+use num_complex::Complex64;
+
+use crate::spectrum::AsEigenvalues;
+
+/// Hilbert transform of a density sampled on a grid, evaluated at the grid points.
+///
+/// Computes the principal-value integral H[rho](x) = p.v. int rho(y) / (x - y) dy,
+/// which is the real part of the Cauchy transform on the real axis. Multiply by
+/// 1/pi for the normalized convention H f(x) = (1/pi) p.v. int f(y) / (x - y) dy.
+///
+/// The density is treated as piecewise linear between grid points and each
+/// segment is integrated in closed form, so the logarithmic singularity at
+/// x = y cancels exactly between neighboring segments instead of being sampled.
+/// The density should vanish (or be negligible) at both ends of the grid.
+///
+/// # Arguments
+///
+/// * `grid` - Strictly increasing evaluation points
+/// * `density` - Density values at the grid points
+///
+/// # Returns
+///
+/// H[rho] at each grid point (empty if lengths differ or fewer than 2 points)
+///
+/// # Example
+///
+/// ```rust
+/// use rmt::{hilbert_transform, wigner_semicircle_density};
+///
+/// // For the unit semicircle, H[rho](x) = x / 2 on [-2, 2].
+/// let grid: Vec<f64> = (0..=400).map(|i| -2.0 + i as f64 / 100.0).collect();
+/// let density: Vec<f64> = grid.iter().map(|&x| wigner_semicircle_density(x, 1.0)).collect();
+/// let h = hilbert_transform(&grid, &density);
+/// assert!((h[300] - 0.5).abs() < 1e-2);
+/// ```
+pub fn hilbert_transform(grid: &[f64], density: &[f64]) -> Vec<f64> {
+    if grid.len() != density.len() || grid.len() < 2 {
+        return vec![];
+    }
+
+    grid.iter()
+        .map(|&x| hilbert_transform_at(grid, density, x))
+        .collect()
+}
+
+/// Hilbert transform of a gridded density at an arbitrary point x.
+///
+/// See [`hilbert_transform`]; x may lie anywhere, on or off the grid.
+pub fn hilbert_transform_at(grid: &[f64], density: &[f64], x: f64) -> f64 {
+    if grid.len() != density.len() || grid.len() < 2 {
+        return 0.0;
+    }
+
+    // For rho(y) = rho(a) + s (y - a) on [a, b]:
+    //   int_a^b rho(y) / (x - y) dy = rho_lin(x) ln|(x - a) / (x - b)| - s (b - a)
+    // where rho_lin is the segment's linear extension. The ln|0| terms at a
+    // grid point x cancel between the two adjacent segments, so both are dropped.
+    let log_abs = |u: f64| if u == 0.0 { 0.0 } else { u.abs().ln() };
+
+    let mut total = 0.0;
+    for i in 0..grid.len() - 1 {
+        let (a, b) = (grid[i], grid[i + 1]);
+        let width = b - a;
+        if width <= 0.0 {
+            continue;
+        }
+        let slope = (density[i + 1] - density[i]) / width;
+        let at_x = density[i] + slope * (x - a);
+        total += at_x * (log_abs(x - a) - log_abs(x - b)) - slope * width;
+    }
+    total
+}
+
+/// Normalized resolvent trace m(z) = (1/n) tr (A - z)^{-1} of an explicit matrix.
+///
+/// Solved directly with a complex LU factorization of A - zI, so no
+/// eigendecomposition is needed. Cost is O(n^3) per z; when evaluating many z
+/// for one matrix, diagonalize once and use [`resolvent_trace_from_eigenvalues`].
+///
+/// # Arguments
+///
+/// * `a` - Square matrix
+/// * `z` - Complex spectral parameter (off the spectrum)
+///
+/// # Returns
+///
+/// m(z), or NaN if A is not square, empty, or A - zI is singular
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::array;
+/// use rmt::{resolvent_trace, Complex64};
+///
+/// let a = array![[1.0, 0.0], [0.0, 3.0]];
+/// let m = resolvent_trace(&a, Complex64::new(0.0, 1.0));
+/// // (1/2) (1/(1 - i) + 1/(3 - i))
+/// assert!((m.re - 0.4).abs() < 1e-12);
+/// ```
+pub fn resolvent_trace(a: &Array2<f64>, z: Complex64) -> Complex64 {
+    let n = a.nrows();
+    let nan = Complex64::new(f64::NAN, f64::NAN);
+    if n == 0 || a.ncols() != n {
+        return nan;
+    }
+
+    let mut lu: Vec<Complex64> = a.iter().map(|&v| Complex64::new(v, 0.0)).collect();
+    for i in 0..n {
+        lu[i * n + i] -= z;
+    }
+
+    // LU with partial pivoting; perm[i] is the original row now at position i.
+    let mut perm: Vec<usize> = (0..n).collect();
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| lu[i * n + k].norm().total_cmp(&lu[j * n + k].norm()))
+            .unwrap_or(k);
+        if lu[pivot * n + k].norm() == 0.0 {
+            return nan;
+        }
+        if pivot != k {
+            for j in 0..n {
+                lu.swap(k * n + j, pivot * n + j);
+            }
+            perm.swap(k, pivot);
+        }
+        let diag = lu[k * n + k];
+        for i in (k + 1)..n {
+            let factor = lu[i * n + k] / diag;
+            lu[i * n + k] = factor;
+            for j in (k + 1)..n {
+                let update = factor * lu[k * n + j];
+                lu[i * n + j] -= update;
+            }
+        }
+    }
+
+    // tr (A - z)^{-1} = sum_j [x_j]_j where (A - z) x_j = e_j.
+    let mut trace = Complex64::new(0.0, 0.0);
+    let mut x = vec![Complex64::new(0.0, 0.0); n];
+    for col in 0..n {
+        for (i, xi) in x.iter_mut().enumerate() {
+            *xi = if perm[i] == col {
+                Complex64::new(1.0, 0.0)
+            } else {
+                Complex64::new(0.0, 0.0)
+            };
+        }
+        for i in 0..n {
+            let mut acc = x[i];
+            for j in 0..i {
+                acc -= lu[i * n + j] * x[j];
+            }
+            x[i] = acc;
+        }
+        for i in (0..n).rev() {
+            let mut acc = x[i];
+            for j in (i + 1)..n {
+                acc -= lu[i * n + j] * x[j];
+            }
+            x[i] = acc / lu[i * n + i];
+        }
+        trace += x[col];
+    }
+
+    trace / n as f64
+}
+
+/// Normalized resolvent trace m(z) = (1/n) sum 1/(lambda_i - z) from precomputed eigenvalues.
+///
+/// Complex-argument version of [`crate::stieltjes_transform`].
+pub fn resolvent_trace_from_eigenvalues<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    z: Complex64,
+) -> Complex64 {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    let n = eigenvalues.len() as f64;
+    eigenvalues
+        .iter()
+        .map(|&ev| (Complex64::new(ev, 0.0) - z).inv())
+        .sum::<Complex64>()
+        / n
+}
+
+/// [`resolvent_trace`] evaluated on a grid of complex points.
+pub fn resolvent_trace_grid(a: &Array2<f64>, zs: &[Complex64]) -> Vec<Complex64> {
+    zs.iter().map(|&z| resolvent_trace(a, z)).collect()
+}
+
+/// [`resolvent_trace_from_eigenvalues`] evaluated on a grid of complex points.
+pub fn resolvent_trace_grid_from_eigenvalues<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    zs: &[Complex64],
+) -> Vec<Complex64> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    zs.iter()
+        .map(|&z| resolvent_trace_from_eigenvalues(eigenvalues, z))
+        .collect()
+}
+
+/// Parallel [`resolvent_trace_grid`]: one LU solve per grid point, spread across threads.
+#[cfg(feature = "parallel")]
+pub fn par_resolvent_trace_grid(a: &Array2<f64>, zs: &[Complex64]) -> Vec<Complex64> {
+    use rayon::prelude::*;
+    zs.par_iter().map(|&z| resolvent_trace(a, z)).collect()
+}
+
+/// Parallel [`resolvent_trace_grid_from_eigenvalues`].
+#[cfg(feature = "parallel")]
+pub fn par_resolvent_trace_grid_from_eigenvalues<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    zs: &[Complex64],
+) -> Vec<Complex64> {
+    use rayon::prelude::*;
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    zs.par_iter()
+        .map(|&z| resolvent_trace_from_eigenvalues(eigenvalues, z))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{marchenko_pastur_density, marchenko_pastur_support, wigner_semicircle_density};
+
+    #[test]
+    fn test_hilbert_semicircle() {
+        let grid: Vec<f64> = (0..=2000).map(|i| -2.0 + i as f64 / 500.0).collect();
+        let density: Vec<f64> = grid
+            .iter()
+            .map(|&x| wigner_semicircle_density(x, 1.0))
+            .collect();
+        let h = hilbert_transform(&grid, &density);
+        for (i, (&x, &hx)) in grid.iter().zip(&h).enumerate().step_by(100) {
+            // The square-root edges converge more slowly than the interior.
+            let tol = if x.abs() > 1.9 { 2e-2 } else { 5e-3 };
+            assert!((hx - x / 2.0).abs() < tol, "i={} x={} h={}", i, x, hx);
+        }
+    }
+
+    #[test]
+    fn test_hilbert_off_grid_outside_support() {
+        // Outside the support H equals G(x) = (x - sqrt(x^2 - 4)) / 2 for the semicircle.
+        let grid: Vec<f64> = (0..=2000).map(|i| -2.0 + i as f64 / 500.0).collect();
+        let density: Vec<f64> = grid
+            .iter()
+            .map(|&x| wigner_semicircle_density(x, 1.0))
+            .collect();
+        let x: f64 = 3.0;
+        let expected = (x - (x * x - 4.0).sqrt()) / 2.0;
+        let h = hilbert_transform_at(&grid, &density, x);
+        assert!((h - expected).abs() < 1e-3, "{} vs {}", h, expected);
+    }
+
+    #[test]
+    fn test_hilbert_marchenko_pastur() {
+        // Inside the MP bulk (sigma^2 = 1, gamma < 1): Re G(x) = (x + gamma - 1) / (2 gamma x).
+        let gamma = 0.5;
+        let (lo, hi) = marchenko_pastur_support(gamma, 1.0);
+        let grid: Vec<f64> = (0..=4000)
+            .map(|i| lo + (hi - lo) * i as f64 / 4000.0)
+            .collect();
+        let density: Vec<f64> = grid
+            .iter()
+            .map(|&x| marchenko_pastur_density(x, gamma, 1.0))
+            .collect();
+        let x = grid[2000];
+        let expected = (x + gamma - 1.0) / (2.0 * gamma * x);
+        let h = hilbert_transform(&grid, &density)[2000];
+        assert!((h - expected).abs() < 5e-3, "{} vs {}", h, expected);
+    }
+
+    #[test]
+    fn test_resolvent_direct_matches_eigenvalues() {
+        // Symmetric tridiagonal with known eigenvalues 2 - 2 cos(k pi / (n + 1)).
+        let n = 6;
+        let mut a = Array2::zeros((n, n));
+        for i in 0..n {
+            a[[i, i]] = 2.0;
+            if i + 1 < n {
+                a[[i, i + 1]] = -1.0;
+                a[[i + 1, i]] = -1.0;
+            }
+        }
+        let eigenvalues: Vec<f64> = (1..=n)
+            .map(|k| 2.0 - 2.0 * (k as f64 * std::f64::consts::PI / (n as f64 + 1.0)).cos())
+            .collect();
+        let zs = [Complex64::new(0.5, 0.1), Complex64::new(-1.0, 2.0)];
+        let direct = resolvent_trace_grid(&a, &zs);
+        let spectral = resolvent_trace_grid_from_eigenvalues(&eigenvalues, &zs);
+        for (d, s) in direct.iter().zip(&spectral) {
+            assert!((d - s).norm() < 1e-10, "{} vs {}", d, s);
+        }
+    }
+
+    #[test]
+    fn test_resolvent_matches_stieltjes_on_real_axis() {
+        let eigenvalues = [1.0, 2.0, 4.0];
+        let m = resolvent_trace_from_eigenvalues(&eigenvalues, Complex64::new(0.5, 0.0));
+        assert!((m.re - crate::stieltjes_transform(&eigenvalues, 0.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_resolvent_non_square() {
+        assert!(
+            resolvent_trace(&Array2::zeros((2, 3)), Complex64::new(0.0, 1.0))
+                .re
+                .is_nan()
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_resolvent_matches_serial() {
+        let a = ndarray::array![[2.0, 1.0], [1.0, 3.0]];
+        let zs: Vec<Complex64> = (0..8).map(|k| Complex64::new(k as f64, 0.5)).collect();
+        assert_eq!(
+            par_resolvent_trace_grid(&a, &zs),
+            resolvent_trace_grid(&a, &zs)
+        );
+    }
+
+    #[test]
+    fn test_hilbert_mismatched_lengths() {
+        assert!(hilbert_transform(&[0.0, 1.0], &[1.0]).is_empty());
+    }
+}
@@ -0,0 +1,337 @@
+//! Weight-matrix diagnostics in the heavy-tailed self-regularization style.
+//!
+//! Martin & Mahoney (2021) observe that the empirical spectral density (ESD)
+//! of a trained layer W moves away from the Marchenko-Pastur law of a random
+//! initialization as training imprints correlations: first a few spikes
+//! leave the bulk, then the edge bleeds out, and finally the whole ESD becomes
+//! heavy-tailed with a power-law exponent alpha roughly in [2, 6].
+
+use ndarray::Array2;
+
+use crate::gof::ks_distance_sorted;
+use crate::metrics::stable_rank;
+use crate::spectrum::AsEigenvalues;
+use crate::spikes::{count_spikes_with, estimate_sigma_sq, SpikeThreshold};
+use crate::tails::{fit_power_law, PowerLawFit};
+use crate::{marchenko_pastur_cdf, marchenko_pastur_support, symmetric_eigenvalues};
+
+/// Bulk KS distance to MP below which the bulk counts as Marchenko-Pastur.
+const MP_KS_TOLERANCE: f64 = 0.1;
+/// Per-test level of the Tracy-Widom outlier count.
+const SPIKE_ALPHA: f64 = 0.01;
+/// Largest fraction of outliers still read as isolated spikes rather than bleeding out.
+const MAX_SPIKE_FRACTION: f64 = 0.05;
+/// Largest KS distance of the tail to its power-law fit still read as a power law.
+const POWER_LAW_KS_TOLERANCE: f64 = 0.1;
+/// Largest power-law exponent read as heavy-tailed.
+const MAX_HEAVY_TAIL_ALPHA: f64 = 6.0;
+
+/// Phase of a layer's ESD in the heavy-tailed self-regularization taxonomy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EsdPhase {
+    /// Pure Marchenko-Pastur bulk: no learned structure
+    RandomLike,
+    /// MP bulk plus a few isolated outliers (low-rank signal)
+    BulkPlusSpikes,
+    /// Many eigenvalues leak continuously past the MP edge
+    BleedingOut,
+    /// No MP bulk; the ESD follows a power law with exponent alpha <= 6
+    HeavyTailed,
+}
+
+/// Structured result of [`classify_esd`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EsdPhaseReport {
+    /// Assigned phase
+    pub phase: EsdPhase,
+    /// Robust noise variance of the bulk (see [`crate::estimate_sigma_sq`])
+    pub sigma_sq: f64,
+    /// KS distance between the eigenvalues inside the MP support and the MP law
+    pub mp_ks_statistic: f64,
+    /// Number of eigenvalues significantly above the MP edge (Tracy-Widom tests at level 0.01)
+    pub spikes: usize,
+    /// Power-law fit of the tail, if there were enough positive eigenvalues
+    pub power_law: Option<PowerLawFit>,
+}
+
+/// Assign a heavy-tailed self-regularization phase to a layer's ESD.
+///
+/// The bulk is matched to MP(gamma, sigma^2) with the robust sigma^2 of
+/// [`crate::estimate_sigma_sq`]. A good bulk fit (KS distance below 0.1)
+/// with at most 5% of the eigenvalues significantly above the edge is
+/// [`EsdPhase::RandomLike`] without outliers and [`EsdPhase::BulkPlusSpikes`]
+/// with some. Otherwise the spectrum is [`EsdPhase::HeavyTailed`] when the
+/// Clauset power-law fit has exponent at most 6 and KS distance at most 0.1,
+/// and [`EsdPhase::BleedingOut`] when the excess mass is not a power law.
+///
+/// # Arguments
+///
+/// * `eigenvalues` - ESD of the layer: eigenvalues of W^T W / n for an n x p weight matrix
+/// * `ratio` - gamma = p/n aspect ratio of the weight matrix
+///
+/// For gamma > 1 the p - n null eigenvalues are dropped and the rest divided
+/// by gamma, which gives the ESD of W W^T / p at ratio 1/gamma, the
+/// orientation [`analyze_weight_matrix`] uses.
+///
+/// # Returns
+///
+/// `None` for an empty spectrum or a non-positive ratio
+///
+/// # Example
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rmt::{classify_esd, sample_wishart_with, symmetric_eigenvalues, EsdPhase};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let esd = symmetric_eigenvalues(&(sample_wishart_with(&mut rng, 400, 200) / 400.0));
+/// assert_eq!(classify_esd(&esd, 0.5).unwrap().phase, EsdPhase::RandomLike);
+/// ```
+pub fn classify_esd<E: AsEigenvalues + ?Sized>(
+    eigenvalues: &E,
+    ratio: f64,
+) -> Option<EsdPhaseReport> {
+    let eigenvalues: &[f64] = &eigenvalues.as_eigenvalues();
+    if eigenvalues.is_empty() || ratio <= 0.0 {
+        return None;
+    }
+    if ratio > 1.0 {
+        let max = eigenvalues
+            .iter()
+            .copied()
+            .filter(|v| v.is_finite())
+            .fold(0.0_f64, f64::max);
+        let oriented: Vec<f64> = eigenvalues
+            .iter()
+            .filter(|&&v| v > 1e-12 * max)
+            .map(|v| v / ratio)
+            .collect();
+        return classify_esd(&oriented, 1.0 / ratio);
+    }
+    let sigma_sq = estimate_sigma_sq(eigenvalues, ratio);
+    if sigma_sq.is_nan() {
+        return None;
+    }
+    let threshold = SpikeThreshold::TracyWidom { alpha: SPIKE_ALPHA };
+    let spikes = count_spikes_with(eigenvalues, ratio, sigma_sq, threshold);
+    let (_, edge) = marchenko_pastur_support(ratio, sigma_sq);
+    let mut bulk: Vec<f64> = eigenvalues
+        .iter()
+        .copied()
+        .filter(|&v| v.is_finite() && v <= edge)
+        .collect();
+    bulk.sort_by(|a, b| a.total_cmp(b));
+    let mp_ks_statistic = ks_distance_sorted(&bulk, |x| marchenko_pastur_cdf(x, ratio, sigma_sq));
+    let power_law = fit_power_law(eigenvalues);
+
+    let isolated = spikes as f64 <= MAX_SPIKE_FRACTION * eigenvalues.len() as f64;
+    let phase = if mp_ks_statistic <= MP_KS_TOLERANCE && isolated {
+        if spikes == 0 {
+            EsdPhase::RandomLike
+        } else {
+            EsdPhase::BulkPlusSpikes
+        }
+    } else if power_law.is_some_and(|fit| {
+        fit.alpha <= MAX_HEAVY_TAIL_ALPHA && fit.ks_statistic <= POWER_LAW_KS_TOLERANCE
+    }) {
+        EsdPhase::HeavyTailed
+    } else {
+        EsdPhase::BleedingOut
+    };
+
+    Some(EsdPhaseReport {
+        phase,
+        sigma_sq,
+        mp_ks_statistic,
+        spikes,
+        power_law,
+    })
+}
+
+/// Spectral diagnostics of one weight matrix, from [`analyze_weight_matrix`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightMatrixReport {
+    /// Number of rows of W
+    pub rows: usize,
+    /// Number of columns of W
+    pub cols: usize,
+    /// Aspect ratio gamma = min(rows, cols) / max(rows, cols) of the MP baseline
+    pub ratio: f64,
+    /// ESD: eigenvalues of the min x min Gram matrix divided by max(rows, cols), ascending
+    pub eigenvalues: Vec<f64>,
+    /// Singular values of W, ascending (sqrt(max(rows, cols) * eigenvalue))
+    pub singular_values: Vec<f64>,
+    /// Upper edge of the fitted MP bulk
+    pub mp_edge: f64,
+    /// Stable rank ||W||_F^2 / ||W||_2^2
+    pub stable_rank: f64,
+    /// Phase classification, noise variance, outlier count, and power-law fit
+    pub phase: EsdPhaseReport,
+}
+
+/// Spectral diagnostics of a layer's weight matrix in one call.
+///
+/// Forms the smaller Gram matrix of W (W^T W for a tall W, W W^T for a wide
+/// one) divided by the larger dimension, so the ESD of i.i.d. weights with
+/// variance sigma^2 follows MP(gamma, sigma^2) with gamma = min / max of the
+/// dimensions. The ESD is then classified by [`classify_esd`], which also
+/// supplies the noise variance, the number of outliers, and the power-law
+/// tail exponent alpha.
+///
+/// # Arguments
+///
+/// * `weights` - Weight matrix of any shape (e.g. out_features x in_features)
+///
+/// # Returns
+///
+/// `None` for an empty or all-zero matrix
+///
+/// # Example
+///
+/// ```rust
+/// use ndarray::Array2;
+/// use rand::SeedableRng;
+/// use rand_distr::{Distribution, StandardNormal};
+/// use rmt::{analyze_weight_matrix, EsdPhase};
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let w: Array2<f64> = Array2::from_shape_fn((300, 600), |_| StandardNormal.sample(&mut rng));
+/// let report = analyze_weight_matrix(&w).unwrap();
+/// assert_eq!(report.ratio, 0.5);
+/// assert_eq!(report.phase.phase, EsdPhase::RandomLike);
+/// ```
+pub fn analyze_weight_matrix(weights: &Array2<f64>) -> Option<WeightMatrixReport> {
+    let (rows, cols) = weights.dim();
+    let (small, large) = (rows.min(cols), rows.max(cols));
+    if small == 0 {
+        return None;
+    }
+    let gram = if cols <= rows {
+        weights.t().dot(weights)
+    } else {
+        weights.dot(&weights.t())
+    };
+    let eigenvalues: Vec<f64> = symmetric_eigenvalues(&(gram / large as f64))
+        .into_iter()
+        .map(|v| v.max(0.0))
+        .collect();
+    let ratio = small as f64 / large as f64;
+    let phase = classify_esd(&eigenvalues, ratio)?;
+
+    let singular_values: Vec<f64> = eigenvalues
+        .iter()
+        .map(|v| (large as f64 * v).sqrt())
+        .collect();
+    let stable_rank = stable_rank(&singular_values);
+    let (_, mp_edge) = marchenko_pastur_support(ratio, phase.sigma_sq);
+
+    Some(WeightMatrixReport {
+        rows,
+        cols,
+        ratio,
+        eigenvalues,
+        singular_values,
+        mp_edge,
+        stable_rank,
+        phase,
+    })
+}
+
+/// [`analyze_weight_matrix`] over the layers of a model, in order.
+pub fn analyze_weight_matrices(layers: &[Array2<f64>]) -> Vec<Option<WeightMatrixReport>> {
+    layers.iter().map(analyze_weight_matrix).collect()
+}
+
+/// Parallel [`analyze_weight_matrices`]: one layer per task.
+#[cfg(feature = "parallel")]
+pub fn par_analyze_weight_matrices(layers: &[Array2<f64>]) -> Vec<Option<WeightMatrixReport>> {
+    use rayon::prelude::*;
+    layers.par_iter().map(analyze_weight_matrix).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::spiked_covariance_eigenvalues;
+    use crate::symmetric_eigenvalues;
+    use ndarray::Array2;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_classify_esd_phases() {
+        let random = classify_esd(&spiked_covariance_eigenvalues(127, 600, 300, &[]), 0.5).unwrap();
+        assert_eq!(random.phase, EsdPhase::RandomLike, "{:?}", random);
+
+        let spiked = classify_esd(
+            &spiked_covariance_eigenvalues(131, 600, 300, &[20.0, 10.0, 5.0]),
+            0.5,
+        )
+        .unwrap();
+        assert_eq!(spiked.phase, EsdPhase::BulkPlusSpikes, "{:?}", spiked);
+        assert_eq!(spiked.spikes, 3);
+
+        let strengths: Vec<f64> = (0..60).map(|k| 0.8 + 0.02 * k as f64).collect();
+        // Wide matrices are classified in the W W^T / p orientation.
+        let wide = spiked_covariance_eigenvalues(149, 300, 600, &[]);
+        let wide = classify_esd(&wide, 2.0).unwrap();
+        assert_eq!(wide.phase, EsdPhase::RandomLike, "{:?}", wide);
+        assert!((wide.sigma_sq - 1.0).abs() < 0.05, "{:?}", wide);
+        let wide_spiked = spiked_covariance_eigenvalues(151, 300, 600, &[20.0, 10.0, 5.0]);
+        let wide_spiked = classify_esd(&wide_spiked, 2.0).unwrap();
+        assert_eq!(
+            wide_spiked.phase,
+            EsdPhase::BulkPlusSpikes,
+            "{:?}",
+            wide_spiked
+        );
+        assert_eq!(wide_spiked.spikes, 3);
+
+        let bleeding = classify_esd(
+            &spiked_covariance_eigenvalues(137, 600, 300, &strengths),
+            0.5,
+        )
+        .unwrap();
+        assert_eq!(bleeding.phase, EsdPhase::BleedingOut, "{:?}", bleeding);
+
+        // Pareto entries with tail index 1.5 give a power-law ESD.
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(139);
+        let (n, p) = (600, 300);
+        let w = Array2::from_shape_fn((n, p), |_| {
+            let sign = if rng.random::<bool>() { 1.0 } else { -1.0 };
+            sign * (1.0 - rng.random::<f64>()).powf(-1.0 / 1.5)
+        });
+        let esd = symmetric_eigenvalues(&(w.t().dot(&w) / n as f64));
+        let heavy = classify_esd(&esd, 0.5).unwrap();
+        assert_eq!(heavy.phase, EsdPhase::HeavyTailed, "{:?}", heavy);
+
+        assert!(classify_esd(&[], 0.5).is_none());
+    }
+
+    #[test]
+    fn test_analyze_weight_matrix_wide_and_tall() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(149);
+        let mut tall: Array2<f64> =
+            Array2::from_shape_fn((500, 200), |_| StandardNormal.sample(&mut rng));
+        tall *= 2.0;
+        tall.column_mut(0).mapv_inplace(|v| v * 5.0);
+        let wide = tall.t().to_owned();
+
+        let reports = analyze_weight_matrices(&[tall, wide, Array2::zeros((3, 0))]);
+        let (a, b) = (reports[0].as_ref().unwrap(), reports[1].as_ref().unwrap());
+        assert_eq!((a.rows, a.cols, b.rows, b.cols), (500, 200, 200, 500));
+        assert_eq!(a.ratio, 0.4);
+        assert_eq!(a.eigenvalues.len(), 200);
+        for (x, y) in a.singular_values.iter().zip(&b.singular_values) {
+            assert!((x - y).abs() < 1e-8 * x.max(1.0));
+        }
+        assert!((a.phase.sigma_sq - 4.0).abs() < 0.2, "{:?}", a.phase);
+        assert_eq!(a.phase.spikes, 1);
+        assert_eq!(a.phase.phase, EsdPhase::BulkPlusSpikes);
+        assert!(a.stable_rank > 1.0 && a.stable_rank < 200.0);
+        assert!(reports[2].is_none());
+    }
+}